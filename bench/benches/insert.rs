@@ -83,6 +83,16 @@ fn append_long_lorem(repeat: usize) -> usize {
     textarea.lines().len()
 }
 
+#[inline]
+fn paste_lorem(repeat: usize) -> usize {
+    let mut textarea = TextArea::default();
+    let mut term = dummy_terminal();
+    let text = vec![LOREM.join("\r\n"); repeat].join("\r\n");
+    textarea.paste_text(&text);
+    term.draw_textarea(&textarea);
+    textarea.lines().len()
+}
+
 fn append(c: &mut Criterion) {
     c.bench_function("insert::append::1_lorem", |b| {
         b.iter(|| black_box(append_lorem(1)))
@@ -120,5 +130,15 @@ fn long(c: &mut Criterion) {
     });
 }
 
-criterion_group!(insert, append, random, long);
+// Pasting text inserts it at once instead of inserting each character
+fn paste(c: &mut Criterion) {
+    c.bench_function("insert::paste::10_lorem", |b| {
+        b.iter(|| black_box(paste_lorem(10)))
+    });
+    c.bench_function("insert::paste::1000_lorem", |b| {
+        b.iter(|| black_box(paste_lorem(1000)))
+    });
+}
+
+criterion_group!(insert, append, random, long, paste);
 criterion_main!(insert);
@@ -0,0 +1,88 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use ratatui::style::{Color, Style};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tui_textarea::{Highlight, TextArea};
+use tui_textarea_bench::{dummy_terminal, dummy_terminal_with_size, TerminalExt, LOREM};
+
+// Allocator counting allocations to report the number of allocations per frame
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn prepare_textarea(highlight: bool) -> TextArea {
+    let mut lines = vec![];
+    for _ in 0..10 {
+        lines.extend(LOREM.iter().map(|s| s.to_string()));
+    }
+    let mut textarea = TextArea::new(lines);
+    if highlight {
+        let highlights = (0..textarea.lines().len())
+            .flat_map(|row| {
+                (0..60).step_by(6).map(move |col| Highlight {
+                    start: (row, col),
+                    end: (row, col + 3),
+                    style: Style::default().fg(Color::Green),
+                })
+            })
+            .collect();
+        textarea.set_highlights(highlights);
+    }
+    textarea
+}
+
+// Render the same static buffer repeatedly. Spans of the lines are reused across frames
+#[inline]
+fn run(textarea: &mut TextArea) {
+    let mut term = dummy_terminal();
+    for _ in 0..100 {
+        term.draw_textarea(textarea);
+    }
+}
+
+// Render the text on a full screen terminal
+#[inline]
+fn run_fullscreen(textarea: &mut TextArea) -> usize {
+    let mut term = dummy_terminal_with_size(200, 60);
+    term.draw_textarea(textarea); // Warm up caches
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..100 {
+        term.draw_textarea(textarea);
+    }
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) / 100
+}
+
+fn bench(c: &mut Criterion) {
+    let mut plain = prepare_textarea(false);
+    let mut highlighted = prepare_textarea(true);
+    c.bench_function("render::static::plain", |b| b.iter(|| run(&mut plain)));
+    c.bench_function("render::static::highlight", |b| {
+        b.iter(|| run(&mut highlighted))
+    });
+
+    let mut fullscreen = prepare_textarea(true);
+    println!(
+        "render::fullscreen: {} allocations per frame",
+        run_fullscreen(&mut fullscreen),
+    );
+    c.bench_function("render::fullscreen", |b| {
+        b.iter(|| run_fullscreen(&mut fullscreen))
+    });
+}
+
+criterion_group!(render, bench);
+criterion_main!(render);
@@ -110,6 +110,16 @@ pub fn dummy_terminal() -> Terminal<DummyBackend> {
     Terminal::new(DummyBackend::default()).unwrap()
 }
 
+#[inline]
+pub fn dummy_terminal_with_size(width: u16, height: u16) -> Terminal<DummyBackend> {
+    let backend = DummyBackend {
+        width,
+        height,
+        ..Default::default()
+    };
+    Terminal::new(backend).unwrap()
+}
+
 pub trait TerminalExt {
     fn draw_textarea(&mut self, textarea: &mut TextArea);
 }
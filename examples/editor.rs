@@ -71,7 +71,7 @@ impl<'a> SearchBox<'a> {
                 ..
             } => None, // Disable shortcuts which inserts a newline. See `single_line` example
             input => {
-                let modified = self.textarea.input(input);
+                let modified = self.textarea.input(input).changed();
                 modified.then(|| self.textarea.lines()[0].as_str())
             }
         }
@@ -339,7 +339,7 @@ impl<'a> Editor<'a> {
                     }
                     input => {
                         let buffer = &mut self.buffers[self.current];
-                        buffer.modified = buffer.textarea.input(input);
+                        buffer.modified = buffer.textarea.input(input).changed();
                     }
                 }
             }
@@ -41,7 +41,7 @@ fn main() -> io::Result<()> {
                 ..
             } => break,
             input => {
-                if textarea.input(input) {
+                if textarea.input(input).changed() {
                     // When the input modified its text, validate the text content
                 }
             }
@@ -64,7 +64,7 @@ fn main() -> io::Result<()> {
             } => {}
             input => {
                 // TextArea::input returns if the input modified its text
-                if textarea.input(input) {
+                if textarea.input(input).changed() {
                     is_valid = validate(&mut textarea);
                 }
             }
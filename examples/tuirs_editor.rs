@@ -73,7 +73,7 @@ impl<'a> SearchBox<'a> {
                 ..
             } => None, // Disable shortcuts which inserts a newline. See `single_line` example
             input => {
-                let modified = self.textarea.input(input);
+                let modified = self.textarea.input(input).changed();
                 modified.then(|| self.textarea.lines()[0].as_str())
             }
         }
@@ -341,7 +341,7 @@ impl<'a> Editor<'a> {
                     }
                     input => {
                         let buffer = &mut self.buffers[self.current];
-                        buffer.modified = buffer.textarea.input(input);
+                        buffer.modified = buffer.textarea.input(input).changed();
                     }
                 }
             }
@@ -299,6 +299,21 @@ impl Vim {
                         ctrl: false,
                         ..
                     } => textarea.move_cursor(CursorMove::Bottom),
+                    Input {
+                        key: Key::Char('H'),
+                        ctrl: false,
+                        ..
+                    } => textarea.move_cursor(CursorMove::TopOfScreen),
+                    Input {
+                        key: Key::Char('M'),
+                        ctrl: false,
+                        ..
+                    } => textarea.move_cursor(CursorMove::MiddleOfScreen),
+                    Input {
+                        key: Key::Char('L'),
+                        ctrl: false,
+                        ..
+                    } => textarea.move_cursor(CursorMove::BottomOfScreen),
                     Input {
                         key: Key::Char(c),
                         ctrl: false,
@@ -0,0 +1,61 @@
+use std::ops::Range;
+
+// Byte ranges of the runs in the line in the visual order paired with if each run is right-to-left. `None` is returned
+// when the line has no right-to-left text since the visual order is the same as the logical order.
+#[cfg(feature = "bidi")]
+pub fn visual_runs(line: &str) -> Option<Vec<(Range<usize>, bool)>> {
+    let info = unicode_bidi::BidiInfo::new(line, None);
+    if !info.has_rtl() {
+        return None;
+    }
+    let para = info.paragraphs.first()?;
+    let (levels, runs) = info.visual_runs(para, para.range.clone());
+    let runs = runs
+        .into_iter()
+        .map(|run| {
+            let rtl = levels[run.start].is_rtl();
+            (run, rtl)
+        })
+        .collect();
+    Some(runs)
+}
+
+#[cfg(not(feature = "bidi"))]
+pub fn visual_runs(_line: &str) -> Option<Vec<(Range<usize>, bool)>> {
+    None
+}
+
+// Indices of the characters in the line in the visual order. `None` is returned when the order is not changed.
+pub fn visual_order(line: &str) -> Option<Vec<usize>> {
+    let runs = visual_runs(line)?;
+    let offsets: Vec<usize> = line.char_indices().map(|(i, _)| i).collect();
+    let mut order = Vec::with_capacity(offsets.len());
+    for (run, rtl) in runs {
+        let start = offsets.partition_point(|&i| i < run.start);
+        let end = offsets.partition_point(|&i| i < run.end);
+        if rtl {
+            order.extend((start..end).rev());
+        } else {
+            order.extend(start..end);
+        }
+    }
+    Some(order)
+}
+
+#[cfg(all(test, feature = "bidi"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visual_order_of_chars() {
+        for (input, want) in [
+            ("abc", None),
+            ("", None),
+            ("אבג", Some(vec![2, 1, 0])),
+            ("ab אבג cd", Some(vec![0, 1, 2, 5, 4, 3, 6, 7, 8])),
+            ("אב 12 גד", Some(vec![7, 6, 5, 3, 4, 2, 1, 0])),
+        ] {
+            assert_eq!(visual_order(input), want, "{:?}", input);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::ratatui::style::Style;
+use crate::ratatui::text::Span;
+#[cfg(feature = "ratatui")]
+use ratatui::text::Line;
+use std::cmp;
+use std::collections::HashMap;
+#[cfg(feature = "tuirs")]
+use tui::text::Spans as Line;
+
+// Spans of the lines built on the previous renders. Lines which are not decorated with the cursor or the text selection
+// are reused so that redrawing an unchanged textarea does not build the same spans again. Each entry keeps the text of
+// the line it was built from so that modifying the text invalidates only the modified lines. The cache must be cleared
+// when a setting affecting the spans of lines is changed.
+//
+// The number of entries is bounded. Entries far from the rendered rows are evicted first so that scrolling back to
+// recently rendered lines reuses them.
+#[derive(Clone, Debug, Default)]
+pub struct RenderCache {
+    revision: u64,
+    lines: HashMap<usize, (String, Vec<Span<'static>>)>,
+}
+
+impl RenderCache {
+    const CAPACITY: usize = 1024;
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    // Build spans of the rows missing in the cache with `build`. When the text was modified since the last update,
+    // entries of the modified lines are dropped. When `local` is false, spans of a line depend on other lines so all
+    // entries are dropped instead.
+    pub fn update<'a>(
+        &mut self,
+        revision: u64,
+        text: &[String],
+        local: bool,
+        rows: impl Iterator<Item = usize>,
+        mut build: impl FnMut(usize) -> Line<'a>,
+    ) {
+        if self.revision != revision {
+            if local {
+                self.lines
+                    .retain(|row, (line, _)| text.get(*row) == Some(line));
+            } else {
+                self.lines.clear();
+            }
+            self.revision = revision;
+        }
+
+        let (mut first, mut last, mut count) = (usize::MAX, 0, 0);
+        for row in rows {
+            first = cmp::min(first, row);
+            last = cmp::max(last, row);
+            count += 1;
+            self.lines.entry(row).or_insert_with(|| {
+                let spans = into_owned_spans(build(row));
+                (text[row].clone(), spans)
+            });
+        }
+
+        let capacity = cmp::max(Self::CAPACITY, count);
+        if self.lines.len() > capacity {
+            let distance = |row: usize| {
+                if row < first {
+                    first - row
+                } else {
+                    row.saturating_sub(last)
+                }
+            };
+            let mut rows: Vec<usize> = self.lines.keys().copied().collect();
+            rows.sort_unstable_by_key(|&row| distance(row));
+            for row in &rows[capacity..] {
+                self.lines.remove(row);
+            }
+        }
+    }
+
+    // Line borrowing the cached spans of the row
+    pub fn line(&self, row: usize) -> Option<Line<'_>> {
+        let (_, spans) = self.lines.get(&row)?;
+        let spans: Vec<_> = spans
+            .iter()
+            .map(|s| Span::styled(s.content.as_ref(), s.style))
+            .collect();
+        Some(Line::from(spans))
+    }
+}
+
+// Buffers used while rendering which are kept across renders so that they are not allocated on every frame
+#[derive(Clone, Debug, Default)]
+pub struct RenderScratch {
+    // Rows of the lines rendered in the viewport
+    pub rows: Vec<usize>,
+    // Display widths of the rendered lines
+    pub widths: Vec<usize>,
+    // Indices of the rendered lines with their fill styles
+    pub fills: Vec<(u16, Style)>,
+}
+
+impl RenderScratch {
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.widths.clear();
+        self.fills.clear();
+    }
+}
+
+fn into_owned_spans(line: Line<'_>) -> Vec<Span<'static>> {
+    #[cfg(feature = "ratatui")]
+    let spans = line.spans;
+    #[cfg(feature = "tuirs")]
+    let spans = line.0;
+    spans
+        .into_iter()
+        .map(|s| Span::styled(s.content.into_owned(), s.style))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(
+        cache: &mut RenderCache,
+        revision: u64,
+        text: &[String],
+        rows: &[usize],
+    ) -> Vec<usize> {
+        let mut built = vec![];
+        cache.update(revision, text, true, rows.iter().copied(), |row| {
+            built.push(row);
+            Line::from(text[row].as_str())
+        });
+        built
+    }
+
+    #[test]
+    fn invalidate_modified_lines() {
+        let mut text: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let mut cache = RenderCache::default();
+        assert_eq!(update(&mut cache, 0, &text, &[0, 1, 2]), [0, 1, 2]);
+        assert!(update(&mut cache, 0, &text, &[0, 1, 2]).is_empty());
+
+        // Only the modified line is built again
+        text[1].push('x');
+        assert_eq!(update(&mut cache, 1, &text, &[0, 1, 2]), [1]);
+
+        // Lines shifted by inserting a line are built again
+        text.insert(0, "z".to_string());
+        assert_eq!(update(&mut cache, 2, &text, &[0, 1, 2, 3]), [0, 1, 2, 3]);
+
+        // All lines are built again when lines depend on other lines
+        let mut built = vec![];
+        cache.update(3, &text, false, 0..4, |row| {
+            built.push(row);
+            Line::from(text[row].as_str())
+        });
+        assert_eq!(built, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn evict_far_lines() {
+        let text: Vec<String> = (0..3000).map(|i| i.to_string()).collect();
+        let mut cache = RenderCache::default();
+        for top in (0..3000).step_by(100) {
+            update(&mut cache, 0, &text, &(top..top + 100).collect::<Vec<_>>());
+        }
+        assert_eq!(cache.lines.len(), RenderCache::CAPACITY);
+
+        // Lines near the last rendered rows are kept
+        assert!(cache.line(2000).is_some());
+        assert!(cache.line(1900).is_none());
+        assert_eq!(update(&mut cache, 0, &text, &[2500, 2000]), []);
+    }
+}
@@ -0,0 +1,97 @@
+/// How control characters in the text are handled. The policy is set with
+/// [`TextArea::set_control_char_handling`](crate::TextArea::set_control_char_handling).
+///
+/// Control characters are C0 and C1 control codes such as NUL, BEL, and ESC (except for hard tabs) and invisible
+/// format characters such as zero-width spaces, byte order marks, and bidirectional overrides. Zero-width joiners and
+/// non-joiners are not included since they are a part of emoji sequences and some scripts.
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ControlCharHandling {
+    /// Keep control characters in the text and render them as they are. This is the default policy.
+    Keep,
+    /// Keep control characters in the text and render them in caret notation such as `^[` for ESC. Characters which
+    /// have no caret notation are rendered with their code points such as `<200b>`. The notation is styled with
+    /// [`TextArea::control_char_style`](crate::TextArea::control_char_style).
+    /// ```
+    /// use tui_textarea::{ControlCharHandling, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["\x1b[0m"]);
+    /// textarea.set_control_char_handling(ControlCharHandling::Visualize);
+    ///
+    /// // ESC is rendered as `^[`
+    /// assert_eq!(textarea.line_display_width(0), 5);
+    /// ```
+    Visualize,
+    /// Remove control characters from text inserted into the textarea. Text which is already in the textarea is not
+    /// modified.
+    /// ```
+    /// use tui_textarea::{ControlCharHandling, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_control_char_handling(ControlCharHandling::Strip);
+    ///
+    /// textarea.insert_str("\x07hello\x1b[0m");
+    /// assert_eq!(textarea.lines(), ["hello[0m"]);
+    /// ```
+    Strip,
+}
+
+impl Default for ControlCharHandling {
+    fn default() -> Self {
+        Self::Keep
+    }
+}
+
+pub fn is_control_char(c: char) -> bool {
+    (c.is_control() && c != '\t')
+        || matches!(
+            c,
+            '\u{200b}'
+                | '\u{200e}'..='\u{200f}'
+                | '\u{2028}'..='\u{202e}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{2066}'..='\u{2069}'
+                | '\u{feff}'
+        )
+}
+
+// Text rendered instead of the control character. C0 control codes and DEL are rendered in caret notation and other
+// characters are rendered with their code points in hex. `None` is returned when the character is not a control
+// character.
+pub fn notation(c: char) -> Option<String> {
+    if !is_control_char(c) {
+        return None;
+    }
+    let notation = match c as u32 {
+        n @ 0x00..=0x1f | n @ 0x7f => format!("^{}", char::from((n ^ 0x40) as u8)),
+        n => format!("<{:x}>", n),
+    };
+    Some(notation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_char_notation() {
+        for (c, want) in [
+            ('\0', Some("^@")),
+            ('\x07', Some("^G")),
+            ('\x1b', Some("^[")),
+            ('\r', Some("^M")),
+            ('\x7f', Some("^?")),
+            ('\u{85}', Some("<85>")),
+            ('\u{200b}', Some("<200b>")),
+            ('\u{feff}', Some("<feff>")),
+            ('\t', None),
+            ('a', None),
+            ('あ', None),
+            ('\u{200d}', None),
+        ] {
+            assert_eq!(notation(c).as_deref(), want, "{:?}", c);
+        }
+    }
+}
@@ -1,5 +1,6 @@
+use crate::util::{next_grapheme_col, prev_grapheme_col};
 use crate::widget::Viewport;
-use crate::word::{find_word_start_backward, find_word_start_forward};
+use crate::word::{find_word_start_backward, find_word_start_forward, WordChars};
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
 use std::cmp;
@@ -12,6 +13,8 @@ use std::cmp;
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub enum CursorMove {
     /// Move cursor forward by one character. When the cursor is at the end of line, it moves to the head of next line.
+    /// The cursor moves over a whole grapheme cluster such as an emoji with ZWJ sequences or a character with
+    /// combining marks. Note that the cursor position is still counted in characters.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -24,7 +27,7 @@ pub enum CursorMove {
     /// ```
     Forward,
     /// Move cursor backward by one character. When the cursor is at the head of line, it moves to the end of previous
-    /// line.
+    /// line. The cursor moves over a whole grapheme cluster as well as [`CursorMove::Forward`].
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -36,7 +39,8 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (0, 1));
     /// ```
     Back,
-    /// Move cursor up by one line.
+    /// Move cursor up by one line. The column is clamped to the length of the line, and successive vertical moves
+    /// return to the original column on longer lines.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -48,7 +52,8 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (1, 0));
     /// ```
     Up,
-    /// Move cursor down by one line.
+    /// Move cursor down by one line. The column is clamped to the length of the line, and successive vertical moves
+    /// return to the original column on longer lines.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -105,8 +110,9 @@ pub enum CursorMove {
     /// ```
     Bottom,
     /// Move cursor forward by one word. Word boundary appears at spaces, punctuations, and others. For example
-    /// `fn foo(a)` consists of words `fn`, `foo`, `(`, `a`, `)`. When the cursor is at the end of line, it moves to the
-    /// head of next line.
+    /// `fn foo_bar(a)` consists of words `fn`, `foo_bar`, `(`, `a`, `)` since `_` is a part of words. Characters of
+    /// words can be changed by [`TextArea::set_word_chars`](crate::TextArea::set_word_chars). When the cursor is at
+    /// the end of line, it moves to the head of next line.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -119,8 +125,9 @@ pub enum CursorMove {
     /// ```
     WordForward,
     /// Move cursor backward by one word.  Word boundary appears at spaces, punctuations, and others. For example
-    /// `fn foo(a)` consists of words `fn`, `foo`, `(`, `a`, `)`.When the cursor is at the head of line, it moves to
-    /// the end of previous line.
+    /// `fn foo_bar(a)` consists of words `fn`, `foo_bar`, `(`, `a`, `)` since `_` is a part of words. Characters of
+    /// words can be changed by [`TextArea::set_word_chars`](crate::TextArea::set_word_chars). When the cursor is at
+    /// the head of line, it moves to the end of previous line.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -201,7 +208,8 @@ pub enum CursorMove {
     /// ```
     /// # use ratatui::buffer::Buffer;
     /// # use ratatui::layout::Rect;
-    /// # use ratatui::widgets::Widget;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
     /// use tui_textarea::{TextArea, CursorMove};
     ///
     /// // Let's say terminal height is 8.
@@ -212,7 +220,7 @@ pub enum CursorMove {
     /// # // Call `render` at least once to populate terminal size
     /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
     /// # let mut b = Buffer::empty(r.clone());
-    /// # textarea.widget().render(r, &mut b);
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
     ///
     /// // Move cursor to the end of lines (line 20). It is outside the viewport (line 1 to line 8)
     /// textarea.move_cursor(CursorMove::Bottom);
@@ -223,6 +231,67 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (7, 0));
     /// ```
     InViewport,
+    /// Move cursor to the first line displayed in the viewport like `H` in Vim. When the scroll margin is set by
+    /// [`TextArea::set_scrolloff`](crate::TextArea::set_scrolloff), the cursor stops at the margin so that the viewport
+    /// does not scroll. Since the viewport is not known until the textarea is rendered, the cursor does not move before
+    /// the first render.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{CursorMove, TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+    /// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// let mut b = Buffer::empty(r);
+    ///
+    /// // The viewport displays line 13 to line 20
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    ///
+    /// textarea.move_cursor(CursorMove::TopOfScreen);
+    /// assert_eq!(textarea.cursor(), (12, 0));
+    /// ```
+    TopOfScreen,
+    /// Move cursor to the middle line displayed in the viewport like `M` in Vim. When the text ends before the bottom
+    /// of the viewport, the cursor moves to the middle of the displayed lines. The cursor does not move before the
+    /// first render.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{CursorMove, TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+    /// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// let mut b = Buffer::empty(r);
+    ///
+    /// // The viewport displays line 1 to line 8
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    ///
+    /// textarea.move_cursor(CursorMove::MiddleOfScreen);
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// ```
+    MiddleOfScreen,
+    /// Move cursor to the last line displayed in the viewport like `L` in Vim. The scroll margin is kept as well as
+    /// [`CursorMove::TopOfScreen`]. The cursor does not move before the first render.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{CursorMove, TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+    /// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// let mut b = Buffer::empty(r);
+    ///
+    /// // The viewport displays line 1 to line 8
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    ///
+    /// textarea.move_cursor(CursorMove::BottomOfScreen);
+    /// assert_eq!(textarea.cursor(), (7, 0));
+    /// ```
+    BottomOfScreen,
 }
 
 impl CursorMove {
@@ -231,6 +300,8 @@ impl CursorMove {
         (row, col): (usize, usize),
         lines: &[String],
         viewport: &Viewport,
+        scrolloff: u16,
+        word: WordChars,
     ) -> Option<(usize, usize)> {
         use CursorMove::*;
 
@@ -242,12 +313,12 @@ impl CursorMove {
             Forward if col >= lines[row].chars().count() => {
                 (row + 1 < lines.len()).then(|| (row + 1, 0))
             }
-            Forward => Some((row, col + 1)),
+            Forward => Some((row, next_grapheme_col(&lines[row], col))),
             Back if col == 0 => {
                 let row = row.checked_sub(1)?;
                 Some((row, lines[row].chars().count()))
             }
-            Back => Some((row, col - 1)),
+            Back => Some((row, prev_grapheme_col(&lines[row], col))),
             Up => {
                 let row = row.checked_sub(1)?;
                 Some((row, fit_col(col, &lines[row])))
@@ -261,7 +332,7 @@ impl CursorMove {
                 Some((row, fit_col(col, &lines[row])))
             }
             WordForward => {
-                if let Some(col) = find_word_start_forward(&lines[row], col) {
+                if let Some(col) = find_word_start_forward(&lines[row], col, word) {
                     Some((row, col))
                 } else if row + 1 < lines.len() {
                     Some((row + 1, 0))
@@ -270,7 +341,7 @@ impl CursorMove {
                 }
             }
             WordBack => {
-                if let Some(col) = find_word_start_backward(&lines[row], col) {
+                if let Some(col) = find_word_start_backward(&lines[row], col, word) {
                     Some((row, col))
                 } else if row > 0 {
                     Some((row - 1, lines[row - 1].chars().count()))
@@ -318,6 +389,27 @@ impl CursorMove {
 
                 Some((row, col))
             }
+            TopOfScreen | MiddleOfScreen | BottomOfScreen => {
+                let (top, _, bottom, _) = viewport.position();
+                let (_, _, _, height) = viewport.rect();
+                if height == 0 {
+                    return None; // Not rendered yet
+                }
+                let last = lines.len() - 1;
+                let (top, bottom) = (top as usize, cmp::min(bottom as usize, last));
+                // The margin is not necessary at the start and the end of the text since the viewport cannot scroll
+                // further
+                let margin = cmp::min(scrolloff, height.saturating_sub(1) / 2) as usize;
+                let row = match self {
+                    TopOfScreen if top == 0 => 0,
+                    TopOfScreen => top + margin,
+                    BottomOfScreen if bottom == last => last,
+                    BottomOfScreen => bottom.saturating_sub(margin),
+                    _ => (top + bottom) / 2,
+                };
+                let row = cmp::min(row, last);
+                Some((row, fit_col(col, &lines[row])))
+            }
         }
     }
 }
@@ -351,4 +443,53 @@ mod tests {
         textarea.move_cursor(CursorMove::InViewport);
         assert_eq!(textarea.cursor(), (7, 0));
     }
+
+    #[test]
+    fn screen_lines() {
+        use crate::ratatui::buffer::Buffer;
+        use crate::ratatui::layout::Rect;
+        use crate::{CursorMove, TextArea};
+
+        let mut textarea: TextArea = (0..100).map(|i| format!("line {i}")).collect();
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 24,
+            height: 10,
+        };
+        let mut b = Buffer::empty(r);
+
+        // The cursor does not move before the first render
+        textarea.move_cursor(CursorMove::BottomOfScreen);
+        assert_eq!(textarea.cursor(), (0, 0));
+
+        // The viewport displays line 41 to line 50
+        textarea.move_cursor(CursorMove::Jump(49, 5));
+        StatefulWidget::render(TextAreaWidget::new(), r, &mut b, &mut textarea);
+        for (m, pos) in [
+            (CursorMove::TopOfScreen, (40, 5)),
+            (CursorMove::BottomOfScreen, (49, 5)),
+            (CursorMove::MiddleOfScreen, (44, 5)),
+        ] {
+            textarea.move_cursor(m);
+            assert_eq!(textarea.cursor(), pos, "{m:?}");
+        }
+
+        // The scroll margin is kept so that the viewport does not scroll
+        textarea.set_scrolloff(2);
+        textarea.move_cursor(CursorMove::BottomOfScreen);
+        assert_eq!(textarea.cursor(), (47, 5));
+        textarea.move_cursor(CursorMove::TopOfScreen);
+        assert_eq!(textarea.cursor(), (42, 5));
+        StatefulWidget::render(TextAreaWidget::new(), r, &mut b, &mut textarea);
+        assert_eq!(textarea.viewport.scroll_top(), (40, 0));
+
+        // The middle of the displayed lines when the text ends before the bottom of the viewport
+        let mut textarea = TextArea::from(["a", "b", "c", "d", "e"]);
+        StatefulWidget::render(TextAreaWidget::new(), r, &mut b, &mut textarea);
+        textarea.move_cursor(CursorMove::MiddleOfScreen);
+        assert_eq!(textarea.cursor(), (2, 0));
+        textarea.move_cursor(CursorMove::BottomOfScreen);
+        assert_eq!(textarea.cursor(), (4, 0));
+    }
 }
@@ -0,0 +1,55 @@
+use std::cmp;
+use unicode_width::UnicodeWidthStr as _;
+
+// Width of the `col`-th cell of the line. Cells are the parts of the line separated by hard tabs.
+fn cell_width(line: &str, col: usize) -> usize {
+    line.split('\t').nth(col).map_or(0, |cell| cell.width())
+}
+
+// Widths of the hard tabs in the line at `row` with elastic tabstops. The cells ending with the `n`-th tab in the
+// adjacent lines which have at least `n` tabs form a column. Each tab is expanded so that all cells in the column have
+// the width of the widest cell plus one space. The width of a column is at least `min_width`.
+pub fn tab_widths(lines: &[String], row: usize, min_width: usize) -> Vec<usize> {
+    let line = &lines[row];
+    if !line.contains('\t') {
+        return vec![];
+    }
+    let cells: Vec<usize> = line.split('\t').map(|cell| cell.width()).collect();
+    let num_tabs = cells.len() - 1;
+    (0..num_tabs)
+        .map(|col| {
+            let in_column = |r: &usize| lines[*r].matches('\t').count() > col;
+            let above = (0..row).rev().take_while(in_column);
+            let below = (row + 1..lines.len()).take_while(in_column);
+            let widest = above
+                .chain(below)
+                .map(|r| cell_width(&lines[r], col))
+                .fold(cells[col], cmp::max);
+            cmp::max(widest + 1, min_width) - cells[col]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elastic_tab_widths() {
+        let lines: Vec<String> = ["a\tbbb\tc", "aaaa\tb", "", "\tx", "aa\tb\tc\td"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        for (row, min_width, want) in [
+            (0, 1, vec![4, 1]),
+            (1, 1, vec![1]),
+            (2, 1, vec![]),
+            (3, 1, vec![3]),
+            (3, 4, vec![4]),
+            (4, 1, vec![1, 1, 1]),
+            (4, 4, vec![2, 3, 3]),
+        ] {
+            assert_eq!(tab_widths(&lines, row, min_width), want, "row={}", row);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::util::RowShift;
+use std::cmp;
+use std::mem;
+
+// Set of folded ranges of lines. Each range is a pair of the first row and the last row (inclusive). The first row of
+// the range is rendered as a summary row and the rest of rows in the range are hidden. Ranges are sorted and never
+// overlap.
+#[derive(Clone, Default, Debug)]
+pub struct Folds(Vec<(usize, usize)>);
+
+impl Folds {
+    pub fn fold(&mut self, start: usize, end: usize) {
+        if end <= start {
+            return;
+        }
+        // Merge with the existing ranges which overlap with the new range
+        let (mut start, mut end) = (start, end);
+        self.0.retain(|&(s, e)| {
+            if e < start || end < s {
+                return true;
+            }
+            start = cmp::min(start, s);
+            end = cmp::max(end, e);
+            false
+        });
+        let idx = self.0.partition_point(|&(s, _)| s < start);
+        self.0.insert(idx, (start, end));
+    }
+
+    pub fn unfold(&mut self, row: usize) -> bool {
+        if let Some(idx) = self.index(row) {
+            self.0.remove(idx);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    // Move the ranges with the lines moved by an edit. A range is unfolded when its first or last line was removed
+    pub fn shift_rows(&mut self, shift: &RowShift) {
+        self.0 = mem::take(&mut self.0)
+            .into_iter()
+            .filter_map(|(s, e)| {
+                let (s, e) = (shift.row(s)?, shift.row(e)?);
+                (s < e).then(|| (s, e))
+            })
+            .collect();
+    }
+
+    fn index(&self, row: usize) -> Option<usize> {
+        let idx = self.0.partition_point(|&(s, _)| s <= row).checked_sub(1)?;
+        let (_, end) = self.0[idx];
+        (row <= end).then(|| idx)
+    }
+
+    // Get the folded range containing the row
+    pub fn range(&self, row: usize) -> Option<(usize, usize)> {
+        self.index(row).map(|idx| self.0[idx])
+    }
+
+    // Get the number of hidden rows when the row is the first row of a folded range
+    pub fn hidden_rows(&self, row: usize) -> Option<usize> {
+        let (start, end) = self.range(row)?;
+        (start == row).then(|| end - start)
+    }
+
+    // Row of the visible line at the row. When the row is hidden, the summary row of the folded range is returned.
+    pub fn visible_row(&self, row: usize) -> usize {
+        match self.range(row) {
+            Some((start, _)) => start,
+            None => row,
+        }
+    }
+
+    // Row of the next visible line after the row
+    pub fn next_row(&self, row: usize) -> usize {
+        match self.range(row) {
+            Some((_, end)) => end + 1,
+            None => row + 1,
+        }
+    }
+
+    // Convert the row in the text into the index of the row on screen
+    pub fn display_row(&self, row: usize) -> usize {
+        let row = self.visible_row(row);
+        let hidden: usize = self
+            .0
+            .iter()
+            .take_while(|&&(_, e)| e < row)
+            .map(|&(s, e)| e - s)
+            .sum();
+        row - hidden
+    }
+
+    // Convert the index of the row on screen into the row in the text
+    pub fn text_row(&self, display_row: usize) -> usize {
+        let mut row = display_row;
+        for &(s, e) in &self.0 {
+            if row <= s {
+                break;
+            }
+            row += e - s;
+        }
+        row
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folds(ranges: &[(usize, usize)]) -> Folds {
+        let mut f = Folds::default();
+        for &(s, e) in ranges {
+            f.fold(s, e);
+        }
+        f
+    }
+
+    #[test]
+    fn fold_merge() {
+        for (ranges, want) in [
+            (&[(1, 3)][..], &[(1, 3)][..]),
+            (&[(3, 3)][..], &[][..]),
+            (&[(3, 1)][..], &[][..]),
+            (&[(5, 7), (1, 3)][..], &[(1, 3), (5, 7)][..]),
+            (&[(1, 3), (3, 5)][..], &[(1, 5)][..]),
+            (&[(1, 5), (2, 3)][..], &[(1, 5)][..]),
+            (&[(2, 3), (1, 5)][..], &[(1, 5)][..]),
+            (&[(1, 2), (4, 5), (2, 4)][..], &[(1, 5)][..]),
+            (&[(1, 2), (3, 4)][..], &[(1, 2), (3, 4)][..]),
+        ] {
+            assert_eq!(folds(ranges).0, want, "{:?}", ranges);
+        }
+    }
+
+    #[test]
+    fn unfold() {
+        let mut f = folds(&[(1, 3), (5, 7)]);
+        assert!(!f.unfold(0));
+        assert!(!f.unfold(4));
+        assert!(f.unfold(6));
+        assert_eq!(f.0, [(1, 3)]);
+        assert!(f.unfold(1));
+        assert!(f.0.is_empty());
+    }
+
+    #[test]
+    fn shift_rows() {
+        for (shift, want) in [
+            ((0, 0, 2), &[(3, 5), (7, 9)][..]),
+            ((2, 0, 1), &[(1, 4), (6, 8)][..]),
+            ((4, 0, 1), &[(1, 3), (6, 8)][..]),
+            ((0, 1, 0), &[(0, 2), (4, 6)][..]),
+            ((2, 1, 0), &[(1, 2), (4, 6)][..]),
+            ((3, 2, 0), &[(3, 5)][..]),
+            ((7, 1, 0), &[(1, 3)][..]),
+        ] {
+            let mut f = folds(&[(1, 3), (5, 7)]);
+            let (row, removed, added) = shift;
+            f.shift_rows(&RowShift {
+                row,
+                removed,
+                added,
+            });
+            assert_eq!(f.0, want, "{:?}", shift);
+        }
+    }
+
+    #[test]
+    fn row_mapping() {
+        let f = folds(&[(1, 3), (5, 7)]);
+        #[rustfmt::skip]
+        let tests = [
+            // (row, hidden, visible_row, next_row, display_row)
+            (0, false, 0, 1, 0),
+            (1, false, 1, 4, 1),
+            (2,  true, 1, 4, 1),
+            (3,  true, 1, 4, 1),
+            (4, false, 4, 5, 2),
+            (5, false, 5, 8, 3),
+            (7,  true, 5, 8, 3),
+            (8, false, 8, 9, 4),
+            (9, false, 9, 10, 5),
+        ];
+        for (row, hidden, visible, next, display) in tests {
+            assert_eq!(f.visible_row(row) != row, hidden, "row={row}");
+            assert_eq!(f.visible_row(row), visible, "row={row}");
+            assert_eq!(f.next_row(row), next, "row={row}");
+            assert_eq!(f.display_row(row), display, "row={row}");
+            if !hidden {
+                assert_eq!(f.text_row(display), row, "row={row}");
+            }
+        }
+        assert_eq!(f.hidden_rows(1), Some(2));
+        assert_eq!(f.hidden_rows(2), None);
+        assert_eq!(f.hidden_rows(0), None);
+    }
+}
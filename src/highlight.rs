@@ -1,44 +1,174 @@
+use crate::bidi::visual_runs;
+use crate::control;
+use crate::ratatui::layout::Alignment;
 use crate::ratatui::style::Style;
 use crate::ratatui::text::Span;
-use crate::util::{num_digits, spaces};
+use crate::util::{char_display_width, num_digits, spaces};
+use crate::word::{is_whole_word, WordChars};
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
 use std::borrow::Cow;
-use std::cmp::Ordering;
+use std::cmp;
+use std::collections::BinaryHeap;
+use std::fmt;
 use std::iter;
+use std::sync::Arc;
 #[cfg(feature = "tuirs")]
 use tui::text::Spans as Line;
-use unicode_width::UnicodeWidthChar as _;
 
-enum Boundary {
-    Cursor(Style),
+/// Styled range of text overlaid on the base style of a textarea. Positions are pairs of row and column (in characters)
+/// like [`TextArea::cursor`](crate::TextArea::cursor). The `end` position is exclusive. Highlights are set with
+/// [`TextArea::set_highlights`](crate::TextArea::set_highlights).
+/// ```
+/// use ratatui::style::{Color, Style};
+/// use tui_textarea::Highlight;
+///
+/// // Highlight "def" in the line "abc def"
+/// let hl = Highlight {
+///     start: (0, 4),
+///     end: (0, 7),
+///     style: Style::default().fg(Color::Blue),
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Highlight {
+    /// Start position of the range (inclusive).
+    pub start: (usize, usize),
+    /// End position of the range (exclusive).
+    pub end: (usize, usize),
+    /// Style applied to the range. It is patched on top of the base style of the line.
+    pub style: Style,
+}
+
+// Highlights indexed by their start rows so that rendering a line does not scan all highlights. Highlights on a row are
+// found by a binary search in the range of start rows which can reach the row.
+#[derive(Clone, Debug, Default)]
+pub struct Highlights {
+    highlights: Vec<Highlight>,
+    // Pairs of the start row and the index of each highlight sorted by the start rows
+    by_start: Vec<(usize, usize)>,
+    // The largest number of rows spanned by a highlight
+    max_rows: usize,
+}
+
+impl Highlights {
+    pub fn new(highlights: Vec<Highlight>) -> Self {
+        let mut by_start: Vec<_> = highlights
+            .iter()
+            .enumerate()
+            .map(|(i, hl)| (hl.start.0, i))
+            .collect();
+        by_start.sort_unstable();
+        let max_rows = highlights
+            .iter()
+            .map(|hl| hl.end.0.saturating_sub(hl.start.0))
+            .max()
+            .unwrap_or(0);
+        Self {
+            highlights,
+            by_start,
+            max_rows,
+        }
+    }
+
+    pub fn as_slice(&self) -> &[Highlight] {
+        &self.highlights
+    }
+
+    pub fn into_vec(self) -> Vec<Highlight> {
+        self.highlights
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.highlights.is_empty()
+    }
+
+    // Highlights covering the row in the order they were set
+    pub fn on_row(&self, row: usize) -> impl Iterator<Item = &'_ Highlight> {
+        let first = row.saturating_sub(self.max_rows);
+        let lo = self.by_start.partition_point(|&(r, _)| r < first);
+        let hi = self.by_start.partition_point(|&(r, _)| r <= row);
+        let mut indices: Vec<usize> = self.by_start[lo..hi]
+            .iter()
+            .map(|&(_, i)| i)
+            .filter(|&i| row <= self.highlights[i].end.0)
+            .collect();
+        indices.sort_unstable();
+        indices.into_iter().map(move |i| &self.highlights[i])
+    }
+}
+
+/// Markers to visualize invisible parts of lines, like the `listchars` option of Vim. Markers are set with
+/// [`TextArea::set_list_chars`](crate::TextArea::set_list_chars). They are not a part of the text so they don't affect
+/// the cursor position.
+/// ```
+/// use tui_textarea::ListChars;
+///
+/// // Show `$` at the end of line and `>`/`<` when a line is partially hidden by horizontal scroll
+/// let list = ListChars {
+///     eol: Some('$'),
+///     extends: Some('>'),
+///     precedes: Some('<'),
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ListChars {
+    /// Character rendered at the end of each line.
+    pub eol: Option<char>,
+    /// Character rendered at the right edge of the viewport when the line continues beyond it.
+    pub extends: Option<char>,
+    /// Character rendered at the left edge of the viewport when the head of the line is hidden by horizontal scroll.
+    pub precedes: Option<char>,
+}
+
+/// Kind of change of a line in a diff view. Lines are marked with [`TextArea::set_diff`](crate::TextArea::set_diff).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DiffKind {
+    /// The line was added.
+    Added,
+    /// The line was removed.
+    Removed,
+    /// The line was modified.
+    Modified,
+}
+
+impl DiffKind {
+    pub(crate) fn index(self) -> usize {
+        match self {
+            Self::Added => 0,
+            Self::Removed => 1,
+            Self::Modified => 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Layer {
+    Highlight(Style),
     Select(Style),
     #[cfg(feature = "search")]
     Search(Style),
-    End,
+    Cursor(Style),
 }
 
-impl Boundary {
-    fn cmp(&self, other: &Boundary) -> Ordering {
-        fn rank(b: &Boundary) -> u8 {
-            match b {
-                Boundary::Cursor(_) => 3,
-                #[cfg(feature = "search")]
-                Boundary::Search(_) => 2,
-                Boundary::Select(_) => 1,
-                Boundary::End => 0,
-            }
+impl Layer {
+    fn rank(&self) -> u8 {
+        match self {
+            Layer::Cursor(_) => 3,
+            #[cfg(feature = "search")]
+            Layer::Search(_) => 2,
+            Layer::Select(_) => 1,
+            Layer::Highlight(_) => 0,
         }
-        rank(self).cmp(&rank(other))
     }
 
-    fn style(&self) -> Option<Style> {
+    fn style(&self, base: Style) -> Style {
         match self {
-            Boundary::Cursor(s) => Some(*s),
-            Boundary::Select(s) => Some(*s),
+            Layer::Cursor(s) => *s,
+            Layer::Select(s) => *s,
             #[cfg(feature = "search")]
-            Boundary::Search(s) => Some(*s),
-            Boundary::End => None,
+            Layer::Search(s) => *s,
+            Layer::Highlight(s) => base.patch(*s),
         }
     }
 }
@@ -47,6 +177,10 @@ struct DisplayTextBuilder {
     tab_len: u8,
     width: usize,
     mask: Option<char>,
+    // Widths of the remaining hard tabs in the line with elastic tabstops
+    tab_widths: Option<std::vec::IntoIter<usize>>,
+    // Render control characters in their notation
+    control: bool,
 }
 
 impl DisplayTextBuilder {
@@ -55,6 +189,8 @@ impl DisplayTextBuilder {
             tab_len,
             width: 0,
             mask,
+            tab_widths: None,
+            control: false,
         }
     }
 
@@ -65,25 +201,31 @@ impl DisplayTextBuilder {
             return Cow::Owned(masked);
         }
 
-        let tab = spaces(self.tab_len);
         let mut buf = String::new();
         for (i, c) in s.char_indices() {
-            if c == '\t' {
+            let notation = if self.control {
+                control::notation(c)
+            } else {
+                None
+            };
+            let width = match (&mut self.tab_widths, &notation) {
+                (_, Some(n)) => n.len(),
+                (Some(widths), _) if c == '\t' => widths.next().unwrap_or(0),
+                _ => char_display_width(c, self.width, self.tab_len),
+            };
+            if c == '\t' || notation.is_some() {
                 if buf.is_empty() {
                     buf.reserve(s.len());
                     buf.push_str(&s[..i]);
                 }
-                if self.tab_len > 0 {
-                    let len = self.tab_len as usize - (self.width % self.tab_len as usize);
-                    buf.push_str(&tab[..len]);
-                    self.width += len;
+                match notation {
+                    Some(n) => buf.push_str(&n),
+                    None => buf.extend(iter::repeat(' ').take(width)),
                 }
-            } else {
-                if !buf.is_empty() {
-                    buf.push(c);
-                }
-                self.width += c.width().unwrap_or(0);
+            } else if !buf.is_empty() {
+                buf.push(c);
             }
+            self.width += width;
         }
 
         if !buf.is_empty() {
@@ -94,23 +236,113 @@ impl DisplayTextBuilder {
     }
 }
 
+// Push the span of the preedit text when it is placed at the offset. `None` offset pushes it anywhere.
+fn push_preedit<'a>(
+    spans: &mut Vec<Span<'a>>,
+    builder: &mut DisplayTextBuilder,
+    preedit: &mut Option<(usize, &'a str, Style)>,
+    offset: Option<usize>,
+) {
+    if let Some((at, text, style)) = *preedit {
+        if offset.map_or(true, |o| o == at) {
+            spans.push(Span::styled(builder.build(text), style));
+            *preedit = None;
+        }
+    }
+}
+
+type LineRuleFn = dyn Fn(&str, usize) -> Option<Style> + Send + Sync;
+
+// Rules computing the fill style of a line from its text and row. The rules are shared by the clones of the textarea.
+#[derive(Clone, Default)]
+pub struct LineRules(Vec<Arc<LineRuleFn>>);
+
+impl LineRules {
+    pub fn push(&mut self, rule: Arc<LineRuleFn>) {
+        self.0.push(rule);
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    // Style of the first rule matching the line
+    pub fn style(&self, line: &str, row: usize) -> Option<Style> {
+        self.0.iter().find_map(|rule| rule(line, row))
+    }
+}
+
+impl fmt::Debug for LineRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LineRules").field(&self.0.len()).finish()
+    }
+}
+
+// Builder of the gutter rendered at the left of each line. The gutter consists of the sign column and line numbers.
+#[derive(Default)]
+pub struct Gutter {
+    spans: Vec<Span<'static>>,
+}
+
+impl Gutter {
+    pub fn sign(&mut self, sign: Option<(char, Style)>) {
+        let span = match sign {
+            Some((c, style)) => Span::styled(c.to_string(), style),
+            None => Span::raw(" "),
+        };
+        self.spans.push(span);
+    }
+
+    pub fn line_number(
+        &mut self,
+        row: usize,
+        lnum_len: u8,
+        alignment: Alignment,
+        separator: &str,
+        style: Style,
+    ) {
+        let pad = lnum_len.saturating_sub(num_digits(row + 1));
+        let (left, right) = match alignment {
+            Alignment::Left => (0, pad),
+            Alignment::Center => (pad / 2, pad - pad / 2),
+            Alignment::Right => (pad, 0),
+        };
+        let (left, right) = (spaces(left + 1), spaces(right));
+        let text = format!("{}{}{}{}", left, row + 1, right, separator);
+        self.spans.push(Span::styled(text, style));
+    }
+
+    pub fn into_spans(self) -> Line<'static> {
+        Line::from(self.spans)
+    }
+}
+
 pub struct LineHighlighter<'a> {
     line: &'a str,
     spans: Vec<Span<'a>>,
-    boundaries: Vec<(Boundary, usize)>, // TODO: Consider smallvec
+    ranges: Vec<(Layer, usize, usize)>, // TODO: Consider smallvec
     style_begin: Style,
     cursor_at_end: bool,
-    cursor_style: Style,
+    // `None` when the cursor is not rendered with a style
+    cursor_style: Option<Style>,
     tab_len: u8,
     mask: Option<char>,
     select_at_end: bool,
     select_style: Style,
+    // Number of cells padded after the end of line as (unselected, selected) for the block selection
+    select_pad: (usize, usize),
+    suffix: Vec<Span<'a>>,
+    eol: Option<(char, Style)>,
+    // Composition text of IME rendered before the character at the byte offset
+    preedit: Option<(usize, &'a str, Style)>,
+    tab_widths: Option<Vec<usize>>,
+    control: bool,
 }
 
 impl<'a> LineHighlighter<'a> {
     pub fn new(
         line: &'a str,
-        cursor_style: Style,
+        cursor_style: Option<Style>,
         tab_len: u8,
         mask: Option<char>,
         select_style: Style,
@@ -118,7 +350,7 @@ impl<'a> LineHighlighter<'a> {
         Self {
             line,
             spans: vec![],
-            boundaries: vec![],
+            ranges: vec![],
             style_begin: Style::default(),
             cursor_at_end: false,
             cursor_style,
@@ -126,32 +358,36 @@ impl<'a> LineHighlighter<'a> {
             mask,
             select_at_end: false,
             select_style,
+            select_pad: (0, 0),
+            suffix: vec![],
+            eol: None,
+            preedit: None,
+            tab_widths: None,
+            control: false,
         }
     }
 
-    pub fn line_number(&mut self, row: usize, lnum_len: u8, style: Style) {
-        let pad = spaces(lnum_len - num_digits(row + 1) + 1);
-        self.spans
-            .push(Span::styled(format!("{}{} ", pad, row + 1), style));
+    pub fn line_style(&mut self, style: Style) {
+        self.style_begin = self.style_begin.patch(style);
     }
 
     pub fn cursor_line(&mut self, cursor_col: usize, style: Style) {
-        if let Some((start, c)) = self.line.char_indices().nth(cursor_col) {
-            self.boundaries
-                .push((Boundary::Cursor(self.cursor_style), start));
-            self.boundaries.push((Boundary::End, start + c.len_utf8()));
-        } else {
-            self.cursor_at_end = true;
+        if let Some(cursor_style) = self.cursor_style {
+            if let Some((start, c)) = self.line.char_indices().nth(cursor_col) {
+                self.ranges
+                    .push((Layer::Cursor(cursor_style), start, start + c.len_utf8()));
+            } else {
+                self.cursor_at_end = true;
+            }
         }
-        self.style_begin = style;
+        self.line_style(style);
     }
 
     #[cfg(feature = "search")]
     pub fn search(&mut self, matches: impl Iterator<Item = (usize, usize)>, style: Style) {
         for (start, end) in matches {
             if start != end {
-                self.boundaries.push((Boundary::Search(style), start));
-                self.boundaries.push((Boundary::End, end));
+                self.ranges.push((Layer::Search(style), start, end));
             }
         }
     }
@@ -180,17 +416,160 @@ impl<'a> LineHighlighter<'a> {
             return;
         };
         if start != end {
-            self.boundaries
-                .push((Boundary::Select(self.select_style), start));
-            self.boundaries.push((Boundary::End, end));
+            self.ranges
+                .push((Layer::Select(self.select_style), start, end));
+        }
+    }
+
+    // Select the characters from `start_col` to `end_col` (exclusive) in the line for the block selection. When the line
+    // is shorter than the block, the rest of the block is padded with spaces.
+    pub fn block_selection(&mut self, start_col: usize, end_col: usize) {
+        let line = self.line;
+        let offset = |col: usize| line.char_indices().nth(col).map_or(line.len(), |(i, _)| i);
+        let (start, end) = (offset(start_col), offset(end_col));
+        if start < end {
+            self.ranges
+                .push((Layer::Select(self.select_style), start, end));
+        }
+
+        // The first cell after the end of line is rendered by the end-of-line marker
+        let len = line.chars().count();
+        if end_col <= len {
+            return;
+        }
+        if start_col <= len {
+            self.select_at_end = true;
+            self.select_pad = (0, end_col - len - 1);
+        } else {
+            self.select_pad = (start_col - len - 1, end_col - start_col);
         }
     }
 
+    // Select the ranges of positions on the line at `current_row` in addition to the current selection. Overlapped
+    // ranges are merged since they are rendered with the same selection layer.
+    pub fn extra_selections<'s>(
+        &mut self,
+        current_row: usize,
+        ranges: impl Iterator<Item = &'s ((usize, usize), (usize, usize))>,
+    ) {
+        let line = self.line;
+        let offset = |col: usize| line.char_indices().nth(col).map_or(line.len(), |(i, _)| i);
+        for &((start_row, start_col), (end_row, end_col)) in ranges {
+            if current_row < start_row || end_row < current_row {
+                continue;
+            }
+            let start = if current_row == start_row {
+                offset(start_col)
+            } else {
+                0
+            };
+            let end = if current_row == end_row {
+                offset(end_col)
+            } else {
+                // The newline at the end of line is selected
+                self.select_at_end = true;
+                line.len()
+            };
+            if start < end {
+                self.ranges
+                    .push((Layer::Select(self.select_style), start, end));
+            }
+        }
+    }
+
+    // Overlay the styles of the byte ranges produced by syntax highlighting. They are rendered below other highlights
+    #[cfg(feature = "syntect")]
+    pub fn syntax(&mut self, styles: &[(usize, usize, Style)]) {
+        for &(start, end, style) in styles {
+            if start < end {
+                self.ranges.push((Layer::Highlight(style), start, end));
+            }
+        }
+    }
+
+    // Overlay the highlights on the line at `current_row`. Later highlights take precedence over earlier ones.
+    pub fn highlights<'h>(
+        &mut self,
+        current_row: usize,
+        highlights: impl Iterator<Item = &'h Highlight>,
+    ) {
+        let line = self.line;
+        let offset = |col: usize| line.char_indices().nth(col).map_or(line.len(), |(i, _)| i);
+        for hl in highlights {
+            let (start_row, start_col) = hl.start;
+            let (end_row, end_col) = hl.end;
+            if current_row < start_row || end_row < current_row {
+                continue;
+            }
+            let start = if current_row == start_row {
+                offset(start_col)
+            } else {
+                0
+            };
+            let end = if current_row == end_row {
+                offset(end_col)
+            } else {
+                line.len()
+            };
+            if start < end {
+                self.ranges.push((Layer::Highlight(hl.style), start, end));
+            }
+        }
+    }
+
+    pub fn eol_marker(&mut self, marker: char, style: Style) {
+        self.eol = Some((marker, style));
+    }
+
+    pub fn trailing_whitespace(&mut self, style: Style) {
+        let end = self.line.trim_end().len();
+        if end < self.line.len() {
+            self.ranges
+                .push((Layer::Highlight(style), end, self.line.len()));
+        }
+    }
+
+    // Expand hard tabs in the line with the widths instead of the tab length for elastic tabstops
+    pub fn tab_widths(&mut self, widths: Vec<usize>) {
+        self.tab_widths = Some(widths);
+    }
+
+    // Render control characters in their notation with the style
+    pub fn control_chars(&mut self, style: Style) {
+        self.control = true;
+        for (i, c) in self.line.char_indices() {
+            if control::is_control_char(c) {
+                self.ranges
+                    .push((Layer::Highlight(style), i, i + c.len_utf8()));
+            }
+        }
+    }
+
+    // Highlight the whole word occurrences of the word in the line
+    pub fn word_occurrences(&mut self, word: &str, word_chars: WordChars, style: Style) {
+        for (i, _) in self.line.match_indices(word) {
+            if is_whole_word(self.line, i, i + word.len(), word_chars) {
+                self.ranges
+                    .push((Layer::Highlight(style), i, i + word.len()));
+            }
+        }
+    }
+
+    // Text rendered inline before the character at the byte offset which is not a part of the text
+    pub fn preedit(&mut self, offset: usize, text: &'a str, style: Style) {
+        self.preedit = Some((offset, text, style));
+    }
+
+    // Text rendered after the line which is not a part of the text
+    pub fn suffix(&mut self, text: impl Into<Cow<'a, str>>, style: Style) {
+        self.suffix.push(Span::styled(text, style));
+    }
+
     pub fn into_spans(self) -> Line<'a> {
         let Self {
             line,
             mut spans,
-            mut boundaries,
+            ranges,
             tab_len,
             style_begin,
             cursor_style,
@@ -198,54 +577,124 @@ impl<'a> LineHighlighter<'a> {
             mask,
             select_at_end,
             select_style,
+            select_pad,
+            suffix,
+            eol,
+            mut preedit,
+            tab_widths,
+            control,
         } = self;
         let mut builder = DisplayTextBuilder::new(tab_len, mask);
+        builder.tab_widths = tab_widths.map(Vec::into_iter);
+        builder.control = control;
+
+        // The cursor and the selection at the end of line are rendered on the end-of-line marker
+        let eol_text = eol.map_or(Cow::Borrowed(" "), |(c, _)| Cow::Owned(c.to_string()));
+        let eol_span = if let Some(style) = cursor_style.filter(|_| cursor_at_end) {
+            Some(Span::styled(eol_text, style))
+        } else if select_at_end {
+            Some(Span::styled(eol_text, select_style))
+        } else if select_pad != (0, 0) {
+            let style = eol.map_or(style_begin, |(_, s)| style_begin.patch(s));
+            Some(Span::styled(eol_text, style))
+        } else {
+            eol.map(|(_, s)| Span::styled(eol_text, style_begin.patch(s)))
+        };
+        let pad_spans = [(select_pad.0, style_begin), (select_pad.1, select_style)]
+            .into_iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, style)| Span::styled(" ".repeat(n), style));
+
+        // Right-to-left runs are reordered for display. Masked text does not need it since all characters are the same
+        let runs = if mask.is_none() {
+            visual_runs(line)
+        } else {
+            None
+        };
 
-        if boundaries.is_empty() {
+        if ranges.is_empty() && runs.is_none() && preedit.is_none() {
             let built = builder.build(line);
             if !built.is_empty() {
                 spans.push(Span::styled(built, style_begin));
             }
-            if cursor_at_end {
-                spans.push(Span::styled(" ", cursor_style));
-            } else if select_at_end {
-                spans.push(Span::styled(" ", select_style));
-            }
+            spans.extend(eol_span);
+            spans.extend(pad_spans);
+            spans.extend(suffix);
             return Line::from(spans);
         }
 
-        boundaries.sort_unstable_by(|(l, i), (r, j)| match i.cmp(j) {
-            Ordering::Equal => l.cmp(r),
-            o => o,
-        });
+        let mut boundaries = Vec::with_capacity(ranges.len() * 2 + 2);
+        boundaries.push(0);
+        boundaries.push(line.len());
+        if let Some((offset, _, _)) = preedit {
+            boundaries.push(offset);
+        }
+        for &(_, start, end) in &ranges {
+            boundaries.push(start);
+            boundaries.push(end);
+        }
+        boundaries.sort_unstable();
+        boundaries.dedup();
 
-        let mut style = style_begin;
-        let mut start = 0;
-        let mut stack = vec![];
+        // Sweep the segments with a heap of the ranges covering the current segment. The layer with the highest rank
+        // wins. When multiple layers have the same rank, the last one wins. Ranges ending before the segment are
+        // removed when they come to the top of the heap.
+        let mut order: Vec<usize> = (0..ranges.len()).collect();
+        order.sort_unstable_by_key(|&i| ranges[i].1);
+        let mut order = order.into_iter().peekable();
+        let mut covering = BinaryHeap::with_capacity(ranges.len());
+        let segments = boundaries.windows(2).map(|w| {
+            let (start, end) = (w[0], w[1]);
+            while let Some(i) = order.next_if(|&i| ranges[i].1 <= start) {
+                covering.push((ranges[i].0.rank(), i));
+            }
+            while let Some(&(_, i)) = covering.peek() {
+                if start < ranges[i].2 {
+                    break;
+                }
+                covering.pop();
+            }
+            let style = covering
+                .peek()
+                .map_or(style_begin, |&(_, i)| ranges[i].0.style(style_begin));
+            (start, end, style)
+        });
 
-        for (next_boundary, end) in boundaries {
-            if start < end {
+        if let Some(runs) = runs {
+            let segments: Vec<_> = segments.collect();
+            for (run, rtl) in runs {
+                let mut pieces: Vec<_> = segments
+                    .iter()
+                    .filter(|(s, e, _)| *s < run.end && run.start < *e)
+                    .map(|&(s, e, style)| (cmp::max(s, run.start), cmp::min(e, run.end), style))
+                    .collect();
+                if rtl {
+                    pieces.reverse();
+                }
+                for (start, end, style) in pieces {
+                    push_preedit(&mut spans, &mut builder, &mut preedit, Some(start));
+                    let text = &line[start..end];
+                    let text = if rtl {
+                        let reversed: String = text.chars().rev().collect();
+                        Cow::Owned(builder.build(&reversed).into_owned())
+                    } else {
+                        builder.build(text)
+                    };
+                    spans.push(Span::styled(text, style));
+                }
+            }
+        } else {
+            for (start, end, style) in segments {
+                push_preedit(&mut spans, &mut builder, &mut preedit, Some(start));
                 spans.push(Span::styled(builder.build(&line[start..end]), style));
             }
-
-            style = if let Some(s) = next_boundary.style() {
-                stack.push(style);
-                s
-            } else {
-                stack.pop().unwrap_or(style_begin)
-            };
-            start = end;
         }
+        // The preedit at the end of line
+        push_preedit(&mut spans, &mut builder, &mut preedit, None);
 
-        if start != line.len() {
-            spans.push(Span::styled(builder.build(&line[start..]), style));
-        }
-
-        if cursor_at_end {
-            spans.push(Span::styled(" ", cursor_style));
-        } else if select_at_end {
-            spans.push(Span::styled(" ", select_style));
-        }
+        spans.extend(eol_span);
+        spans.extend(pad_spans);
+        spans.extend(suffix);
 
         Line::from(spans)
     }
@@ -377,7 +826,7 @@ mod tests {
         ];
         for test in tests {
             let (line, want) = test;
-            let lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            let lh = LineHighlighter::new(line, Some(CUR), 4, None, SEL);
             assert_spans(lh, want, test);
         }
     }
@@ -396,24 +845,54 @@ mod tests {
 
         for test in tests {
             let (line, col, want) = test;
-            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            let mut lh = LineHighlighter::new(line, Some(CUR), 4, None, SEL);
             lh.cursor_line(col, LINE);
             assert_spans(lh, want, test);
         }
     }
 
+    #[track_caller]
+    fn assert_gutter_spans<T: Debug>(g: Gutter, want: &[(&str, Style)], context: T) {
+        let line = g.into_spans();
+        let have = line
+            .spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style))
+            .collect::<Vec<_>>();
+        assert_eq!(&have, want, "Test case: {context:?}");
+    }
+
     #[test]
-    fn into_spans_line_number() {
+    fn gutter_line_number() {
         let tests = [
-            (0, 1, &[(" 1 ", LNUM)][..]),
-            (123, 3, &[(" 124 ", LNUM)][..]),
-            (123, 5, &[("   124 ", LNUM)][..]),
+            (0, 1, Alignment::Right, " ", &[(" 1 ", LNUM)][..]),
+            (123, 3, Alignment::Right, " ", &[(" 124 ", LNUM)][..]),
+            (123, 5, Alignment::Right, " ", &[("   124 ", LNUM)][..]),
+            (123, 5, Alignment::Left, " ", &[(" 124   ", LNUM)][..]),
+            (123, 6, Alignment::Center, " ", &[("  124   ", LNUM)][..]),
+            (0, 1, Alignment::Right, "│ ", &[(" 1│ ", LNUM)][..]),
+            (0, 3, Alignment::Left, "", &[(" 1  ", LNUM)][..]),
         ];
         for test in tests {
-            let (row, len, want) = test;
-            let mut lh = LineHighlighter::new("", CUR, 4, None, SEL);
-            lh.line_number(row, len, LNUM);
-            assert_spans(lh, want, test);
+            let (row, len, align, sep, want) = test;
+            let mut g = Gutter::default();
+            g.line_number(row, len, align, sep, LNUM);
+            assert_gutter_spans(g, want, test);
+        }
+    }
+
+    #[test]
+    fn gutter_sign() {
+        let tests = [
+            (None, &[(" ", DEFAULT), (" 1 ", LNUM)][..]),
+            (Some(('●', CUR)), &[("●", CUR), (" 1 ", LNUM)][..]),
+        ];
+        for test in tests {
+            let (sign, want) = test;
+            let mut g = Gutter::default();
+            g.sign(sign);
+            g.line_number(0, 1, Alignment::Right, " ", LNUM);
+            assert_gutter_spans(g, want, test);
         }
     }
 
@@ -476,7 +955,7 @@ mod tests {
 
         for test in tests {
             let (line, matches, want) = test;
-            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            let mut lh = LineHighlighter::new(line, Some(CUR), 4, None, SEL);
             lh.search(matches.iter().copied(), SEARCH);
             assert_spans(lh, want, test);
         }
@@ -516,19 +995,204 @@ mod tests {
 
         for test in tests {
             let (line, (row, start_row, start_off, end_row, end_off), want) = test;
-            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            let mut lh = LineHighlighter::new(line, Some(CUR), 4, None, SEL);
             lh.selection(row, start_row, start_off, end_row, end_off);
             assert_spans(lh, want, test);
         }
     }
 
+    #[test]
+    fn into_spans_highlights() {
+        const RED: Style = Style::new().fg(Color::Red);
+        const BLUE: Style = Style::new().fg(Color::Blue);
+        let hl = |start, end, style| Highlight { start, end, style };
+
+        let tests = [
+            // (line, row, highlights, want)
+            ("abcde", 0, vec![], &[("abcde", DEFAULT)][..]),
+            (
+                "abcde",
+                0,
+                vec![hl((0, 1), (0, 3), RED)],
+                &[("a", DEFAULT), ("bc", RED), ("de", DEFAULT)][..],
+            ),
+            // Highlights on other lines
+            (
+                "abcde",
+                1,
+                vec![hl((0, 1), (0, 3), RED), hl((2, 0), (2, 3), RED)],
+                &[("abcde", DEFAULT)][..],
+            ),
+            // Later one wins on overlapping
+            (
+                "abcde",
+                0,
+                vec![hl((0, 0), (0, 3), RED), hl((0, 2), (0, 5), BLUE)],
+                &[("ab", RED), ("c", BLUE), ("de", BLUE)][..],
+            ),
+            (
+                "abcde",
+                0,
+                vec![hl((0, 2), (0, 5), BLUE), hl((0, 0), (0, 3), RED)],
+                &[("ab", RED), ("c", RED), ("de", BLUE)][..],
+            ),
+            (
+                "abcde",
+                0,
+                vec![hl((0, 0), (0, 5), RED), hl((0, 1), (0, 2), BLUE)],
+                &[("a", RED), ("b", BLUE), ("cde", RED)][..],
+            ),
+            // Multi-line highlights
+            (
+                "abcde",
+                1,
+                vec![hl((0, 2), (2, 1), RED)],
+                &[("abcde", RED)][..],
+            ),
+            (
+                "abcde",
+                1,
+                vec![hl((0, 2), (1, 2), RED)],
+                &[("ab", RED), ("cde", DEFAULT)][..],
+            ),
+            (
+                "abcde",
+                1,
+                vec![hl((1, 3), (2, 0), RED)],
+                &[("abc", DEFAULT), ("de", RED)][..],
+            ),
+            // Columns are counted in characters
+            (
+                "あいう\t",
+                0,
+                vec![hl((0, 1), (0, 2), RED), hl((0, 3), (0, 10), BLUE)],
+                &[("あ", DEFAULT), ("い", RED), ("う", DEFAULT), ("  ", BLUE)][..],
+            ),
+            // Empty range
+            (
+                "abcde",
+                0,
+                vec![hl((0, 2), (0, 2), RED)],
+                &[("abcde", DEFAULT)][..],
+            ),
+        ];
+
+        for test in tests {
+            let (line, row, highlights, want) = &test;
+            let mut lh = LineHighlighter::new(line, Some(CUR), 4, None, SEL);
+            lh.highlights(*row, highlights.iter());
+            assert_spans(lh, want, &test);
+        }
+
+        // Highlights are patched on the cursor line style and the cursor is rendered on top of them
+        let mut lh = LineHighlighter::new("abc", Some(CUR), 4, None, SEL);
+        lh.cursor_line(1, LINE);
+        lh.highlights(0, [hl((0, 0), (0, 3), RED)].iter());
+        let want = [("a", LINE.patch(RED)), ("b", CUR), ("c", LINE.patch(RED))];
+        assert_spans(lh, &want, "cursor line");
+    }
+
+    #[test]
+    fn highlights_on_row() {
+        let hl = |start, end| Highlight {
+            start,
+            end,
+            style: Style::default(),
+        };
+        let highlights = Highlights::new(vec![
+            hl((5, 0), (5, 1)),
+            hl((0, 0), (3, 1)),
+            hl((2, 0), (2, 1)),
+            hl((2, 3), (6, 0)),
+        ]);
+        let starts = |row| -> Vec<_> { highlights.on_row(row).map(|h| h.start).collect() };
+        // Highlights are in the order they were set
+        assert_eq!(starts(0), [(0, 0)]);
+        assert_eq!(starts(2), [(0, 0), (2, 0), (2, 3)]);
+        assert_eq!(starts(4), [(2, 3)]);
+        assert_eq!(starts(5), [(5, 0), (2, 3)]);
+        assert_eq!(starts(7), []);
+        assert!(Highlights::default().on_row(0).next().is_none());
+    }
+
+    #[test]
+    fn into_spans_eol_marker() {
+        const EOL: Style = Style::new().fg(Color::DarkGray);
+        let tests = [
+            ("no cursor", None, &[("abc", DEFAULT), ("$", EOL)][..]),
+            (
+                "cursor in line",
+                Some(1),
+                &[("a", LINE), ("b", CUR), ("c", LINE), ("$", LINE.patch(EOL))][..],
+            ),
+            ("cursor at end", Some(3), &[("abc", LINE), ("$", CUR)][..]),
+        ];
+        for test in tests {
+            let (_, cursor, want) = test;
+            let mut lh = LineHighlighter::new("abc", Some(CUR), 4, None, SEL);
+            if let Some(col) = cursor {
+                lh.cursor_line(col, LINE);
+            }
+            lh.eol_marker('$', EOL);
+            assert_spans(lh, want, test.0);
+        }
+    }
+
+    #[test]
+    fn into_spans_masked() {
+        let tests = [
+            (
+                "cursor",
+                {
+                    let mut lh = LineHighlighter::new("aあ\tb", Some(CUR), 4, Some('*'), SEL);
+                    lh.cursor_line(1, LINE);
+                    lh
+                },
+                &[("*", LINE), ("*", CUR), ("**", LINE)][..],
+            ),
+            (
+                "cursor at end",
+                {
+                    let mut lh = LineHighlighter::new("あい", Some(CUR), 4, Some('*'), SEL);
+                    lh.cursor_line(2, LINE);
+                    lh
+                },
+                &[("**", LINE), (" ", CUR)][..],
+            ),
+            (
+                "selection",
+                {
+                    let mut lh = LineHighlighter::new("a\tbc", Some(CUR), 4, Some('*'), SEL);
+                    lh.selection(0, 0, 1, 0, 3);
+                    lh
+                },
+                &[("*", DEFAULT), ("**", SEL), ("*", DEFAULT)][..],
+            ),
+            #[cfg(feature = "search")]
+            (
+                "search",
+                {
+                    let mut lh = LineHighlighter::new("abあc", Some(CUR), 4, Some('*'), SEL);
+                    lh.search([(1, 5)].into_iter(), SEARCH);
+                    lh
+                },
+                &[("*", DEFAULT), ("**", SEARCH), ("*", DEFAULT)][..],
+            ),
+        ];
+
+        for test in tests {
+            let (_, lh, want) = test;
+            assert_spans(lh, want, test.0);
+        }
+    }
+
     #[test]
     fn into_spans_mixed_highlights() {
         let tests = [
             (
                 "cursor on selection",
                 {
-                    let mut lh = LineHighlighter::new("abcde", CUR, 4, None, SEL);
+                    let mut lh = LineHighlighter::new("abcde", Some(CUR), 4, None, SEL);
                     lh.cursor_line(2, LINE);
                     lh.selection(0, 0, 1, 0, 4);
                     lh
@@ -539,7 +1203,7 @@ mod tests {
             (
                 "cursor + selection + search",
                 {
-                    let mut lh = LineHighlighter::new("abcdefg", CUR, 4, None, SEL);
+                    let mut lh = LineHighlighter::new("abcdefg", Some(CUR), 4, None, SEL);
                     lh.cursor_line(3, LINE);
                     lh.selection(0, 0, 2, 0, 5);
                     lh.search([(1, 2), (5, 6)].into_iter(), SEARCH);
@@ -558,7 +1222,7 @@ mod tests {
             (
                 "selection + cursor at end",
                 {
-                    let mut lh = LineHighlighter::new("ab", CUR, 4, None, SEL);
+                    let mut lh = LineHighlighter::new("ab", Some(CUR), 4, None, SEL);
                     lh.cursor_line(2, LINE);
                     lh.selection(0, 0, 1, 2, 0);
                     lh
@@ -568,7 +1232,7 @@ mod tests {
             (
                 "cursor at start of selection",
                 {
-                    let mut lh = LineHighlighter::new("abcd", CUR, 4, None, SEL);
+                    let mut lh = LineHighlighter::new("abcd", Some(CUR), 4, None, SEL);
                     lh.cursor_line(1, LINE);
                     lh.selection(0, 0, 1, 0, 3);
                     lh
@@ -578,7 +1242,7 @@ mod tests {
             (
                 "cursor at end of selection",
                 {
-                    let mut lh = LineHighlighter::new("abcd", CUR, 4, None, SEL);
+                    let mut lh = LineHighlighter::new("abcd", Some(CUR), 4, None, SEL);
                     lh.cursor_line(2, LINE);
                     lh.selection(0, 0, 1, 0, 3);
                     lh
@@ -588,13 +1252,41 @@ mod tests {
             (
                 "cursor covers selection",
                 {
-                    let mut lh = LineHighlighter::new("abc", CUR, 4, None, SEL);
+                    let mut lh = LineHighlighter::new("abc", Some(CUR), 4, None, SEL);
                     lh.cursor_line(1, LINE);
                     lh.selection(0, 0, 1, 0, 2);
                     lh
                 },
                 &[("a", LINE), ("b", CUR), ("c", LINE)][..],
             ),
+            (
+                "block selection",
+                {
+                    let mut lh = LineHighlighter::new("abcde", Some(CUR), 4, None, SEL);
+                    lh.block_selection(1, 3);
+                    lh
+                },
+                &[("a", DEFAULT), ("bc", SEL), ("de", DEFAULT)][..],
+            ),
+            (
+                "block selection padded after end of line",
+                {
+                    let mut lh = LineHighlighter::new("abc", Some(CUR), 4, None, SEL);
+                    lh.block_selection(1, 5);
+                    lh
+                },
+                &[("a", DEFAULT), ("bc", SEL), (" ", SEL), (" ", SEL)][..],
+            ),
+            (
+                "block selection after end of line",
+                {
+                    let mut lh = LineHighlighter::new("a", Some(CUR), 4, None, SEL);
+                    lh.cursor_line(1, LINE);
+                    lh.block_selection(3, 5);
+                    lh
+                },
+                &[("a", LINE), (" ", CUR), (" ", LINE), ("  ", SEL)][..],
+            ),
         ];
 
         for (what, lh, want) in tests {
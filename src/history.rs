@@ -1,4 +1,4 @@
-use crate::util::Pos;
+use crate::util::{Pos, RowShift};
 use std::collections::VecDeque;
 
 #[derive(Clone, Debug)]
@@ -11,6 +11,8 @@ pub enum EditKind {
     DeleteStr(String),
     InsertChunk(Vec<String>),
     DeleteChunk(Vec<String>),
+    // Replace the whole text. The first element is the text before the edit and the second is after
+    ReplaceAll(Vec<String>, Vec<String>),
 }
 
 impl EditKind {
@@ -61,7 +63,7 @@ impl EditKind {
                 // Remove middle lines of chunk
                 let mut last_line = lines
                     .drain(after.row + 1..after.row + c.len())
-                    .last()
+                    .next_back()
                     .unwrap();
                 // Remove last line of chunk
                 last_line.drain(..c[c.len() - 1].len());
@@ -71,6 +73,36 @@ impl EditKind {
                 first_line.truncate(after.offset);
                 first_line.push_str(&last_line);
             }
+            EditKind::ReplaceAll(_, replaced) => {
+                lines.clone_from(replaced);
+            }
+        }
+    }
+
+    // Lines moved by applying the edit with the same positions as `EditKind::apply`. When the text is inserted at the
+    // head of a line or the deleted text starts at the head of a line, the line is moved instead of being modified
+    pub(crate) fn row_shift(&self, before: &Pos, after: &Pos) -> Option<RowShift> {
+        let shift = |row: usize, removed: usize, added: usize| RowShift {
+            row,
+            removed,
+            added,
+        };
+        match self {
+            EditKind::InsertNewline => Some(shift(before.row + (before.offset > 0) as usize, 0, 1)),
+            // `after.offset` is the length of the previous line before joining the lines
+            EditKind::DeleteNewline => Some(shift(before.row - (after.offset == 0) as usize, 1, 0)),
+            EditKind::InsertChunk(c) => Some(shift(
+                before.row + (before.offset > 0) as usize,
+                0,
+                c.len() - 1,
+            )),
+            EditKind::DeleteChunk(c) => Some(shift(
+                after.row + (after.offset > 0) as usize,
+                c.len() - 1,
+                0,
+            )),
+            EditKind::ReplaceAll(before, after) => RowShift::between(before, after),
+            _ => None,
         }
     }
 
@@ -85,15 +117,24 @@ impl EditKind {
             DeleteStr(s) => InsertStr(s),
             InsertChunk(c) => DeleteChunk(c),
             DeleteChunk(c) => InsertChunk(c),
+            ReplaceAll(before, after) => ReplaceAll(after, before),
         }
     }
 }
 
+// Cursor position and the start of the text selection
+pub type SelectionState = ((usize, usize), Option<(usize, usize)>);
+
 #[derive(Clone, Debug)]
 pub struct Edit {
     kind: EditKind,
     before: Pos,
     after: Pos,
+    // When true, this edit is undone and redone together with the previous edit
+    joined: bool,
+    // Cursor and selection restored by undo/redo. When `None`, the position of the edit is restored without selection
+    selection_before: Option<SelectionState>,
+    selection_after: Option<SelectionState>,
 }
 
 impl Edit {
@@ -102,23 +143,32 @@ impl Edit {
             kind,
             before,
             after,
+            joined: false,
+            selection_before: None,
+            selection_after: None,
         }
     }
 
-    pub fn redo(&self, lines: &mut Vec<String>) {
+    pub fn redo(&self, lines: &mut Vec<String>) -> Option<RowShift> {
         self.kind.apply(lines, &self.before, &self.after);
+        self.kind.row_shift(&self.before, &self.after)
     }
 
-    pub fn undo(&self, lines: &mut Vec<String>) {
-        self.kind.invert().apply(lines, &self.after, &self.before); // Undo is redo of inverted edit
+    pub fn undo(&self, lines: &mut Vec<String>) -> Option<RowShift> {
+        // Undo is redo of inverted edit
+        let kind = self.kind.invert();
+        kind.apply(lines, &self.after, &self.before);
+        kind.row_shift(&self.after, &self.before)
     }
 
-    pub fn cursor_before(&self) -> (usize, usize) {
-        (self.before.row, self.before.col)
+    pub fn selection_before(&self) -> SelectionState {
+        let cursor = (self.before.row, self.before.col);
+        self.selection_before.unwrap_or((cursor, None))
     }
 
-    pub fn cursor_after(&self) -> (usize, usize) {
-        (self.after.row, self.after.col)
+    pub fn selection_after(&self) -> SelectionState {
+        let cursor = (self.after.row, self.after.col);
+        self.selection_after.unwrap_or((cursor, None))
     }
 }
 
@@ -127,6 +177,10 @@ pub struct History {
     index: usize,
     max_items: usize,
     edits: VecDeque<Edit>,
+    // Index of the saved point. `None` means the saved point is no longer reachable by undo/redo
+    saved: Option<usize>,
+    // Number of edits pushed so far. Unlike `index`, it is not affected by dropping old edits
+    pushed: u64,
 }
 
 impl History {
@@ -135,47 +189,152 @@ impl History {
             index: 0,
             max_items,
             edits: VecDeque::new(),
+            saved: Some(0),
+            pushed: 0,
         }
     }
 
     pub fn push(&mut self, edit: Edit) {
         if self.max_items == 0 {
+            self.saved = None;
             return;
         }
 
         if self.edits.len() == self.max_items {
             self.edits.pop_front();
             self.index = self.index.saturating_sub(1);
+            self.saved = self.saved.and_then(|i| i.checked_sub(1));
         }
 
         if self.index < self.edits.len() {
             self.edits.truncate(self.index);
+            // The saved point was in the discarded edits
+            if matches!(self.saved, Some(i) if i > self.index) {
+                self.saved = None;
+            }
         }
 
         self.index += 1;
         self.edits.push_back(edit);
+        self.pushed = self.pushed.wrapping_add(1);
     }
 
-    pub fn redo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
-        if self.index == self.edits.len() {
-            return None;
+    // Lines moved by the redone edits are pushed to `shifts` in order
+    pub fn redo(
+        &mut self,
+        lines: &mut Vec<String>,
+        shifts: &mut Vec<RowShift>,
+    ) -> Option<SelectionState> {
+        let mut selection = None;
+        while let Some(edit) = self.edits.get(self.index) {
+            if selection.is_some() && !edit.joined {
+                break;
+            }
+            shifts.extend(edit.redo(lines));
+            self.index += 1;
+            selection = Some(edit.selection_after());
+        }
+        selection
+    }
+
+    // Lines moved by the undone edits are pushed to `shifts` in order
+    pub fn undo(
+        &mut self,
+        lines: &mut Vec<String>,
+        shifts: &mut Vec<RowShift>,
+    ) -> Option<SelectionState> {
+        let mut selection = None;
+        while let Some(index) = self.index.checked_sub(1) {
+            let edit = &self.edits[index];
+            shifts.extend(edit.undo(lines));
+            self.index = index;
+            selection = Some(edit.selection_before());
+            if !edit.joined {
+                break;
+            }
+        }
+        selection
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    // Join the edit at the index with the previous one so that they are undone and redone at once
+    pub fn join(&mut self, index: usize) {
+        if let Some(edit) = self.edits.get_mut(index) {
+            edit.joined = true;
+        }
+    }
+
+    // Join the last edit with the previous one
+    pub fn join_last(&mut self) {
+        if let Some(i) = self.index.checked_sub(1) {
+            self.join(i);
         }
-        let edit = &self.edits[self.index];
-        edit.redo(lines);
-        self.index += 1;
-        Some(edit.cursor_after())
     }
 
-    pub fn undo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
-        self.index = self.index.checked_sub(1)?;
-        let edit = &self.edits[self.index];
-        edit.undo(lines);
-        Some(edit.cursor_before())
+    // Mark to pass to `History::join_since`
+    pub fn pushed(&self) -> u64 {
+        self.pushed
+    }
+
+    // Join all edits pushed after the mark returned from `History::pushed` so that they are undone and redone at once
+    pub fn join_since(&mut self, mark: u64) {
+        let count = self.pushed.wrapping_sub(mark) as usize;
+        for i in (self.index + 1).saturating_sub(count)..self.index {
+            self.join(i);
+        }
+    }
+
+    // Set the cursor and the selection restored by undoing and redoing the edits pushed after the mark returned from
+    // `History::pushed`. The state before is set to the first edit and the state after is set to the last edit.
+    pub fn set_selection_since(
+        &mut self,
+        mark: u64,
+        before: SelectionState,
+        after: SelectionState,
+    ) {
+        let count = self.pushed.wrapping_sub(mark) as usize;
+        if count == 0 {
+            return;
+        }
+        if let Some(edit) = self.edits.get_mut(self.index.saturating_sub(count)) {
+            edit.selection_before = Some(before);
+        }
+        if let Some(edit) = self
+            .index
+            .checked_sub(1)
+            .and_then(|i| self.edits.get_mut(i))
+        {
+            edit.selection_after = Some(after);
+        }
     }
 
     pub fn max_items(&self) -> usize {
         self.max_items
     }
+
+    pub fn set_max_items(&mut self, max_items: usize) {
+        self.clear(false);
+        self.max_items = max_items;
+    }
+
+    // Remove all edits. `modified` means the text was modified without recording the history.
+    pub fn clear(&mut self, modified: bool) {
+        let saved = !modified && !self.is_modified();
+        self.index = 0;
+        self.edits.clear();
+        self.saved = saved.then(|| 0);
+    }
+
+    pub fn save(&mut self) {
+        self.saved = Some(self.index);
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.saved != Some(self.index)
+    }
 }
 
 #[cfg(test)]
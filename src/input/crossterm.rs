@@ -1,7 +1,8 @@
 use super::{Input, Key};
 use crate::crossterm::event::{
-    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind,
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
+use std::convert::TryFrom;
 
 impl From<Event> for Input {
     /// Convert [`crossterm::event::Event`] into [`Input`].
@@ -25,8 +26,9 @@ impl From<KeyCode> for Key {
             KeyCode::Right => Key::Right,
             KeyCode::Up => Key::Up,
             KeyCode::Down => Key::Down,
-            KeyCode::Tab => Key::Tab,
+            KeyCode::Tab | KeyCode::BackTab => Key::Tab,
             KeyCode::Delete => Key::Delete,
+            KeyCode::Insert => Key::Insert,
             KeyCode::Home => Key::Home,
             KeyCode::End => Key::End,
             KeyCode::PageUp => Key::PageUp,
@@ -49,7 +51,8 @@ impl From<KeyEvent> for Input {
 
         let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
         let alt = key.modifiers.contains(KeyModifiers::ALT);
-        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        // Shift is not always reported with BackTab
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT) || key.code == KeyCode::BackTab;
         let key = Key::from(key.code);
 
         Self {
@@ -88,6 +91,27 @@ impl From<MouseEvent> for Input {
     }
 }
 
+impl TryFrom<MouseEvent> for super::MouseEvent {
+    type Error = MouseEvent;
+
+    /// Convert [`crossterm::event::MouseEvent`] of the left button into [`MouseEvent`](super::MouseEvent) to pass it to
+    /// [`TextArea::on_mouse`](crate::TextArea::on_mouse). Events of other buttons are returned as the error.
+    fn try_from(mouse: MouseEvent) -> Result<Self, Self::Error> {
+        let kind = match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => super::MouseEventKind::Down,
+            MouseEventKind::Drag(MouseButton::Left) => super::MouseEventKind::Drag,
+            MouseEventKind::Up(MouseButton::Left) => super::MouseEventKind::Up,
+            _ => return Err(mouse),
+        };
+        Ok(Self {
+            kind,
+            column: mouse.column,
+            row: mouse.row,
+            shift: mouse.modifiers.contains(KeyModifiers::SHIFT),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,7 +170,33 @@ mod tests {
                 key_event(KeyCode::NumLock, KeyModifiers::CONTROL),
                 input(Key::Null, true, false, false),
             ),
+            (
+                key_event(KeyCode::BackTab, KeyModifiers::empty()),
+                input(Key::Tab, false, false, true),
+            ),
+        ] {
+            assert_eq!(Input::from(from), to, "{:?} -> {:?}", from, to);
+        }
+
+        // Special keys without modifiers
+        for (code, key) in [
+            (KeyCode::Up, Key::Up),
+            (KeyCode::Down, Key::Down),
+            (KeyCode::Left, Key::Left),
+            (KeyCode::Right, Key::Right),
+            (KeyCode::Home, Key::Home),
+            (KeyCode::End, Key::End),
+            (KeyCode::PageUp, Key::PageUp),
+            (KeyCode::PageDown, Key::PageDown),
+            (KeyCode::Backspace, Key::Backspace),
+            (KeyCode::Delete, Key::Delete),
+            (KeyCode::Insert, Key::Insert),
+            (KeyCode::Tab, Key::Tab),
+            (KeyCode::Enter, Key::Enter),
+            (KeyCode::Esc, Key::Esc),
         ] {
+            let from = key_event(code, KeyModifiers::empty());
+            let to = input(key, false, false, false);
             assert_eq!(Input::from(from), to, "{:?} -> {:?}", from, to);
         }
     }
@@ -186,6 +236,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mouse_to_mouse_event() {
+        use crate::input::{MouseEvent as Mouse, MouseEventKind as Kind};
+
+        for (kind, to) in [
+            (MouseEventKind::Down(MouseButton::Left), Kind::Down),
+            (MouseEventKind::Drag(MouseButton::Left), Kind::Drag),
+            (MouseEventKind::Up(MouseButton::Left), Kind::Up),
+        ] {
+            let from = MouseEvent {
+                kind,
+                column: 3,
+                row: 4,
+                modifiers: KeyModifiers::SHIFT,
+            };
+            let to = Mouse {
+                kind: to,
+                column: 3,
+                row: 4,
+                shift: true,
+            };
+            assert_eq!(Mouse::try_from(from), Ok(to), "{:?} -> {:?}", from, to);
+        }
+
+        for kind in [
+            MouseEventKind::Down(MouseButton::Right),
+            MouseEventKind::Drag(MouseButton::Middle),
+            MouseEventKind::Moved,
+            MouseEventKind::ScrollUp,
+        ] {
+            let from = mouse_event(kind, KeyModifiers::empty());
+            assert_eq!(Mouse::try_from(from), Err(from), "{:?}", from);
+        }
+    }
+
     #[test]
     fn event_to_input() {
         for (from, to) in [
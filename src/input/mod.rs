@@ -1,10 +1,13 @@
 #[cfg(any(feature = "crossterm", feature = "tuirs-crossterm"))]
 mod crossterm;
+mod parse;
 #[cfg(any(feature = "termion", feature = "tuirs-termion"))]
 mod termion;
 #[cfg(feature = "termwiz")]
 mod termwiz;
 
+pub use parse::ParseInputError;
+
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
 
@@ -12,7 +15,7 @@ use arbitrary::Arbitrary;
 ///
 /// This type is marked as `#[non_exhaustive]` since more keys may be supported in the future.
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub enum Key {
     /// Normal letter key input
@@ -35,6 +38,8 @@ pub enum Key {
     Tab,
     /// Delete key
     Delete,
+    /// Insert key
+    Insert,
     /// Home key
     Home,
     /// End key
@@ -100,8 +105,17 @@ impl Default for Key {
 ///     shift: false,
 /// });
 /// ```
-#[derive(Debug, Clone, Default, PartialEq, Hash)]
+///
+/// `Input` can also be parsed from a key combination such as `"ctrl+shift+k"` with [`Input::parse`]. When `serde`
+/// feature is enabled, `Input` is serialized and deserialized in the same notation so that it can be used as a key of
+/// key bindings in configuration files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
 pub struct Input {
     /// Typed key.
     pub key: Key,
@@ -113,6 +127,135 @@ pub struct Input {
     pub shift: bool,
 }
 
+impl From<Key> for Input {
+    /// Create an input of the key without any modifier keys.
+    /// ```
+    /// use tui_textarea::{Input, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.input(Input::from(Key::Char('a')));
+    /// assert_eq!(textarea.lines(), ["a"]);
+    /// ```
+    fn from(key: Key) -> Self {
+        Self {
+            key,
+            ..Self::default()
+        }
+    }
+}
+
+/// Backend-agnostic kind of a mouse event with the left button. See [`MouseEvent`].
+///
+/// This type is marked as `#[non_exhaustive]` since more kinds may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum MouseEventKind {
+    /// The left button was pressed
+    Down,
+    /// The mouse moved while the left button was pressed
+    Drag,
+    /// The left button was released
+    Up,
+}
+
+/// Backend-agnostic mouse event handled by [`TextArea::on_mouse`](crate::TextArea::on_mouse). `column` and `row` are
+/// the position of the mouse cursor on the terminal screen. `crossterm` and `termion` features enable conversion from
+/// their mouse events of the left button with [`TryFrom`].
+/// ```
+/// use tui_textarea::{MouseEvent, MouseEventKind};
+///
+/// // Left click at the top-left cell of the screen
+/// let event = MouseEvent {
+///     kind: MouseEventKind::Down,
+///     column: 0,
+///     row: 0,
+///     shift: false,
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct MouseEvent {
+    /// Kind of the event.
+    pub kind: MouseEventKind,
+    /// Column of the mouse cursor on the screen.
+    pub column: u16,
+    /// Row of the mouse cursor on the screen.
+    pub row: u16,
+    /// Shift modifier key. `true` means Shift key was pressed.
+    pub shift: bool,
+}
+
+/// Outcome of handling an [`Input`] by [`TextArea::input`](crate::TextArea::input) and
+/// [`TextArea::input_without_shortcuts`](crate::TextArea::input_without_shortcuts).
+///
+/// This type is marked as `#[non_exhaustive]` since more outcomes may be reported in the future.
+/// ```
+/// use tui_textarea::{TextArea, Input, InputResult, Key};
+///
+/// let mut textarea = TextArea::from(["abc"]);
+///
+/// // Typing a character modifies the text
+/// let input = Input { key: Key::Char('x'), ctrl: false, alt: false, shift: false };
+/// assert_eq!(textarea.input(input), InputResult::Changed);
+///
+/// // Moving the cursor does not modify the text
+/// let input = Input { key: Key::Left, ctrl: false, alt: false, shift: false };
+/// assert_eq!(textarea.input(input), InputResult::CursorMoved);
+///
+/// // Keys without any mapping are reported so that they can be handled by the caller
+/// let input = Input { key: Key::F(1), ctrl: false, alt: false, shift: false };
+/// assert_eq!(textarea.input(input), InputResult::Unhandled);
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputResult {
+    /// The text contents of the textarea were modified.
+    Changed,
+    /// The text contents were not modified but the cursor moved. Text selection with the Shift modifier is also
+    /// reported as this variant.
+    CursorMoved,
+    /// The input was handled but neither the text contents nor the cursor changed. For example, copying text or
+    /// moving the cursor at the edge of the text.
+    Handled,
+    /// No key mapping is defined for the input. The caller may want to handle the input by itself.
+    Unhandled,
+    /// A key to insert a newline such as Enter was input while the textarea is in single-line mode. The text contents
+    /// are not modified. See [`TextArea::set_single_line`](crate::TextArea::set_single_line).
+    Submit,
+}
+
+impl InputResult {
+    /// Return if the input modified the text contents or not.
+    /// ```
+    /// use tui_textarea::InputResult;
+    ///
+    /// assert!(InputResult::Changed.changed());
+    /// assert!(!InputResult::CursorMoved.changed());
+    /// ```
+    pub fn changed(self) -> bool {
+        self == Self::Changed
+    }
+
+    /// Return if the input was handled by the textarea or not.
+    /// ```
+    /// use tui_textarea::InputResult;
+    ///
+    /// assert!(InputResult::Handled.handled());
+    /// assert!(!InputResult::Unhandled.handled());
+    /// ```
+    pub fn handled(self) -> bool {
+        self != Self::Unhandled
+    }
+}
+
+impl From<InputResult> for bool {
+    /// Convert [`InputResult`] into `bool` which represents if the text was modified or not.
+    fn from(result: InputResult) -> Self {
+        result.changed()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
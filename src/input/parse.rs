@@ -0,0 +1,377 @@
+use super::{Input, Key};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when a string cannot be parsed as an [`Input`]. See [`Input::parse`].
+/// ```
+/// use tui_textarea::Input;
+///
+/// let err = Input::parse("ctrl+foo").unwrap_err();
+/// assert_eq!(err.to_string(), r#"unknown key "foo" in "ctrl+foo""#);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseInputError {
+    input: String,
+    reason: String,
+}
+
+impl ParseInputError {
+    fn new(input: &str, reason: String) -> Self {
+        Self {
+            input: input.to_string(),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for ParseInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in {:?}", self.reason, self.input)
+    }
+}
+
+impl Error for ParseInputError {}
+
+// Names of keys other than characters and function keys. The first name of each key is used for formatting
+const KEY_NAMES: &[(Key, &[&str])] = &[
+    (Key::Backspace, &["backspace", "bs"]),
+    (Key::Enter, &["enter", "return", "ret", "cr"]),
+    (Key::Left, &["left"]),
+    (Key::Right, &["right"]),
+    (Key::Up, &["up"]),
+    (Key::Down, &["down"]),
+    (Key::Tab, &["tab"]),
+    (Key::Delete, &["delete", "del"]),
+    (Key::Insert, &["insert", "ins"]),
+    (Key::Home, &["home"]),
+    (Key::End, &["end"]),
+    (Key::PageUp, &["pageup", "pgup"]),
+    (Key::PageDown, &["pagedown", "pgdown", "pgdn"]),
+    (Key::Esc, &["esc", "escape"]),
+    (Key::Copy, &["copy"]),
+    (Key::Cut, &["cut"]),
+    (Key::Paste, &["paste"]),
+    (Key::MouseScrollDown, &["scrolldown"]),
+    (Key::MouseScrollUp, &["scrollup"]),
+    (Key::Char(' '), &["space"]),
+    (Key::Null, &["null"]),
+];
+
+fn parse_key(name: &str) -> Option<Key> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(Key::Char(c));
+    }
+    let lower = name.to_ascii_lowercase();
+    if let Some((key, _)) = KEY_NAMES
+        .iter()
+        .find(|(_, names)| names.contains(&lower.as_str()))
+    {
+        return Some(*key);
+    }
+    let n = lower.strip_prefix('f')?.parse().ok()?;
+    Some(Key::F(n))
+}
+
+impl fmt::Display for Key {
+    /// Format the key in the notation accepted by [`Input::parse`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((_, names)) = KEY_NAMES.iter().find(|(key, _)| key == self) {
+            return f.write_str(names[0]);
+        }
+        match self {
+            Key::Char(c) => write!(f, "{}", c),
+            Key::F(n) => write!(f, "f{}", n),
+            _ => f.write_str("null"),
+        }
+    }
+}
+
+impl Input {
+    /// Parse a key combination such as `"ctrl+shift+k"` into an [`Input`]. This is useful for loading key bindings
+    /// from a configuration file.
+    ///
+    /// The string consists of modifiers `ctrl`, `alt`, and `shift` followed by a key, separated with `+`. Modifiers
+    /// and key names are case-insensitive. A key is a single character, a function key like `f1`, or a name such as
+    /// `enter`, `esc`, `tab`, `backspace`, `delete`, `insert`, `home`, `end`, `pageup`, `pagedown`, `up`, `down`,
+    /// `left`, `right`, and `space`. The `+` key is written as `ctrl++`. Formatting an [`Input`] with
+    /// [`Display`](fmt::Display) produces the same notation so that the result can be parsed back.
+    /// ```
+    /// use tui_textarea::{Input, Key};
+    ///
+    /// let input = Input::parse("ctrl+shift+k").unwrap();
+    /// assert_eq!(input, Input { key: Key::Char('k'), ctrl: true, alt: false, shift: true });
+    ///
+    /// let input: Input = "Alt+PageDown".parse().unwrap();
+    /// assert_eq!(input, Input { key: Key::PageDown, ctrl: false, alt: true, shift: false });
+    /// assert_eq!(input.to_string(), "alt+pagedown");
+    ///
+    /// assert!(Input::parse("ctrl+").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, ParseInputError> {
+        // The last `+` is the key itself when the string ends with `++` or is `+`
+        let (mods, key) = match s.strip_suffix('+') {
+            Some(rest) if rest.is_empty() || rest.ends_with('+') => (rest, "+"),
+            _ => match s.rfind('+') {
+                Some(i) => (&s[..i + 1], &s[i + 1..]),
+                None => ("", s),
+            },
+        };
+
+        if key.is_empty() {
+            return Err(ParseInputError::new(s, "missing key".to_string()));
+        }
+        let key = match parse_key(key) {
+            Some(key) => key,
+            None => return Err(ParseInputError::new(s, format!("unknown key {:?}", key))),
+        };
+
+        let mut input = Input {
+            key,
+            ..Default::default()
+        };
+        for m in mods.split_terminator('+') {
+            let flag = match m.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => &mut input.ctrl,
+                "alt" | "meta" => &mut input.alt,
+                "shift" => &mut input.shift,
+                _ => {
+                    let reason = format!("unknown modifier {:?}", m);
+                    return Err(ParseInputError::new(s, reason));
+                }
+            };
+            if *flag {
+                let reason = format!("duplicate modifier {:?}", m);
+                return Err(ParseInputError::new(s, reason));
+            }
+            *flag = true;
+        }
+        Ok(input)
+    }
+
+    /// Parse a sequence of key inputs written in the key notation of Vim such as `"ihello<Esc>dd"`. Characters outside
+    /// `<` and `>` are typed as they are. `<...>` is a special key like `<Esc>`, `<CR>`, `<BS>`, `<Tab>`, `<Del>`,
+    /// `<Up>`, or `<F1>`, optionally with modifiers like `<C-a>` (Ctrl), `<A-x>` or `<M-x>` (Alt), and `<S-Tab>`
+    /// (Shift). Key names are the same as [`Input::parse`] and case-insensitive. `<` and `>` are written as `<lt>` and
+    /// `<gt>`. The whole sequence is parsed before returning, so nothing is returned when some part is invalid.
+    /// ```
+    /// use tui_textarea::{Input, Key};
+    ///
+    /// let inputs = Input::parse_sequence("a<C-e><lt>").unwrap();
+    /// assert_eq!(inputs, [
+    ///     Input::from(Key::Char('a')),
+    ///     Input { key: Key::Char('e'), ctrl: true, alt: false, shift: false },
+    ///     Input::from(Key::Char('<')),
+    /// ]);
+    ///
+    /// assert!(Input::parse_sequence("<Foo>").is_err());
+    /// ```
+    pub fn parse_sequence(s: &str) -> Result<Vec<Self>, ParseInputError> {
+        let mut inputs = vec![];
+        let mut rest = s;
+        while let Some(c) = rest.chars().next() {
+            if c != '<' {
+                inputs.push(Input::from(Key::Char(c)));
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+            let end = match rest.find('>') {
+                Some(end) => end,
+                None => return Err(ParseInputError::new(s, "unclosed \"<\"".to_string())),
+            };
+            inputs.push(parse_special_key(s, &rest[1..end])?);
+            rest = &rest[end + 1..];
+        }
+        Ok(inputs)
+    }
+}
+
+// Parse the notation of a special key between `<` and `>` such as `C-S-Tab`
+fn parse_special_key(input: &str, notation: &str) -> Result<Input, ParseInputError> {
+    let mut ret = Input::default();
+    let mut name = notation;
+    while let Some((m, rest)) = name
+        .split_once('-')
+        .filter(|(m, r)| m.len() == 1 && !r.is_empty())
+    {
+        let flag = match m.to_ascii_lowercase().as_str() {
+            "c" => &mut ret.ctrl,
+            "a" | "m" => &mut ret.alt,
+            "s" => &mut ret.shift,
+            _ => break,
+        };
+        if *flag {
+            let reason = format!("duplicate modifier {:?}", m);
+            return Err(ParseInputError::new(input, reason));
+        }
+        *flag = true;
+        name = rest;
+    }
+
+    ret.key = match name.to_ascii_lowercase().as_str() {
+        "lt" => Key::Char('<'),
+        "gt" => Key::Char('>'),
+        _ => match parse_key(name) {
+            Some(key) => key,
+            None => {
+                let reason = format!("unknown key \"<{}>\"", notation);
+                return Err(ParseInputError::new(input, reason));
+            }
+        },
+    };
+    Ok(ret)
+}
+
+impl FromStr for Input {
+    type Err = ParseInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Input {
+    type Error = ParseInputError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s)
+    }
+}
+
+impl From<Input> for String {
+    fn from(input: Input) -> Self {
+        input.to_string()
+    }
+}
+
+impl fmt::Display for Input {
+    /// Format the input in the notation accepted by [`Input::parse`] such as `ctrl+shift+k`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("ctrl+")?;
+        }
+        if self.alt {
+            f.write_str("alt+")?;
+        }
+        if self.shift {
+            f.write_str("shift+")?;
+        }
+        self.key.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_format_round_trip() {
+        for (s, key, ctrl, alt, shift) in [
+            ("a", Key::Char('a'), false, false, false),
+            ("ctrl+shift+k", Key::Char('k'), true, false, true),
+            ("ctrl+alt+shift+delete", Key::Delete, true, true, true),
+            ("alt+backspace", Key::Backspace, false, true, false),
+            ("ctrl++", Key::Char('+'), true, false, false),
+            ("+", Key::Char('+'), false, false, false),
+            ("shift+space", Key::Char(' '), false, false, true),
+            ("f12", Key::F(12), false, false, false),
+            ("alt+あ", Key::Char('あ'), false, true, false),
+            ("insert", Key::Insert, false, false, false),
+        ] {
+            let want = Input {
+                key,
+                ctrl,
+                alt,
+                shift,
+            };
+            let input = Input::parse(s).unwrap();
+            assert_eq!(input, want, "{:?}", s);
+            assert_eq!(input.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn parse_aliases() {
+        for (s, want) in [
+            ("Ctrl+Shift+K", "ctrl+shift+K"),
+            ("control+return", "ctrl+enter"),
+            ("meta+ESCAPE", "alt+esc"),
+            ("shift+ctrl+x", "ctrl+shift+x"),
+            ("PgDn", "pagedown"),
+            ("F1", "f1"),
+        ] {
+            assert_eq!(Input::parse(s).unwrap().to_string(), want, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        for (s, reason) in [
+            ("", "missing key"),
+            ("ctrl+", "missing key"),
+            ("ctrl+foo", "unknown key \"foo\""),
+            ("f256", "unknown key \"f256\""),
+            ("super+a", "unknown modifier \"super\""),
+            ("ctrl+ctrl+a", "duplicate modifier \"ctrl\""),
+            ("ctrl++a", "unknown modifier \"\""),
+        ] {
+            let err = Input::parse(s).unwrap_err();
+            assert_eq!(err.reason, reason, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn parse_key_sequence() {
+        let ctrl = |key| Input {
+            key,
+            ctrl: true,
+            ..Default::default()
+        };
+        let inputs =
+            Input::parse_sequence("iあ<Esc><C-a><c-S-Tab><M-x><lt><gt><C-->-<F12><cr>").unwrap();
+        assert_eq!(
+            inputs,
+            [
+                Input::from(Key::Char('i')),
+                Input::from(Key::Char('あ')),
+                Input::from(Key::Esc),
+                ctrl(Key::Char('a')),
+                Input {
+                    key: Key::Tab,
+                    ctrl: true,
+                    alt: false,
+                    shift: true,
+                },
+                Input {
+                    key: Key::Char('x'),
+                    alt: true,
+                    ..Default::default()
+                },
+                Input::from(Key::Char('<')),
+                Input::from(Key::Char('>')),
+                ctrl(Key::Char('-')),
+                Input::from(Key::Char('-')),
+                Input::from(Key::F(12)),
+                Input::from(Key::Enter),
+            ],
+        );
+        assert_eq!(Input::parse_sequence("").unwrap(), []);
+    }
+
+    #[test]
+    fn parse_key_sequence_error() {
+        for (s, reason) in [
+            ("abc<Esc", "unclosed \"<\""),
+            ("<>", "unknown key \"<>\""),
+            ("a<Foo>", "unknown key \"<Foo>\""),
+            ("<X-a>", "unknown key \"<X-a>\""),
+            ("<C-C-a>", "duplicate modifier \"C\""),
+        ] {
+            let err = Input::parse_sequence(s).unwrap_err();
+            assert_eq!(err.reason, reason, "{:?}", s);
+            assert_eq!(err.input, s);
+        }
+    }
+}
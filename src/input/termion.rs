@@ -1,4 +1,5 @@
 use super::{Input, Key};
+use std::convert::TryFrom;
 use termion::event::{Event, Key as KeyEvent, MouseButton, MouseEvent};
 
 impl From<Event> for Input {
@@ -17,6 +18,7 @@ impl From<KeyEvent> for Input {
     fn from(key: KeyEvent) -> Self {
         let mut ctrl = false;
         let mut alt = false;
+        let mut shift = false;
         let key = match key {
             KeyEvent::Char('\n' | '\r') => Key::Enter,
             KeyEvent::Char(c) => Key::Char(c),
@@ -37,8 +39,12 @@ impl From<KeyEvent> for Input {
             KeyEvent::End => Key::End,
             KeyEvent::PageUp => Key::PageUp,
             KeyEvent::PageDown => Key::PageDown,
-            KeyEvent::BackTab => Key::Tab,
+            KeyEvent::BackTab => {
+                shift = true;
+                Key::Tab
+            }
             KeyEvent::Delete => Key::Delete,
+            KeyEvent::Insert => Key::Insert,
             KeyEvent::Esc => Key::Esc,
             KeyEvent::F(x) => Key::F(x),
             _ => Key::Null,
@@ -48,7 +54,7 @@ impl From<KeyEvent> for Input {
             key,
             ctrl,
             alt,
-            shift: false,
+            shift,
         }
     }
 }
@@ -81,6 +87,28 @@ impl From<MouseEvent> for Input {
     }
 }
 
+impl TryFrom<MouseEvent> for super::MouseEvent {
+    type Error = MouseEvent;
+
+    /// Convert [`termion::event::MouseEvent`] of the left button into [`MouseEvent`](super::MouseEvent) to pass it to
+    /// [`TextArea::on_mouse`](crate::TextArea::on_mouse). termion reports 1-based positions so they are converted into
+    /// 0-based ones. Events of other buttons are returned as the error.
+    fn try_from(mouse: MouseEvent) -> Result<Self, Self::Error> {
+        let (kind, x, y) = match mouse {
+            MouseEvent::Press(MouseButton::Left, x, y) => (super::MouseEventKind::Down, x, y),
+            MouseEvent::Hold(x, y) => (super::MouseEventKind::Drag, x, y),
+            MouseEvent::Release(x, y) => (super::MouseEventKind::Up, x, y),
+            _ => return Err(mouse),
+        };
+        Ok(Self {
+            kind,
+            column: x.saturating_sub(1),
+            row: y.saturating_sub(1),
+            shift: false,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,7 +132,7 @@ mod tests {
             (KeyEvent::Char('\n'), input(Key::Enter, false, false, false)),
             (KeyEvent::Char('\r'), input(Key::Enter, false, false, false)),
             (KeyEvent::F(1), input(Key::F(1), false, false, false)),
-            (KeyEvent::BackTab, input(Key::Tab, false, false, false)),
+            (KeyEvent::BackTab, input(Key::Tab, false, false, true)),
             (KeyEvent::Null, input(Key::Null, false, false, false)),
         ] {
             assert_eq!(Input::from(from), to, "{:?} -> {:?}", from, to);
@@ -139,6 +167,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mouse_to_mouse_event() {
+        use crate::input::{MouseEvent as Mouse, MouseEventKind as Kind};
+
+        let mouse = |kind, column, row| Mouse {
+            kind,
+            column,
+            row,
+            shift: false,
+        };
+        for (from, to) in [
+            (
+                MouseEvent::Press(MouseButton::Left, 3, 4),
+                Ok(mouse(Kind::Down, 2, 3)),
+            ),
+            (MouseEvent::Hold(1, 1), Ok(mouse(Kind::Drag, 0, 0))),
+            (MouseEvent::Release(5, 1), Ok(mouse(Kind::Up, 4, 0))),
+            (
+                MouseEvent::Press(MouseButton::Right, 1, 1),
+                Err(MouseEvent::Press(MouseButton::Right, 1, 1)),
+            ),
+            (
+                MouseEvent::Press(MouseButton::WheelUp, 1, 1),
+                Err(MouseEvent::Press(MouseButton::WheelUp, 1, 1)),
+            ),
+        ] {
+            assert_eq!(Mouse::try_from(from), to, "{:?} -> {:?}", from, to);
+        }
+    }
+
     #[test]
     fn event_to_input() {
         for (from, to) in [
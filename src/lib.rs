@@ -7,13 +7,26 @@
 #[cfg(all(feature = "ratatui", feature = "tuirs"))]
 compile_error!("ratatui support and tui-rs support are exclusive. only one of them can be enabled at the same time. see https://github.com/rhysd/tui-textarea#installation");
 
+mod bidi;
+mod cache;
+mod control;
 mod cursor;
+mod elastic;
+mod fold;
 mod highlight;
 mod history;
 mod input;
+mod minimap;
+mod newline;
 mod scroll;
 #[cfg(feature = "search")]
 mod search;
+mod selection;
+mod shared;
+mod snapshot;
+mod sort;
+#[cfg(feature = "syntect")]
+mod syntax;
 mod textarea;
 mod util;
 mod widget;
@@ -31,8 +44,21 @@ use crossterm;
 #[cfg(feature = "tuirs-crossterm")]
 use crossterm_025 as crossterm;
 
+pub use control::ControlCharHandling;
 pub use cursor::CursorMove;
-pub use input::{Input, Key};
-pub use scroll::Scrolling;
+pub use highlight::{DiffKind, Highlight, ListChars};
+pub use input::{Input, InputResult, Key, MouseEvent, MouseEventKind, ParseInputError};
+pub use minimap::{MinimapRow, MinimapWidget};
+pub use newline::TrailingNewline;
+pub use scroll::{ScrollInfo, Scrolling};
+pub use selection::SelectionMode;
+pub use shared::SharedTextArea;
+pub use snapshot::TextAreaSnapshot;
+pub use sort::SortOptions;
 pub use textarea::TextArea;
-pub use widget::TextAreaWidget;
+pub use widget::{ScrollbarConfig, TextAreaWidget, Viewport};
+
+// Syntax sets and themes passed to `TextArea` must be built with the same version of syntect
+#[cfg(feature = "syntect")]
+#[cfg_attr(docsrs, doc(cfg(feature = "syntect")))]
+pub use syntect;
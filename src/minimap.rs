@@ -0,0 +1,470 @@
+use crate::highlight::Highlight;
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::style::{Color, Style};
+use crate::ratatui::widgets::{Block, StatefulWidget, Widget};
+use crate::textarea::TextArea;
+use crate::util::char_display_width;
+use std::cmp;
+use std::mem;
+use std::ops::Range;
+
+// Bits of braille dots in a cell. Each cell has 2 columns and 4 rows of dots.
+// https://en.wikipedia.org/wiki/Braille_Patterns
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+// Area of the minimap on the last render and the number of lines represented by each row of it. It is used to map
+// mouse positions on the minimap into lines.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MinimapArea {
+    rect: Rect,
+    lines_per_row: usize,
+}
+
+impl MinimapArea {
+    // Map the position on screen into the first line represented by the row at the position. When `clamp` is true, the
+    // position outside the minimap is clamped into the area.
+    pub fn line_at(&self, x: u16, y: u16, clamp: bool) -> Option<usize> {
+        let Rect {
+            x: ox,
+            y: oy,
+            width,
+            height,
+        } = self.rect;
+        let in_area = |p: u16, origin: u16, len: u16| origin <= p && p - origin < len;
+        let inside = in_area(x, ox, width) && in_area(y, oy, height);
+        if !clamp && !inside {
+            return None;
+        }
+        let dy = cmp::min(y.saturating_sub(oy), height - 1) as usize;
+        Some(dy * self.lines_per_row)
+    }
+}
+
+/// Summary of a bucket of consecutive lines returned by [`TextArea::minimap_rows`]. It is useful to render a minimap
+/// in a custom way instead of [`MinimapWidget`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinimapRow {
+    /// Rows of the lines summarized by this row.
+    pub lines: Range<usize>,
+    /// Smallest indentation width of the non-blank lines in display columns.
+    pub indent: usize,
+    /// Largest display width of the lines.
+    pub width: usize,
+    /// Color of the highlight covering the most characters in the lines. The foreground color of the highlight style is
+    /// used, or its background color when no foreground color is set. `None` when no highlight with colors covers the
+    /// lines.
+    pub color: Option<Color>,
+}
+
+// Columns from the head of the indentation to the end of the text in the line. Both are 0 for a blank line.
+fn line_extent(line: &str, tab_len: u8) -> (usize, usize) {
+    let (mut col, mut start, mut end) = (0, None, 0);
+    for c in line.chars() {
+        let width = char_display_width(c, col, tab_len);
+        if !c.is_whitespace() {
+            start.get_or_insert(col);
+            end = col + width;
+        }
+        col += width;
+    }
+    (start.unwrap_or(0), end)
+}
+
+// Extents of the lines computed on the last render of the minimap. They are computed again only when the text or the
+// tab length has been changed since then.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MinimapExtents {
+    key: Option<(u64, u8)>,
+    extents: Vec<(usize, usize)>,
+}
+
+impl MinimapExtents {
+    pub fn update(&mut self, lines: &[String], revision: u64, tab_len: u8) -> &[(usize, usize)] {
+        if self.key != Some((revision, tab_len)) {
+            self.extents.clear();
+            self.extents
+                .extend(lines.iter().map(|line| line_extent(line, tab_len)));
+            self.key = Some((revision, tab_len));
+        }
+        &self.extents
+    }
+}
+
+// Summarize the lines into at most `height` buckets of the same number of lines
+pub(crate) fn minimap_rows(
+    lines: &[String],
+    highlights: &[Highlight],
+    tab_len: u8,
+    height: u16,
+) -> Vec<MinimapRow> {
+    if height == 0 {
+        return vec![];
+    }
+    let height = height as usize;
+    let lines_per_row = cmp::max((lines.len() + height - 1) / height, 1);
+
+    let mut rows: Vec<_> = lines
+        .chunks(lines_per_row)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let start = i * lines_per_row;
+            let extents = chunk.iter().map(|l| line_extent(l, tab_len));
+            let indent = extents
+                .clone()
+                .filter(|&(s, e)| s < e)
+                .map(|(s, _)| s)
+                .min();
+            MinimapRow {
+                lines: start..start + chunk.len(),
+                indent: indent.unwrap_or(0),
+                width: extents.map(|(_, e)| e).max().unwrap_or(0),
+                color: None,
+            }
+        })
+        .collect();
+
+    // Number of characters covered by highlights of each color in each bucket
+    let mut coverage: Vec<Vec<(Color, usize)>> = vec![vec![]; rows.len()];
+    for hl in highlights {
+        let color = match hl.style.fg.or(hl.style.bg) {
+            Some(color) => color,
+            None => continue,
+        };
+        let last = cmp::min(hl.end.0, lines.len().saturating_sub(1));
+        for row in hl.start.0..=last {
+            let len = lines[row].chars().count();
+            let start = if row == hl.start.0 { hl.start.1 } else { 0 };
+            let end = if row == hl.end.0 { hl.end.1 } else { len };
+            let count = cmp::min(end, len).saturating_sub(start);
+            if count == 0 {
+                continue;
+            }
+            let counts = &mut coverage[row / lines_per_row];
+            match counts.iter_mut().find(|(c, _)| *c == color) {
+                Some((_, n)) => *n += count,
+                None => counts.push((color, count)),
+            }
+        }
+    }
+    for (row, counts) in rows.iter_mut().zip(coverage) {
+        // The first color wins on ties
+        row.color = counts
+            .into_iter()
+            .fold(None, |acc: Option<(Color, usize)>, (c, n)| match acc {
+                Some((_, m)) if m >= n => acc,
+                _ => Some((c, n)),
+            })
+            .map(|(c, _)| c);
+    }
+    rows
+}
+
+/// A widget to render a compressed overview of the whole text in [`TextArea`]. Each line is drawn as braille dots
+/// approximating its indentation and length, and the rows covering the viewport of the textarea on the last render
+/// are highlighted. When the text has more lines than the dots in the area, several lines are merged into one row of
+/// dots so that the whole text always fits in the area.
+///
+/// Clicking or dragging on the minimap with [`TextArea::on_mouse`] scrolls the textarea to the lines at the mouse
+/// position.
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use ratatui::widgets::StatefulWidget;
+/// use tui_textarea::{MinimapWidget, MouseEvent, MouseEventKind, TextArea, TextAreaWidget};
+///
+/// let mut textarea: TextArea = (0..100).map(|i| "x".repeat(i % 8)).collect();
+///
+/// // Render the textarea and the minimap side by side
+/// let main = Rect { x: 0, y: 0, width: 20, height: 10 };
+/// let minimap = Rect { x: 20, y: 0, width: 4, height: 10 };
+/// let mut b = Buffer::empty(Rect { x: 0, y: 0, width: 24, height: 10 });
+/// TextAreaWidget::new().render(main, &mut b, &mut textarea);
+/// MinimapWidget::new().render(minimap, &mut b, &mut textarea);
+///
+/// // Click on the bottom of the minimap scrolls the textarea to the end of the text. The cursor moves into the
+/// // viewport
+/// let click = MouseEvent { kind: MouseEventKind::Down, column: 21, row: 9, shift: false };
+/// textarea.on_mouse(click);
+/// assert_eq!(textarea.cursor(), (90, 0));
+/// ```
+pub struct MinimapWidget<'a> {
+    block: Option<Block<'a>>,
+    style: Style,
+    viewport_style: Style,
+}
+
+impl<'a> Default for MinimapWidget<'a> {
+    fn default() -> Self {
+        Self {
+            block: None,
+            style: Style::default(),
+            viewport_style: Style::default().bg(Color::DarkGray),
+        }
+    }
+}
+
+impl<'a> MinimapWidget<'a> {
+    /// Create a new minimap widget with the default styles and no block.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the block surrounding the minimap. By default, no block is set.
+    pub fn block<'b: 'a>(mut self, block: Block<'b>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Set the style of the minimap. By default, the style is empty.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the rows covering the viewport of the textarea. By default, the background is dark gray.
+    pub fn viewport_style(mut self, style: Style) -> Self {
+        self.viewport_style = style;
+        self
+    }
+}
+
+impl<'a> StatefulWidget for MinimapWidget<'a> {
+    type State = TextArea;
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let inner = if let Some(b) = self.block {
+            let inner = b.inner(area);
+            b.render(area, buf);
+            inner
+        } else {
+            area
+        };
+        if inner.width == 0 || inner.height == 0 {
+            state.minimap = None;
+            return;
+        }
+
+        // Columns from the head of the indentation to the end of the text in each line
+        let mut cache = mem::take(&mut state.minimap_extents);
+        let extents = cache.update(state.lines(), state.revision(), state.tab_length());
+
+        let num_lines = extents.len();
+        let dot_rows = inner.height as usize * 4;
+        let lines_per_dot = cmp::max((num_lines + dot_rows - 1) / dot_rows, 1);
+        let lines_per_row = lines_per_dot * 4;
+        let max_width = extents.iter().map(|&(_, e)| e).max().unwrap_or(0);
+        let dot_cols = inner.width as usize * 2;
+        let cols_per_dot = cmp::max((max_width + dot_cols - 1) / dot_cols, 1);
+
+        // Lines on the viewport of the textarea at the last render
+        let (top_row, _, _, height) = state.viewport.rect();
+        let folds = state.folds();
+        let top = folds.display_row(top_row as usize) + height as usize;
+        let bottom = cmp::min(folds.text_row(top).saturating_sub(1), num_lines - 1);
+        let visible = top_row as usize..=cmp::max(bottom, top_row as usize);
+
+        buf.set_style(inner, self.style);
+        for y in 0..inner.height {
+            let first = y as usize * lines_per_row;
+            if first >= num_lines {
+                break;
+            }
+            let last = cmp::min(first + lines_per_row, num_lines) - 1;
+            if first <= *visible.end() && *visible.start() <= last {
+                let row = Rect {
+                    y: inner.y + y,
+                    height: 1,
+                    ..inner
+                };
+                buf.set_style(row, self.viewport_style);
+            }
+
+            // Merge the extents of the lines represented by each row of dots
+            let mut rows = [None; 4];
+            for (i, row) in rows.iter_mut().enumerate() {
+                let start = first + i * lines_per_dot;
+                let end = cmp::min(start + lines_per_dot, num_lines);
+                *row = extents.get(start..end).and_then(|e| {
+                    e.iter()
+                        .copied()
+                        .filter(|&(s, e)| s < e)
+                        .reduce(|(s1, e1), (s2, e2)| (cmp::min(s1, s2), cmp::max(e1, e2)))
+                });
+            }
+
+            for x in 0..inner.width {
+                let mut bits = 0;
+                for (dots, row) in BRAILLE_DOTS.iter().zip(rows.iter()) {
+                    if let Some((start, end)) = *row {
+                        for (d, bit) in dots.iter().enumerate() {
+                            let col = (x as usize * 2 + d) * cols_per_dot;
+                            if start < col + cols_per_dot && col < end {
+                                bits |= bit;
+                            }
+                        }
+                    }
+                }
+                if bits != 0 {
+                    let c = char::from_u32(0x2800 + bits).unwrap_or(' ');
+                    buf[(inner.x + x, inner.y + y)].set_char(c);
+                }
+            }
+        }
+
+        state.minimap = Some(MinimapArea {
+            rect: inner,
+            lines_per_row,
+        });
+        state.minimap_extents = cache;
+    }
+}
+
+#[cfg(all(test, feature = "ratatui"))]
+mod tests {
+    use super::*;
+    use crate::{CursorMove, MouseEvent, MouseEventKind, TextAreaWidget};
+
+    fn render(textarea: &mut TextArea) -> Buffer {
+        let mut buf = Buffer::empty(Rect {
+            x: 0,
+            y: 0,
+            width: 14,
+            height: 20,
+        });
+        let main = Rect {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 10,
+        };
+        let minimap = Rect {
+            x: 10,
+            y: 0,
+            width: 4,
+            height: 20,
+        };
+        TextAreaWidget::new().render(main, &mut buf, textarea);
+        MinimapWidget::new().render(minimap, &mut buf, textarea);
+        buf
+    }
+
+    fn highlighted_rows(buf: &Buffer) -> Vec<u16> {
+        (0..20)
+            .filter(|&y| buf[(10, y)].bg == Color::DarkGray)
+            .collect()
+    }
+
+    #[test]
+    fn viewport_window() {
+        let mut t: TextArea = (0..500).map(|i| "x".repeat(i % 16)).collect();
+
+        // 500 lines are merged into 18 rows. Each row represents 28 lines
+        let b = render(&mut t);
+        assert_eq!(highlighted_rows(&b), [0]);
+        assert_eq!(b[(10, 17)].symbol(), "⣿");
+        assert_eq!(b[(10, 18)].symbol(), " ");
+
+        // Lines 291..=300 are on the 10th row
+        t.move_cursor(CursorMove::Jump(300, 0));
+        let b = render(&mut t);
+        assert_eq!(t.viewport.scroll_top(), (291, 0));
+        assert_eq!(highlighted_rows(&b), [10]);
+
+        // Lines 330..=339 are on the 11th and 12th rows
+        t.move_cursor(CursorMove::Jump(339, 0));
+        let b = render(&mut t);
+        assert_eq!(highlighted_rows(&b), [11, 12]);
+
+        // Click on the 15th row (line 420) scrolls the textarea
+        let click = MouseEvent {
+            kind: MouseEventKind::Down,
+            column: 12,
+            row: 15,
+            shift: false,
+        };
+        t.on_mouse(click);
+        assert_eq!(t.viewport.scroll_top(), (415, 0));
+        let b = render(&mut t);
+        assert_eq!(highlighted_rows(&b), [14, 15]);
+
+        // Dragging from the minimap keeps scrolling even outside of it
+        let drag = MouseEvent {
+            kind: MouseEventKind::Drag,
+            column: 0,
+            row: 1,
+            ..click
+        };
+        t.on_mouse(drag);
+        assert_eq!(t.viewport.scroll_top(), (23, 0));
+    }
+
+    #[test]
+    fn dots() {
+        let mut t = TextArea::from(["abcd", "  ab", "", "\tx"]);
+        t.set_tab_length(2);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 2,
+            height: 1,
+        };
+        let mut b = Buffer::empty(r);
+        MinimapWidget::new().render(r, &mut b, &mut t);
+        // Each dot represents one column
+        assert_eq!(b[(0, 0)].symbol(), "⠉");
+        assert_eq!(b[(1, 0)].symbol(), "⡛");
+
+        // Dots are updated after the text or the tab length is modified
+        t.move_cursor(CursorMove::Jump(0, 2));
+        t.delete_line_by_end();
+        let mut b = Buffer::empty(r);
+        MinimapWidget::new().render(r, &mut b, &mut t);
+        assert_eq!(b[(0, 0)].symbol(), "⠉");
+        assert_eq!(b[(1, 0)].symbol(), "⡒");
+        t.set_tab_length(4);
+        let mut b = Buffer::empty(r);
+        MinimapWidget::new().render(r, &mut b, &mut t);
+        assert_eq!(b[(0, 0)].symbol(), "⠑");
+        assert_eq!(b[(1, 0)].symbol(), "⡀");
+    }
+
+    #[test]
+    fn rows_summary() {
+        let mut t: TextArea = (0..10).map(|i| " ".repeat(i % 3) + "abc").collect();
+        let hl = |row, style: Style| Highlight {
+            start: (row, 0),
+            end: (row + 1, 0),
+            style,
+        };
+        t.set_highlights(vec![
+            hl(0, Style::default().fg(Color::Red)),
+            hl(1, Style::default().bg(Color::Blue)),
+            hl(2, Style::default().bg(Color::Blue)),
+            hl(4, Style::default()),
+            // Out of the text
+            hl(20, Style::default().fg(Color::Red)),
+        ]);
+
+        // 10 lines are split into 4 rows of 3 lines
+        let rows = t.minimap_rows(4);
+        let summary: Vec<_> = rows
+            .iter()
+            .map(|r| (r.lines.clone(), r.indent, r.width, r.color))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (0..3, 0, 5, Some(Color::Blue)),
+                (3..6, 0, 5, None),
+                (6..9, 0, 5, None),
+                (9..10, 0, 3, None),
+            ],
+        );
+
+        // Blank lines are not counted for indentation
+        let t = TextArea::from(["", "  a", "    b"]);
+        let rows = t.minimap_rows(1);
+        assert_eq!((rows[0].indent, rows[0].width), (2, 5));
+        assert!(t.minimap_rows(0).is_empty());
+    }
+}
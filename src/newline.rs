@@ -0,0 +1,46 @@
+/// How the newline at the end of the text is exported by [`TextArea::text`](crate::TextArea::text),
+/// [`TextArea::write_to`](crate::TextArea::write_to), and [`TextArea::into_lines`](crate::TextArea::into_lines). The
+/// policy is set with [`TextArea::set_trailing_newline`](crate::TextArea::set_trailing_newline).
+///
+/// The policy only affects the exported text. The text buffer is not modified, so empty lines at the end of the text
+/// stay in [`TextArea::lines`](crate::TextArea::lines) and the cursor can move to them.
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TrailingNewline {
+    /// Export the lines as they are. The text ends with a newline only when the last line is empty. This is the default
+    /// policy.
+    /// ```
+    /// use tui_textarea::{TextArea, TrailingNewline};
+    ///
+    /// let mut textarea = TextArea::from(["hello", ""]);
+    /// textarea.set_trailing_newline(TrailingNewline::Preserve);
+    /// assert_eq!(textarea.text(), "hello\n");
+    /// ```
+    Preserve,
+    /// Export the text ending with exactly one newline. Empty lines at the end of the text are removed.
+    /// ```
+    /// use tui_textarea::{TextArea, TrailingNewline};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "", ""]);
+    /// textarea.set_trailing_newline(TrailingNewline::Ensure);
+    /// assert_eq!(textarea.text(), "hello\n");
+    /// ```
+    Ensure,
+    /// Export the text ending without a newline. Empty lines at the end of the text are removed.
+    /// ```
+    /// use tui_textarea::{TextArea, TrailingNewline};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "", ""]);
+    /// textarea.set_trailing_newline(TrailingNewline::Strip);
+    /// assert_eq!(textarea.text(), "hello");
+    /// ```
+    Strip,
+}
+
+impl Default for TrailingNewline {
+    fn default() -> Self {
+        Self::Preserve
+    }
+}
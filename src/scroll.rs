@@ -1,9 +1,13 @@
 use crate::widget::Viewport;
+use std::cmp;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
 
 /// Specify how to scroll the textarea.
 ///
 /// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future. Note that the cursor will
-/// not move until it goes out the viewport. See also: [`TextArea::scroll`]
+/// not move until it goes out the viewport on scrolling by [`Scrolling::Delta`]. On scrolling by pages, the cursor moves
+/// along with the viewport. See also: [`TextArea::scroll`]
 ///
 /// [`TextArea::scroll`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.scroll
 #[non_exhaustive]
@@ -15,7 +19,8 @@ pub enum Scrolling {
     /// ```
     /// # use ratatui::buffer::Buffer;
     /// # use ratatui::layout::Rect;
-    /// # use ratatui::widgets::Widget;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
     /// use tui_textarea::{TextArea, Scrolling};
     ///
     /// // Let's say terminal height is 8.
@@ -25,7 +30,7 @@ pub enum Scrolling {
     /// # // Call `render` at least once to populate terminal size
     /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
     /// # let mut b = Buffer::empty(r.clone());
-    /// # textarea.widget().render(r, &mut b);
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
     ///
     /// // Scroll down by 2 lines.
     /// textarea.scroll(Scrolling::Delta{rows: 2, cols: 0});
@@ -41,7 +46,8 @@ pub enum Scrolling {
     /// ```
     /// # use ratatui::buffer::Buffer;
     /// # use ratatui::layout::Rect;
-    /// # use ratatui::widgets::Widget;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
     /// use tui_textarea::{TextArea, Scrolling};
     ///
     /// // Let's say terminal height is 8.
@@ -51,7 +57,7 @@ pub enum Scrolling {
     /// # // Call `render` at least once to populate terminal size
     /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
     /// # let mut b = Buffer::empty(r.clone());
-    /// # textarea.widget().render(r, &mut b);
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
     ///
     /// // Scroll down by one page (8 lines)
     /// textarea.scroll(Scrolling::PageDown);
@@ -67,7 +73,8 @@ pub enum Scrolling {
     /// ```
     /// # use ratatui::buffer::Buffer;
     /// # use ratatui::layout::Rect;
-    /// # use ratatui::widgets::Widget;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
     /// use tui_textarea::{TextArea, Scrolling, CursorMove};
     ///
     /// // Let's say terminal height is 8.
@@ -77,19 +84,19 @@ pub enum Scrolling {
     /// # // Call `render` at least once to populate terminal size
     /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
     /// # let mut b = Buffer::empty(r.clone());
-    /// # textarea.widget().render(r.clone(), &mut b);
+    /// # TextAreaWidget::new().render(r.clone(), &mut b, &mut textarea);
     ///
     /// // Go to the last line at first
     /// textarea.move_cursor(CursorMove::Bottom);
     /// assert_eq!(textarea.cursor(), (19, 0));
     /// # // Call `render` to populate terminal size
-    /// # textarea.widget().render(r.clone(), &mut b);
+    /// # TextAreaWidget::new().render(r.clone(), &mut b, &mut textarea);
     ///
     /// // Scroll up by one page (8 lines)
     /// textarea.scroll(Scrolling::PageUp);
     /// assert_eq!(textarea.cursor(), (11, 0));
     /// textarea.scroll(Scrolling::PageUp);
-    /// assert_eq!(textarea.cursor(), (7, 0)); // Reached top of the textarea
+    /// assert_eq!(textarea.cursor(), (3, 0)); // Reached top of the textarea
     /// ```
     PageUp,
     /// Scroll down the textarea by half of the page.
@@ -97,7 +104,8 @@ pub enum Scrolling {
     /// ```
     /// # use ratatui::buffer::Buffer;
     /// # use ratatui::layout::Rect;
-    /// # use ratatui::widgets::Widget;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
     /// use tui_textarea::{TextArea, Scrolling};
     ///
     /// // Let's say terminal height is 8.
@@ -107,7 +115,7 @@ pub enum Scrolling {
     /// # // Call `render` at least once to populate terminal size
     /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
     /// # let mut b = Buffer::empty(r.clone());
-    /// # textarea.widget().render(r, &mut b);
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
     ///
     /// // Scroll down by half-page (4 lines)
     /// textarea.scroll(Scrolling::HalfPageDown);
@@ -123,7 +131,8 @@ pub enum Scrolling {
     /// ```
     /// # use ratatui::buffer::Buffer;
     /// # use ratatui::layout::Rect;
-    /// # use ratatui::widgets::Widget;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
     /// use tui_textarea::{TextArea, Scrolling, CursorMove};
     ///
     /// // Let's say terminal height is 8.
@@ -133,13 +142,13 @@ pub enum Scrolling {
     /// # // Call `render` at least once to populate terminal size
     /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
     /// # let mut b = Buffer::empty(r.clone());
-    /// # textarea.widget().render(r.clone(), &mut b);
+    /// # TextAreaWidget::new().render(r.clone(), &mut b, &mut textarea);
     ///
     /// // Go to the last line at first
     /// textarea.move_cursor(CursorMove::Bottom);
     /// assert_eq!(textarea.cursor(), (19, 0));
     /// # // Call `render` to populate terminal size
-    /// # textarea.widget().render(r.clone(), &mut b);
+    /// # TextAreaWidget::new().render(r.clone(), &mut b, &mut textarea);
     ///
     /// // Scroll up by half-page (4 lines)
     /// textarea.scroll(Scrolling::HalfPageUp);
@@ -151,8 +160,21 @@ pub enum Scrolling {
 }
 
 impl Scrolling {
-    pub(crate) fn scroll(self, viewport: &mut Viewport) {
-        let (rows, cols) = match self {
+    // Returns `false` when the scroll amount is zero. It happens when scrolling by pages before the first render since
+    // the viewport size is not known yet.
+    pub(crate) fn scroll(self, viewport: &mut Viewport, max_row: usize) -> bool {
+        let (rows, cols) = self.delta(viewport);
+        if rows == 0 && cols == 0 {
+            return false;
+        }
+        viewport.scroll(rows, cols);
+        viewport.clamp_top_row(max_row);
+        true
+    }
+
+    // Scroll amount in rows and columns
+    pub(crate) fn delta(&self, viewport: &Viewport) -> (i16, i16) {
+        match *self {
             Self::Delta { rows, cols } => (rows, cols),
             Self::PageDown => {
                 let (_, _, _, height) = viewport.rect();
@@ -170,8 +192,28 @@ impl Scrolling {
                 let (_, _, _, height) = viewport.rect();
                 (-(height as i16) / 2, 0)
             }
+        }
+    }
+}
+
+// Where the cursor line should be placed in the viewport. This is used by `TextArea::scroll_cursor_*` methods.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum CursorAnchor {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl CursorAnchor {
+    // Calculate the top row of the viewport to place the cursor line at the anchor. The result is clamped so that the
+    // viewport does not go beyond the end of the text.
+    pub(crate) fn top_row(self, cursor_row: usize, height: usize, num_lines: usize) -> usize {
+        let top = match self {
+            Self::Top => cursor_row,
+            Self::Center => cursor_row.saturating_sub(height / 2),
+            Self::Bottom => (cursor_row + 1).saturating_sub(height),
         };
-        viewport.scroll(rows, cols);
+        cmp::min(top, num_lines.saturating_sub(height))
     }
 }
 
@@ -181,6 +223,58 @@ impl From<(i16, i16)> for Scrolling {
     }
 }
 
+/// State of the viewport passed to the callback set by [`TextArea::set_on_scroll`](crate::TextArea::set_on_scroll).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ScrollInfo {
+    /// Row and column at the top-left corner of the viewport.
+    pub scroll_top: (u16, u16),
+    /// Width of the viewport.
+    pub width: u16,
+    /// Height of the viewport.
+    pub height: u16,
+    /// Number of lines in the text.
+    pub lines: usize,
+}
+
+type ScrollFn = dyn FnMut(ScrollInfo) + Send;
+
+// Callback called when the viewport changed on rendering. The callback is shared by the clones of the textarea.
+#[derive(Clone, Default)]
+pub(crate) struct ScrollCallback {
+    callback: Option<Arc<Mutex<Box<ScrollFn>>>>,
+    last: Option<ScrollInfo>,
+}
+
+impl ScrollCallback {
+    pub(crate) fn new(callback: Box<ScrollFn>, last: Option<ScrollInfo>) -> Self {
+        Self {
+            callback: Some(Arc::new(Mutex::new(callback))),
+            last,
+        }
+    }
+
+    // Call the callback only when the viewport changed from the last call
+    pub(crate) fn notify(&mut self, info: ScrollInfo) {
+        if self.last == Some(info) {
+            return;
+        }
+        self.last = Some(info);
+        if let Some(callback) = &self.callback {
+            let mut callback = callback.lock().unwrap_or_else(PoisonError::into_inner);
+            callback(info);
+        }
+    }
+}
+
+impl fmt::Debug for ScrollCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScrollCallback")
+            .field("callback", &self.callback.as_ref().map(|_| ".."))
+            .field("last", &self.last)
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ratatui::widgets::StatefulWidget;
@@ -212,4 +306,106 @@ mod tests {
         textarea.scroll((1, 0));
         assert_eq!(textarea.cursor(), (3, 0));
     }
+
+    #[test]
+    fn page_and_half_page() {
+        use crate::ratatui::buffer::Buffer;
+        use crate::ratatui::layout::Rect;
+        use crate::TextArea;
+
+        let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+
+        // Scrolling by pages before the first render does nothing
+        textarea.scroll_page_down();
+        textarea.scroll_half_page_down();
+        assert_eq!(textarea.cursor(), (0, 0));
+        assert_eq!(textarea.viewport.scroll_top(), (0, 0));
+
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 24,
+            height: 8,
+        };
+        let mut b = Buffer::empty(r);
+        StatefulWidget::render(TextAreaWidget::new(), r, &mut b, &mut textarea);
+
+        textarea.scroll_page_down();
+        assert_eq!(textarea.viewport.scroll_top(), (8, 0));
+        assert_eq!(textarea.cursor(), (8, 0));
+        textarea.scroll_half_page_down();
+        assert_eq!(textarea.viewport.scroll_top(), (12, 0));
+        assert_eq!(textarea.cursor(), (12, 0));
+
+        // Scrolling stops at the last line
+        textarea.scroll_page_down();
+        textarea.scroll_page_down();
+        textarea.scroll_page_down();
+        assert_eq!(textarea.viewport.scroll_top(), (19, 0));
+        assert_eq!(textarea.cursor(), (19, 0));
+
+        // The cursor moves along with the viewport
+        textarea.scroll_half_page_up();
+        assert_eq!(textarea.viewport.scroll_top(), (15, 0));
+        assert_eq!(textarea.cursor(), (15, 0));
+        textarea.scroll_page_up();
+        assert_eq!(textarea.viewport.scroll_top(), (7, 0));
+        assert_eq!(textarea.cursor(), (7, 0));
+        textarea.scroll_page_up();
+        textarea.scroll_page_up();
+        assert_eq!(textarea.viewport.scroll_top(), (0, 0));
+        assert_eq!(textarea.cursor(), (0, 0));
+
+        // The cursor keeps its position in the viewport and its column
+        textarea.move_cursor(crate::CursorMove::Jump(3, 1));
+        textarea.scroll_page_down();
+        assert_eq!(textarea.viewport.scroll_top(), (8, 0));
+        assert_eq!(textarea.cursor(), (11, 1));
+        textarea.scroll_half_page_up();
+        assert_eq!(textarea.viewport.scroll_top(), (4, 0));
+        assert_eq!(textarea.cursor(), (7, 1));
+    }
+
+    #[test]
+    fn cursor_anchor() {
+        use crate::ratatui::buffer::Buffer;
+        use crate::ratatui::layout::Rect;
+        use crate::{CursorMove, TextArea};
+
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 24,
+            height: 8,
+        };
+        let mut b = Buffer::empty(r);
+
+        // Scrolling before the first render is applied on the next render
+        let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+        textarea.move_cursor(CursorMove::Jump(10, 0));
+        textarea.scroll_cursor_top();
+        StatefulWidget::render(TextAreaWidget::new(), r, &mut b, &mut textarea);
+        assert_eq!(textarea.viewport.scroll_top(), (10, 0));
+
+        for (row, f, top) in [
+            (10, TextArea::scroll_cursor_center as fn(&mut TextArea), 6),
+            (10, TextArea::scroll_cursor_top, 10),
+            (10, TextArea::scroll_cursor_bottom, 3),
+            // Clamped at the start of the text
+            (2, TextArea::scroll_cursor_center, 0),
+            (2, TextArea::scroll_cursor_bottom, 0),
+            // Clamped at the end of the text
+            (18, TextArea::scroll_cursor_center, 12),
+            (18, TextArea::scroll_cursor_top, 12),
+            (19, TextArea::scroll_cursor_bottom, 12),
+        ] {
+            textarea.move_cursor(CursorMove::Jump(row, 0));
+            f(&mut textarea);
+            assert_eq!(textarea.viewport.scroll_top(), (top, 0), "row={row}");
+            // The scroll position is kept by rendering
+            StatefulWidget::render(TextAreaWidget::new(), r, &mut b, &mut textarea);
+            assert_eq!(textarea.viewport.scroll_top(), (top, 0), "row={row}");
+            assert_eq!(textarea.cursor(), (row as usize, 0));
+        }
+    }
 }
@@ -1,17 +1,58 @@
 use crate::ratatui::style::{Color, Style};
-use regex::Regex;
+use crate::word::{is_whole_word, is_word_char, WordChars};
+use regex::{Captures, Match, Regex, RegexBuilder};
+use std::sync::{Mutex, PoisonError};
+
+// Key of the cached matches: the revision of the text, the pattern, and the flags of the search
+type MatchCacheKey = (u64, String, bool, bool);
+type MatchPositions = Vec<(usize, usize)>;
+
+// Positions of all matches in the text. They are computed lazily on the first request and cached until the text or
+// the search pattern changes. The mutex allows filling the cache through a shared reference.
+#[derive(Debug, Default)]
+pub struct MatchCache(Mutex<Option<(MatchCacheKey, MatchPositions)>>);
+
+impl Clone for MatchCache {
+    fn clone(&self) -> Self {
+        let cache = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        Self(Mutex::new(cache.clone()))
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Search {
     pub pat: Option<Regex>,
+    // Query the current pattern was built from. This differs from the source of `pat` in literal mode
+    pub query: String,
     pub style: Style,
+    pub current_style: Style,
+    // Cursor position, pattern, and query saved at the start of incremental search to restore them on cancel
+    pub saved: Option<((usize, usize), Option<Regex>, String)>,
+    pub case_insensitive: bool,
+    pub literal: bool,
+    pub whole_word: bool,
+    pub word_chars: WordChars,
+    pub wrap: bool,
+    // If the last search wrapped around the text
+    pub wrapped: bool,
+    pub cache: MatchCache,
 }
 
 impl Default for Search {
     fn default() -> Self {
         Self {
             pat: None,
+            query: String::new(),
             style: Style::default().bg(Color::Blue),
+            current_style: Style::default().bg(Color::Yellow),
+            saved: None,
+            case_insensitive: false,
+            literal: false,
+            whole_word: false,
+            word_chars: is_word_char,
+            wrap: true,
+            wrapped: false,
+            cache: MatchCache::default(),
         }
     }
 }
@@ -22,25 +63,135 @@ impl Search {
         line: &'a str,
     ) -> Option<impl Iterator<Item = (usize, usize)> + 'a> {
         let pat = self.pat.as_ref()?;
-        let matches = pat.find_iter(line).map(|m| (m.start(), m.end()));
+        let matches = self.find_iter(pat, line).map(|m| (m.start(), m.end()));
         Some(matches)
     }
 
+    // Get the match starting at the byte offset in the line
+    pub fn match_at(&self, line: &str, offset: usize) -> Option<(usize, usize)> {
+        let m = self.find_at(self.pat.as_ref()?, line, offset)?;
+        (m.start() == offset && m.start() < m.end()).then(|| (m.start(), m.end()))
+    }
+
     pub fn set_pattern(&mut self, query: &str) -> Result<(), regex::Error> {
         match &self.pat {
-            Some(r) if r.as_str() == query => {}
+            Some(_) if self.query == query => {}
             _ if query.is_empty() => self.pat = None,
-            _ => self.pat = Some(Regex::new(query)?),
+            _ => self.pat = Some(self.build(query)?),
         }
+        self.query = query.to_string();
         Ok(())
     }
 
+    pub fn set_case_insensitive(&mut self, enabled: bool) {
+        if self.case_insensitive == enabled {
+            return;
+        }
+        self.case_insensitive = enabled;
+        // Rebuild the current pattern with the new flag. The pattern was already compiled once so this never fails
+        self.rebuild();
+    }
+
+    pub fn set_literal(&mut self, enabled: bool) {
+        if self.literal == enabled {
+            return;
+        }
+        self.literal = enabled;
+        // A literal query may not be a valid regular expression. In the case the pattern is cleared
+        self.rebuild();
+    }
+
+    fn rebuild(&mut self) {
+        if self.pat.is_some() {
+            self.pat = self.build(&self.query).ok();
+            if self.pat.is_none() {
+                self.query.clear();
+            }
+        }
+    }
+
+    fn build(&self, query: &str) -> Result<Regex, regex::Error> {
+        let escaped;
+        let query = if self.literal {
+            escaped = regex::escape(query);
+            &escaped
+        } else {
+            query
+        };
+        RegexBuilder::new(query)
+            .case_insensitive(self.case_insensitive)
+            .build()
+    }
+
+    fn expand(&self, caps: &Captures<'_>, replacement: &str, dst: &mut String) {
+        if self.literal {
+            dst.push_str(replacement);
+        } else {
+            caps.expand(replacement, dst);
+        }
+    }
+
+    fn is_accepted(&self, line: &str, m: &Match<'_>) -> bool {
+        !self.whole_word || is_whole_word(line, m.start(), m.end(), self.word_chars)
+    }
+
+    fn find_iter<'p, 't>(
+        &'p self,
+        pat: &'p Regex,
+        line: &'t str,
+    ) -> impl Iterator<Item = Match<'t>> + 'p
+    where
+        't: 'p,
+    {
+        pat.find_iter(line)
+            .filter(move |m| self.is_accepted(line, m))
+    }
+
+    // Find the first match at or after the byte offset in the line. When a match is rejected by the whole word
+    // option, retry from the next character of its start.
+    fn find_at<'t>(&self, pat: &Regex, line: &'t str, mut start: usize) -> Option<Match<'t>> {
+        loop {
+            let m = pat.find_at(line, start)?;
+            if self.is_accepted(line, &m) {
+                return Some(m);
+            }
+            start = m.start() + line[m.start()..].chars().next()?.len_utf8();
+        }
+    }
+
+    fn find<'t>(&self, pat: &Regex, line: &'t str) -> Option<Match<'t>> {
+        self.find_at(pat, line, 0)
+    }
+
     pub fn forward(
         &mut self,
         lines: &[String],
         cursor: (usize, usize),
         match_cursor: bool,
     ) -> Option<(usize, usize)> {
+        let found = self.find_forward(lines, cursor, match_cursor);
+        self.wrapped = found.map_or(false, |(_, wrapped)| wrapped);
+        found.map(|(pos, _)| pos)
+    }
+
+    pub fn back(
+        &mut self,
+        lines: &[String],
+        cursor: (usize, usize),
+        match_cursor: bool,
+    ) -> Option<(usize, usize)> {
+        let found = self.find_back(lines, cursor, match_cursor);
+        self.wrapped = found.map_or(false, |(_, wrapped)| wrapped);
+        found.map(|(pos, _)| pos)
+    }
+
+    // Find the next match and return its position with whether the search wrapped around the text
+    fn find_forward(
+        &self,
+        lines: &[String],
+        cursor: (usize, usize),
+        match_cursor: bool,
+    ) -> Option<((usize, usize), bool)> {
         let pat = if let Some(pat) = &self.pat {
             pat
         } else {
@@ -52,25 +203,29 @@ impl Search {
         // Search current line after cursor
         let start_col = if match_cursor { col } else { col + 1 };
         if let Some((i, _)) = current_line.char_indices().nth(start_col) {
-            if let Some(m) = pat.find_at(current_line, i) {
+            if let Some(m) = self.find_at(pat, current_line, i) {
                 let col = start_col + current_line[i..m.start()].chars().count();
-                return Some((row, col));
+                return Some(((row, col), false));
             }
         }
 
         // Search lines after cursor
         for (i, line) in lines[row + 1..].iter().enumerate() {
-            if let Some(m) = pat.find(line) {
+            if let Some(m) = self.find(pat, line) {
                 let col = line[..m.start()].chars().count();
-                return Some((row + 1 + i, col));
+                return Some(((row + 1 + i, col), false));
             }
         }
 
+        if !self.wrap {
+            return None;
+        }
+
         // Search lines before cursor (wrap)
         for (i, line) in lines[..row].iter().enumerate() {
-            if let Some(m) = pat.find(line) {
+            if let Some(m) = self.find(pat, line) {
                 let col = line[..m.start()].chars().count();
-                return Some((i, col));
+                return Some(((i, col), true));
             }
         }
 
@@ -80,23 +235,24 @@ impl Search {
             .nth(col)
             .map(|(i, _)| i)
             .unwrap_or(current_line.len());
-        if let Some(m) = pat.find(current_line) {
+        if let Some(m) = self.find(pat, current_line) {
             let i = m.start();
             if i <= col_idx {
                 let col = current_line[..i].chars().count();
-                return Some((row, col));
+                return Some(((row, col), true));
             }
         }
 
         None
     }
 
-    pub fn back(
-        &mut self,
+    // Find the previous match and return its position with whether the search wrapped around the text
+    fn find_back(
+        &self,
         lines: &[String],
         cursor: (usize, usize),
         match_cursor: bool,
-    ) -> Option<(usize, usize)> {
+    ) -> Option<((usize, usize), bool)> {
         let pat = if let Some(pat) = &self.pat {
             pat
         } else {
@@ -109,45 +265,142 @@ impl Search {
         if col > 0 || match_cursor {
             let start_col = if match_cursor { col } else { col - 1 };
             if let Some((i, _)) = current_line.char_indices().nth(start_col) {
-                if let Some(m) = pat
-                    .find_iter(current_line)
+                if let Some(m) = self
+                    .find_iter(pat, current_line)
                     .take_while(|m| m.start() <= i)
                     .last()
                 {
                     let col = current_line[..m.start()].chars().count();
-                    return Some((row, col));
+                    return Some(((row, col), false));
                 }
             }
         }
 
         // Search lines before cursor
         for (i, line) in lines[..row].iter().enumerate().rev() {
-            if let Some(m) = pat.find_iter(line).last() {
+            if let Some(m) = self.find_iter(pat, line).last() {
                 let col = line[..m.start()].chars().count();
-                return Some((i, col));
+                return Some(((i, col), false));
             }
         }
 
+        if !self.wrap {
+            return None;
+        }
+
         // Search lines after cursor (wrap)
         for (i, line) in lines[row + 1..].iter().enumerate().rev() {
-            if let Some(m) = pat.find_iter(line).last() {
+            if let Some(m) = self.find_iter(pat, line).last() {
                 let col = line[..m.start()].chars().count();
-                return Some((row + 1 + i, col));
+                return Some(((row + 1 + i, col), true));
             }
         }
 
         // Search current line after cursor
         if let Some((i, _)) = current_line.char_indices().nth(col) {
-            if let Some(m) = pat
-                .find_iter(current_line)
+            if let Some(m) = self
+                .find_iter(pat, current_line)
                 .skip_while(|m| m.start() < i)
                 .last()
             {
                 let col = col + current_line[i..m.start()].chars().count();
-                return Some((row, col));
+                return Some(((row, col), true));
             }
         }
 
         None
     }
+
+    // Call the function with the sorted positions of all non-empty matches in the lines. `revision` is the revision
+    // of the lines to detect modifications of the text since the positions were cached.
+    pub fn with_positions<R>(
+        &self,
+        lines: &[String],
+        revision: u64,
+        f: impl FnOnce(&[(usize, usize)]) -> R,
+    ) -> R {
+        let pat = match &self.pat {
+            Some(pat) => pat,
+            None => return f(&[]),
+        };
+        let key = (
+            revision,
+            pat.as_str().to_string(),
+            self.case_insensitive,
+            self.whole_word,
+        );
+        let mut cache = self.cache.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match &*cache {
+            Some((k, _)) if *k == key => {}
+            _ => {
+                let mut positions = vec![];
+                for (row, line) in lines.iter().enumerate() {
+                    for m in self.find_iter(pat, line).filter(|m| m.start() < m.end()) {
+                        positions.push((row, line[..m.start()].chars().count()));
+                    }
+                }
+                *cache = Some((key, positions));
+            }
+        }
+        let positions = cache.as_ref().map_or(&[][..], |(_, p)| p.as_slice());
+        f(positions)
+    }
+
+    // Get the end column of the match starting at the column in the line and the replacement text for the match.
+    // `$1` and `$name` in the replacement are expanded with the capture groups unless in literal mode.
+    pub fn replacement(
+        &self,
+        line: &str,
+        col: usize,
+        replacement: &str,
+    ) -> Option<(usize, String)> {
+        let pat = self.pat.as_ref()?;
+        let start = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        // Search from the column so that a match overlapping the previous one is also found
+        let caps = pat.captures_at(line, start)?;
+        let m = caps.get(0)?;
+        if m.start() != start || !self.is_accepted(line, &m) {
+            return None;
+        }
+        let mut text = String::new();
+        self.expand(&caps, replacement, &mut text);
+        Some((col + line[start..m.end()].chars().count(), text))
+    }
+
+    // Replace all matches in the lines. Returns the replaced lines and the number of replaced matches. Newlines in the
+    // replacement split the lines.
+    pub fn replace_all(&self, lines: &[String], replacement: &str) -> Option<(Vec<String>, usize)> {
+        let pat = self.pat.as_ref()?;
+        let mut count = 0;
+        let mut replaced = Vec::with_capacity(lines.len());
+        for line in lines {
+            // Matches rejected by the whole word option are replaced with themselves
+            let line = pat.replace_all(line, |caps: &Captures<'_>| {
+                let mut text = String::new();
+                match caps.get(0) {
+                    Some(m) if self.is_accepted(line, &m) => {
+                        count += 1;
+                        self.expand(caps, replacement, &mut text);
+                    }
+                    Some(m) => text.push_str(m.as_str()),
+                    None => {}
+                }
+                text
+            });
+            // `\r\n` in the replacement is one newline. `\r` at the end of the original line is kept
+            let mut parts = line.split('\n').peekable();
+            while let Some(part) = parts.next() {
+                let part = match parts.peek() {
+                    Some(_) => part.strip_suffix('\r').unwrap_or(part),
+                    None => part,
+                };
+                replaced.push(part.to_string());
+            }
+        }
+        Some((replaced, count))
+    }
 }
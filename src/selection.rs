@@ -0,0 +1,65 @@
+/// Mode of text selection set by [`TextArea::set_selection_mode`](crate::TextArea::set_selection_mode). The mode
+/// changes which text is selected between the selection start and the cursor.
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SelectionMode {
+    /// Select characters from the selection start to the cursor. This is the default mode.
+    /// ```
+    /// use tui_textarea::{CursorMove, SelectionMode, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.set_selection_mode(SelectionMode::Charwise);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "bc\nd");
+    /// ```
+    Charwise,
+    /// Select whole lines from the line of the selection start to the line of the cursor including the newline at the
+    /// end of the last line.
+    /// ```
+    /// use tui_textarea::{CursorMove, SelectionMode, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def", "ghi"]);
+    /// textarea.set_selection_mode(SelectionMode::Linewise);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.cut();
+    /// assert_eq!(textarea.yank_text(), "abc\ndef\n");
+    /// assert_eq!(textarea.lines(), ["ghi"]);
+    /// ```
+    Linewise,
+    /// Select a rectangle whose corners are the selection start and the cursor. Columns of the rectangle are counted
+    /// in characters. The end column is exclusive as well as the charwise selection. Text cut or copied from the
+    /// rectangle is pasted column-wise at the cursor across the consecutive lines.
+    /// ```
+    /// use tui_textarea::{CursorMove, SelectionMode, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["abcd", "efgh"]);
+    /// textarea.set_selection_mode(SelectionMode::Block);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(1, 3));
+    /// textarea.cut();
+    /// assert_eq!(textarea.yank_text(), "bc\nfg");
+    /// assert_eq!(textarea.lines(), ["ad", "eh"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["adbc", "ehfg"]);
+    /// ```
+    Block,
+}
+
+impl Default for SelectionMode {
+    fn default() -> Self {
+        Self::Charwise
+    }
+}
@@ -0,0 +1,121 @@
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::widgets::{StatefulWidget, Widget};
+use crate::textarea::TextArea;
+use crate::widget::TextAreaWidget;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Thread-safe handle of [`TextArea`] shared between threads or async tasks.
+///
+/// This is useful when rendering and handling inputs run on separate tasks. Cloning this handle is cheap and all
+/// clones refer to the same [`TextArea`] instance.
+///
+/// All operations including rendering go through the internal lock. Rendering the textarea updates its scroll
+/// position so it needs to lock the textarea exclusively. It means that rendering waits until other threads release
+/// the lock, and it deadlocks when the rendering thread itself holds the guard returned from
+/// [`SharedTextArea::lock`]. Drop the guard before rendering.
+/// ```
+/// # use ratatui::buffer::Buffer;
+/// # use ratatui::layout::Rect;
+/// use ratatui::widgets::Widget;
+/// use std::thread;
+/// use tui_textarea::{SharedTextArea, TextArea, TextAreaWidget};
+///
+/// let textarea = SharedTextArea::new(TextArea::default());
+///
+/// // Modify the textarea on another thread
+/// let handle = textarea.clone();
+/// thread::spawn(move || {
+///     handle.lock().insert_str("hello");
+/// })
+/// .join()
+/// .unwrap();
+///
+/// // Render the shared textarea
+/// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+/// let mut b = Buffer::empty(r.clone());
+/// textarea.widget(TextAreaWidget::new()).render(r, &mut b);
+/// assert_eq!(textarea.lock().lines(), ["hello"]);
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct SharedTextArea(Arc<Mutex<TextArea>>);
+
+impl SharedTextArea {
+    /// Create a new shared handle of the textarea.
+    pub fn new(textarea: TextArea) -> Self {
+        Self(Arc::new(Mutex::new(textarea)))
+    }
+
+    /// Lock the textarea to access it. The lock is released when the returned guard is dropped. When another thread
+    /// panicked while holding the lock, the textarea is still returned since it is always in a consistent state.
+    /// ```
+    /// use tui_textarea::{SharedTextArea, TextArea};
+    ///
+    /// let textarea = SharedTextArea::new(TextArea::from(["abc"]));
+    ///
+    /// textarea.lock().insert_char('x');
+    /// assert_eq!(textarea.lock().lines(), ["xabc"]);
+    /// ```
+    pub fn lock(&self) -> MutexGuard<'_, TextArea> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Build a widget to render the shared textarea with the [`TextAreaWidget`]. The textarea is locked while
+    /// rendering. The returned widget can be rendered with `ratatui::Frame::render_widget`.
+    pub fn widget<'a>(&'a self, widget: TextAreaWidget<'a>) -> impl Widget + 'a {
+        SharedRenderer {
+            widget,
+            textarea: self,
+        }
+    }
+}
+
+impl From<TextArea> for SharedTextArea {
+    fn from(textarea: TextArea) -> Self {
+        Self::new(textarea)
+    }
+}
+
+struct SharedRenderer<'a> {
+    widget: TextAreaWidget<'a>,
+    textarea: &'a SharedTextArea,
+}
+
+impl Widget for SharedRenderer<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut textarea = self.textarea.lock();
+        self.widget.render(area, buf, &mut textarea);
+    }
+}
+
+#[cfg(all(test, feature = "ratatui"))]
+mod tests {
+    use super::*;
+    use crate::CursorMove;
+    use std::thread;
+
+    #[test]
+    fn render_from_another_thread() {
+        let textarea = SharedTextArea::new((0..20).map(|i| i.to_string()).collect());
+
+        let handle = textarea.clone();
+        thread::spawn(move || {
+            let r = Rect {
+                x: 0,
+                y: 0,
+                width: 10,
+                height: 5,
+            };
+            let mut b = Buffer::empty(r);
+            handle.lock().move_cursor(CursorMove::Bottom);
+            handle.widget(TextAreaWidget::new()).render(r, &mut b);
+            assert_eq!(b[(0, 4)].symbol(), "1");
+            assert_eq!(b[(1, 4)].symbol(), "9");
+        })
+        .join()
+        .unwrap();
+
+        // The scroll position updated by rendering is shared
+        assert_eq!(textarea.lock().viewport.scroll_top(), (15, 0));
+    }
+}
@@ -0,0 +1,42 @@
+/// Snapshot of the text contents, the cursor position, and the text selection of [`TextArea`](crate::TextArea).
+///
+/// A snapshot is created by [`TextArea::snapshot`](crate::TextArea::snapshot) and restored by
+/// [`TextArea::restore`](crate::TextArea::restore). This is useful for managing save points or undo across multiple
+/// widgets in an application. When `serde` feature is enabled, snapshots can be serialized and deserialized to persist
+/// them.
+/// ```
+/// use tui_textarea::{CursorMove, TextArea};
+///
+/// let mut textarea = TextArea::from(["hello"]);
+/// textarea.move_cursor(CursorMove::End);
+/// let snapshot = textarea.snapshot();
+///
+/// assert_eq!(snapshot.lines(), ["hello"]);
+/// assert_eq!(snapshot.cursor(), (0, 5));
+/// assert_eq!(snapshot.selection_start(), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextAreaSnapshot {
+    pub(crate) lines: Vec<String>,
+    pub(crate) cursor: (usize, usize),
+    pub(crate) selection_start: Option<(usize, usize)>,
+}
+
+impl TextAreaSnapshot {
+    /// Get the text lines in the snapshot.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Get the cursor position in the snapshot as `(row, column)`.
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    /// Get the start position of the text selection in the snapshot as `(row, column)`. `None` means no text was
+    /// selected.
+    pub fn selection_start(&self) -> Option<(usize, usize)> {
+        self.selection_start
+    }
+}
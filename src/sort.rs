@@ -0,0 +1,86 @@
+use std::cmp::Ordering;
+
+/// Options to sort lines with [`TextArea::sort_lines`](crate::TextArea::sort_lines). By default, lines are sorted in
+/// ascending lexicographic order of their characters.
+/// ```
+/// use tui_textarea::{SortOptions, TextArea};
+///
+/// let mut textarea = TextArea::from(["10", "9", "100"]);
+///
+/// // Sort lines by numbers in descending order
+/// let opts = SortOptions {
+///     numeric: true,
+///     reverse: true,
+///     ..Default::default()
+/// };
+/// textarea.sort_lines(None, opts);
+/// assert_eq!(textarea.lines(), ["100", "10", "9"]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SortOptions {
+    /// Compare lines ignoring the case of letters.
+    pub case_insensitive: bool,
+    /// Compare lines by the numbers at the head of them. Leading whitespaces are ignored. Lines not starting with a
+    /// number are handled as 0.
+    pub numeric: bool,
+    /// Sort lines in descending order.
+    pub reverse: bool,
+    /// Remove adjacent duplicates after sorting. Lines which compare as equal with the other options are duplicates.
+    /// For example, lines with the same leading number are duplicates with `numeric`. The first one of them is kept.
+    pub unique: bool,
+}
+
+impl SortOptions {
+    pub(crate) fn compare(&self, a: &str, b: &str) -> Ordering {
+        let ord = if self.numeric {
+            leading_number(a)
+                .partial_cmp(&leading_number(b))
+                .unwrap_or(Ordering::Equal)
+        } else if self.case_insensitive {
+            a.to_lowercase().cmp(&b.to_lowercase())
+        } else {
+            a.cmp(b)
+        };
+        if self.reverse {
+            ord.reverse()
+        } else {
+            ord
+        }
+    }
+}
+
+fn leading_number(s: &str) -> f64 {
+    let s = s.trim_start();
+    let sign = s.strip_prefix(['-', '+']).map_or(0, |_| 1);
+    let mut dot = false;
+    let len = s[sign..]
+        .find(|c: char| {
+            if c == '.' && !dot {
+                dot = true;
+                return false;
+            }
+            !c.is_ascii_digit()
+        })
+        .map_or(s.len(), |i| sign + i);
+    s[..len].parse().unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn leading_number() {
+        for (input, want) in [
+            ("", 0.0),
+            ("abc", 0.0),
+            ("42", 42.0),
+            ("  42 apples", 42.0),
+            ("-3.5x", -3.5),
+            ("+7", 7.0),
+            ("1.2.3", 1.2),
+            ("-", 0.0),
+            (".5", 0.5),
+        ] {
+            assert_eq!(super::leading_number(input), want, "{:?}", input);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::ratatui::style::{Color, Modifier, Style};
+use std::cmp;
+use std::sync::Arc;
+use syntect::highlighting::{
+    FontStyle, HighlightState, Highlighter, RangedHighlightIterator, Theme, ThemeSet,
+};
+use syntect::parsing::{ParseState, ScopeStack, SyntaxReference, SyntaxSet};
+
+// Theme used until another theme is set with `TextArea::set_syntax_theme`
+const DEFAULT_THEME: &str = "base16-ocean.dark";
+
+// Styles of the byte ranges in a line
+pub type LineStyles = Vec<(usize, usize, Style)>;
+
+// Highlighted line with the text it was highlighted from and the parser states at the end of the line
+#[derive(Clone, Debug)]
+struct HighlightedLine {
+    text: String,
+    styles: LineStyles,
+    state: (ParseState, HighlightState),
+}
+
+// Syntax highlighting of the text with syntect. Since the parser carries its state from a line to the next line, lines
+// are highlighted from the top of the text to the last rendered row. The states at the end of lines are cached with
+// the texts of the lines so that only the lines after the first modified line are highlighted again.
+#[derive(Clone, Debug)]
+pub struct SyntaxHighlighter {
+    syntaxes: Arc<SyntaxSet>,
+    theme: Arc<Theme>,
+    syntax: Option<SyntaxReference>,
+    lines: Vec<HighlightedLine>,
+    revision: u64,
+}
+
+impl Default for SyntaxHighlighter {
+    fn default() -> Self {
+        let theme = ThemeSet::load_defaults()
+            .themes
+            .remove(DEFAULT_THEME)
+            .unwrap_or_default();
+        Self {
+            syntaxes: Arc::new(SyntaxSet::load_defaults_newlines()),
+            theme: Arc::new(theme),
+            syntax: None,
+            lines: vec![],
+            revision: 0,
+        }
+    }
+}
+
+impl SyntaxHighlighter {
+    // Find the syntax by its name like "Rust" or by a file extension like "rs"
+    pub fn set_syntax(&mut self, token: &str) -> bool {
+        self.syntax = self
+            .syntaxes
+            .find_syntax_by_token(token)
+            .or_else(|| self.syntaxes.find_syntax_by_name(token))
+            .cloned();
+        self.lines.clear();
+        self.syntax.is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.syntax = None;
+        self.lines.clear();
+    }
+
+    pub fn syntax(&self) -> Option<&str> {
+        self.syntax.as_ref().map(|s| s.name.as_str())
+    }
+
+    // The current syntax is looked up again by its name in the new set
+    pub fn set_syntax_set(&mut self, syntaxes: SyntaxSet) {
+        self.syntaxes = Arc::new(syntaxes);
+        if let Some(name) = self.syntax.take().map(|s| s.name) {
+            self.syntax = self.syntaxes.find_syntax_by_name(&name).cloned();
+        }
+        self.lines.clear();
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = Arc::new(theme);
+        self.lines.clear();
+    }
+
+    // Highlight the lines until the row. `revision` is the revision of the text to detect modifications since the
+    // last update.
+    pub fn update(&mut self, text: &[String], revision: u64, last_row: usize) {
+        let syntax = match &self.syntax {
+            Some(syntax) => syntax,
+            None => return,
+        };
+        if self.revision != revision {
+            let unchanged = self
+                .lines
+                .iter()
+                .zip(text)
+                .take_while(|(l, t)| &l.text == *t)
+                .count();
+            self.lines.truncate(unchanged);
+            self.revision = revision;
+        }
+
+        let highlighter = Highlighter::new(&self.theme);
+        let end = cmp::min(last_row.saturating_add(1), text.len());
+        let mut buf = String::new();
+        while self.lines.len() < end {
+            let line = &text[self.lines.len()];
+            let (mut parse, mut highlight) = match self.lines.last() {
+                Some(l) => l.state.clone(),
+                None => (
+                    ParseState::new(syntax),
+                    HighlightState::new(&highlighter, ScopeStack::new()),
+                ),
+            };
+            // Syntaxes loaded for lines with newlines are expected
+            buf.clear();
+            buf.push_str(line);
+            buf.push('\n');
+            // A line which failed to be parsed is not styled
+            let styles = match parse.parse_line(&buf, &self.syntaxes) {
+                Ok(ops) => RangedHighlightIterator::new(&mut highlight, &ops, &buf, &highlighter)
+                    .filter(|(_, _, r)| r.start < line.len())
+                    .map(|(s, _, r)| (r.start, cmp::min(r.end, line.len()), convert_style(s)))
+                    .collect(),
+                Err(_) => vec![],
+            };
+            self.lines.push(HighlightedLine {
+                text: line.clone(),
+                styles,
+                state: (parse, highlight),
+            });
+        }
+    }
+
+    // Styles of the line at the row. `None` is returned when the line was not highlighted yet or was modified after
+    // it was highlighted.
+    pub fn line_styles(&self, row: usize, line: &str) -> Option<&[(usize, usize, Style)]> {
+        self.syntax.as_ref()?;
+        let l = self.lines.get(row)?;
+        (l.text == line).then(|| l.styles.as_slice())
+    }
+}
+
+// Only the foreground color and the font style are used so that the background of the textarea is kept
+fn convert_style(style: syntect::highlighting::Style) -> Style {
+    let fg = style.foreground;
+    let mut s = Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b));
+    for (font, modifier) in [
+        (FontStyle::BOLD, Modifier::BOLD),
+        (FontStyle::ITALIC, Modifier::ITALIC),
+        (FontStyle::UNDERLINE, Modifier::UNDERLINED),
+    ] {
+        if style.font_style.contains(font) {
+            s = s.add_modifier(modifier);
+        }
+    }
+    s
+}
@@ -1,26 +1,73 @@
+use crate::bidi::visual_order;
+use crate::cache::{RenderCache, RenderScratch};
+use crate::control::{self, ControlCharHandling};
 use crate::cursor::CursorMove;
-use crate::highlight::LineHighlighter;
-use crate::history::{Edit, EditKind, History};
-use crate::input::{Input, Key};
+use crate::elastic;
+use crate::fold::Folds;
+use crate::highlight::{
+    DiffKind, Gutter, Highlight, Highlights, LineHighlighter, LineRules, ListChars,
+};
+use crate::history::{Edit, EditKind, History, SelectionState};
+use crate::input::{Input, InputResult, Key, MouseEvent, MouseEventKind, ParseInputError};
+use crate::minimap::{self, MinimapArea, MinimapExtents, MinimapRow};
+use crate::newline::TrailingNewline;
 use crate::ratatui::layout::Alignment;
 use crate::ratatui::style::{Color, Modifier, Style};
-use crate::scroll::Scrolling;
+use crate::scroll::{CursorAnchor, ScrollCallback, ScrollInfo, Scrolling};
 #[cfg(feature = "search")]
 use crate::search::Search;
-use crate::util::{spaces, Pos};
-use crate::widget::Viewport;
-use crate::word::{find_word_end_forward, find_word_start_backward};
+use crate::selection::SelectionMode;
+use crate::snapshot::TextAreaSnapshot;
+use crate::sort::SortOptions;
+#[cfg(feature = "syntect")]
+use crate::syntax::SyntaxHighlighter;
+use crate::util::{
+    char_display_width, next_grapheme_col, num_digits, prev_grapheme_col, spaces, tab_stop_width,
+    Pos, RowShift,
+};
+use crate::widget::{take_columns, Viewport};
+use crate::word::{
+    find_word_at, find_word_end_forward, find_word_prefix_start, find_word_range,
+    find_word_start_backward, is_word_char, WordChars,
+};
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io;
+use std::mem;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+#[cfg(feature = "syntect")]
+use syntect::highlighting::Theme;
+#[cfg(feature = "syntect")]
+use syntect::parsing::SyntaxSet;
 #[cfg(feature = "tuirs")]
 use tui::text::Spans as Line;
-use unicode_width::UnicodeWidthChar as _;
+use unicode_segmentation::UnicodeSegmentation as _;
+use unicode_width::{UnicodeWidthChar as _, UnicodeWidthStr as _};
+
+// Successive clicks within this interval at the same position are counted as a double-click or a triple-click
+const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+// Pairs of opening and closing characters deleted at once by pair backspace
+const PAIRS: [(char, char); 6] = [
+    ('(', ')'),
+    ('[', ']'),
+    ('{', '}'),
+    ('"', '"'),
+    ('\'', '\''),
+    ('`', '`'),
+];
 
 #[derive(Debug, Clone)]
 enum YankText {
     Piece(String),
     Chunk(Vec<String>),
+    // Rectangular text cut or copied in block selection mode. Each element is the text in each line
+    Block(Vec<String>),
 }
 
 impl Default for YankText {
@@ -44,20 +91,78 @@ impl From<Vec<String>> for YankText {
     }
 }
 
-impl ToString for YankText {
-    fn to_string(&self) -> String {
+impl fmt::Display for YankText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Piece(s) => s.clone(),
-            Self::Chunk(ss) => ss.join("\n"),
+            Self::Piece(s) => write!(f, "{}", s),
+            Self::Chunk(ss) | Self::Block(ss) => write!(f, "{}", ss.join("\n")),
         }
     }
 }
 
+impl YankText {
+    // Split the text into lines. `\n` and `\r\n` are recognized as newline but `\r` isn't
+    fn from_text(text: &str) -> Self {
+        // `str::lines` is not available since it strips a newline at end
+        let lines: Vec<_> = text
+            .split('\n')
+            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
+            .collect();
+        lines.into()
+    }
+}
+
+// Recently yanked texts. The newest one is at the front
+#[derive(Debug, Clone)]
+struct KillRing {
+    texts: VecDeque<YankText>,
+    max: usize,
+    // Index of the text in the ring which was pasted last
+    index: usize,
+}
+
+impl Default for KillRing {
+    fn default() -> Self {
+        Self {
+            texts: VecDeque::new(),
+            max: 10,
+            index: 0,
+        }
+    }
+}
+
+impl KillRing {
+    fn push(&mut self, yank: YankText) {
+        self.index = 0;
+        if self.max == 0 {
+            return;
+        }
+        self.texts.push_front(yank);
+        self.texts.truncate(self.max);
+    }
+}
+
+// Range of the text inserted by the last paste and the revision after the paste. It is replaced by `TextArea::yank_pop`
+// while the text is not modified by others
+#[derive(Debug, Clone)]
+struct LastPaste {
+    start: (usize, usize),
+    end: (usize, usize),
+    revision: u64,
+}
+
+// Start and end positions of the text selection
+type SelectionRange = ((usize, usize), (usize, usize));
+
 /// A type to manage state of textarea.
 ///
 /// [`TextArea::default`] creates an empty textarea. [`TextArea::new`] creates a textarea with given text lines.
 /// [`TextArea::from`] creates a textarea from an iterator of lines. [`TextArea::input`] handles key input.
 /// [`TextArea::widget`] builds a widget to render. And [`TextArea::lines`] returns line texts.
+///
+/// Decorations attached to rows such as highlights, signs, diff marks, virtual text, alignments of lines, folded
+/// ranges, and extra selections move with their lines when lines are inserted or removed before them. They are dropped
+/// when their lines are removed. Their columns are not updated on editing text in a line.
 /// ```
 /// use tui_textarea::{TextArea, Input, Key};
 ///
@@ -78,22 +183,89 @@ pub struct TextArea {
     lines: Vec<String>,
     style: Style,
     cursor: (usize, usize), // 0-base
+    // Column remembered by vertical cursor moves with the cursor position and the revision when it was remembered. It
+    // is stale once the cursor moved or the text was modified by other operations
+    goal_col: Option<(usize, (usize, usize), u64)>,
     tab_len: u8,
     hard_tab_indent: bool,
+    smart_backspace: bool,
+    pair_backspace: bool,
+    overwrite: bool,
+    cursor_past_eol: bool,
+    elastic_tabs: bool,
+    tab_stops: Vec<u16>,
+    comment_after_indent: bool,
+    word_chars: WordChars,
     history: History,
+    // Incremented on every modification of the text contents
+    revision: u64,
+    // Incremented on every move of the cursor and every change of the text selection
+    cursor_revision: u64,
+    selection_revision: u64,
+    // Text deleted while recording it with `TextArea::record_deleted`
+    deleted: Option<String>,
     cursor_line_style: Style,
     line_number_style: Option<Style>,
+    current_line_number_style: Style,
+    line_number_separator: String,
+    line_number_alignment: Alignment,
+    line_number_min_width: u8,
+    sign_column: bool,
+    signs: HashMap<usize, (char, Style)>,
     pub(crate) viewport: Viewport,
+    on_scroll: ScrollCallback,
+    pub(crate) cursor_anchor: Option<CursorAnchor>,
     cursor_style: Style,
+    hide_cursor_style: bool,
     yank: YankText,
+    kill_ring: KillRing,
+    last_paste: Option<LastPaste>,
+    registers: HashMap<char, String>,
     #[cfg(feature = "search")]
     search: Search,
     alignment: Alignment,
+    line_alignments: HashMap<usize, Alignment>,
     pub(crate) placeholder: String,
     pub(crate) placeholder_style: Style,
     mask: Option<char>,
     selection_start: Option<(usize, usize)>,
+    selection_mode: SelectionMode,
+    // Time, screen position, and count of the last click to detect double-clicks and triple-clicks
+    last_click: Option<(Instant, (u16, u16), u8)>,
+    pub(crate) minimap: Option<MinimapArea>,
+    // If the mouse button was pressed on the minimap and is still held
+    minimap_drag: bool,
+    pub(crate) minimap_extents: MinimapExtents,
+    pub(crate) render_cache: RenderCache,
+    pub(crate) render_scratch: RenderScratch,
     select_style: Style,
+    highlights: Highlights,
+    extra_selections: Vec<SelectionRange>,
+    diff: HashMap<usize, DiffKind>,
+    diff_styles: [Style; 3],
+    line_rules: LineRules,
+    #[cfg(feature = "syntect")]
+    syntax: Option<SyntaxHighlighter>,
+    folds: Folds,
+    virtual_texts: HashMap<usize, Vec<(String, Style)>>,
+    preedit: Option<String>,
+    preedit_style: Style,
+    empty_line_style: Style,
+    end_of_buffer_marker: Option<char>,
+    list_chars: ListChars,
+    list_style: Style,
+    ruler_columns: Vec<u16>,
+    ruler_style: Style,
+    trailing_whitespace_style: Option<Style>,
+    current_word_style: Option<Style>,
+    control_chars: ControlCharHandling,
+    control_char_style: Style,
+    scrolloff: u16,
+    sidescrolloff: u16,
+    hscroll_step: u16,
+    fold_style: Style,
+    single_line: bool,
+    trailing_newline: TrailingNewline,
 }
 
 /// Convert any iterator whose elements can be converted into [`String`] into [`TextArea`]. Each [`String`] element is
@@ -182,22 +354,88 @@ impl TextArea {
             lines,
             style: Style::default(),
             cursor: (0, 0),
+            goal_col: None,
             tab_len: 4,
             hard_tab_indent: false,
+            smart_backspace: false,
+            pair_backspace: false,
+            overwrite: false,
+            cursor_past_eol: true,
+            elastic_tabs: false,
+            tab_stops: vec![],
+            comment_after_indent: true,
+            word_chars: is_word_char,
             history: History::new(50),
+            revision: 0,
+            cursor_revision: 0,
+            selection_revision: 0,
+            deleted: None,
             cursor_line_style: Style::default().add_modifier(Modifier::UNDERLINED),
             line_number_style: None,
+            current_line_number_style: Style::default(),
+            line_number_separator: " ".to_string(),
+            line_number_alignment: Alignment::Right,
+            line_number_min_width: 0,
+            sign_column: false,
+            signs: HashMap::new(),
             viewport: Viewport::default(),
+            on_scroll: ScrollCallback::default(),
+            cursor_anchor: None,
             cursor_style: Style::default().add_modifier(Modifier::REVERSED),
+            hide_cursor_style: false,
             yank: YankText::default(),
+            kill_ring: KillRing::default(),
+            last_paste: None,
+            registers: HashMap::new(),
             #[cfg(feature = "search")]
             search: Search::default(),
             alignment: Alignment::Left,
+            line_alignments: HashMap::new(),
             placeholder: String::new(),
             placeholder_style: Style::default().fg(Color::DarkGray),
             mask: None,
             selection_start: None,
+            selection_mode: SelectionMode::default(),
+            last_click: None,
+            minimap: None,
+            minimap_drag: false,
+            minimap_extents: MinimapExtents::default(),
+            render_cache: RenderCache::default(),
+            render_scratch: RenderScratch::default(),
             select_style: Style::default().bg(Color::LightBlue),
+            highlights: Highlights::default(),
+            extra_selections: vec![],
+            diff: HashMap::new(),
+            line_rules: LineRules::default(),
+            #[cfg(feature = "syntect")]
+            syntax: None,
+            diff_styles: [
+                Style::default().bg(Color::Green),
+                Style::default()
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::CROSSED_OUT),
+                Style::default().bg(Color::Yellow),
+            ],
+            folds: Folds::default(),
+            virtual_texts: HashMap::new(),
+            preedit: None,
+            preedit_style: Style::default().add_modifier(Modifier::UNDERLINED),
+            empty_line_style: Style::default(),
+            end_of_buffer_marker: None,
+            list_chars: ListChars::default(),
+            list_style: Style::default().fg(Color::DarkGray),
+            ruler_columns: vec![],
+            ruler_style: Style::default().bg(Color::DarkGray),
+            trailing_whitespace_style: None,
+            current_word_style: None,
+            control_chars: ControlCharHandling::default(),
+            control_char_style: Style::default().fg(Color::Blue),
+            scrolloff: 0,
+            sidescrolloff: 0,
+            hscroll_step: 1,
+            fold_style: Style::default().add_modifier(Modifier::DIM),
+            single_line: false,
+            trailing_newline: TrailingNewline::default(),
         }
     }
 
@@ -205,9 +443,11 @@ impl TextArea {
     /// [the module document](./index.html).
     /// `crossterm`, `termion`, and `termwiz` features enable conversion from their own key event types into
     /// [`Input`] so this method can take the event values directly.
-    /// This method returns if the input modified text contents or not in the textarea.
+    /// This method returns an [`InputResult`] which describes what happened by the input. For example,
+    /// [`InputResult::Changed`] means the text contents were modified and [`InputResult::Unhandled`] means no key
+    /// mapping was defined for the input. Use [`InputResult::changed`] to know if the text was modified or not.
     /// ```ignore
-    /// use tui_textarea::{TextArea, Key, Input};
+    /// use tui_textarea::{TextArea, Key, Input, InputResult};
     ///
     /// let mut textarea = TextArea::default();
     ///
@@ -234,11 +474,13 @@ impl TextArea {
     ///
     /// // Handle backend-agnostic key input
     /// let input = Input { key: Key::Char('a'), ctrl: false, alt: false, shift: false };
-    /// let modified = textarea.input(input);
-    /// assert!(modified);
+    /// let result = textarea.input(input);
+    /// assert_eq!(result, InputResult::Changed);
+    /// assert!(result.changed());
     /// ```
-    pub fn input(&mut self, input: impl Into<Input>) -> bool {
+    pub fn input(&mut self, input: impl Into<Input>) -> InputResult {
         let input = input.into();
+        let cursor = self.cursor;
         let modified = match input {
             Input {
                 key: Key::Char('m'),
@@ -255,6 +497,9 @@ impl TextArea {
             | Input {
                 key: Key::Enter, ..
             } => {
+                if self.single_line {
+                    return InputResult::Submit;
+                }
                 self.insert_newline();
                 true
             }
@@ -271,8 +516,14 @@ impl TextArea {
                 key: Key::Tab,
                 ctrl: false,
                 alt: false,
-                ..
-            } => self.insert_tab(),
+                shift: false,
+            } => self.tab(),
+            Input {
+                key: Key::Tab,
+                ctrl: false,
+                alt: false,
+                shift: true,
+            } => self.dedent_lines(),
             Input {
                 key: Key::Char('h'),
                 ctrl: true,
@@ -326,6 +577,12 @@ impl TextArea {
                 ctrl: false,
                 alt: true,
                 ..
+            }
+            | Input {
+                key: Key::Backspace,
+                ctrl: true,
+                alt: false,
+                ..
             } => self.delete_word(),
             Input {
                 key: Key::Delete,
@@ -333,6 +590,12 @@ impl TextArea {
                 alt: true,
                 ..
             }
+            | Input {
+                key: Key::Delete,
+                ctrl: true,
+                alt: false,
+                ..
+            }
             | Input {
                 key: Key::Char('d'),
                 ctrl: false,
@@ -562,6 +825,12 @@ impl TextArea {
             | Input {
                 key: Key::Paste, ..
             } => self.paste(),
+            Input {
+                key: Key::Char('y'),
+                ctrl: false,
+                alt: true,
+                ..
+            } => self.yank_pop(),
             Input {
                 key: Key::Char('x'),
                 ctrl: true,
@@ -607,6 +876,15 @@ impl TextArea {
                 self.scroll_with_shift(Scrolling::PageUp, shift);
                 false
             }
+            Input {
+                key: Key::Insert,
+                ctrl: false,
+                alt: false,
+                shift: false,
+            } => {
+                self.overwrite = !self.overwrite;
+                false
+            }
             Input {
                 key: Key::MouseScrollDown,
                 shift,
@@ -623,7 +901,7 @@ impl TextArea {
                 self.scroll_with_shift((-1, 0).into(), shift);
                 false
             }
-            _ => false,
+            _ => return InputResult::Unhandled,
         };
 
         // Check invariants
@@ -645,7 +923,17 @@ impl TextArea {
             input,
         );
 
-        modified
+        self.input_result(modified, cursor)
+    }
+
+    fn input_result(&self, modified: bool, cursor_before: (usize, usize)) -> InputResult {
+        if modified {
+            InputResult::Changed
+        } else if self.cursor != cursor_before {
+            InputResult::CursorMoved
+        } else {
+            InputResult::Handled
+        }
     }
 
     /// Handle a key input without default key mappings. This method handles only
@@ -656,12 +944,28 @@ impl TextArea {
     /// - Backspace
     /// - Delete
     ///
-    /// This method returns if the input modified text contents or not in the textarea.
+    /// This method returns an [`InputResult`] which describes what happened by the input as [`TextArea::input`] does.
     ///
     /// This method is useful when you want to define your own key mappings and don't want default key mappings.
     /// See 'Define your own key mappings' section in [the module document](./index.html).
-    pub fn input_without_shortcuts(&mut self, input: impl Into<Input>) -> bool {
-        match input.into() {
+    pub fn input_without_shortcuts(&mut self, input: impl Into<Input>) -> InputResult {
+        let cursor = self.cursor;
+        let modified = match input.into() {
+            Input {
+                key: Key::Char('\n' | '\r'),
+                ctrl: false,
+                alt: false,
+                ..
+            }
+            | Input {
+                key: Key::Enter, ..
+            } => {
+                if self.single_line {
+                    return InputResult::Submit;
+                }
+                self.insert_newline();
+                true
+            }
             Input {
                 key: Key::Char(c),
                 ctrl: false,
@@ -675,8 +979,14 @@ impl TextArea {
                 key: Key::Tab,
                 ctrl: false,
                 alt: false,
-                ..
-            } => self.insert_tab(),
+                shift: false,
+            } => self.tab(),
+            Input {
+                key: Key::Tab,
+                ctrl: false,
+                alt: false,
+                shift: true,
+            } => self.dedent_lines(),
             Input {
                 key: Key::Backspace,
                 ..
@@ -684,12 +994,6 @@ impl TextArea {
             Input {
                 key: Key::Delete, ..
             } => self.delete_next_char(),
-            Input {
-                key: Key::Enter, ..
-            } => {
-                self.insert_newline();
-                true
-            }
             Input {
                 key: Key::MouseScrollDown,
                 ..
@@ -704,1497 +1008,5833 @@ impl TextArea {
                 self.scroll((-1, 0));
                 false
             }
-            _ => false,
-        }
-    }
-
-    fn push_history(&mut self, kind: EditKind, before: Pos, after_offset: usize) {
-        let (row, col) = self.cursor;
-        let after = Pos::new(row, col, after_offset);
-        let edit = Edit::new(kind, before, after);
-        self.history.push(edit);
+            _ => return InputResult::Unhandled,
+        };
+        self.input_result(modified, cursor)
     }
 
-    /// Insert a single character at current cursor position.
+    /// Apply a sequence of key inputs written in the key notation of Vim such as `"hello<CR>world<C-a>"` with the
+    /// default key mappings of [`TextArea::input`]. This is useful for driving the textarea in tests and scripts
+    /// without synthesizing events of terminal backends. See [`Input::parse_sequence`] for the notation. When the
+    /// notation is invalid, an error is returned and no input is applied. Otherwise this method returns if the text
+    /// was modified by some of the inputs.
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// textarea.insert_char('a');
-    /// assert_eq!(textarea.lines(), ["a"]);
+    /// textarea.feed_keys("hello<CR>world<C-a><C-k>").unwrap();
+    /// assert_eq!(textarea.lines(), ["hello", ""]);
+    ///
+    /// textarea.feed_keys("<C-u>").unwrap();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    ///
+    /// assert!(textarea.feed_keys("<Unknown>").is_err());
     /// ```
-    pub fn insert_char(&mut self, c: char) {
-        if c == '\n' || c == '\r' {
-            self.insert_newline();
-            return;
+    pub fn feed_keys(&mut self, keys: &str) -> Result<bool, ParseInputError> {
+        let mut changed = false;
+        for input in Input::parse_sequence(keys)? {
+            changed |= self.input(input).changed();
         }
-
-        self.delete_selection(false);
-        let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        let i = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
-        line.insert(i, c);
-        self.cursor.1 += 1;
-        self.push_history(
-            EditKind::InsertChar(c),
-            Pos::new(row, col, i),
-            i + c.len_utf8(),
-        );
+        Ok(changed)
     }
 
-    /// Insert a string at current cursor position. This method returns if some text was inserted or not in the textarea.
-    /// Both `\n` and `\r\n` are recognized as newlines but `\r` isn't.
+    /// Handle a mouse event with the left button. The position of the event is mapped into the text with the
+    /// position of the textarea on the last render so this method does nothing before the first render.
+    ///
+    /// - Clicking moves the cursor to the clicked position. Clicking with Shift extends the text selection
+    /// - Double-click selects the word under the mouse cursor
+    /// - Triple-click selects the line under the mouse cursor
+    /// - Dragging selects text from the position where the button was pressed
+    ///
+    /// Successive clicks at the same position within a short interval are counted as a double-click or a
+    /// triple-click. Clicks outside the text area are not handled and [`InputResult::Unhandled`] is returned.
+    /// Clicking or dragging on the minimap rendered by [`MinimapWidget`](crate::MinimapWidget) scrolls the textarea
+    /// to the lines at the mouse position.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{MouseEvent, MouseEventKind, TextArea, TextAreaWidget};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["hello world_wide"]);
+    /// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// let mut b = Buffer::empty(r.clone());
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
     ///
-    /// textarea.insert_str("hello");
-    /// assert_eq!(textarea.lines(), ["hello"]);
+    /// let click = MouseEvent { kind: MouseEventKind::Down, column: 8, row: 0, shift: false };
     ///
-    /// textarea.insert_str(", world\ngoodbye, world");
-    /// assert_eq!(textarea.lines(), ["hello, world", "goodbye, world"]);
+    /// // Click moves the cursor
+    /// textarea.on_mouse(click);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    ///
+    /// // Double-click selects the word
+    /// textarea.on_mouse(click);
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "world_wide");
+    ///
+    /// // Triple-click selects the line
+    /// textarea.on_mouse(click);
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "hello world_wide");
     /// ```
-    pub fn insert_str<S: AsRef<str>>(&mut self, s: S) -> bool {
-        let modified = self.delete_selection(false);
-        let mut lines: Vec<_> = s
-            .as_ref()
-            .split('\n')
-            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
-            .collect();
-        match lines.len() {
-            0 => modified,
-            1 => self.insert_piece(lines.remove(0)),
-            _ => self.insert_chunk(lines),
+    pub fn on_mouse(&mut self, event: MouseEvent) -> InputResult {
+        if let Some(result) = self.on_minimap_mouse(&event) {
+            return result;
         }
-    }
-
-    fn insert_chunk(&mut self, chunk: Vec<String>) -> bool {
-        debug_assert!(chunk.len() > 1, "Chunk size must be > 1: {:?}", chunk);
-
-        let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        let i = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
-        let before = Pos::new(row, col, i);
-
-        let (row, col) = (
-            row + chunk.len() - 1,
-            chunk[chunk.len() - 1].chars().count(),
-        );
-        self.cursor = (row, col);
-
-        let end_offset = chunk.last().unwrap().len();
-
-        let edit = EditKind::InsertChunk(chunk);
-        edit.apply(&mut self.lines, &before, &Pos::new(row, col, end_offset));
-
-        self.push_history(edit, before, end_offset);
-        true
-    }
-
-    fn insert_piece(&mut self, s: String) -> bool {
-        if s.is_empty() {
-            return false;
+        let MouseEvent {
+            kind,
+            column,
+            row,
+            shift,
+        } = event;
+        let before = (self.cursor, self.selection_start);
+        match kind {
+            MouseEventKind::Down => {
+                let pos = match self.position_on_screen(column, row, false) {
+                    Some(pos) => pos,
+                    None => return InputResult::Unhandled,
+                };
+                let now = Instant::now();
+                let count = match self.last_click {
+                    Some((time, at, count))
+                        if at == (column, row)
+                            && now.duration_since(time) < MULTI_CLICK_INTERVAL =>
+                    {
+                        count % 3 + 1
+                    }
+                    _ => 1,
+                };
+                self.last_click = Some((now, (column, row), count));
+
+                let (r, c) = pos;
+                match count {
+                    2 if !shift => {
+                        let (start, end) = find_word_range(&self.lines[r], c, self.word_chars);
+                        self.set_selection_start(Some((r, start)));
+                        self.set_cursor((r, end));
+                    }
+                    3 if !shift => {
+                        self.set_selection_start(Some((r, 0)));
+                        self.set_cursor((r, self.lines[r].chars().count()));
+                    }
+                    _ if shift => {
+                        if self.selection_start.is_none() {
+                            self.start_selection();
+                        }
+                        self.set_cursor(pos);
+                    }
+                    _ => {
+                        self.cancel_selection();
+                        self.set_cursor(self.clamp_past_eol(pos));
+                    }
+                }
+            }
+            MouseEventKind::Drag => {
+                let pos = match self.position_on_screen(column, row, true) {
+                    Some(pos) => pos,
+                    None => return InputResult::Unhandled,
+                };
+                if self.selection_start.is_none() {
+                    self.start_selection();
+                }
+                self.set_cursor(pos);
+            }
+            MouseEventKind::Up => return InputResult::Handled,
+        }
+        if (self.cursor, self.selection_start) != before {
+            InputResult::CursorMoved
+        } else {
+            InputResult::Handled
         }
-
-        let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        debug_assert!(
-            !s.contains('\n'),
-            "string given to TextArea::insert_piece must not contain newline: {:?}",
-            line,
-        );
-
-        let i = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
-        line.insert_str(i, &s);
-        let end_offset = i + s.len();
-
-        self.cursor.1 += s.chars().count();
-        self.push_history(EditKind::InsertStr(s), Pos::new(row, col, i), end_offset);
-        true
     }
 
-    fn delete_range(&mut self, start: Pos, end: Pos, should_yank: bool) {
-        self.cursor = (start.row, start.col);
-
-        if start.row == end.row {
-            let removed = self.lines[start.row]
-                .drain(start.offset..end.offset)
-                .as_str()
-                .to_string();
-            if should_yank {
-                self.yank = removed.clone().into();
+    // Scroll the viewport to center the lines at the mouse position on the minimap. `None` is returned when the event
+    // is not for the minimap.
+    fn on_minimap_mouse(&mut self, event: &MouseEvent) -> Option<InputResult> {
+        let clamp = match event.kind {
+            MouseEventKind::Down => {
+                self.minimap_drag = false;
+                false
             }
-            self.push_history(EditKind::DeleteStr(removed), end, start.offset);
-            return;
+            MouseEventKind::Drag if self.minimap_drag => true,
+            MouseEventKind::Drag => return None,
+            MouseEventKind::Up => {
+                self.minimap_drag = false;
+                return None;
+            }
+        };
+        let line = self.minimap?.line_at(event.column, event.row, clamp)?;
+        self.minimap_drag = true;
+
+        let (top, _, _, height) = self.viewport.rect();
+        let last = self.lines.len() - 1;
+        let target = cmp::min(line, last).saturating_sub(height as usize / 2);
+        let target = cmp::min(target, (last + 1).saturating_sub(height as usize));
+        let delta = target as isize - top as isize;
+        let delta = cmp::max(cmp::min(delta, i16::MAX as isize), i16::MIN as isize) as i16;
+        let before = (self.cursor, self.selection_start);
+        self.scroll((delta, 0));
+        if (self.cursor, self.selection_start) != before {
+            Some(InputResult::CursorMoved)
+        } else {
+            Some(InputResult::Handled)
         }
+    }
 
-        let mut deleted = vec![self.lines[start.row]
-            .drain(start.offset..)
-            .as_str()
-            .to_string()];
-        deleted.extend(self.lines.drain(start.row + 1..end.row));
-        if start.row + 1 < self.lines.len() {
-            let mut last_line = self.lines.remove(start.row + 1);
-            self.lines[start.row].push_str(&last_line[end.offset..]);
-            last_line.truncate(end.offset);
-            deleted.push(last_line);
+    // Map the position on screen into the position in the text using the viewport on the last render. When `clamp`
+    // is true, the position outside the text area is clamped into the area.
+    fn position_on_screen(&self, x: u16, y: u16, clamp: bool) -> Option<(usize, usize)> {
+        let (top_row, top_col, width, height) = self.viewport.rect();
+        if width == 0 || height == 0 {
+            return None;
         }
-
-        if should_yank {
-            self.yank = YankText::Chunk(deleted.clone());
+        let (origin_x, origin_y) = self.viewport.origin();
+        let in_area = |p: u16, origin: u16, len: u16| origin <= p && p - origin < len;
+        let inside = in_area(x, origin_x, width) && in_area(y, origin_y, height);
+        if !clamp && !inside {
+            return None;
         }
-
-        let edit = if deleted.len() == 1 {
-            EditKind::DeleteStr(deleted.remove(0))
-        } else {
-            EditKind::DeleteChunk(deleted)
+        let dx = cmp::min(x.saturating_sub(origin_x), width - 1) as usize;
+        let dy = cmp::min(y.saturating_sub(origin_y), height - 1) as usize;
+
+        let display_row = self.folds.display_row(top_row as usize) + dy;
+        let row = cmp::min(self.folds.text_row(display_row), self.lines.len() - 1);
+        let row = self.folds.visible_row(row);
+
+        // Find the character rendered at the column
+        let (offset, scroll) = self.line_screen_offset(row, width, top_col);
+        let target = match dx.checked_sub(offset) {
+            Some(dx) => scroll + dx,
+            None => return Some((row, 0)), // Clicked before the head of the aligned line
         };
-        self.push_history(edit, end, start.offset);
+        let mut width = 0;
+        for (col, w) in self.visual_widths(row) {
+            // The preedit is rendered before the cursor. Clicking it moves the cursor to the same position
+            if (row, col) == self.cursor {
+                width += self.preedit_width();
+            }
+            width += w;
+            if target < width {
+                return Some((row, col));
+            }
+        }
+        Some((row, self.lines[row].chars().count()))
     }
 
-    /// Delete a string from the current cursor position. The `chars` parameter means number of characters, not a byte
-    /// length of the string. Newlines at the end of lines are counted in the number. This method returns if some text
-    /// was deleted or not.
+    // Offset of the head of the line at `row` from the left edge of the text area and the number of columns hidden by
+    // horizontal scroll. Lines aligned to the center or the right are shifted by the alignment instead of being
+    // scrolled horizontally.
+    fn line_screen_offset(&self, row: usize, width: u16, top_col: u16) -> (usize, usize) {
+        let width = width as usize;
+        let line_width = || cmp::min(self.line_spans(&self.lines[row], row).width(), width);
+        match self.line_alignment(row) {
+            Alignment::Left => (0, top_col as usize),
+            Alignment::Center => ((width / 2).saturating_sub(line_width() / 2), 0),
+            Alignment::Right => (width.saturating_sub(line_width()), 0),
+        }
+    }
+
+    /// Get the position of the cursor on screen as `(x, y)`. The position is calculated with the area and the scroll
+    /// position at the last rendering and considers the text alignment set by [`TextArea::set_alignment`]. This is
+    /// useful for placing the terminal cursor such as `ratatui::Frame::set_cursor_position` for input methods. When
+    /// the textarea has not been rendered yet or the cursor is out of the viewport, this method returns `None`.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::{Alignment, Rect};
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{CursorMove, TextArea, TextAreaWidget};
     ///
-    /// let mut textarea = TextArea::from(["🐱🐶🐰🐮"]);
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_alignment(Alignment::Right);
     /// textarea.move_cursor(CursorMove::Forward);
+    /// assert_eq!(textarea.cursor_screen_position(), None);
     ///
-    /// textarea.delete_str(2);
-    /// assert_eq!(textarea.lines(), ["🐱🐮"]);
-    ///
-    /// let mut textarea = TextArea::from(["🐱", "🐶", "🐰", "🐮"]);
-    /// textarea.move_cursor(CursorMove::Down);
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 3 };
+    /// let mut b = Buffer::empty(r);
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
     ///
-    /// textarea.delete_str(4); // Deletes 🐶, \n, 🐰, \n
-    /// assert_eq!(textarea.lines(), ["🐱", "🐮"]);
+    /// // "abc" is aligned to the right edge and the cursor is on "b"
+    /// assert_eq!(textarea.cursor_screen_position(), Some((8, 0)));
     /// ```
-    pub fn delete_str(&mut self, chars: usize) -> bool {
-        if self.delete_selection(false) {
-            return true;
+    pub fn cursor_screen_position(&self) -> Option<(u16, u16)> {
+        let (top_row, top_col, width, height) = self.viewport.rect();
+        if width == 0 || height == 0 {
+            return None;
         }
-        if chars == 0 {
-            return false;
+        let row = self.cursor.0;
+        let dy = self
+            .folds
+            .display_row(row)
+            .checked_sub(self.folds.display_row(top_row as usize))?;
+        if dy >= height as usize {
+            return None;
         }
+        let (offset, scroll) = self.line_screen_offset(row, width, top_col);
+        let dx = (offset + self.cursor_display_col()).checked_sub(scroll)?;
+        if dx >= width as usize {
+            return None;
+        }
+        let (x, y) = self.viewport.origin();
+        Some((x + dx as u16, y + dy as u16))
+    }
 
-        let (start_row, start_col) = self.cursor;
-
-        let mut remaining = chars;
-        let mut find_end = move |line: &str| {
-            let mut col = 0usize;
-            for (i, _) in line.char_indices() {
-                if remaining == 0 {
-                    return Some((i, col));
-                }
-                col += 1;
-                remaining -= 1;
-            }
-            if remaining == 0 {
-                Some((line.len(), col))
-            } else {
-                remaining -= 1;
-                None
-            }
-        };
+    /// Get the first and the last lines displayed in the viewport on the last render as the inclusive range of line
+    /// indices. A folded range is displayed as its first line. When the text ends before the bottom of the viewport,
+    /// the last line of the text is the last visible line. This returns `None` before the first render.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+    /// assert_eq!(textarea.visible_range(), None);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// let mut b = Buffer::empty(r);
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// assert_eq!(textarea.visible_range(), Some((0, 7)));
+    ///
+    /// textarea.scroll((15, 0));
+    /// assert_eq!(textarea.visible_range(), Some((15, 19)));
+    /// ```
+    pub fn visible_range(&self) -> Option<(usize, usize)> {
+        let (top_row, _, width, height) = self.viewport.rect();
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let last_row = self.lines.len() - 1;
+        let first = self.folds.visible_row(cmp::min(top_row as usize, last_row));
+        let bottom = self.folds.display_row(first) + height as usize - 1;
+        let last = self
+            .folds
+            .visible_row(cmp::min(self.folds.text_row(bottom), last_row));
+        Some((first, last))
+    }
 
-        let line = &self.lines[start_row];
-        let start_offset = {
-            line.char_indices()
-                .nth(start_col)
-                .map(|(i, _)| i)
-                .unwrap_or(line.len())
-        };
+    /// Get the size of the viewport on the last render as `(width, height)`. The size excludes the block, the padding,
+    /// the gutter, and the scrollbars. This returns `(0, 0)` before the first render.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.viewport_size(), (0, 0));
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// let mut b = Buffer::empty(r);
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// assert_eq!(textarea.viewport_size(), (24, 8));
+    /// ```
+    pub fn viewport_size(&self) -> (u16, u16) {
+        let (_, _, width, height) = self.viewport.rect();
+        (width, height)
+    }
 
-        // First line
-        if let Some((offset_delta, col_delta)) = find_end(&line[start_offset..]) {
-            let end_offset = start_offset + offset_delta;
-            let end_col = start_col + col_delta;
-            let removed = self.lines[start_row]
-                .drain(start_offset..end_offset)
-                .as_str()
-                .to_string();
-            self.yank = removed.clone().into();
-            self.push_history(
-                EditKind::DeleteStr(removed),
-                Pos::new(start_row, end_col, end_offset),
-                start_offset,
-            );
-            return true;
+    // Widths of characters in the line at `row` on screen considering hard tabs, wide characters, and the mask
+    // character
+    fn display_widths(&self, row: usize) -> impl Iterator<Item = usize> + '_ {
+        let mask = self.mask;
+        let mut tabs = self.tab_widths(row).map(Vec::into_iter);
+        let mut col = 0;
+        self.lines[row].chars().map(move |c| {
+            let width = match (mask, &mut tabs) {
+                (Some(m), _) => m.width().unwrap_or(0),
+                (None, Some(tabs)) if c == '\t' => tabs.next().unwrap_or(0),
+                (None, _) => self.char_width(c, col),
+            };
+            col += width;
+            width
+        })
+    }
+
+    // Width of the character rendered at the display column `col`. Control characters are as wide as their notation
+    // when they are visualized
+    fn char_width(&self, c: char, col: usize) -> usize {
+        if self.control_chars == ControlCharHandling::Visualize {
+            if let Some(n) = control::notation(c) {
+                return n.len();
+            }
         }
+        char_display_width(c, col, self.tab_len)
+    }
 
-        let mut r = start_row + 1;
-        let mut offset = 0;
+    // Widths of hard tabs in the line at `row` when elastic tabstops or tab stops are set. `None` means hard tabs are
+    // expanded with the tab length
+    fn tab_widths(&self, row: usize) -> Option<Vec<usize>> {
+        if self.mask.is_some() {
+            return None;
+        }
+        if self.elastic_tabs {
+            return Some(elastic::tab_widths(&self.lines, row, self.tab_len as usize));
+        }
+        if self.tab_stops.is_empty() {
+            return None;
+        }
+        let mut widths = vec![];
         let mut col = 0;
+        for c in self.lines[row].chars() {
+            col += if c == '\t' {
+                let width = tab_stop_width(col, &self.tab_stops, self.tab_len);
+                widths.push(width);
+                width
+            } else {
+                self.char_width(c, col)
+            };
+        }
+        Some(widths)
+    }
 
-        while r < self.lines.len() {
-            let line = &self.lines[r];
-            if let Some((o, c)) = find_end(line) {
-                offset = o;
-                col = c;
-                break;
-            }
-            r += 1;
+    /// Get the width of the line at `row` on screen. Hard tabs are expanded with the tab width and wide characters
+    /// such as CJK characters occupy two columns. When the mask character is set, the width of the masked text is
+    /// returned. Virtual text is not included. When the line does not exist, this method returns 0.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc", "\tx", "日本語"]);
+    ///
+    /// assert_eq!(textarea.line_display_width(0), 3);
+    /// assert_eq!(textarea.line_display_width(1), 5);
+    /// assert_eq!(textarea.line_display_width(2), 6);
+    /// assert_eq!(textarea.line_display_width(3), 0);
+    ///
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.line_display_width(2), 3);
+    /// ```
+    pub fn line_display_width(&self, row: usize) -> usize {
+        if row < self.lines.len() {
+            self.display_widths(row).sum()
+        } else {
+            0
         }
+    }
 
-        let start = Pos::new(start_row, start_col, start_offset);
-        let end = Pos::new(r, col, offset);
-        self.delete_range(start, end, true);
-        true
+    /// Get the number of rows on screen occupied by the whole text when it is rendered in a textarea with the `width`.
+    /// This is useful for sizing a scrollable container of the textarea. Each folded range is counted as one row.
+    /// Lines are not wrapped in the textarea so the height does not depend on the width for now.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d", "e"]);
+    /// assert_eq!(textarea.display_height(10), 5);
+    ///
+    /// textarea.fold(1, 3);
+    /// assert_eq!(textarea.display_height(10), 3);
+    /// ```
+    pub fn display_height(&self, _width: u16) -> usize {
+        self.folds.display_row(self.lines.len() - 1) + 1
     }
 
-    fn delete_piece(&mut self, col: usize, chars: usize) -> bool {
-        if chars == 0 {
-            return false;
+    /// Get the styled lines on screen for the display rows in `rows` when the textarea is rendered with the `width`.
+    /// This is useful for rendering the text with your own widget instead of [`TextAreaWidget`](crate::TextAreaWidget).
+    /// Each line contains the line number and the sign column followed by the text decorated with the cursor, the
+    /// selection, search matches, and highlights in the same way as the widget. Lines are cut at `width` columns.
+    ///
+    /// Rows are counted on screen so each folded range is one row (see [`TextArea::display_height`]). Rows past the
+    /// end of the text are not yielded. Horizontal scroll, the alignment of lines, and the placeholder are not applied.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::Style;
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world", "!"]);
+    /// textarea.set_line_number_style(Style::default());
+    ///
+    /// let lines: Vec<String> = textarea
+    ///     .display_lines(6, 1..5)
+    ///     .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+    ///     .collect();
+    /// assert_eq!(lines, [" 2 wor", " 3 !"]);
+    /// ```
+    pub fn display_lines(&self, width: u16, rows: Range<usize>) -> impl Iterator<Item = Line<'_>> {
+        let height = self.display_height(width);
+        let rows = cmp::min(rows.start, height)..cmp::min(rows.end, height);
+        let lnum_len = self.line_number_len();
+        let base = self.style();
+        rows.map(move |display_row| {
+            let row = self.folds.text_row(display_row);
+            // The widget fills the background of the row before rendering the text on it
+            let text_style = match self.line_fill_style(row) {
+                Some(fill) => base.patch(fill),
+                None => base,
+            };
+            let gutter = self.gutter_spans(row, lnum_len);
+            let text = self.line_spans(&self.lines[row], row);
+            #[cfg(feature = "ratatui")]
+            let (gutter, text) = (gutter.spans, text.spans);
+            #[cfg(feature = "tuirs")]
+            let (gutter, text) = (gutter.0, text.0);
+
+            let mut spans = Vec::with_capacity(gutter.len() + text.len());
+            spans.extend(gutter.into_iter().map(|mut s| {
+                s.style = base.patch(s.style);
+                s
+            }));
+            spans.extend(text.into_iter().map(|mut s| {
+                s.style = text_style.patch(s.style);
+                s
+            }));
+            take_columns(Line::from(spans), width as usize)
+        })
+    }
+
+    /// Get the column of the cursor on screen. This is the width of the text before the cursor in the cursor line
+    /// measured in the same way as [`TextArea::line_display_width`]. Horizontal scroll is not considered. When `bidi`
+    /// feature is enabled, this is the width of the text rendered at the left of the cursor since right-to-left text
+    /// is reordered on screen.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["\t日本語"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 2));
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    /// assert_eq!(textarea.cursor_display_col(), 6);
+    /// ```
+    pub fn cursor_display_col(&self) -> usize {
+        let (row, col) = self.cursor;
+        self.visual_widths(row)
+            .into_iter()
+            .take_while(|&(c, _)| c != col)
+            .map(|(_, w)| w)
+            .sum::<usize>()
+            + self.preedit_width()
+    }
+
+    // Number of cells occupied by the character at the cursor. It is 1 at the end of line
+    pub(crate) fn cursor_cell_width(&self) -> usize {
+        let (row, col) = self.cursor;
+        self.visual_widths(row)
+            .into_iter()
+            .find(|&(c, _)| c == col)
+            .map_or(1, |(_, w)| cmp::max(w, 1))
+    }
+
+    // Width of the composition text of IME rendered before the cursor
+    fn preedit_width(&self) -> usize {
+        self.preedit.as_deref().map_or(0, |s| {
+            let mut width = 0;
+            for c in s.chars() {
+                width += match self.mask {
+                    Some(m) => m.width().unwrap_or(0),
+                    None => self.char_width(c, width),
+                };
+            }
+            width
+        })
+    }
+
+    // Columns of characters in the line paired with their widths in the order on screen. Right-to-left text is
+    // reordered when `bidi` feature is enabled.
+    fn visual_widths(&self, row: usize) -> Vec<(usize, usize)> {
+        let line = &self.lines[row];
+        let order = match self.mask {
+            Some(_) => None,
+            None => visual_order(line),
+        };
+        match order {
+            Some(order) if self.elastic_tabs || !self.tab_stops.is_empty() => {
+                let widths: Vec<usize> = self.display_widths(row).collect();
+                order.into_iter().map(|col| (col, widths[col])).collect()
+            }
+            Some(order) => {
+                let chars: Vec<char> = line.chars().collect();
+                let mut width = 0;
+                order
+                    .into_iter()
+                    .map(|col| {
+                        let w = self.char_width(chars[col], width);
+                        width += w;
+                        (col, w)
+                    })
+                    .collect()
+            }
+            None => self.display_widths(row).enumerate().collect(),
         }
+    }
 
-        #[inline]
-        fn bytes_and_chars(claimed: usize, s: &str) -> (usize, usize) {
-            // Note: `claimed` may be larger than characters in `s` (e.g. usize::MAX)
-            let mut last_col = 0;
-            for (col, (bytes, _)) in s.char_indices().enumerate() {
-                if col == claimed {
-                    return (bytes, claimed);
-                }
-                last_col = col;
+    fn push_history(&mut self, kind: EditKind, before: Pos, after_offset: usize) {
+        let (row, col) = self.cursor;
+        // Every edit must leave the cursor in the text
+        debug_assert!(
+            row < self.lines.len() && col <= self.lines[row].chars().count(),
+            "Cursor {:?} is out of the text after edit {:?}",
+            self.cursor,
+            kind,
+        );
+        let after = Pos::new(row, col, after_offset);
+        if let Some(deleted) = &mut self.deleted {
+            match &kind {
+                EditKind::DeleteChar(c) => deleted.push(*c),
+                EditKind::DeleteNewline => deleted.push('\n'),
+                EditKind::DeleteStr(s) => deleted.push_str(s),
+                EditKind::DeleteChunk(c) => deleted.push_str(&c.join("\n")),
+                _ => {}
             }
-            (s.len(), last_col + 1)
         }
+        if let Some(shift) = kind.row_shift(&before, &after) {
+            self.shift_rows(shift);
+        }
+        let edit = Edit::new(kind, before, after);
+        self.history.push(edit);
+        self.revision = self.revision.wrapping_add(1);
+    }
 
-        let (row, _) = self.cursor;
-        let line = &mut self.lines[row];
-        if let Some((i, _)) = line.char_indices().nth(col) {
-            let (bytes, chars) = bytes_and_chars(chars, &line[i..]);
-            let removed = line.drain(i..i + bytes).as_str().to_string();
+    // Move the decorations attached to rows with the lines moved by an edit. Decorations on the removed lines are
+    // dropped. This must be called on every modification of the text which inserts or removes lines.
+    fn shift_rows(&mut self, shift: RowShift) {
+        fn shift_keys<V>(map: &mut HashMap<usize, V>, shift: &RowShift) {
+            if !map.is_empty() {
+                *map = map
+                    .drain()
+                    .filter_map(|(row, v)| Some((shift.row(row)?, v)))
+                    .collect();
+            }
+        }
+        shift_keys(&mut self.signs, &shift);
+        shift_keys(&mut self.line_alignments, &shift);
+        shift_keys(&mut self.diff, &shift);
+        shift_keys(&mut self.virtual_texts, &shift);
+        self.folds.shift_rows(&shift);
+
+        let pos = |(row, col): (usize, usize)| Some((shift.row(row)?, col));
+        let highlights = mem::take(&mut self.highlights)
+            .into_vec()
+            .into_iter()
+            .filter_map(|hl| {
+                Some(Highlight {
+                    start: pos(hl.start)?,
+                    end: pos(hl.end)?,
+                    style: hl.style,
+                })
+            })
+            .collect();
+        self.highlights = Highlights::new(highlights);
+        self.extra_selections = mem::take(&mut self.extra_selections)
+            .into_iter()
+            .filter_map(|(start, end)| Some((pos(start)?, pos(end)?)))
+            .collect();
+        self.render_cache.clear();
+    }
 
-            self.cursor = (row, col);
-            self.push_history(
-                EditKind::DeleteStr(removed.clone()),
-                Pos::new(row, col + chars, i + bytes),
-                i,
+    /// Insert a single character at current cursor position.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_char('a');
+    /// assert_eq!(textarea.lines(), ["a"]);
+    /// ```
+    pub fn insert_char(&mut self, c: char) {
+        if c == '\n' || c == '\r' {
+            // Note: `insert_newline` does nothing in single-line mode
+            self.insert_newline();
+            return;
+        }
+        if self.control_chars == ControlCharHandling::Strip && control::is_control_char(c) {
+            return;
+        }
+
+        let mut buf = [0; 4];
+        self.replace_selection_or_overwrite(c.encode_utf8(&mut buf), |t| {
+            let (row, col) = t.cursor;
+            let line = &mut t.lines[row];
+            let i = line
+                .char_indices()
+                .nth(col)
+                .map(|(i, _)| i)
+                .unwrap_or(line.len());
+            line.insert(i, c);
+            t.set_cursor((row, col + 1));
+            t.push_history(
+                EditKind::InsertChar(c),
+                Pos::new(row, col, i),
+                i + c.len_utf8(),
             );
-            self.yank = removed.into();
             true
-        } else {
-            false
+        });
+    }
+
+    // Delete the selected text and insert some text with `insert`. The deletion and the insertion are recorded as one
+    // modification in the edit history so that replacing the selected text is undone at once. `insert` returns if some
+    // text was inserted or not.
+    fn replace_selection(&mut self, insert: impl FnOnce(&mut Self) -> bool) -> bool {
+        let deleted = self.remove_selection(false);
+        self.insert_after_deletion(deleted, insert)
+    }
+
+    // Same as `replace_selection`, but in overwrite mode the characters after the cursor are deleted when no text is
+    // selected so that `text` overwrites them. Only characters on the cursor line are overwritten; when `text` is
+    // longer than the rest of the line, the remaining part is appended. `text` is the first line of the inserted text.
+    fn replace_selection_or_overwrite(
+        &mut self,
+        text: &str,
+        insert: impl FnOnce(&mut Self) -> bool,
+    ) -> bool {
+        if !self.overwrite || self.selection_start.is_some() {
+            return self.replace_selection(insert);
+        }
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let end = text
+            .graphemes(true)
+            .fold(col, |end, _| next_grapheme_col(line, end));
+        let deleted = end > col;
+        if deleted {
+            // Undoing the overwrite moves the cursor back to the head of the overwritten text
+            let (start, end) = (self.pos_at((row, col)), self.pos_at((row, end)));
+            let removed = self.lines[row].drain(start.offset..end.offset).collect();
+            let offset = start.offset;
+            self.push_history(EditKind::DeleteStr(removed), start, offset);
         }
+        self.insert_after_deletion(deleted, insert)
     }
 
-    /// Insert a tab at current cursor position. Note that this method does nothing when the tab length is 0. This
-    /// method returns if a tab string was inserted or not in the textarea.
+    fn insert_after_deletion(
+        &mut self,
+        deleted: bool,
+        insert: impl FnOnce(&mut Self) -> bool,
+    ) -> bool {
+        let index = self.history.index();
+        let inserted = insert(self);
+        if deleted && self.history.index() > index {
+            // Join the first inserted edit with the deletion. The rest of the inserted edits are already joined
+            self.history.join(index);
+        }
+        deleted || inserted
+    }
+
+    /// Insert a string at current cursor position. This method returns if some text was inserted or not in the textarea.
+    /// Both `\n` and `\r\n` are recognized as newlines but `\r` isn't. In single-line mode, newlines are replaced with
+    /// spaces. See [`TextArea::set_single_line`]. The cursor moves to the end of the inserted text. When some text is
+    /// selected, the selected text is replaced with the string and [`TextArea::undo`] reverts the replacement at once.
+    ///
+    /// The whole string is inserted in one operation and recorded in the edit history as one modification even if it
+    /// contains many lines, so this is much faster than inserting each character with [`TextArea::insert_char`]. Use
+    /// this method or [`TextArea::paste_text`] for inserting a large text such as pasted one.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["hi"]);
+    /// let mut textarea = TextArea::default();
     ///
-    /// textarea.move_cursor(CursorMove::End); // Move to the end of line
+    /// textarea.insert_str("hello");
+    /// assert_eq!(textarea.lines(), ["hello"]);
     ///
-    /// textarea.insert_tab();
-    /// assert_eq!(textarea.lines(), ["hi  "]);
-    /// textarea.insert_tab();
-    /// assert_eq!(textarea.lines(), ["hi      "]);
+    /// textarea.insert_str(", world\ngoodbye, world");
+    /// assert_eq!(textarea.lines(), ["hello, world", "goodbye, world"]);
     /// ```
-    pub fn insert_tab(&mut self) -> bool {
-        let modified = self.delete_selection(false);
-        if self.tab_len == 0 {
-            return modified;
+    pub fn insert_str<S: AsRef<str>>(&mut self, s: S) -> bool {
+        let mut lines: Vec<_> = s
+            .as_ref()
+            .split('\n')
+            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
+            .collect();
+        if self.control_chars == ControlCharHandling::Strip {
+            for line in &mut lines {
+                line.retain(|c| !control::is_control_char(c));
+            }
         }
-
-        if self.hard_tab_indent {
-            self.insert_char('\t');
-            return true;
+        if self.single_line && lines.len() > 1 {
+            lines = vec![lines.join(" ")];
         }
-
-        let (row, col) = self.cursor;
-        let width: usize = self.lines[row]
-            .chars()
-            .take(col)
-            .map(|c| c.width().unwrap_or(0))
-            .sum();
-        let len = self.tab_len - (width % self.tab_len as usize) as u8;
-        self.insert_piece(spaces(len).to_string())
+        let first = lines.first().cloned().unwrap_or_default();
+        self.replace_selection_or_overwrite(&first, |t| match lines.len() {
+            0 => false,
+            1 => t.insert_piece(lines.remove(0)),
+            _ => t.insert_chunk(lines),
+        })
     }
 
-    /// Insert a newline at current cursor position.
+    /// Insert a string at the position `(row, col)` without moving the cursor to there. This is useful for applying
+    /// text edits from outside such as collaborative editing or language servers. The position is clamped into the
+    /// text. The cursor and the text selection after the position are shifted so that they stay at the same text.
+    /// The insertion is recorded in the edit history as one modification. This method returns if some text was
+    /// inserted or not.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["hi"]);
+    /// let mut textarea = TextArea::from(["world"]);
+    /// textarea.move_cursor(CursorMove::End);
     ///
-    /// textarea.move_cursor(CursorMove::Forward);
-    /// textarea.insert_newline();
-    /// assert_eq!(textarea.lines(), ["h", "i"]);
+    /// textarea.insert_str_at((0, 0), "hello\n");
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// // The cursor is still at the end of "world"
+    /// assert_eq!(textarea.cursor(), (1, 5));
     /// ```
-    pub fn insert_newline(&mut self) {
-        self.delete_selection(false);
+    pub fn insert_str_at<S: AsRef<str>>(&mut self, pos: (usize, usize), s: S) -> bool {
+        let start = self.clamp_pos(pos);
+        let saved = self.saved_positions();
+        let (cursor, selection_start) = saved.0;
+        self.set_selection_start(None);
+        self.set_cursor(start);
+        let inserted = self.insert_str(s);
+        let end = self.cursor;
+
+        // Shift the position after the inserted text
+        let shift = |p: (usize, usize)| -> (usize, usize) {
+            match p {
+                p if p < start => p,
+                (row, col) if row == start.0 => (end.0, end.1 + col - start.1),
+                (row, col) => (row + end.0 - start.0, col),
+            }
+        };
+        self.restore_positions(saved);
+        self.set_selection_start(selection_start.map(shift));
+        self.set_cursor(shift(cursor));
+        inserted
+    }
+
+    /// Set the predicate of characters which are part of words. Word motions such as [`CursorMove::WordForward`], word
+    /// deletions such as [`TextArea::delete_word`], selecting a word by double click, completion with
+    /// [`TextArea::word_before_cursor`], whole word search, and highlighting the word under the cursor share this
+    /// predicate so that they agree on word boundaries. Whitespaces are never part of words and other characters which
+    /// are not word characters are treated as punctuations. By default, characters other than ASCII punctuations are
+    /// word characters and `_` is a word character as well.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["max-line-length = 80"]);
+    ///
+    /// // Treat `-` as a part of words for editing kebab-case keys
+    /// textarea.set_word_chars(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    /// assert!((textarea.word_chars())('-'));
+    ///
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// assert_eq!(textarea.cursor(), (0, 16));
+    ///
+    /// textarea.delete_word();
+    /// assert_eq!(textarea.lines(), ["= 80"]);
+    /// ```
+    pub fn set_word_chars(&mut self, word_chars: fn(char) -> bool) {
+        self.render_cache.clear();
+        self.word_chars = word_chars;
+        #[cfg(feature = "search")]
+        {
+            self.search.word_chars = word_chars;
+            self.search.cache = Default::default();
+        }
+    }
+
+    /// Get the predicate of characters which are part of words. See [`TextArea::set_word_chars`] for more details.
+    pub fn word_chars(&self) -> fn(char) -> bool {
+        self.word_chars
+    }
 
+    /// Get the partial word before the cursor and its start position as `(row, col)`. This is useful for implementing
+    /// completion. Words are separated by whitespaces and punctuations in the same way as word motions such as
+    /// [`CursorMove::WordBack`]. When no word is before the cursor, an empty string and the cursor position are
+    /// returned. The start position with [`TextArea::cursor_screen_position`] helps placing a completion popup.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["let fo"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert_eq!(textarea.word_before_cursor(), ("fo".to_string(), (0, 4)));
+    ///
+    /// textarea.insert_char('.');
+    /// assert_eq!(textarea.word_before_cursor(), ("".to_string(), (0, 7)));
+    /// ```
+    pub fn word_before_cursor(&self) -> (String, (usize, usize)) {
         let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        let offset = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
-        let next_line = line[offset..].to_string();
-        line.truncate(offset);
+        let line = &self.lines[row];
+        let start = find_word_prefix_start(line, col, self.word_chars);
+        let word = line.chars().skip(start).take(col - start).collect();
+        (word, (row, start))
+    }
 
-        self.lines.insert(row + 1, next_line);
-        self.cursor = (row + 1, 0);
-        self.push_history(EditKind::InsertNewline, Pos::new(row, col, offset), 0);
+    /// Replace the partial word before the cursor with `text` to accept a completion. The word is the one returned
+    /// from [`TextArea::word_before_cursor`]. The cursor moves to the end of the inserted text. The replacement is
+    /// recorded in the edit history as one modification so [`TextArea::undo`] reverts it at once. The text selection
+    /// is cancelled. This method returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["let fo = 1;"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 6));
+    ///
+    /// textarea.complete_with("foo");
+    /// assert_eq!(textarea.lines(), ["let foo = 1;"]);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["let fo = 1;"]);
+    /// ```
+    pub fn complete_with(&mut self, text: &str) -> bool {
+        self.cancel_selection();
+        let (_, start) = self.word_before_cursor();
+        let deleted = !self.delete_range(start, self.cursor).is_empty();
+        let index = self.history.index();
+        let inserted = self.insert_str(text);
+        if deleted && self.history.index() > index {
+            // Join the insertion with the deletion so that the completion is undone at once
+            self.history.join(index);
+        }
+        deleted || inserted
+    }
+
+    /// Insert `text` at the cursor and select the part of the inserted text from `select_from` to `select_to`. The
+    /// offsets are counted in characters of `text` including newlines, and are clamped into the text. The cursor is
+    /// placed at the end of the selection. When both offsets are the same, nothing is selected and the cursor is
+    /// placed at the offset. This is useful for expanding snippets of completions where the cursor moves to a
+    /// placeholder. When some text is selected before inserting, it is replaced. The insertion is recorded in the edit
+    /// history as one modification. This method returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// // Select the placeholder `cond` in the inserted snippet
+    /// textarea.insert_and_select("if cond {\n}", 3, 7);
+    /// assert_eq!(textarea.lines(), ["if cond {", "}"]);
+    /// assert!(textarea.is_selecting());
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    ///
+    /// // Typing replaces the placeholder
+    /// textarea.insert_str("x > 0");
+    /// assert_eq!(textarea.lines(), ["if x > 0 {", "}"]);
+    /// ```
+    pub fn insert_and_select(&mut self, text: &str, select_from: usize, select_to: usize) -> bool {
+        let offset = |i: usize| text.char_indices().nth(i).map_or(text.len(), |(i, _)| i);
+        let (from, to) = (offset(select_from), offset(select_to));
+        let (from, to) = (cmp::min(from, to), cmp::max(from, to));
+
+        // Insert the text separately before, in, and after the selected part to know the positions of the selection
+        let (mark, before) = (self.history.pushed(), self.selection_state());
+        let mut modified = false;
+        let mut insert = |t: &mut Self, s: &str| {
+            let inserted = t.insert_str(s);
+            if inserted && modified {
+                // Join the insertions so that they are undone at once
+                t.history.join_last();
+            }
+            modified |= inserted;
+        };
+        insert(self, &text[..from]);
+        let start = self.cursor;
+        insert(self, &text[from..to]);
+        let end = self.cursor;
+        insert(self, &text[to..]);
+
+        self.set_selection_start((start != end).then(|| start));
+        self.set_cursor(end);
+        self.record_selection(mark, before);
+        modified
+    }
+
+    fn insert_chunk(&mut self, chunk: Vec<String>) -> bool {
+        debug_assert!(chunk.len() > 1, "Chunk size must be > 1: {:?}", chunk);
+
+        let (row, col) = self.cursor;
+        let line = &mut self.lines[row];
+        let i = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        let before = Pos::new(row, col, i);
+
+        let (row, col) = (
+            row + chunk.len() - 1,
+            chunk[chunk.len() - 1].chars().count(),
+        );
+        self.set_cursor((row, col));
+
+        let end_offset = chunk.last().unwrap().len();
+
+        let edit = EditKind::InsertChunk(chunk);
+        edit.apply(&mut self.lines, &before, &Pos::new(row, col, end_offset));
+
+        self.push_history(edit, before, end_offset);
+        true
+    }
+
+    fn insert_piece(&mut self, s: String) -> bool {
+        if s.is_empty() {
+            return false;
+        }
+
+        let (row, col) = self.cursor;
+        let line = &mut self.lines[row];
+        debug_assert!(
+            !s.contains('\n'),
+            "string given to TextArea::insert_piece must not contain newline: {:?}",
+            line,
+        );
+
+        let i = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        line.insert_str(i, &s);
+        let end_offset = i + s.len();
+
+        self.set_cursor((row, col + s.chars().count()));
+        self.push_history(EditKind::InsertStr(s), Pos::new(row, col, i), end_offset);
+        true
+    }
+
+    fn delete_pos_range(&mut self, start: Pos, end: Pos, should_yank: bool) {
+        self.set_cursor((start.row, start.col));
+
+        if start.row == end.row {
+            let removed = self.lines[start.row]
+                .drain(start.offset..end.offset)
+                .as_str()
+                .to_string();
+            if should_yank {
+                self.set_yank(removed.clone().into());
+            }
+            self.push_history(EditKind::DeleteStr(removed), end, start.offset);
+            return;
+        }
+
+        let mut deleted = vec![self.lines[start.row]
+            .drain(start.offset..)
+            .as_str()
+            .to_string()];
+        deleted.extend(self.lines.drain(start.row + 1..end.row));
+        if start.row + 1 < self.lines.len() {
+            let mut last_line = self.lines.remove(start.row + 1);
+            self.lines[start.row].push_str(&last_line[end.offset..]);
+            last_line.truncate(end.offset);
+            deleted.push(last_line);
+        }
+
+        if should_yank {
+            self.set_yank(YankText::Chunk(deleted.clone()));
+        }
+
+        let edit = if deleted.len() == 1 {
+            EditKind::DeleteStr(deleted.remove(0))
+        } else {
+            EditKind::DeleteChunk(deleted)
+        };
+        self.push_history(edit, end, start.offset);
+    }
+
+    /// Delete a string from the current cursor position. The `chars` parameter means number of characters, not a byte
+    /// length of the string. Newlines at the end of lines are counted in the number. This method returns if some text
+    /// was deleted or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["🐱🐶🐰🐮"]);
+    /// textarea.move_cursor(CursorMove::Forward);
+    ///
+    /// textarea.delete_str(2);
+    /// assert_eq!(textarea.lines(), ["🐱🐮"]);
+    ///
+    /// let mut textarea = TextArea::from(["🐱", "🐶", "🐰", "🐮"]);
+    /// textarea.move_cursor(CursorMove::Down);
+    ///
+    /// textarea.delete_str(4); // Deletes 🐶, \n, 🐰, \n
+    /// assert_eq!(textarea.lines(), ["🐱", "🐮"]);
+    /// ```
+    pub fn delete_str(&mut self, chars: usize) -> bool {
+        if self.remove_selection(false) {
+            return true;
+        }
+        if chars == 0 {
+            return false;
+        }
+
+        let (start_row, start_col) = self.cursor;
+
+        let mut remaining = chars;
+        let mut find_end = move |line: &str| {
+            let mut col = 0usize;
+            for (i, _) in line.char_indices() {
+                if remaining == 0 {
+                    return Some((i, col));
+                }
+                col += 1;
+                remaining -= 1;
+            }
+            if remaining == 0 {
+                Some((line.len(), col))
+            } else {
+                remaining -= 1;
+                None
+            }
+        };
+
+        let line = &self.lines[start_row];
+        let start_offset = {
+            line.char_indices()
+                .nth(start_col)
+                .map(|(i, _)| i)
+                .unwrap_or(line.len())
+        };
+
+        // First line
+        if let Some((offset_delta, col_delta)) = find_end(&line[start_offset..]) {
+            let end_offset = start_offset + offset_delta;
+            let end_col = start_col + col_delta;
+            let removed = self.lines[start_row]
+                .drain(start_offset..end_offset)
+                .as_str()
+                .to_string();
+            self.set_yank(removed.clone().into());
+            self.push_history(
+                EditKind::DeleteStr(removed),
+                Pos::new(start_row, end_col, end_offset),
+                start_offset,
+            );
+            return true;
+        }
+
+        let mut r = start_row + 1;
+        let mut offset = 0;
+        let mut col = 0;
+
+        while r < self.lines.len() {
+            let line = &self.lines[r];
+            if let Some((o, c)) = find_end(line) {
+                offset = o;
+                col = c;
+                break;
+            }
+            r += 1;
+        }
+
+        let start = Pos::new(start_row, start_col, start_offset);
+        let end = Pos::new(r, col, offset);
+        self.delete_pos_range(start, end, true);
+        true
+    }
+
+    /// Delete the text between the positions `start` and `end` without moving the cursor to there, and return the
+    /// deleted text. Each position is `(row, col)` and the order of them does not matter. Positions are clamped into
+    /// the text. This is useful for applying text edits from outside such as collaborative editing or language
+    /// servers. The cursor and the text selection after the range are shifted so that they stay at the same text.
+    /// When they are in the range, they move to the start of the range. The deletion is recorded in the edit history
+    /// as one modification. Unlike [`TextArea::delete_str`], the deleted text is not yanked.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def", "ghi"]);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// let deleted = textarea.delete_range((0, 1), (1, 2));
+    /// assert_eq!(deleted, "bc\nde");
+    /// assert_eq!(textarea.lines(), ["af", "ghi"]);
+    /// // The cursor is still at the end of "ghi"
+    /// assert_eq!(textarea.cursor(), (1, 3));
+    /// ```
+    pub fn delete_range(&mut self, start: (usize, usize), end: (usize, usize)) -> String {
+        let (start, end) = (self.clamp_pos(start), self.clamp_pos(end));
+        let (start, end) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        if start == end {
+            return String::new();
+        }
+        let (start_pos, end_pos) = (self.pos_at(start), self.pos_at(end));
+        let deleted = if start.0 == end.0 {
+            self.lines[start.0][start_pos.offset..end_pos.offset].to_string()
+        } else {
+            let mut deleted = self.lines[start.0][start_pos.offset..].to_string();
+            for line in &self.lines[start.0 + 1..end.0] {
+                deleted.push('\n');
+                deleted.push_str(line);
+            }
+            deleted.push('\n');
+            deleted.push_str(&self.lines[end.0][..end_pos.offset]);
+            deleted
+        };
+
+        let saved = self.saved_positions();
+        let (cursor, selection_start) = saved.0;
+        self.set_selection_start(None);
+        self.delete_pos_range(start_pos, end_pos, false);
+
+        // Shift the position after the deleted text
+        let shift = |p: (usize, usize)| -> (usize, usize) {
+            match p {
+                p if p <= start => p,
+                p if p < end => start,
+                (row, col) if row == end.0 => (start.0, start.1 + col - end.1),
+                (row, col) => (row - (end.0 - start.0), col),
+            }
+        };
+        self.restore_positions(saved);
+        self.set_selection_start(selection_start.map(shift));
+        self.set_cursor(shift(cursor));
+        deleted
+    }
+
+    /// Clamp the cursor and the start of the text selection into the text. Methods of [`TextArea`] keep these
+    /// positions valid after modifying the text, so calling this method is usually not necessary. It is a guard against
+    /// positions left out of the text by a bug and it is also called before rendering the textarea. A scroll position
+    /// past the end of the text is fixed on the next render.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.delete_range((0, 1), (1, 3));
+    ///
+    /// textarea.clamp_positions();
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// ```
+    pub fn clamp_positions(&mut self) {
+        self.set_cursor(self.clamp_pos(self.cursor));
+        if let Some(start) = self.selection_start {
+            self.set_selection_start(Some(self.clamp_pos(start)));
+        }
+    }
+
+    /// Convert a position in the Language Server Protocol into a position `(row, col)` in the textarea. LSP positions
+    /// count columns in UTF-16 code units while this crate counts them in characters, so a character outside the
+    /// Basic Multilingual Plane such as an emoji occupies two columns in LSP. Positions out of the text are clamped.
+    /// A column pointing in the middle of a character is rounded down to the character. The returned position can be
+    /// passed to [`TextArea::insert_str_at`] and [`TextArea::delete_range`] to apply LSP text edits.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["a🐱b"]);
+    ///
+    /// assert_eq!(textarea.lsp_to_pos(0, 3), (0, 2));
+    /// assert_eq!(textarea.lsp_to_pos(0, 99), (0, 3));
+    /// ```
+    pub fn lsp_to_pos(&self, line: u32, utf16_col: u32) -> (usize, usize) {
+        let row = cmp::min(line as usize, self.lines.len() - 1);
+        let mut units = 0;
+        for (col, c) in self.lines[row].chars().enumerate() {
+            units += c.len_utf16();
+            if units > utf16_col as usize {
+                return (row, col);
+            }
+        }
+        (row, self.lines[row].chars().count())
+    }
+
+    /// Convert a position `(row, col)` in the textarea into a position in the Language Server Protocol as
+    /// `(line, character)`. The column is counted in UTF-16 code units. This is the inverse of
+    /// [`TextArea::lsp_to_pos`]. Positions out of the text are clamped.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["a🐱b"]);
+    ///
+    /// assert_eq!(textarea.pos_to_lsp((0, 2)), (0, 3));
+    /// assert_eq!(textarea.pos_to_lsp((9, 9)), (0, 4));
+    /// ```
+    pub fn pos_to_lsp(&self, pos: (usize, usize)) -> (u32, u32) {
+        let (row, col) = self.clamp_pos(pos);
+        let units: usize = self.lines[row].chars().take(col).map(char::len_utf16).sum();
+        (row as u32, units as u32)
+    }
+
+    /// Sort the lines in the range of rows with the [`SortOptions`]. When the range is `None`, the lines of the text
+    /// selection are sorted, or the entire text is sorted when no text is selected. The range is clamped into the text.
+    /// The sort is stable so equal lines keep their order. Adjacent duplicates are removed after sorting when
+    /// [`SortOptions::unique`] is set. Sorting the lines is recorded as one modification in the edit history. The cursor
+    /// moves to the head of the first line in the range. This method returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::{SortOptions, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["b", "C", "a", "x"]);
+    ///
+    /// let opts = SortOptions {
+    ///     case_insensitive: true,
+    ///     ..Default::default()
+    /// };
+    /// assert!(textarea.sort_lines(Some(0..3), opts));
+    /// assert_eq!(textarea.lines(), ["a", "b", "C", "x"]);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    pub fn sort_lines(&mut self, range: Option<Range<usize>>, opts: SortOptions) -> bool {
+        let range = self.line_range(range);
+        let mut sorted = self.lines[range.clone()].to_vec();
+        sorted.sort_by(|a, b| opts.compare(a, b));
+        if opts.unique {
+            sorted.dedup_by(|a, b| opts.compare(a, b) == Ordering::Equal);
+        }
+        if sorted[..] == self.lines[range.clone()] {
+            return false;
+        }
+        let mut lines = self.lines.clone();
+        lines.splice(range.clone(), sorted);
+        self.cancel_selection();
+        self.replace_lines(lines, (range.start, 0));
+        true
+    }
+
+    /// Sort the lines of the text selection, or the entire text when no text is selected, with the [`SortOptions`].
+    /// Unlike [`TextArea::sort_lines`], the text selection is kept after sorting. It covers the sorted lines from the
+    /// head of the first line to the end of the last line. Sorting the lines is recorded as one modification in the
+    /// edit history. This method returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::{CursorMove, SortOptions, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["use c;", "use a;", "use c;", "use b;", "", "fn main() {}"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(3, 0));
+    ///
+    /// let opts = SortOptions {
+    ///     unique: true,
+    ///     ..Default::default()
+    /// };
+    /// assert!(textarea.sort_selected_lines(opts));
+    /// assert_eq!(textarea.lines(), ["use a;", "use b;", "use c;", "", "fn main() {}"]);
+    /// assert!(textarea.is_selecting());
+    /// assert_eq!(textarea.cursor(), (2, 6));
+    /// ```
+    pub fn sort_selected_lines(&mut self, opts: SortOptions) -> bool {
+        let (mark, before) = (self.history.pushed(), self.selection_state());
+        let selecting = self.selection_start.is_some();
+        let range = self.line_range(None);
+        let after = self.lines.len() - range.end;
+        if !self.sort_lines(Some(range.clone()), opts) {
+            return false;
+        }
+        if selecting {
+            let last = self.lines.len() - after - 1;
+            self.set_selection_start(Some((range.start, 0)));
+            self.set_cursor((last, self.lines[last].chars().count()));
+        }
+        self.record_selection(mark, before);
+        true
+    }
+
+    /// Remove consecutive duplicate lines in the range of rows and return the number of removed lines. The range is
+    /// handled in the same way as [`TextArea::sort_lines`]. Removing the lines is recorded as one modification in the
+    /// edit history. The cursor moves to the head of the first line in the range.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "a", "b", "a", "a"]);
+    ///
+    /// assert_eq!(textarea.dedup_lines(None), 2);
+    /// assert_eq!(textarea.lines(), ["a", "b", "a"]);
+    /// ```
+    pub fn dedup_lines(&mut self, range: Option<Range<usize>>) -> usize {
+        let range = self.line_range(range);
+        let mut deduped = self.lines[range.clone()].to_vec();
+        deduped.dedup();
+        let removed = range.len() - deduped.len();
+        if removed == 0 {
+            return 0;
+        }
+        let mut lines = self.lines.clone();
+        lines.splice(range.clone(), deduped);
+        self.cancel_selection();
+        self.replace_lines(lines, (range.start, 0));
+        removed
+    }
+
+    /// Toggle line comments of the lines in the text selection, or the cursor line when no text is selected. When all
+    /// the lines are already commented with `prefix`, the comment markers are removed with one space following them.
+    /// Otherwise `prefix` and one space are inserted to each line. Lines containing only whitespaces are not commented.
+    /// Where the marker is inserted is configured with [`TextArea::set_comment_after_indent`]. Toggling the comments
+    /// is recorded as one modification in the edit history, and the cursor and the text selection stay at the same
+    /// text. This method returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["    foo();", "        bar();"]);
+    /// textarea.select_all();
+    ///
+    /// textarea.toggle_line_comment("//");
+    /// assert_eq!(textarea.lines(), ["    // foo();", "    //     bar();"]);
+    ///
+    /// textarea.toggle_line_comment("//");
+    /// assert_eq!(textarea.lines(), ["    foo();", "        bar();"]);
+    /// ```
+    pub fn toggle_line_comment(&mut self, prefix: &str) -> bool {
+        if prefix.is_empty() {
+            return false;
+        }
+        let rows = self.selected_rows();
+        let indent = |line: &str| line.chars().take_while(|c| c.is_whitespace()).count();
+        let commented_lines: Vec<_> = rows
+            .clone()
+            .filter(|&row| !self.lines[row].trim().is_empty())
+            .collect();
+        if commented_lines.is_empty() {
+            return false;
+        }
+
+        let uncomment = commented_lines
+            .iter()
+            .all(|&row| self.lines[row].trim_start().starts_with(prefix));
+        let col = if self.comment_after_indent {
+            commented_lines
+                .iter()
+                .map(|&row| indent(&self.lines[row]))
+                .min()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        let marker = format!("{} ", prefix);
+
+        let (mark, before) = (self.history.pushed(), self.selection_state());
+        for (i, row) in commented_lines.into_iter().enumerate() {
+            if uncomment {
+                let line = &self.lines[row];
+                let start = indent(line);
+                let rest = &line.trim_start()[prefix.len()..];
+                let len = prefix.chars().count() + rest.starts_with(' ') as usize;
+                self.delete_range((row, start), (row, start + len));
+            } else {
+                self.insert_str_at((row, col), &marker);
+            }
+            // All lines are toggled at once
+            if i > 0 {
+                self.history.join_last();
+            }
+        }
+        self.record_selection(mark, before);
+        true
+    }
+
+    /// Set where [`TextArea::toggle_line_comment`] inserts comment markers. When `true`, the markers are inserted
+    /// after the indentation shared by the commented lines so that the indentation is preserved. When `false`, the
+    /// markers are inserted at the head of lines. The default value is `true`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["    foo();"]);
+    ///
+    /// textarea.set_comment_after_indent(false);
+    /// assert!(!textarea.comment_after_indent());
+    /// textarea.toggle_line_comment("#");
+    /// assert_eq!(textarea.lines(), ["#     foo();"]);
+    /// ```
+    pub fn set_comment_after_indent(&mut self, enabled: bool) {
+        self.comment_after_indent = enabled;
+    }
+
+    /// Get if [`TextArea::toggle_line_comment`] inserts comment markers after the indentation. See
+    /// [`TextArea::set_comment_after_indent`].
+    pub fn comment_after_indent(&self) -> bool {
+        self.comment_after_indent
+    }
+
+    // Range of rows clamped into the text. When the range is not given, the rows of the selection or all rows are used
+    fn line_range(&self, range: Option<Range<usize>>) -> Range<usize> {
+        let range = range.unwrap_or_else(|| match self.selection_start {
+            Some((row, _)) => {
+                let cursor = self.cursor.0;
+                cmp::min(row, cursor)..cmp::max(row, cursor) + 1
+            }
+            None => 0..self.lines.len(),
+        });
+        let end = cmp::min(range.end, self.lines.len());
+        cmp::min(range.start, end)..end
+    }
+
+    fn clamp_pos(&self, (row, col): (usize, usize)) -> (usize, usize) {
+        let row = cmp::min(row, self.lines.len() - 1);
+        (row, cmp::min(col, self.lines[row].chars().count()))
+    }
+
+    fn pos_at(&self, (row, col): (usize, usize)) -> Pos {
+        let line = &self.lines[row];
+        let offset = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        Pos::new(row, col, offset)
+    }
+
+    fn delete_piece(&mut self, col: usize, chars: usize) -> bool {
+        if chars == 0 {
+            return false;
+        }
+
+        #[inline]
+        fn bytes_and_chars(claimed: usize, s: &str) -> (usize, usize) {
+            // Note: `claimed` may be larger than characters in `s` (e.g. usize::MAX)
+            let mut last_col = 0;
+            for (col, (bytes, _)) in s.char_indices().enumerate() {
+                if col == claimed {
+                    return (bytes, claimed);
+                }
+                last_col = col;
+            }
+            (s.len(), last_col + 1)
+        }
+
+        let (row, _) = self.cursor;
+        let line = &mut self.lines[row];
+        if let Some((i, _)) = line.char_indices().nth(col) {
+            let (bytes, chars) = bytes_and_chars(chars, &line[i..]);
+            let removed = line.drain(i..i + bytes).as_str().to_string();
+
+            self.set_cursor((row, col));
+            self.push_history(
+                EditKind::DeleteStr(removed.clone()),
+                Pos::new(row, col + chars, i + bytes),
+                i,
+            );
+            self.set_yank(removed.into());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Insert a tab at current cursor position. Note that this method does nothing when the tab length is 0. This
+    /// method returns if a tab string was inserted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hi"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End); // Move to the end of line
+    ///
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["hi  "]);
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["hi      "]);
+    /// ```
+    pub fn insert_tab(&mut self) -> bool {
+        self.replace_selection(|t| {
+            if t.tab_len == 0 {
+                return false;
+            }
+
+            if t.hard_tab_indent {
+                t.insert_char('\t');
+                return true;
+            }
+
+            let (row, col) = t.cursor;
+            let width: usize = t.lines[row]
+                .chars()
+                .take(col)
+                .map(|c| c.width().unwrap_or(0))
+                .sum();
+            let len = t.tab_len - (width % t.tab_len as usize) as u8;
+            t.insert_piece(spaces(len).to_string())
+        })
+    }
+
+    /// Indent the lines in the text selection, or the cursor line when no text is selected, by inserting
+    /// [`TextArea::indent`] at the head of each line. Empty lines are not indented. Indenting the lines is recorded as
+    /// one modification in the edit history, and the cursor and the text selection stay at the same text. This method
+    /// returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["if x {", "foo();", "", "bar();", "}"]);
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(3, 2));
+    ///
+    /// assert!(textarea.indent_lines());
+    /// assert_eq!(textarea.lines(), ["if x {", "    foo();", "", "    bar();", "}"]);
+    /// assert!(textarea.is_selecting());
+    /// assert_eq!(textarea.cursor(), (3, 6));
+    /// ```
+    pub fn indent_lines(&mut self) -> bool {
+        let indent = self.indent();
+        if indent.is_empty() {
+            return false;
+        }
+        let (mark, before) = (self.history.pushed(), self.selection_state());
+        let mut modified = false;
+        for row in self.selected_rows() {
+            if self.lines[row].is_empty() {
+                continue;
+            }
+            self.insert_str_at((row, 0), indent);
+            // All lines are indented at once
+            if modified {
+                self.history.join_last();
+            }
+            modified = true;
+        }
+        self.record_selection(mark, before);
+        modified
+    }
+
+    /// Dedent the lines in the text selection, or the cursor line when no text is selected. One hard tab or spaces up
+    /// to the tab length are removed from the head of each line. Dedenting the lines is recorded as one modification in
+    /// the edit history, and the cursor and the text selection stay at the same text. This method returns if the text
+    /// was modified or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["      foo();", "\tbar();", "  baz();"]);
+    /// textarea.select_all();
+    ///
+    /// assert!(textarea.dedent_lines());
+    /// assert_eq!(textarea.lines(), ["  foo();", "bar();", "baz();"]);
+    /// ```
+    pub fn dedent_lines(&mut self) -> bool {
+        let (mark, before) = (self.history.pushed(), self.selection_state());
+        let mut modified = false;
+        for row in self.selected_rows() {
+            let line = &self.lines[row];
+            let len = if line.starts_with('\t') {
+                1
+            } else {
+                line.chars()
+                    .take(self.tab_len as usize)
+                    .take_while(|&c| c == ' ')
+                    .count()
+            };
+            if len == 0 {
+                continue;
+            }
+            self.delete_range((row, 0), (row, len));
+            // All lines are dedented at once
+            if modified {
+                self.history.join_last();
+            }
+            modified = true;
+        }
+        self.record_selection(mark, before);
+        modified
+    }
+
+    // Rows of the lines in the text selection, or the cursor line when no text is selected
+    fn selected_rows(&self) -> Range<usize> {
+        match self.selection_start {
+            Some((row, _)) => cmp::min(row, self.cursor.0)..cmp::max(row, self.cursor.0) + 1,
+            None => self.cursor.0..self.cursor.0 + 1,
+        }
+    }
+
+    // Tab indents the selected lines when the selection spans multiple lines. Otherwise it inserts a tab
+    fn tab(&mut self) -> bool {
+        match self.selection_start {
+            Some((row, _)) if row != self.cursor.0 => self.indent_lines(),
+            _ => self.insert_tab(),
+        }
+    }
+
+    /// Insert a newline at current cursor position. This method does nothing in single-line mode. See
+    /// [`TextArea::set_single_line`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hi"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["h", "i"]);
+    /// ```
+    pub fn insert_newline(&mut self) {
+        if self.single_line {
+            return;
+        }
+        self.replace_selection(|t| {
+            let (row, col) = t.cursor;
+            let line = &mut t.lines[row];
+            let offset = line
+                .char_indices()
+                .nth(col)
+                .map(|(i, _)| i)
+                .unwrap_or(line.len());
+            let next_line = line[offset..].to_string();
+            line.truncate(offset);
+
+            t.lines.insert(row + 1, next_line);
+            t.set_cursor((row + 1, 0));
+            t.push_history(EditKind::InsertNewline, Pos::new(row, col, offset), 0);
+            true
+        });
+    }
+
+    /// Delete a newline from **head** of current cursor line. This method returns if a newline was deleted or not in
+    /// the textarea. When some text is selected, it is deleted instead.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.delete_newline();
+    /// assert_eq!(textarea.lines(), ["helloworld"]);
+    /// ```
+    pub fn delete_newline(&mut self) -> bool {
+        if self.remove_selection(false) {
+            return true;
+        }
+
+        let (row, _) = self.cursor;
+        if row == 0 {
+            return false;
+        }
+
+        let line = self.lines.remove(row);
+        let prev_line = &mut self.lines[row - 1];
+        let prev_line_end = prev_line.len();
+        let col = prev_line.chars().count();
+        prev_line.push_str(&line);
+
+        self.set_cursor((row - 1, col));
+        self.push_history(EditKind::DeleteNewline, Pos::new(row, 0, 0), prev_line_end);
+        true
+    }
+
+    /// Delete one character before cursor. When the cursor is at head of line, the newline before the cursor will be
+    /// removed. This method returns if some text was deleted or not in the textarea. When some text is selected, it is
+    /// deleted instead. A grapheme cluster consisting of multiple characters such as a flag emoji is deleted at once.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.delete_char();
+    /// assert_eq!(textarea.lines(), ["bc"]);
+    /// ```
+    pub fn delete_char(&mut self) -> bool {
+        if self.remove_selection(false) {
+            return true;
+        }
+
+        let (row, col) = self.cursor;
+        if col == 0 {
+            return self.delete_newline();
+        }
+
+        // Delete the whole grapheme cluster before the cursor. For example, a flag emoji consists of two characters
+        let start = match self.smart_backspace_col() {
+            Some(start) => start,
+            None => prev_grapheme_col(&self.lines[row], col),
+        };
+        // The closing character of an empty pair is deleted with the opening one
+        let end_col = if start + 1 == col && self.is_empty_pair_at(row, start) {
+            col + 1
+        } else {
+            col
+        };
+        let line = &mut self.lines[row];
+        let (offset, c) = match line.char_indices().nth(start) {
+            Some(found) => found,
+            None => return false,
+        };
+        let end = line
+            .char_indices()
+            .nth(end_col)
+            .map_or(line.len(), |(i, _)| i);
+        let kind = if offset + c.len_utf8() == end {
+            line.remove(offset);
+            EditKind::DeleteChar(c)
+        } else {
+            EditKind::DeleteStr(line.drain(offset..end).collect())
+        };
+        self.set_cursor((row, start));
+        self.push_history(kind, Pos::new(row, col, end), offset);
+        true
+    }
+
+    // Return if the characters at the column and the next column are an empty pair of brackets or quotes deleted at
+    // once by pair backspace
+    fn is_empty_pair_at(&self, row: usize, col: usize) -> bool {
+        if !self.pair_backspace {
+            return false;
+        }
+        let mut chars = self.lines[row].chars().skip(col);
+        match (chars.next(), chars.next()) {
+            (Some(open), Some(close)) => PAIRS.contains(&(open, close)),
+            _ => false,
+        }
+    }
+
+    // Column to delete spaces back to the previous indent level by smart backspace. `None` is returned when the text
+    // before the cursor is not indentation ending with a space.
+    fn smart_backspace_col(&self) -> Option<usize> {
+        if !self.smart_backspace || self.tab_len == 0 {
+            return None;
+        }
+        let (row, col) = self.cursor;
+        let indent: Vec<char> = self.lines[row].chars().take(col).collect();
+        if indent.len() < col
+            || indent.last() != Some(&' ')
+            || indent.iter().any(|c| !c.is_whitespace())
+        {
+            return None;
+        }
+        let width = indent
+            .iter()
+            .fold(0, |w, &c| w + char_display_width(c, w, self.tab_len));
+        let tab_len = self.tab_len as usize;
+        let target = (width - 1) / tab_len * tab_len;
+        let spaces = indent.iter().rev().take_while(|&&c| c == ' ').count();
+        Some(col - cmp::min(width - target, spaces))
+    }
+
+    /// Delete one character next to cursor. When the cursor is at end of line, the newline next to the cursor will be
+    /// removed. This method returns if a character was deleted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.delete_next_char();
+    /// assert_eq!(textarea.lines(), ["ac"]);
+    /// ```
+    pub fn delete_next_char(&mut self) -> bool {
+        if self.remove_selection(false) {
+            return true;
+        }
+
+        let saved = self.saved_positions();
+        let before = self.cursor;
+        self.move_cursor_with_shift(CursorMove::Forward, false);
+        if before == self.cursor {
+            return false; // Cursor didn't move, meant no character at next of cursor.
+        }
+
+        let deleted = self.delete_char();
+        // The cursor only moved forward temporarily, so it should not be counted as a move
+        if self.cursor == before {
+            self.restore_positions(saved);
+        }
+        deleted
+    }
+
+    /// Delete string from cursor to end of the line. When the cursor is at end of line, the newline next to the cursor
+    /// is removed. This method returns if some text was deleted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abcde"]);
+    ///
+    /// // Move to 'c'
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.move_cursor(CursorMove::Forward);
+    ///
+    /// textarea.delete_line_by_end();
+    /// assert_eq!(textarea.lines(), ["ab"]);
+    /// ```
+    pub fn delete_line_by_end(&mut self) -> bool {
+        if self.remove_selection(false) {
+            return true;
+        }
+        if self.delete_piece(self.cursor.1, usize::MAX) {
+            return true;
+        }
+        self.delete_next_char() // At the end of the line. Try to delete next line
+    }
+
+    /// Delete string from cursor to head of the line. When the cursor is at head of line, the newline before the cursor
+    /// will be removed. This method returns if some text was deleted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abcde"]);
+    ///
+    /// // Move to 'c'
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.move_cursor(CursorMove::Forward);
+    ///
+    /// textarea.delete_line_by_head();
+    /// assert_eq!(textarea.lines(), ["cde"]);
+    /// ```
+    pub fn delete_line_by_head(&mut self) -> bool {
+        if self.remove_selection(false) {
+            return true;
+        }
+        if self.delete_piece(0, self.cursor.1) {
+            return true;
+        }
+        self.delete_newline()
+    }
+
+    /// Delete a word before cursor. Word boundary appears at spaces, punctuations, and others. For example `fn foo(a)`
+    /// consists of words `fn`, `foo`, `(`, `a`, `)`. When the cursor is at head of line, the newline before the cursor
+    /// will be removed.
+    ///
+    /// This method returns if some text was deleted or not in the textarea.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.delete_word();
+    /// assert_eq!(textarea.lines(), ["aaa bbb "]);
+    /// textarea.delete_word();
+    /// assert_eq!(textarea.lines(), ["aaa "]);
+    /// ```
+    pub fn delete_word(&mut self) -> bool {
+        if self.remove_selection(false) {
+            return true;
+        }
+        let (r, c) = self.cursor;
+        if let Some(col) = find_word_start_backward(&self.lines[r], c, self.word_chars) {
+            self.delete_piece(col, c - col)
+        } else if c > 0 {
+            self.delete_piece(0, c)
+        } else {
+            self.delete_newline()
+        }
+    }
+
+    /// Delete a word next to cursor. Word boundary appears at spaces, punctuations, and others. For example `fn foo(a)`
+    /// consists of words `fn`, `foo`, `(`, `a`, `)`. When the cursor is at end of line, the newline next to the cursor
+    /// will be removed.
+    ///
+    /// This method returns if some text was deleted or not in the textarea.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    ///
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" bbb ccc"]);
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" ccc"]);
+    /// ```
+    pub fn delete_next_word(&mut self) -> bool {
+        if self.remove_selection(false) {
+            return true;
+        }
+        let (r, c) = self.cursor;
+        let line = &self.lines[r];
+        if let Some(col) = find_word_end_forward(line, c, self.word_chars) {
+            self.delete_piece(c, col - c)
+        } else {
+            let end_col = line.chars().count();
+            if c < end_col {
+                self.delete_piece(c, end_col - c)
+            } else if r + 1 < self.lines.len() {
+                self.set_cursor((r + 1, 0));
+                self.delete_newline()
+            } else {
+                false
+            }
+        }
+    }
+
+    /// Delete a word before cursor as [`TextArea::delete_word`] does and return the deleted text. When nothing was
+    /// deleted, this method returns an empty string. This is useful for implementing a custom kill ring.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa", "bbb ccc"]);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert_eq!(textarea.delete_word_backward(), "ccc");
+    /// assert_eq!(textarea.delete_word_backward(), "bbb ");
+    /// assert_eq!(textarea.delete_word_backward(), "\n");
+    /// assert_eq!(textarea.lines(), ["aaa"]);
+    /// ```
+    pub fn delete_word_backward(&mut self) -> String {
+        self.record_deleted(|t| t.delete_word()).unwrap_or_default()
+    }
+
+    /// Delete a word next to cursor as [`TextArea::delete_next_word`] does and return the deleted text. When nothing
+    /// was deleted, this method returns an empty string.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb"]);
+    ///
+    /// assert_eq!(textarea.delete_word_forward(), "aaa");
+    /// assert_eq!(textarea.delete_word_forward(), " bbb");
+    /// assert_eq!(textarea.delete_word_forward(), "");
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn delete_word_forward(&mut self) -> String {
+        self.record_deleted(|t| t.delete_next_word())
+            .unwrap_or_default()
+    }
+
+    /// Delete string from cursor to end of the line as [`TextArea::delete_line_by_end`] does and return the deleted
+    /// text. When nothing was deleted, this method returns an empty string.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.move_cursor(CursorMove::Forward);
+    ///
+    /// assert_eq!(textarea.delete_line_to_end(), "bc");
+    /// assert_eq!(textarea.delete_line_to_end(), "\n");
+    /// assert_eq!(textarea.lines(), ["adef"]);
+    /// ```
+    pub fn delete_line_to_end(&mut self) -> String {
+        self.record_deleted(|t| t.delete_line_by_end())
+            .unwrap_or_default()
+    }
+
+    /// Delete string from cursor to head of the line as [`TextArea::delete_line_by_head`] does and return the deleted
+    /// text. When nothing was deleted, this method returns an empty string.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::Forward);
+    ///
+    /// assert_eq!(textarea.delete_line_to_head(), "d");
+    /// assert_eq!(textarea.delete_line_to_head(), "\n");
+    /// assert_eq!(textarea.lines(), ["abcef"]);
+    /// ```
+    pub fn delete_line_to_head(&mut self) -> String {
+        self.record_deleted(|t| t.delete_line_by_head())
+            .unwrap_or_default()
+    }
+
+    /// Kill text from cursor to end of the line like `C-k` of Emacs and return the killed text. When the cursor is at
+    /// end of the line, the newline next to the cursor is killed to join the next line. Unlike
+    /// [`TextArea::delete_line_to_end`], the killed text is always yanked including the newline so that
+    /// [`TextArea::paste`] restores it. Each kill is recorded as one modification in the edit history. When nothing was
+    /// killed, this method returns an empty string and the yanked text is not changed.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.move_cursor(CursorMove::Forward);
+    ///
+    /// assert_eq!(textarea.delete_to_line_end(), "bc");
+    /// assert_eq!(textarea.delete_to_line_end(), "\n");
+    /// assert_eq!(textarea.lines(), ["adef"]);
+    ///
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["a", "def"]);
+    /// ```
+    pub fn delete_to_line_end(&mut self) -> String {
+        let text = self.delete_line_to_end();
+        if !text.is_empty() {
+            self.set_yank_text(text.as_str());
+        }
+        text
+    }
+
+    /// Kill text from head of the line to cursor like `C-u` of shells and return the killed text. When the cursor is at
+    /// head of the line, the newline before the cursor is killed to join the previous line. The killed text is always
+    /// yanked as [`TextArea::delete_to_line_end`] does.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::Forward);
+    ///
+    /// assert_eq!(textarea.delete_to_line_start(), "d");
+    /// assert_eq!(textarea.yank_text(), "d");
+    /// assert_eq!(textarea.delete_to_line_start(), "\n");
+    /// assert_eq!(textarea.yank_text(), "\n");
+    /// assert_eq!(textarea.lines(), ["abcef"]);
+    /// ```
+    pub fn delete_to_line_start(&mut self) -> String {
+        let text = self.delete_line_to_head();
+        if !text.is_empty() {
+            self.set_yank_text(text.as_str());
+        }
+        text
+    }
+
+    /// Paste a string previously deleted by [`TextArea::delete_line_by_head`], [`TextArea::delete_line_by_end`],
+    /// [`TextArea::delete_word`], [`TextArea::delete_next_word`]. This method returns if some text was inserted or not
+    /// in the textarea. In single-line mode, newlines in the pasted text are replaced with spaces.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    ///
+    /// textarea.delete_next_word();
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), [" bbb cccaaa"]);
+    /// ```
+    pub fn paste(&mut self) -> bool {
+        let yank = self.yank.clone();
+        self.paste_yank(yank)
+    }
+
+    // Paste the text and remember the range of the pasted text for `TextArea::yank_pop`
+    fn paste_yank(&mut self, yank: YankText) -> bool {
+        let block = matches!(yank, YankText::Block(_)) && !self.single_line;
+        let mut start = None;
+        let first = match &yank {
+            YankText::Piece(s) => s.clone(),
+            YankText::Chunk(c) | YankText::Block(c) if self.single_line => c.join(" "),
+            YankText::Chunk(c) => c.first().cloned().unwrap_or_default(),
+            YankText::Block(_) => String::new(),
+        };
+        let pasted = self.replace_selection_or_overwrite(&first, |t| {
+            start = Some(t.cursor);
+            match yank {
+                YankText::Piece(s) => t.insert_piece(s),
+                YankText::Chunk(c) | YankText::Block(c) if t.single_line => {
+                    t.insert_piece(c.join(" "))
+                }
+                YankText::Chunk(c) => t.insert_chunk(c),
+                YankText::Block(b) => t.insert_block(b),
+            }
+        });
+        // A rectangular block is not a continuous range of text
+        self.last_paste = match start {
+            Some(start) if pasted && !block => Some(LastPaste {
+                start,
+                end: self.cursor,
+                revision: self.revision,
+            }),
+            _ => None,
+        };
+        pasted
+    }
+
+    /// Replace the text inserted by the last [`TextArea::paste`] with the text yanked before it, like `M-y` in Emacs.
+    /// Recently yanked texts are remembered in the kill ring, and calling this method repeatedly cycles through them
+    /// from the newest to the oldest. The replacing text becomes the yanked text so the next paste inserts it. This
+    /// method does nothing and returns `false` when the text was modified after the paste, when the pasted text was a
+    /// rectangle yanked in block selection mode, or when the kill ring has only one text. The replacement is recorded
+    /// as one modification in the edit history. See [`TextArea::set_kill_ring_size`] for the number of
+    /// remembered texts.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar baz"]);
+    ///
+    /// // Yank "foo", " bar", and " baz" in order
+    /// textarea.delete_next_word();
+    /// textarea.delete_next_word();
+    /// textarea.delete_next_word();
+    ///
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), [" baz"]);
+    /// assert!(textarea.yank_pop());
+    /// assert_eq!(textarea.lines(), [" bar"]);
+    /// assert!(textarea.yank_pop());
+    /// assert_eq!(textarea.lines(), ["foo"]);
+    /// // Cycles back to the newest text
+    /// assert!(textarea.yank_pop());
+    /// assert_eq!(textarea.lines(), [" baz"]);
+    /// ```
+    pub fn yank_pop(&mut self) -> bool {
+        let last = match self.last_paste.take() {
+            Some(last) if last.revision == self.revision && self.kill_ring.texts.len() > 1 => last,
+            last => {
+                self.last_paste = last;
+                return false;
+            }
+        };
+        let index = (self.kill_ring.index + 1) % self.kill_ring.texts.len();
+        let yank = self.kill_ring.texts[index].clone();
+
+        let mark = self.history.pushed();
+        self.cancel_selection();
+        self.delete_range(last.start, last.end);
+        self.set_cursor(last.start);
+        // The next text replaces the previous one instead of overwriting the characters after it
+        let overwrite = mem::replace(&mut self.overwrite, false);
+        self.paste_yank(yank.clone());
+        self.overwrite = overwrite;
+        // Deleting the previous text and pasting the next text are undone at once
+        self.history.join_since(mark);
+
+        self.yank = yank;
+        self.kill_ring.index = index;
+        true
+    }
+
+    /// Set how many yanked texts are remembered in the kill ring for [`TextArea::yank_pop`]. Setting 0 disables the
+    /// kill ring. The default value is 10.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a b"]);
+    /// textarea.set_kill_ring_size(1);
+    /// assert_eq!(textarea.kill_ring_size(), 1);
+    ///
+    /// textarea.delete_next_word();
+    /// textarea.delete_next_word();
+    /// textarea.paste();
+    /// // "a " was already dropped from the kill ring
+    /// assert!(!textarea.yank_pop());
+    /// ```
+    pub fn set_kill_ring_size(&mut self, size: usize) {
+        self.kill_ring.max = size;
+        self.kill_ring.texts.truncate(size);
+        self.kill_ring.index = 0;
+    }
+
+    /// Get how many yanked texts are remembered in the kill ring. See [`TextArea::set_kill_ring_size`].
+    pub fn kill_ring_size(&self) -> usize {
+        self.kill_ring.max
+    }
+
+    /// Set the text of the register named `name`. Registers hold texts separately from the yanked text, like named
+    /// registers in Vim. The text can be inserted by [`TextArea::paste_register`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_register('a', "hello");
+    /// assert_eq!(textarea.register('a'), Some("hello"));
+    /// assert_eq!(textarea.register('b'), None);
+    /// ```
+    pub fn set_register(&mut self, name: char, text: impl Into<String>) {
+        self.registers.insert(name, text.into());
+    }
+
+    /// Get the text of the register named `name`. `None` is returned when nothing was stored in the register. When
+    /// multiple lines were stored, they are joined with `\n`.
+    pub fn register(&self, name: char) -> Option<&str> {
+        self.registers.get(&name).map(String::as_str)
+    }
+
+    /// Copy the selected text to the register named `name`. The text is also yanked as [`TextArea::copy`] does. When
+    /// nothing is selected, this method does nothing. This method returns if some text was copied or not.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello world"]);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    ///
+    /// assert!(textarea.copy_to_register('a'));
+    /// assert_eq!(textarea.register('a'), Some("hello"));
+    /// ```
+    pub fn copy_to_register(&mut self, name: char) -> bool {
+        if !self.is_selecting() {
+            return false;
+        }
+        self.copy();
+        self.set_register(name, self.yank.to_string());
+        true
+    }
+
+    /// Cut the selected text to the register named `name`. The text is also yanked as [`TextArea::cut`] does. When
+    /// nothing is selected, this method does nothing. This method returns if the text was modified or not.
+    pub fn cut_to_register(&mut self, name: char) -> bool {
+        if !self.is_selecting() {
+            return false;
+        }
+        let modified = self.cut();
+        self.set_register(name, self.yank.to_string());
+        modified
+    }
+
+    /// Paste the text of the register named `name` at the cursor. The yanked text is not changed. When some text is
+    /// selected, it is replaced. This method returns if some text was inserted or not. Nothing is inserted when the
+    /// register is empty.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_register('a', "hello\nworld");
+    /// assert!(textarea.paste_register('a'));
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// assert!(!textarea.paste_register('b'));
+    /// ```
+    pub fn paste_register(&mut self, name: char) -> bool {
+        let yank = match self.registers.get(&name) {
+            Some(text) => YankText::from_text(text),
+            None => return false,
+        };
+        let pasted = self.paste_yank(yank);
+        // Only the text pasted from the kill ring can be replaced by `TextArea::yank_pop`
+        self.last_paste = None;
+        pasted
+    }
+
+    /// Paste the text sent from the terminal by bracketed paste, such as [`crossterm::event::Event::Paste`]. The text
+    /// is inserted at the cursor as it is and recorded as one modification in the edit history, so pasting many lines
+    /// is undone at once. Unlike typing the text with [`TextArea::input`], key bindings are not triggered by the pasted
+    /// text. Editing behaviors like indentation should not be applied while pasting code; call this method instead of
+    /// sending each character as a key input. Since terminals often send newlines as `\r` on bracketed paste, `\r\n`
+    /// and `\r` are handled as newlines as well as `\n`. When some text is selected, the selected text is replaced.
+    /// This method returns if some text was inserted or not.
+    ///
+    /// [`crossterm::event::Event::Paste`]: https://docs.rs/crossterm/latest/crossterm/event/enum.Event.html#variant.Paste
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn main() {", "}"]);
+    /// textarea.move_cursor(tui_textarea::CursorMove::Down);
+    ///
+    /// textarea.paste_text("    let a = 1;\r    let b = 2;\r");
+    /// assert_eq!(textarea.lines(), ["fn main() {", "    let a = 1;", "    let b = 2;", "}"]);
+    ///
+    /// // The paste is undone at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["fn main() {", "}"]);
+    /// ```
+    pub fn paste_text(&mut self, text: &str) -> bool {
+        if text.contains('\r') {
+            self.insert_str(text.replace("\r\n", "\n").replace('\r', "\n"))
+        } else {
+            self.insert_str(text)
+        }
+    }
+
+    // Insert each line of the block at the cursor column across the consecutive lines. Short lines are padded with
+    // spaces and missing lines are appended at the end of the text. The cursor stays at the top-left of the block.
+    fn insert_block(&mut self, block: Vec<String>) -> bool {
+        let (row, col) = self.cursor;
+        let mut inserted = false;
+        for (i, s) in block.into_iter().enumerate() {
+            if s.is_empty() {
+                continue;
+            }
+            let r = row + i;
+            let mut pos = (r, col);
+            let mut text = s;
+            if r >= self.lines.len() {
+                pos = (self.lines.len() - 1, usize::MAX);
+                text = "\n".repeat(r - pos.0) + &" ".repeat(col) + &text;
+            } else {
+                let len = self.lines[r].chars().count();
+                if len < col {
+                    pos.1 = len;
+                    text = " ".repeat(col - len) + &text;
+                }
+            }
+            self.insert_str_at(pos, text);
+            // All insertions are undone at once
+            if inserted {
+                self.history.join_last();
+            }
+            inserted = true;
+        }
+        self.set_cursor((row, col));
+        inserted
+    }
+
+    /// Start text selection at the cursor position. If text selection is already ongoing, the start position is reset.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "aaa ");
+    /// ```
+    pub fn start_selection(&mut self) {
+        self.set_selection_start(Some(self.cursor));
+    }
+
+    /// Stop the current text selection. This method does nothing if text selection is not ongoing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    ///
+    /// // Cancel the ongoing text selection
+    /// textarea.cancel_selection();
+    ///
+    /// // As the result, this `copy` call does nothing
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "");
+    /// ```
+    pub fn cancel_selection(&mut self) {
+        self.set_selection_start(None);
+    }
+
+    /// Select the entire text. Cursor moves to the end of the text buffer. When text selection is already ongoing,
+    /// it is canceled.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa", "bbb", "ccc"]);
+    ///
+    /// textarea.select_all();
+    ///
+    /// // Cut the entire text;
+    /// textarea.cut();
+    ///
+    /// assert_eq!(textarea.lines(), [""]); // Buffer is now empty
+    /// assert_eq!(textarea.yank_text(), "aaa\nbbb\nccc");
+    /// ```
+    pub fn select_all(&mut self) {
+        self.move_cursor(CursorMove::Jump(u16::MAX, u16::MAX));
+        self.set_selection_start(Some((0, 0)));
+    }
+
+    /// Return if text selection is ongoing or not.
+    /// ```
+    /// use tui_textarea::{TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.is_selecting());
+    /// textarea.start_selection();
+    /// assert!(textarea.is_selecting());
+    /// textarea.cancel_selection();
+    /// assert!(!textarea.is_selecting());
+    /// ```
+    pub fn is_selecting(&self) -> bool {
+        self.selection_start.is_some()
+    }
+
+    /// Set the mode of text selection. The mode changes which text is selected between the selection start and the
+    /// cursor, and how the selected text is cut, copied, and pasted. See [`SelectionMode`] for each mode. The default
+    /// mode is [`SelectionMode::Charwise`].
+    /// ```
+    /// use tui_textarea::{CursorMove, SelectionMode, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def", "ghi"]);
+    ///
+    /// textarea.set_selection_mode(SelectionMode::Block);
+    /// assert_eq!(textarea.selection_mode(), SelectionMode::Block);
+    ///
+    /// // Select the second column of the first two lines
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(1, 2));
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "b\ne");
+    /// ```
+    pub fn set_selection_mode(&mut self, mode: SelectionMode) {
+        self.selection_mode = mode;
+    }
+
+    /// Get the mode of text selection set by [`TextArea::set_selection_mode`].
+    pub fn selection_mode(&self) -> SelectionMode {
+        self.selection_mode
+    }
+
+    fn line_offset(&self, row: usize, col: usize) -> usize {
+        let line = self
+            .lines
+            .get(row)
+            .unwrap_or(&self.lines[self.lines.len() - 1]);
+        line.char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len())
+    }
+
+    /// Set the style used for text selection. The default style is light blue.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Style, Color};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// // Change the selection color from the default to Red
+    /// textarea.set_selection_style(Style::default().bg(Color::Red));
+    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::Red));
+    /// ```
+    pub fn set_selection_style(&mut self, style: Style) {
+        self.select_style = style;
+    }
+
+    /// Get the style used for text selection.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Style, Color};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::LightBlue));
+    /// ```
+    pub fn selection_style(&mut self) -> Style {
+        self.select_style
+    }
+
+    /// Set ranges rendered with the selection style in addition to the current selection. This is useful for showing
+    /// selections of multiple cursors or all matches of a pattern. Each range is a pair of positions as `(row, col)`
+    /// and the end position is exclusive. Ranges can be in any order and may overlap each other or the current
+    /// selection. Overlapped parts are rendered once with the selection style. The cursor and search matches are
+    /// rendered on top of the ranges and highlights set by [`TextArea::set_highlights`] are rendered below them.
+    ///
+    /// Note that the ranges are only rendered. They don't affect operations on the selection such as
+    /// [`TextArea::copy`].
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo bar foo"]);
+    /// textarea.set_selection_style(Style::default().bg(Color::Red));
+    ///
+    /// // Select both occurrences of "foo"
+    /// textarea.set_extra_selections(vec![((0, 0), (0, 3)), ((0, 8), (0, 11))]);
+    /// assert_eq!(textarea.extra_selections().len(), 2);
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert_eq!(b[(1, 0)].bg, Color::Red);
+    /// # assert_eq!(b[(4, 0)].bg, Color::Reset);
+    /// # assert_eq!(b[(8, 0)].bg, Color::Red);
+    /// ```
+    pub fn set_extra_selections(&mut self, ranges: Vec<SelectionRange>) {
+        self.extra_selections = ranges
+            .into_iter()
+            .map(|(a, b)| if a <= b { (a, b) } else { (b, a) })
+            .collect();
+    }
+
+    /// Get the ranges set by [`TextArea::set_extra_selections`]. The start and the end of each range are ordered.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_extra_selections(vec![((0, 2), (0, 1))]);
+    /// assert_eq!(textarea.extra_selections(), &[((0, 1), (0, 2))]);
+    /// ```
+    pub fn extra_selections(&self) -> &[SelectionRange] {
+        &self.extra_selections
+    }
+
+    /// Remove all ranges set by [`TextArea::set_extra_selections`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_extra_selections(vec![((0, 0), (0, 1))]);
+    /// textarea.clear_extra_selections();
+    /// assert!(textarea.extra_selections().is_empty());
+    /// ```
+    pub fn clear_extra_selections(&mut self) {
+        self.extra_selections.clear();
+    }
+
+    /// Set styled ranges overlaid on the text. This is useful for coloring text with an external highlighter such as
+    /// tree-sitter, LSP semantic tokens, or grep results. Each [`Highlight`] style is patched on top of the base style
+    /// of the line. When highlights overlap, the later one in the vector takes precedence. The cursor, search matches,
+    /// and selection are rendered on top of the highlights.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::{Highlight, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["fn main() {}"]);
+    ///
+    /// // Color the keyword `fn`
+    /// textarea.set_highlights(vec![Highlight {
+    ///     start: (0, 0),
+    ///     end: (0, 2),
+    ///     style: Style::default().fg(Color::Red),
+    /// }]);
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert_eq!(b[(1, 0)].fg, Color::Red);
+    /// # assert_eq!(b[(2, 0)].fg, Color::Reset);
+    /// ```
+    pub fn set_highlights(&mut self, highlights: Vec<Highlight>) {
+        self.render_cache.clear();
+        self.highlights = Highlights::new(highlights);
+    }
+
+    /// Get the highlights set by [`TextArea::set_highlights`].
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::{Highlight, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(textarea.highlights().is_empty());
+    ///
+    /// let hl = Highlight { start: (0, 0), end: (0, 1), style: Style::default() };
+    /// textarea.set_highlights(vec![hl]);
+    /// assert_eq!(textarea.highlights(), &[hl]);
+    /// ```
+    pub fn highlights(&self) -> &[Highlight] {
+        self.highlights.as_slice()
+    }
+
+    /// Remove all highlights set by [`TextArea::set_highlights`].
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::{Highlight, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let hl = Highlight { start: (0, 0), end: (0, 1), style: Style::default() };
+    /// textarea.set_highlights(vec![hl]);
+    /// textarea.clear_highlights();
+    /// assert!(textarea.highlights().is_empty());
+    /// ```
+    pub fn clear_highlights(&mut self) {
+        self.render_cache.clear();
+        self.highlights = Highlights::default();
+    }
+
+    /// Highlight the text with the syntax of syntect. `name` is a name of the syntax like `"Rust"` or a file extension
+    /// like `"rs"`. This method returns if the syntax was found. When it was not found, syntax highlighting is
+    /// disabled. The colors and the font styles of the theme are rendered below the highlights set by
+    /// [`TextArea::set_highlights`]. The background colors of the theme are not used.
+    ///
+    /// Syntaxes and the theme are loaded from the defaults of syntect on the first call unless they were set by
+    /// [`TextArea::set_syntax_set`] and [`TextArea::set_syntax_theme`]. Lines are highlighted from the top of the text
+    /// to the bottom of the viewport on rendering. Results are cached and only lines after a modified line are
+    /// highlighted again.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use ratatui::style::Color;
+    /// # use tui_textarea::TextAreaWidget;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn main() {}"]);
+    ///
+    /// assert!(textarea.set_syntax("rs"));
+    /// assert_eq!(textarea.syntax(), Some("Rust"));
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert!(matches!(b[(1, 0)].fg, Color::Rgb(..)));
+    ///
+    /// assert!(!textarea.set_syntax("unknown"));
+    /// assert_eq!(textarea.syntax(), None);
+    /// ```
+    #[cfg(feature = "syntect")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "syntect")))]
+    pub fn set_syntax(&mut self, name: &str) -> bool {
+        self.render_cache.clear();
+        self.syntax
+            .get_or_insert_with(SyntaxHighlighter::default)
+            .set_syntax(name)
+    }
+
+    /// Get the name of the syntax set by [`TextArea::set_syntax`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.syntax(), None);
+    ///
+    /// textarea.set_syntax("py");
+    /// assert_eq!(textarea.syntax(), Some("Python"));
+    /// ```
+    #[cfg(feature = "syntect")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "syntect")))]
+    pub fn syntax(&self) -> Option<&str> {
+        self.syntax.as_ref().and_then(SyntaxHighlighter::syntax)
+    }
+
+    /// Disable syntax highlighting enabled by [`TextArea::set_syntax`]. The syntax set and the theme are kept.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_syntax("rs");
+    /// textarea.clear_syntax();
+    /// assert_eq!(textarea.syntax(), None);
+    /// ```
+    #[cfg(feature = "syntect")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "syntect")))]
+    pub fn clear_syntax(&mut self) {
+        self.render_cache.clear();
+        if let Some(syntax) = &mut self.syntax {
+            syntax.clear();
+        }
+    }
+
+    /// Set the set of syntaxes looked up by [`TextArea::set_syntax`] instead of the defaults of syntect. Lines are
+    /// parsed with a newline at the end, so the syntaxes should be loaded for lines with newlines like
+    /// [`SyntaxSet::load_defaults_newlines`](syntect::parsing::SyntaxSet::load_defaults_newlines). The current syntax
+    /// is looked up again by its name in the new set.
+    /// ```
+    /// use tui_textarea::syntect::parsing::SyntaxSet;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_syntax_set(SyntaxSet::load_defaults_newlines());
+    /// assert!(textarea.set_syntax("Rust"));
+    /// ```
+    #[cfg(feature = "syntect")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "syntect")))]
+    pub fn set_syntax_set(&mut self, syntaxes: SyntaxSet) {
+        self.render_cache.clear();
+        self.syntax
+            .get_or_insert_with(SyntaxHighlighter::default)
+            .set_syntax_set(syntaxes);
+    }
+
+    /// Set the theme of syntax highlighting instead of the default theme `base16-ocean.dark` of syntect.
+    /// ```
+    /// use tui_textarea::syntect::highlighting::ThemeSet;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut themes = ThemeSet::load_defaults();
+    /// let theme = themes.themes.remove("InspiredGitHub").unwrap();
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_syntax_theme(theme);
+    /// textarea.set_syntax("rs");
+    /// ```
+    #[cfg(feature = "syntect")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "syntect")))]
+    pub fn set_syntax_theme(&mut self, theme: Theme) {
+        self.render_cache.clear();
+        self.syntax
+            .get_or_insert_with(SyntaxHighlighter::default)
+            .set_theme(theme);
+    }
+
+    // Highlight the syntax of lines until the row before rendering them
+    #[cfg(feature = "syntect")]
+    pub(crate) fn update_syntax(&mut self, last_row: usize) {
+        if let Some(syntax) = &mut self.syntax {
+            syntax.update(&self.lines, self.revision, last_row);
+        }
+    }
+
+    // If spans of a line depend on other lines. Elastic tabstops align cells across lines and syntax highlighting
+    // carries the parser state from the previous lines
+    pub(crate) fn spans_depend_on_other_lines(&self) -> bool {
+        #[cfg(feature = "syntect")]
+        if self.syntax().is_some() {
+            return true;
+        }
+        self.elastic_tabs
+    }
+
+    /// Summarize the whole text into at most `height` rows for rendering an overview of the text such as a minimap.
+    /// Lines are split into buckets of the same number of consecutive lines so that all lines fit in `height` rows.
+    /// Each [`MinimapRow`] has the indentation and the width of the lines in the bucket and the dominant color of the
+    /// highlights set by [`TextArea::set_highlights`]. [`MinimapWidget`](crate::MinimapWidget) renders a minimap
+    /// without this method.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::{Highlight, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {", "    x", "}", "", "// end"]);
+    /// textarea.set_highlights(vec![Highlight {
+    ///     start: (4, 0),
+    ///     end: (4, 6),
+    ///     style: Style::default().fg(Color::Green),
+    /// }]);
+    ///
+    /// let rows = textarea.minimap_rows(3);
+    /// assert_eq!(rows.len(), 3);
+    /// assert_eq!(rows[0].lines, 0..2);
+    /// assert_eq!((rows[0].indent, rows[0].width), (0, 8));
+    /// assert_eq!(rows[2].lines, 4..5);
+    /// assert_eq!(rows[2].color, Some(Color::Green));
+    /// ```
+    pub fn minimap_rows(&self, height: u16) -> Vec<MinimapRow> {
+        minimap::minimap_rows(
+            &self.lines,
+            self.highlights.as_slice(),
+            self.tab_length(),
+            height,
+        )
+    }
+
+    /// Mark lines with kinds of changes for rendering a diff view. `changes` is a slice of pairs of row and
+    /// [`DiffKind`]. Marks set previously are replaced. The background of marked lines is filled with the style of
+    /// each kind over the whole width of the textarea. The styles can be changed by [`TextArea::set_diff_style`].
+    /// ```
+    /// use tui_textarea::{DiffKind, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["foo", "bar", "baz"]);
+    ///
+    /// textarea.set_diff(&[(1, DiffKind::Added), (2, DiffKind::Removed)]);
+    /// assert_eq!(textarea.line_diff(0), None);
+    /// assert_eq!(textarea.line_diff(1), Some(DiffKind::Added));
+    /// assert_eq!(textarea.line_diff(2), Some(DiffKind::Removed));
+    /// ```
+    pub fn set_diff(&mut self, changes: &[(usize, DiffKind)]) {
+        self.render_cache.clear();
+        self.diff.clear();
+        self.diff.extend(changes.iter().copied());
+    }
+
+    /// Get the kind of change of the line at `row` marked by [`TextArea::set_diff`].
+    pub fn line_diff(&self, row: usize) -> Option<DiffKind> {
+        self.diff.get(&row).copied()
+    }
+
+    /// Remove all marks set by [`TextArea::set_diff`].
+    /// ```
+    /// use tui_textarea::{DiffKind, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["foo", "bar"]);
+    ///
+    /// textarea.set_diff(&[(1, DiffKind::Modified)]);
+    /// textarea.clear_diff();
+    /// assert_eq!(textarea.line_diff(1), None);
+    /// ```
+    pub fn clear_diff(&mut self) {
+        self.render_cache.clear();
+        self.diff.clear();
+    }
+
+    /// Add a rule to style lines depending on their contents. `rule` is called with the text and the row of each line
+    /// on rendering and returns the style filling the whole width of the line, or `None` when the line does not match.
+    /// When multiple rules are added, the first matching rule wins. Since the rules run on every render, the styles
+    /// follow edits of the text without any bookkeeping. Rules run only for lines visible on screen. Lines marked by
+    /// [`TextArea::set_diff`] are styled with the diff styles instead.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["INFO: started", "ERROR: failed", "WARN: retrying"]);
+    ///
+    /// // Color log levels
+    /// textarea.add_line_rule(|line, _| {
+    ///     line.contains("ERROR").then(|| Style::default().bg(Color::Red))
+    /// });
+    /// textarea.add_line_rule(|line, _| {
+    ///     line.contains("WARN").then(|| Style::default().bg(Color::Yellow))
+    /// });
+    /// ```
+    pub fn add_line_rule(
+        &mut self,
+        rule: impl Fn(&str, usize) -> Option<Style> + Send + Sync + 'static,
+    ) {
+        self.line_rules.push(Arc::new(rule));
+    }
+
+    /// Remove all rules added by [`TextArea::add_line_rule`].
+    pub fn clear_line_rules(&mut self) {
+        self.line_rules.clear();
+    }
+
+    /// Set the style of lines marked with the [`DiffKind`] by [`TextArea::set_diff`]. The background color of the
+    /// style fills the whole width of the line. By default, added lines are green, removed lines are red and crossed
+    /// out, and modified lines are yellow.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::{DiffKind, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// // Render removed lines without strikethrough
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_diff_style(DiffKind::Removed, style);
+    /// assert_eq!(textarea.diff_style(DiffKind::Removed), style);
+    /// ```
+    pub fn set_diff_style(&mut self, kind: DiffKind, style: Style) {
+        self.render_cache.clear();
+        self.diff_styles[kind.index()] = style;
+    }
+
+    /// Get the style of lines marked with the [`DiffKind`]. See [`TextArea::set_diff_style`].
+    pub fn diff_style(&self, kind: DiffKind) -> Style {
+        self.diff_styles[kind.index()]
+    }
+
+    /// Fold lines from `start_line` to `end_line` (inclusive). The folded lines are rendered as one summary row which
+    /// shows the text of the first line followed by the number of hidden lines. The cursor skips the hidden lines on
+    /// moving. When the cursor is in the hidden lines, it moves to the first line of the folded range. Ranges
+    /// overlapping with existing folded ranges are merged. When the sign column is enabled, a fold indicator is
+    /// rendered at the summary row.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {", "    a();", "    b();", "}"]);
+    ///
+    /// textarea.fold(0, 2);
+    /// assert_eq!(textarea.folded_range(1), Some((0, 2)));
+    ///
+    /// // The cursor skips the folded lines
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// textarea.move_cursor(CursorMove::Up);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    pub fn fold(&mut self, start_line: usize, end_line: usize) {
+        self.render_cache.clear();
+        let end_line = cmp::min(end_line, self.lines.len() - 1);
+        self.folds.fold(start_line, end_line);
+        self.set_cursor(self.skip_folded_lines(self.cursor, false));
+    }
+
+    /// Unfold the folded range which contains the line at `line`. This method returns if some range was unfolded or
+    /// not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    ///
+    /// textarea.fold(0, 2);
+    /// assert!(textarea.unfold(1));
+    /// assert_eq!(textarea.folded_range(1), None);
+    /// assert!(!textarea.unfold(1));
+    /// ```
+    pub fn unfold(&mut self, line: usize) -> bool {
+        self.render_cache.clear();
+        self.folds.unfold(line)
+    }
+
+    /// Unfold all folded ranges.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d", "e"]);
+    ///
+    /// textarea.fold(0, 1);
+    /// textarea.fold(2, 4);
+    /// textarea.unfold_all();
+    /// assert_eq!(textarea.folded_range(0), None);
+    /// assert_eq!(textarea.folded_range(3), None);
+    /// ```
+    pub fn unfold_all(&mut self) {
+        self.render_cache.clear();
+        self.folds.clear();
+    }
+
+    /// Get the folded range which contains the line at `line` as a pair of the first line and the last line.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d"]);
+    ///
+    /// textarea.fold(1, 2);
+    /// assert_eq!(textarea.folded_range(0), None);
+    /// assert_eq!(textarea.folded_range(1), Some((1, 2)));
+    /// assert_eq!(textarea.folded_range(2), Some((1, 2)));
+    /// ```
+    pub fn folded_range(&self, line: usize) -> Option<(usize, usize)> {
+        self.folds.range(line)
+    }
+
+    /// Set the style of the summary of folded lines and the fold indicator in the sign column. By default, they are
+    /// dimmed.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::DarkGray);
+    /// textarea.set_fold_style(style);
+    /// assert_eq!(textarea.fold_style(), style);
+    /// ```
+    pub fn set_fold_style(&mut self, style: Style) {
+        self.render_cache.clear();
+        self.fold_style = style;
+    }
+
+    /// Get the style of the summary of folded lines. See [`TextArea::set_fold_style`].
+    pub fn fold_style(&self) -> Style {
+        self.fold_style
+    }
+
+    /// Set virtual text rendered after the content of the line at `row`. Virtual text is a list of pairs of text and
+    /// its style. It is useful for showing non-editable hints such as inferred types or diagnostics. Virtual text is
+    /// not a part of the text contents so it does not affect [`TextArea::lines`], the cursor, or selection. Virtual
+    /// text exceeding the width of the textarea is clipped. Passing an empty vector removes the virtual text of the
+    /// line.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["let x = 1;"]);
+    ///
+    /// // Show the type of the variable after the line: "let x = 1; // i32"
+    /// let style = Style::default().fg(Color::DarkGray);
+    /// textarea.set_virtual_text(0, vec![(" // i32".to_string(), style)]);
+    /// assert_eq!(textarea.lines(), ["let x = 1;"]);
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert_eq!(b[(14, 0)].symbol(), "i");
+    /// # assert_eq!(b[(14, 0)].fg, Color::DarkGray);
+    /// ```
+    pub fn set_virtual_text(&mut self, row: usize, texts: Vec<(String, Style)>) {
+        self.render_cache.clear();
+        if texts.is_empty() {
+            self.virtual_texts.remove(&row);
+        } else {
+            self.virtual_texts.insert(row, texts);
+        }
+    }
+
+    /// Get the virtual text of the line at `row` set by [`TextArea::set_virtual_text`].
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// assert_eq!(textarea.virtual_text(0), None);
+    ///
+    /// textarea.set_virtual_text(0, vec![("hint".to_string(), Style::default())]);
+    /// assert_eq!(textarea.virtual_text(0), Some(&[("hint".to_string(), Style::default())][..]));
+    /// ```
+    pub fn virtual_text(&self, row: usize) -> Option<&[(String, Style)]> {
+        self.virtual_texts.get(&row).map(Vec::as_slice)
+    }
+
+    /// Remove all virtual text set by [`TextArea::set_virtual_text`].
+    pub fn clear_virtual_text(&mut self) {
+        self.render_cache.clear();
+        self.virtual_texts.clear();
+    }
+
+    /// Set the composition text of an input method (IME), which is called preedit. The preedit is rendered inline at
+    /// the cursor position with the style set by [`TextArea::set_preedit_style`] and the text after the cursor is
+    /// shifted on screen. The preedit is not a part of the text so [`TextArea::lines`] is not changed. When the input
+    /// method commits the composition, clear the preedit by setting `None` and insert the committed string with
+    /// [`TextArea::insert_str`].
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea = TextArea::from(["ab"]);
+    /// textarea.move_cursor(tui_textarea::CursorMove::Forward);
+    ///
+    /// // Composing the text with IME
+    /// textarea.set_preedit(Some("にほん".to_string()));
+    /// assert_eq!(textarea.preedit(), Some("にほん"));
+    /// assert_eq!(textarea.lines(), ["ab"]);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 1 };
+    /// let mut b = Buffer::empty(r.clone());
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// assert_eq!(b[(1, 0)].symbol(), "に");
+    /// assert_eq!(b[(7, 0)].symbol(), "b");
+    ///
+    /// // Commit the composition
+    /// textarea.set_preedit(None);
+    /// textarea.insert_str("日本");
+    /// assert_eq!(textarea.lines(), ["a日本b"]);
+    /// ```
+    pub fn set_preedit(&mut self, preedit: Option<String>) {
+        self.preedit = preedit.filter(|s| !s.is_empty());
+    }
+
+    /// Get the composition text of an input method set by [`TextArea::set_preedit`].
+    pub fn preedit(&self) -> Option<&str> {
+        self.preedit.as_deref()
+    }
+
+    /// Set the style of the composition text of an input method. The default style is underlined.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().fg(Color::Yellow);
+    /// textarea.set_preedit_style(style);
+    /// assert_eq!(textarea.preedit_style(), style);
+    /// ```
+    pub fn set_preedit_style(&mut self, style: Style) {
+        self.preedit_style = style;
+    }
+
+    /// Get the style of the composition text of an input method. See [`TextArea::set_preedit_style`].
+    pub fn preedit_style(&self) -> Style {
+        self.preedit_style
+    }
+
+    pub(crate) fn folds(&self) -> &Folds {
+        &self.folds
+    }
+
+    // Range of the selected text. In block selection mode, this returns `None`. Use `block_range` instead
+    fn selection_range(&self) -> Option<(Pos, Pos)> {
+        let (sr, sc) = self.selection_start?;
+        let (er, ec) = self.cursor;
+        let ((sr, sc), (er, ec)) = match self.selection_mode {
+            SelectionMode::Charwise => ((sr, sc), (er, ec)),
+            SelectionMode::Linewise => {
+                let (top, bottom) = (cmp::min(sr, er), cmp::max(sr, er));
+                // Include the newline at the end of the last line
+                let end = if bottom + 1 < self.lines.len() {
+                    (bottom + 1, 0)
+                } else {
+                    (bottom, self.lines[bottom].chars().count())
+                };
+                ((top, 0), end)
+            }
+            SelectionMode::Block => return None,
+        };
+        let (so, eo) = (self.line_offset(sr, sc), self.line_offset(er, ec));
+        let s = Pos::new(sr, sc, so);
+        let e = Pos::new(er, ec, eo);
+        match (sr, so).cmp(&(er, eo)) {
+            Ordering::Less => Some((s, e)),
+            Ordering::Equal => None,
+            Ordering::Greater => Some((e, s)),
+        }
+    }
+
+    // Rows and columns of the rectangle selected in block selection mode as `((top, bottom), (left, right))`. The
+    // bottom row is inclusive and the right column is exclusive
+    fn block_range(&self) -> Option<((usize, usize), (usize, usize))> {
+        if self.selection_mode != SelectionMode::Block {
+            return None;
+        }
+        let (sr, sc) = self.selection_start?;
+        let (er, ec) = self.cursor;
+        let cols = (cmp::min(sc, ec), cmp::max(sc, ec));
+        (cols.0 < cols.1).then(|| ((cmp::min(sr, er), cmp::max(sr, er)), cols))
+    }
+
+    fn take_selection_range(&mut self) -> Option<(Pos, Pos)> {
+        let range = self.selection_range();
+        self.cancel_selection();
+        range
+    }
+
+    /// Copy the selection text to the yank buffer. When nothing is selected, this method does nothing.
+    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// ```
+    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World"]);
+    ///
+    /// // Start text selection at 'W'
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.start_selection();
+    ///
+    /// // Select the word "World" and copy the selected text
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.copy();
+    ///
+    /// assert_eq!(textarea.yank_text(), "World");
+    /// assert_eq!(textarea.lines(), ["Hello World"]); // Text does not change
+    /// ```
+    pub fn copy(&mut self) {
+        if let Some(((top, bottom), (left, right))) = self.block_range() {
+            self.cancel_selection();
+            let block = self.lines[top..=bottom]
+                .iter()
+                .map(|line| line.chars().skip(left).take(right - left).collect())
+                .collect();
+            self.set_yank(YankText::Block(block));
+            return;
+        }
+        if let Some((start, end)) = self.take_selection_range() {
+            if start.row == end.row {
+                let yank = self.lines[start.row][start.offset..end.offset].to_string();
+                self.set_yank(yank.into());
+                return;
+            }
+            let mut chunk = vec![self.lines[start.row][start.offset..].to_string()];
+            chunk.extend(self.lines[start.row + 1..end.row].iter().cloned());
+            chunk.push(self.lines[end.row][..end.offset].to_string());
+            self.set_yank(YankText::Chunk(chunk));
+        }
+    }
+
+    /// Cut the selected text and place it in the yank buffer. This method returns whether the text was modified.
+    /// The cursor will move to the start position of the text selection.
+    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// ```
+    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World"]);
+    ///
+    /// // Start text selection at 'W'
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.start_selection();
+    ///
+    /// // Select the word "World" and copy the selected text
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.cut();
+    ///
+    /// assert_eq!(textarea.yank_text(), "World");
+    /// assert_eq!(textarea.lines(), ["Hello "]);
+    /// ```
+    pub fn cut(&mut self) -> bool {
+        self.remove_selection(true)
+    }
+
+    fn remove_selection(&mut self, should_yank: bool) -> bool {
+        let (mark, before) = (self.history.pushed(), self.selection_state());
+        if let Some(((top, bottom), (left, right))) = self.block_range() {
+            self.cancel_selection();
+            let mut block = Vec::with_capacity(bottom - top + 1);
+            let mut deleted = false;
+            for row in top..=bottom {
+                if row > top {
+                    if let Some(text) = &mut self.deleted {
+                        text.push('\n');
+                    }
+                }
+                let removed = self.delete_range((row, left), (row, right));
+                if !removed.is_empty() {
+                    // All deletions are undone at once
+                    if deleted {
+                        self.history.join_last();
+                    }
+                    deleted = true;
+                }
+                block.push(removed);
+            }
+            if should_yank {
+                self.set_yank(YankText::Block(block));
+            }
+            self.set_cursor(self.clamp_pos((top, left)));
+            self.record_selection(mark, before);
+            return deleted;
+        }
+        if let Some((s, e)) = self.take_selection_range() {
+            self.delete_pos_range(s, e, should_yank);
+            self.record_selection(mark, before);
+            return true;
+        }
+        false
+    }
+
+    // Cursor position and the start of the text selection recorded in the edit history
+    fn selection_state(&self) -> SelectionState {
+        (self.cursor, self.selection_start)
+    }
+
+    // Make undo and redo of the edits pushed after the mark restore the cursor and the text selection. `before` is the
+    // state before the edits and the current state is restored by redo.
+    fn record_selection(&mut self, mark: u64, before: SelectionState) {
+        let after = self.selection_state();
+        self.history.set_selection_since(mark, before, after);
+    }
+
+    // Run the deletion and return the text deleted by it
+    fn record_deleted(&mut self, delete: impl FnOnce(&mut Self) -> bool) -> Option<String> {
+        self.deleted = Some(String::new());
+        let deleted = delete(self);
+        let text = self.deleted.take();
+        if deleted {
+            text
+        } else {
+            None
+        }
+    }
+
+    /// Delete the selected text and return it. Unlike [`TextArea::cut`], the deleted text is not yanked. When no text
+    /// is selected, this method does nothing and returns `None`.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World"]);
+    /// assert_eq!(textarea.delete_selection(), None);
+    ///
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert_eq!(textarea.delete_selection().as_deref(), Some("World"));
+    /// assert_eq!(textarea.lines(), ["Hello "]);
+    /// ```
+    pub fn delete_selection(&mut self) -> Option<String> {
+        self.record_deleted(|t| t.remove_selection(false))
+    }
+
+    /// Move the cursor to the position specified by the [`CursorMove`] parameter. For each kind of cursor moves, see
+    /// the document of [`CursorMove`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// ```
+    pub fn move_cursor(&mut self, m: CursorMove) {
+        self.move_cursor_with_shift(m, self.selection_start.is_some());
+    }
+
+    /// Move the cursor to the position at `row` and `col`. Unlike [`CursorMove::Jump`], this method accepts `usize`
+    /// positions. When the position is out of the text, it is clamped to the last line and the end of the line. The
+    /// viewport is scrolled to make the cursor visible on the next render. When text is being selected, the selection
+    /// is extended to the new position. When the position is in hidden lines of a folded range, the cursor moves to
+    /// the first line of the range.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    ///
+    /// textarea.move_to(1, 2);
+    /// assert_eq!(textarea.cursor(), (1, 2));
+    ///
+    /// // Clamped to the end of the last line
+    /// textarea.move_to(10, 10);
+    /// assert_eq!(textarea.cursor(), (1, 3));
+    /// ```
+    pub fn move_to(&mut self, row: usize, col: usize) {
+        let row = cmp::min(row, self.lines.len() - 1);
+        let col = cmp::min(col, self.lines[row].chars().count());
+        self.set_cursor(self.skip_folded_lines((row, col), false));
+    }
+
+    /// Move the cursor to the line at `row` keeping the current column. This is useful for "go to line" features.
+    /// When `row` is out of the text, the cursor moves to the last line. When the line is shorter than the current
+    /// column, the cursor moves to the end of the line.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abcde", "fg", "hijkl"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 4));
+    /// textarea.move_to_line(2);
+    /// assert_eq!(textarea.cursor(), (2, 4));
+    ///
+    /// textarea.move_to_line(1);
+    /// assert_eq!(textarea.cursor(), (1, 2));
+    /// ```
+    pub fn move_to_line(&mut self, row: usize) {
+        self.move_to(row, self.cursor.1);
+    }
+
+    fn move_cursor_with_shift(&mut self, m: CursorMove, shift: bool) {
+        use CursorMove::*;
+
+        // Vertical moves start from the column remembered by the previous vertical move so that the cursor returns
+        // to the column after passing through shorter lines
+        let vertical = matches!(
+            m,
+            Up | Down
+                | Top
+                | Bottom
+                | ParagraphForward
+                | ParagraphBack
+                | TopOfScreen
+                | MiddleOfScreen
+                | BottomOfScreen
+        );
+        let (row, col) = self.cursor;
+        let col = match self.goal_col {
+            Some((goal, cursor, revision))
+                if vertical && cursor == self.cursor && revision == self.revision =>
+            {
+                goal
+            }
+            _ => col,
+        };
+        self.goal_col = vertical.then(|| (col, self.cursor, self.revision));
+
+        if let Some(cursor) = m.next_cursor(
+            (row, col),
+            &self.lines,
+            &self.viewport,
+            self.scrolloff,
+            self.word_chars,
+        ) {
+            if shift {
+                if self.selection_start.is_none() {
+                    self.start_selection();
+                }
+            } else {
+                self.cancel_selection();
+            }
+            // Jumping into a folded range lands on its first line
+            let forward = cursor.0 > self.cursor.0 && !matches!(m, Jump(..));
+            let cursor = self.skip_folded_lines(cursor, forward);
+            let cursor = if shift {
+                cursor
+            } else {
+                self.clamp_past_eol(cursor)
+            };
+            self.set_cursor(cursor);
+            if vertical {
+                self.goal_col = Some((col, self.cursor, self.revision));
+            }
+        }
+    }
+
+    // Move the position at the end of line onto the last character when the cursor cannot be past the end of line
+    fn clamp_past_eol(&self, (row, col): (usize, usize)) -> (usize, usize) {
+        if self.cursor_past_eol {
+            return (row, col);
+        }
+        let line = &self.lines[row];
+        let len = line.chars().count();
+        if col < len {
+            (row, col)
+        } else {
+            (row, prev_grapheme_col(line, len))
+        }
+    }
+
+    // When the cursor is in hidden lines of a folded range, move it to the next visible line when moving forward or
+    // to the summary line of the range otherwise.
+    fn skip_folded_lines(&self, (row, col): (usize, usize), forward: bool) -> (usize, usize) {
+        match self.folds.range(row) {
+            Some((start, end)) if start < row => {
+                let row = if forward && end + 1 < self.lines.len() {
+                    end + 1
+                } else {
+                    start
+                };
+                (row, cmp::min(col, self.lines[row].chars().count()))
+            }
+            _ => (row, col),
+        }
+    }
+
+    /// Undo the last modification. This method returns if the undo modified text contents or not in the textarea.
+    /// The cursor and the text selection before the modification are restored when the modification was done on the
+    /// selection, for example by [`TextArea::indent_lines`] or [`TextArea::cut`]. Otherwise the cursor moves to the
+    /// position of the modification and the selection is canceled.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc def"]);
+    ///
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        let mut shifts = vec![];
+        if let Some((cursor, selection_start)) = self.history.undo(&mut self.lines, &mut shifts) {
+            for shift in shifts {
+                self.shift_rows(shift);
+            }
+            self.set_cursor(cursor);
+            self.set_selection_start(selection_start);
+            self.revision = self.revision.wrapping_add(1);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Take a snapshot of the text contents, the cursor position, and the text selection. The snapshot can be restored
+    /// later with [`TextArea::restore`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// let snapshot = textarea.snapshot();
+    ///
+    /// textarea.insert_str("x");
+    /// textarea.restore(&snapshot, false);
+    /// assert_eq!(textarea.lines(), ["abc"]);
+    /// assert_eq!(textarea.snapshot(), snapshot);
+    /// ```
+    pub fn snapshot(&self) -> TextAreaSnapshot {
+        TextAreaSnapshot {
+            lines: self.lines.clone(),
+            cursor: self.cursor,
+            selection_start: self.selection_start,
+        }
+    }
+
+    /// Restore the text contents, the cursor position, and the text selection from the snapshot taken by
+    /// [`TextArea::snapshot`].
+    ///
+    /// When `undoable` is `true`, restoring the text is recorded in the edit history as one modification so that it can
+    /// be reverted with [`TextArea::undo`]. When `false`, the edit history is cleared since the past modifications no
+    /// longer match the text.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// let snapshot = textarea.snapshot();
+    /// textarea.insert_str("x");
+    ///
+    /// textarea.restore(&snapshot, true);
+    /// assert_eq!(textarea.lines(), ["abc"]);
+    ///
+    /// // Restoring the snapshot can be undone
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["xabc"]);
+    /// ```
+    pub fn restore(&mut self, snapshot: &TextAreaSnapshot, undoable: bool) {
+        let TextAreaSnapshot {
+            lines,
+            cursor,
+            selection_start,
+        } = snapshot;
+        // The snapshot may be deserialized from outside. Ensure the text has at least one line and the positions are
+        // in the text
+        let empty = [String::new()];
+        let lines: &[String] = if lines.is_empty() { &empty } else { lines };
+        let clamp = |(row, col): (usize, usize)| {
+            let row = cmp::min(row, lines.len() - 1);
+            (row, cmp::min(col, lines[row].chars().count()))
+        };
+        let cursor = clamp(*cursor);
+        if undoable {
+            if self.lines != lines {
+                self.replace_lines(lines.to_vec(), cursor);
+            }
+        } else {
+            let modified = self.lines != lines;
+            if modified {
+                if let Some(shift) = RowShift::between(&self.lines, lines) {
+                    self.shift_rows(shift);
+                }
+                self.lines = lines.to_vec();
+                self.revision = self.revision.wrapping_add(1);
+            }
+            self.history.clear(modified);
+        }
+        self.set_cursor(cursor);
+        self.set_selection_start(selection_start.map(clamp));
+    }
+
+    // Replace the whole text as one modification in the edit history
+    fn replace_lines(&mut self, lines: Vec<String>, cursor: (usize, usize)) {
+        let (row, col) = self.cursor;
+        let before = Pos::new(row, col, 0);
+        let edit = EditKind::ReplaceAll(self.lines.clone(), lines);
+        edit.apply(&mut self.lines, &before, &Pos::new(cursor.0, cursor.1, 0));
+        self.set_cursor(cursor);
+        self.push_history(edit, before, 0);
+    }
+
+    /// Get the revision number of the text contents. The number increases on every modification of the text including
+    /// undo and redo, so comparing it with the number saved previously tells if the text was modified since then.
+    /// This is useful for dirty-tracking and autosave. Note that the number does not go back to the previous value
+    /// when the modification is undone.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let saved = textarea.revision();
+    ///
+    /// textarea.insert_str("hello");
+    /// assert_ne!(textarea.revision(), saved);
+    ///
+    /// // Moving the cursor does not modify the text
+    /// let saved = textarea.revision();
+    /// textarea.move_cursor(tui_textarea::CursorMove::Back);
+    /// assert_eq!(textarea.revision(), saved);
+    ///
+    /// // Undo also modifies the text
+    /// textarea.undo();
+    /// assert_ne!(textarea.revision(), saved);
+    /// ```
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Get the revision number of the cursor position. The number increases every time the cursor moves, so comparing
+    /// it with the number saved previously tells if the cursor moved since then, even when it moved back to the same
+    /// position. Unlike [`TextArea::revision`], modifying the text does not change the number unless the cursor moves.
+    /// This is useful for updating a status line only when it is necessary.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// let saved = textarea.cursor_revision();
+    ///
+    /// // Modifying the text after the cursor does not move the cursor
+    /// textarea.delete_next_char();
+    /// assert_eq!(textarea.cursor_revision(), saved);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// assert_ne!(textarea.cursor_revision(), saved);
+    /// ```
+    pub fn cursor_revision(&self) -> u64 {
+        self.cursor_revision
+    }
+
+    /// Get the revision number of the text selection. The number increases every time the selected range changes,
+    /// including starting and cancelling the selection. Moving the cursor without selecting text does not change the
+    /// number.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// let saved = textarea.selection_revision();
+    ///
+    /// // Moving the cursor without selection
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert_eq!(textarea.selection_revision(), saved);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert_ne!(textarea.selection_revision(), saved);
+    /// ```
+    pub fn selection_revision(&self) -> u64 {
+        self.selection_revision
+    }
+
+    // Move the cursor. Moving the cursor while selecting text also changes the selection
+    fn set_cursor(&mut self, cursor: (usize, usize)) {
+        if cursor != self.cursor {
+            self.cursor = cursor;
+            self.cursor_revision = self.cursor_revision.wrapping_add(1);
+            if self.selection_start.is_some() {
+                self.selection_revision = self.selection_revision.wrapping_add(1);
+            }
+        }
+    }
+
+    fn set_selection_start(&mut self, start: Option<(usize, usize)>) {
+        if start != self.selection_start {
+            self.selection_start = start;
+            self.selection_revision = self.selection_revision.wrapping_add(1);
+        }
+    }
+
+    // Save the cursor and the selection before an edit which moves them temporarily
+    fn saved_positions(&self) -> (SelectionState, u64, u64) {
+        let state = (self.cursor, self.selection_start);
+        (state, self.cursor_revision, self.selection_revision)
+    }
+
+    // Put back the positions saved by `saved_positions` so that the temporary moves are not counted as changes
+    fn restore_positions(&mut self, ((cursor, selection_start), c, s): (SelectionState, u64, u64)) {
+        self.cursor = cursor;
+        self.selection_start = selection_start;
+        self.cursor_revision = c;
+        self.selection_revision = s;
+    }
+
+    /// Mark the current text as saved. After this, [`TextArea::is_modified`] returns `false` until the text is
+    /// modified again. Call this method when the text is saved to a file.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_str("hello");
+    /// assert!(textarea.is_modified());
+    ///
+    /// textarea.set_saved_point();
+    /// assert!(!textarea.is_modified());
+    /// ```
+    pub fn set_saved_point(&mut self) {
+        self.history.save();
+    }
+
+    /// Get if the text was modified since the last saved point set by [`TextArea::set_saved_point`]. When no saved
+    /// point is set, the initial text is considered as saved. Undoing or redoing modifications back to the saved point
+    /// makes the text unmodified again. When the saved point is no longer reachable by undo or redo (for example,
+    /// when the text was modified after undoing to before the saved point), the text is considered as modified.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// assert!(!textarea.is_modified());
+    ///
+    /// textarea.insert_char('x');
+    /// assert!(textarea.is_modified());
+    ///
+    /// // Undo back to the saved point
+    /// textarea.undo();
+    /// assert!(!textarea.is_modified());
+    /// ```
+    pub fn is_modified(&self) -> bool {
+        self.history.is_modified()
+    }
+
+    /// Redo the last undo change. This method returns if the redo modified text contents or not in the textarea.
+    /// Like [`TextArea::undo`], the cursor and the text selection after the change are restored.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc def"]);
+    ///
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// textarea.redo();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// ```
+    pub fn redo(&mut self) -> bool {
+        let mut shifts = vec![];
+        if let Some((cursor, selection_start)) = self.history.redo(&mut self.lines, &mut shifts) {
+            for shift in shifts {
+                self.shift_rows(shift);
+            }
+            self.set_cursor(cursor);
+            self.set_selection_start(selection_start);
+            self.revision = self.revision.wrapping_add(1);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Number of digits of line numbers
+    pub(crate) fn line_number_len(&self) -> u8 {
+        cmp::max(num_digits(self.lines.len()), self.line_number_min_width)
+    }
+
+    // Width of the gutter at the left of the text. The gutter consists of the sign column and line numbers.
+    pub(crate) fn gutter_width(&self, lnum_len: u8) -> u16 {
+        let mut width = 0;
+        if self.sign_column {
+            width += 1;
+        }
+        if self.line_number_style.is_some() {
+            width += lnum_len as u16 + 1 + self.line_number_separator.width() as u16;
+        }
+        width
+    }
+
+    pub(crate) fn gutter_spans(&self, row: usize, lnum_len: u8) -> Line<'static> {
+        let mut gutter = Gutter::default();
+
+        if self.sign_column {
+            let sign = self
+                .signs
+                .get(&row)
+                .copied()
+                .or_else(|| self.folds.hidden_rows(row).map(|_| ('▸', self.fold_style)));
+            gutter.sign(sign);
+        }
+
+        if let Some(mut style) = self.line_number_style {
+            if row == self.cursor.0 {
+                style = style.patch(self.current_line_number_style);
+            }
+            gutter.line_number(
+                row,
+                lnum_len,
+                self.line_number_alignment,
+                &self.line_number_separator,
+                style,
+            );
+        }
+
+        gutter.into_spans()
+    }
+
+    // Style filling the whole width of the line at `row`
+    pub(crate) fn line_fill_style(&self, row: usize) -> Option<Style> {
+        if let Some(kind) = self.diff.get(&row) {
+            if let Some(bg) = self.diff_styles[kind.index()].bg {
+                return Some(Style::default().bg(bg));
+            }
+        }
+        self.line_rules.style(&self.lines[row], row)
+    }
+
+    // Return if the line at `row` is decorated with the cursor or the text selection. Spans of other lines can be
+    // reused while the text is not modified.
+    pub(crate) fn has_overlay(&self, row: usize) -> bool {
+        let cursor = self.cursor.0;
+        if self.current_word_style.is_some() {
+            // Occurrences of the word under the cursor may be on any row
+            return true;
+        }
+        // Extra selections are not cached so that changing the selection style is applied to them
+        if self
+            .extra_selections
+            .iter()
+            .any(|&((start, _), (end, _))| start <= row && row <= end)
+        {
+            return true;
+        }
+        match self.selection_start {
+            Some((start, _)) => cmp::min(start, cursor) <= row && row <= cmp::max(start, cursor),
+            None => row == cursor,
+        }
+    }
+
+    // The word under the cursor to highlight its occurrences
+    fn current_word(&self) -> Option<&str> {
+        if self.mask.is_some() {
+            return None;
+        }
+        let line = &self.lines[self.cursor.0];
+        let (start, end) = find_word_at(line, self.cursor.1, self.word_chars)?;
+        Some(&line[start..end])
+    }
+
+    pub(crate) fn line_spans<'b>(&'b self, line: &'b str, row: usize) -> Line<'b> {
+        // The cursor is not styled when the application shows the terminal cursor instead
+        let cursor_style = (!self.hide_cursor_style).then(|| self.cursor_style);
+        let mut hl = LineHighlighter::new(
+            line,
+            cursor_style,
+            self.tab_len,
+            self.mask,
+            self.select_style,
+        );
+
+        if let Some(kind) = self.diff.get(&row) {
+            hl.line_style(self.diff_styles[kind.index()]);
+        }
+
+        if let Some(widths) = self.tab_widths(row) {
+            hl.tab_widths(widths);
+        }
+
+        if self.control_chars == ControlCharHandling::Visualize {
+            hl.control_chars(self.control_char_style);
+        }
+
+        if row == self.cursor.0 {
+            hl.cursor_line(self.cursor.1, self.cursor_line_style);
+        }
+
+        // Masked text is not highlighted not to reveal its structure
+        #[cfg(feature = "syntect")]
+        if let Some(styles) = self
+            .syntax
+            .as_ref()
+            .filter(|_| self.mask.is_none())
+            .and_then(|s| s.line_styles(row, line))
+        {
+            hl.syntax(styles);
+        }
+
+        if !self.highlights.is_empty() {
+            hl.highlights(row, self.highlights.on_row(row));
+        }
+
+        if let Some(style) = self.current_word_style {
+            if let Some(word) = self.current_word() {
+                hl.word_occurrences(word, self.word_chars, style);
+            }
+        }
+
+        #[cfg(feature = "search")]
+        if let Some(matches) = self.search.matches(line) {
+            hl.search(matches, self.search.style);
+        }
+
+        // The match at the cursor is emphasized as the current match
+        #[cfg(feature = "search")]
+        if row == self.cursor.0 {
+            let offset = self.line_offset(row, self.cursor.1);
+            if let Some(m) = self.search.match_at(line, offset) {
+                hl.search(Some(m).into_iter(), self.search.current_style);
+            }
+        }
+
+        if let Some((start, end)) = self.selection_range() {
+            hl.selection(row, start.row, start.offset, end.row, end.offset);
+        } else if let Some(((top, bottom), (left, right))) = self.block_range() {
+            if top <= row && row <= bottom {
+                hl.block_selection(left, right);
+            }
+        }
+        hl.extra_selections(row, self.extra_selections.iter());
+
+        if let Some(style) = self.trailing_whitespace_style {
+            // Whitespaces being typed at the end of the cursor line are not highlighted
+            let typing = row == self.cursor.0 && self.cursor.1 >= line.trim_end().chars().count();
+            if !typing {
+                hl.trailing_whitespace(style);
+            }
+        }
+
+        if let Some(c) = self.list_chars.eol {
+            hl.eol_marker(c, self.list_style);
+        }
+
+        if let Some(hidden) = self.folds.hidden_rows(row) {
+            hl.suffix(format!(" … {} lines", hidden), self.fold_style);
+        }
+
+        if row == self.cursor.0 {
+            if let Some(text) = &self.preedit {
+                let offset = self.line_offset(row, self.cursor.1);
+                hl.preedit(offset, text, self.preedit_style);
+            }
+        }
+
+        if let Some(texts) = self.virtual_texts.get(&row) {
+            for (text, style) in texts {
+                hl.suffix(text.as_str(), *style);
+            }
+        }
+
+        hl.into_spans()
+    }
+
+    /* /// Build a ratatui (or tui-rs) widget to render the current state of the textarea. The widget instance returned
+    /// from this method can be rendered with [`ratatui::terminal::Frame::render_widget`].
+    /// ```no_run
+    /// use ratatui::backend::CrosstermBackend;
+    /// use ratatui::layout::{Constraint, Direction, Layout};
+    /// use ratatui::Terminal;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let layout = Layout::default()
+    ///     .direction(Direction::Vertical)
+    ///     .constraints([Constraint::Min(1)].as_ref());
+    /// let backend = CrosstermBackend::new(std::io::stdout());
+    /// let mut term = Terminal::new(backend).unwrap();
+    ///
+    /// loop {
+    ///     term.draw(|f| {
+    ///         let chunks = layout.split(f.size());
+    ///         let widget = textarea.widget();
+    ///         f.render_widget(widget, chunks[0]);
+    ///     }).unwrap();
+    ///
+    ///     // ...
+    /// }
+    /// ```
+    pub fn widget(&self) -> impl Widget + {
+        Renderer::new(self)
+    } */
+
+    /// Set the style of textarea. By default, textarea is not styled.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_style(style);
+    /// assert_eq!(textarea.style(), style);
+    /// ```
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Get the current style of textarea.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Set the length of tab character. Setting 0 disables tab inputs.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
+    ///
+    /// textarea.set_tab_length(8);
+    /// textarea.input(tab_input.clone());
+    /// assert_eq!(textarea.lines(), ["        "]);
+    ///
+    /// textarea.set_tab_length(2);
+    /// textarea.input(tab_input);
+    /// assert_eq!(textarea.lines(), ["          "]);
+    /// ```
+    pub fn set_tab_length(&mut self, len: u8) {
+        self.render_cache.clear();
+        self.tab_len = len;
+    }
+
+    /// Get how many spaces are used for representing tab character. The default value is 4.
+    pub fn tab_length(&self) -> u8 {
+        self.tab_len
+    }
+
+    /// Enable elastic tabstops. When enabled, hard tabs in adjacent lines are aligned to shared tabstops so that
+    /// tab-separated columns like simple tables are rendered aligned. The cells ending with the n-th tab in the
+    /// consecutive lines which have at least n tabs form a column, and the width of each column is the width of the
+    /// widest cell in it plus one space. The width of a column is at least the tab length. This only changes how hard
+    /// tabs are rendered and the text is not modified. The default value is `false`.
+    ///
+    /// Note that elastic tabstops have some limitations. Widths of tabs are calculated by scanning the adjacent lines
+    /// on every render so rendering text with many consecutive lines containing tabs may be slow. Elastic tabstops are
+    /// not applied to masked text. In lines containing right-to-left text, the widths are calculated in the logical
+    /// order of characters and each hard tab keeps its width after the line is reordered for display.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea = TextArea::from(["name\tage", "alexander\t30"]);
+    /// textarea.set_elastic_tabs(true);
+    /// assert!(textarea.elastic_tabs());
+    ///
+    /// // The second column starts at the same column in both lines
+    /// assert_eq!(textarea.line_display_width(0), 13);
+    /// assert_eq!(textarea.line_display_width(1), 12);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 20, height: 2 };
+    /// let mut b = Buffer::empty(r.clone());
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// assert_eq!(b[(10, 0)].symbol(), "a");
+    /// assert_eq!(b[(10, 1)].symbol(), "3");
+    /// ```
+    pub fn set_elastic_tabs(&mut self, enabled: bool) {
+        self.render_cache.clear();
+        self.elastic_tabs = enabled;
+    }
+
+    /// Get if elastic tabstops are enabled. See [`TextArea::set_elastic_tabs`] for more details.
+    pub fn elastic_tabs(&self) -> bool {
+        self.elastic_tabs
+    }
+
+    /// Set the list of tab stops as display columns. A hard tab advances to the next tab stop in the list. Past the
+    /// last stop, hard tabs are expanded with the tab length as usual. This is useful for aligning columns of tabular
+    /// data at arbitrary positions. The stops are sorted and duplicates are removed. Setting an empty list resets tab
+    /// stops. This only changes how hard tabs are rendered and the text is not modified. Tab stops are not applied
+    /// when elastic tabstops are enabled (see [`TextArea::set_elastic_tabs`]) or when the text is masked.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a\tb\tc\td", "\t\t\t"]);
+    /// textarea.set_tab_length(4);
+    /// textarea.set_tab_stops(vec![10, 3]);
+    /// assert_eq!(textarea.tab_stops(), [3, 10]);
+    ///
+    /// // Tabs advance to the columns 3 and 10, then to the next multiple of the tab length
+    /// assert_eq!(textarea.line_display_width(0), 13);
+    /// assert_eq!(textarea.line_display_width(1), 12);
+    /// ```
+    pub fn set_tab_stops(&mut self, mut stops: Vec<u16>) {
+        self.render_cache.clear();
+        stops.sort_unstable();
+        stops.dedup();
+        self.tab_stops = stops;
+    }
+
+    /// Get the list of tab stops set by [`TextArea::set_tab_stops`].
+    pub fn tab_stops(&self) -> &[u16] {
+        &self.tab_stops
+    }
+
+    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
+    /// spaces. By default, hard tab is disabled.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_hard_tab_indent(true);
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// ```
+    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
+        self.hard_tab_indent = enabled;
+    }
+
+    /// Get if a hard tab is used for indent or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.hard_tab_indent());
+    /// textarea.set_hard_tab_indent(true);
+    /// assert!(textarea.hard_tab_indent());
+    /// ```
+    pub fn hard_tab_indent(&self) -> bool {
+        self.hard_tab_indent
+    }
+
+    /// Set if [`TextArea::delete_char`] deletes one indent level at once. When `true` is set and only whitespaces are
+    /// before the cursor, deleting a character removes spaces back to the previous multiple of the tab length instead
+    /// of one space. By default, smart backspace is disabled.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["      foo"]);
+    /// textarea.set_smart_backspace(true);
+    /// textarea.move_cursor(CursorMove::WordForward);
+    ///
+    /// textarea.delete_char();
+    /// assert_eq!(textarea.lines(), ["    foo"]);
+    /// textarea.delete_char();
+    /// assert_eq!(textarea.lines(), ["foo"]);
+    /// ```
+    pub fn set_smart_backspace(&mut self, enabled: bool) {
+        self.smart_backspace = enabled;
+    }
+
+    /// Get if smart backspace is enabled. See [`TextArea::set_smart_backspace`] for more details.
+    pub fn smart_backspace(&self) -> bool {
+        self.smart_backspace
+    }
+
+    /// Set if [`TextArea::delete_char`] deletes an empty pair of brackets or quotes at once. When `true` is set and the
+    /// cursor is between an opening character and its closing character like `(|)`, deleting the opening character
+    /// also deletes the closing one. The pairs are `()`, `[]`, `{}`, `""`, `''`, and ` `` `. By default, pair
+    /// backspace is disabled.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["f()"]);
+    /// textarea.set_pair_backspace(true);
+    /// textarea.move_cursor(CursorMove::Jump(0, 2));
+    ///
+    /// textarea.delete_char();
+    /// assert_eq!(textarea.lines(), ["f"]);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// ```
+    pub fn set_pair_backspace(&mut self, enabled: bool) {
+        self.pair_backspace = enabled;
+    }
+
+    /// Get if pair backspace is enabled. See [`TextArea::set_pair_backspace`] for more details.
+    pub fn pair_backspace(&self) -> bool {
+        self.pair_backspace
+    }
+
+    /// Set if overwrite mode is enabled. In overwrite mode, typed characters replace the characters under the cursor
+    /// instead of being inserted before them. At the end of line, characters are appended. Inserting a string with
+    /// [`TextArea::insert_str`] or pasting text replaces the same number of characters on the cursor line. When some
+    /// text is selected, the selection is replaced as usual. The default key mappings of [`TextArea::input`] toggle
+    /// the mode with the Insert key. By default, overwrite mode is disabled.
+    ///
+    /// The cursor is rendered in the same style in both modes. Applications can show the mode by changing the cursor
+    /// style with [`TextArea::set_cursor_style`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_overwrite(true);
+    ///
+    /// textarea.insert_char('x');
+    /// textarea.insert_str("yz!");
+    /// assert_eq!(textarea.lines(), ["xyz!"]);
+    ///
+    /// // The overwrite is undone at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["xbc"]);
+    /// ```
+    pub fn set_overwrite(&mut self, enabled: bool) {
+        self.overwrite = enabled;
+    }
+
+    /// Get if overwrite mode is enabled. See [`TextArea::set_overwrite`] for more details.
+    pub fn overwrite(&self) -> bool {
+        self.overwrite
+    }
+
+    /// Set if the cursor can be placed past the end of line. When `false` is set, moving the cursor and clicking in the
+    /// textarea put the cursor on the last character of the line instead of after it, like the normal mode of Vim.
+    /// The cursor is at column 0 on empty lines. Vertical moves still return to the original column on longer lines.
+    /// While selecting text, the cursor can move past the end of line so that the selection can include the last
+    /// character. Editing the text does not move the cursor back, so characters can be typed at the end of line.
+    /// By default, the cursor can be past the end of line.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["abc", ""]);
+    /// textarea.set_cursor_past_eol(false);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// ```
+    pub fn set_cursor_past_eol(&mut self, enabled: bool) {
+        self.cursor_past_eol = enabled;
+        if !enabled && self.selection_start.is_none() {
+            self.set_cursor(self.clamp_past_eol(self.cursor));
+        }
+    }
+
+    /// Get if the cursor can be placed past the end of line. See [`TextArea::set_cursor_past_eol`] for more details.
+    pub fn cursor_past_eol(&self) -> bool {
+        self.cursor_past_eol
+    }
+
+    /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.indent(), "    ");
+    /// textarea.set_tab_length(2);
+    /// assert_eq!(textarea.indent(), "  ");
+    /// textarea.set_hard_tab_indent(true);
+    /// assert_eq!(textarea.indent(), "\t");
+    /// ```
+    pub fn indent(&self) -> &'static str {
+        if self.hard_tab_indent {
+            "\t"
+        } else {
+            spaces(self.tab_len)
+        }
+    }
+
+    /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo.
+    pub fn set_max_histories(&mut self, max: usize) {
+        self.history.set_max_items(max);
+    }
+
+    /// Get how many modifications are remembered for undo/redo. The default value is 50.
+    pub fn max_histories(&self) -> usize {
+        self.history.max_items()
+    }
+
+    /// Set the style of line at cursor. By default, the cursor line is styled with underline. To stop styling the
+    /// cursor line, set the default style.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_cursor_line_style(style);
+    /// assert_eq!(textarea.cursor_line_style(), style);
+    ///
+    /// // Disable cursor line style
+    /// textarea.set_cursor_line_style(Style::default());
+    /// ```
+    pub fn set_cursor_line_style(&mut self, style: Style) {
+        self.cursor_line_style = style;
+    }
+
+    /// Get the style of cursor line. By default it is styled with underline.
+    pub fn cursor_line_style(&self) -> Style {
+        self.cursor_line_style
+    }
+
+    /// Set the style of line number. By setting the style with this method, line numbers are drawn in textarea, meant
+    /// that line numbers are disabled by default. If you want to show line numbers but don't want to style them, set
+    /// the default style.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// // Show line numbers in dark gray background
+    /// let style = Style::default().bg(Color::DarkGray);
+    /// textarea.set_line_number_style(style);
+    /// assert_eq!(textarea.line_number_style(), Some(style));
+    /// ```
+    pub fn set_line_number_style(&mut self, style: Style) {
+        self.line_number_style = Some(style);
+    }
+
+    /// Remove the style of line number which was set by [`TextArea::set_line_number_style`]. After calling this
+    /// method, Line numbers will no longer be shown.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_line_number_style(Style::default().bg(Color::DarkGray));
+    /// textarea.remove_line_number();
+    /// assert_eq!(textarea.line_number_style(), None);
+    /// ```
+    pub fn remove_line_number(&mut self) {
+        self.line_number_style = None;
+    }
+
+    /// Get the style of line number if set.
+    pub fn line_number_style(&self) -> Option<Style> {
+        self.line_number_style
+    }
+
+    /// Set the style of the line number at the cursor line. The style is applied on top of the style set by
+    /// [`TextArea::set_line_number_style`] so that the cursor line stands out in the gutter. By default, the line
+    /// number at the cursor line is not emphasized.
+    /// ```
+    /// use ratatui::style::{Color, Modifier, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_line_number_style(Style::default().fg(Color::DarkGray));
+    /// let style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    /// textarea.set_current_line_number_style(style);
+    /// assert_eq!(textarea.current_line_number_style(), style);
+    /// ```
+    pub fn set_current_line_number_style(&mut self, style: Style) {
+        self.current_line_number_style = style;
+    }
+
+    /// Get the style of the line number at the cursor line. See [`TextArea::set_current_line_number_style`] for more
+    /// details.
+    pub fn current_line_number_style(&self) -> Style {
+        self.current_line_number_style
+    }
+
+    /// Set the separator string rendered between line numbers and text. The default separator is one space.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_line_number_separator("│ ");
+    /// assert_eq!(textarea.line_number_separator(), "│ ");
+    /// ```
+    pub fn set_line_number_separator(&mut self, separator: impl Into<String>) {
+        self.line_number_separator = separator.into();
+    }
+
+    /// Get the separator string rendered between line numbers and text.
+    pub fn line_number_separator(&self) -> &str {
+        &self.line_number_separator
+    }
+
+    /// Set the alignment of line numbers in the gutter. The default alignment is [`Alignment::Right`].
+    /// ```
+    /// use ratatui::layout::Alignment;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_line_number_alignment(Alignment::Left);
+    /// assert_eq!(textarea.line_number_alignment(), Alignment::Left);
+    /// ```
+    pub fn set_line_number_alignment(&mut self, alignment: Alignment) {
+        self.line_number_alignment = alignment;
+    }
+
+    /// Get the alignment of line numbers in the gutter.
+    pub fn line_number_alignment(&self) -> Alignment {
+        self.line_number_alignment
+    }
+
+    /// Set the minimum width of line numbers in digits. Reserving enough digits prevents the text from shifting when
+    /// the number of lines crosses a power of ten. The default value is 0, meaning that the width of line numbers
+    /// depends on the number of lines.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_line_number_style(Style::default());
+    ///
+    /// // Line numbers are rendered with 3 digits: "   1 abc"
+    /// textarea.set_line_number_min_width(3);
+    /// assert_eq!(textarea.line_number_min_width(), 3);
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert_eq!(b[(3, 0)].symbol(), "1");
+    /// # assert_eq!(b[(5, 0)].symbol(), "a");
+    /// ```
+    pub fn set_line_number_min_width(&mut self, width: u8) {
+        self.line_number_min_width = width;
+    }
+
+    /// Get the minimum width of line numbers in digits. See [`TextArea::set_line_number_min_width`] for more details.
+    pub fn line_number_min_width(&self) -> u8 {
+        self.line_number_min_width
+    }
+
+    /// Set if the sign column is shown or not. The sign column is a one-cell column rendered at the left of line
+    /// numbers. It is useful for showing markers such as breakpoints, diagnostics, or git changes on each line. Signs
+    /// are set with [`TextArea::set_line_sign`]. The sign column is hidden by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_sign_column(true);
+    /// assert!(textarea.sign_column());
+    /// ```
+    pub fn set_sign_column(&mut self, enabled: bool) {
+        self.sign_column = enabled;
+    }
+
+    /// Get if the sign column is shown or not. See [`TextArea::set_sign_column`].
+    pub fn sign_column(&self) -> bool {
+        self.sign_column
+    }
+
+    /// Set a sign character and its style shown in the sign column at the line of `row`. Passing `None` removes the
+    /// sign of the line. The sign should be a single-width character since the sign column is one cell wide. Signs
+    /// are only rendered when the sign column is enabled by [`TextArea::set_sign_column`].
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    ///
+    /// // Show a red marker at the second line
+    /// textarea.set_sign_column(true);
+    /// textarea.set_line_sign(1, Some(('●', Style::default().fg(Color::Red))));
+    /// assert_eq!(textarea.line_sign(1), Some(('●', Style::default().fg(Color::Red))));
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert_eq!(b[(0, 1)].symbol(), "●");
+    ///
+    /// // Remove the marker
+    /// textarea.set_line_sign(1, None);
+    /// assert_eq!(textarea.line_sign(1), None);
+    /// ```
+    pub fn set_line_sign(&mut self, row: usize, sign: Option<(char, Style)>) {
+        if let Some(sign) = sign {
+            self.signs.insert(row, sign);
+        } else {
+            self.signs.remove(&row);
+        }
+    }
+
+    /// Get the sign character and its style at the line of `row` set by [`TextArea::set_line_sign`].
+    pub fn line_sign(&self, row: usize) -> Option<(char, Style)> {
+        self.signs.get(&row).copied()
+    }
+
+    /// Remove all signs set by [`TextArea::set_line_sign`].
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    ///
+    /// textarea.set_line_sign(0, Some(('+', Style::default())));
+    /// textarea.set_line_sign(1, Some(('-', Style::default())));
+    /// textarea.clear_line_signs();
+    /// assert_eq!(textarea.line_sign(0), None);
+    /// assert_eq!(textarea.line_sign(1), None);
+    /// ```
+    pub fn clear_line_signs(&mut self) {
+        self.signs.clear();
+    }
+
+    /// Set the placeholder text. The text is set in the textarea when no text is input. Setting a non-empty string `""`
+    /// enables the placeholder. The default value is an empty string so the placeholder is disabled by default.
+    /// To customize the text style, see [`TextArea::set_placeholder_style`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_text(), "");
+    /// assert!(textarea.placeholder_style().is_none());
+    ///
+    /// textarea.set_placeholder_text("Hello");
+    /// assert_eq!(textarea.placeholder_text(), "Hello");
+    /// assert!(textarea.placeholder_style().is_some());
+    /// ```
+    pub fn set_placeholder_text(&mut self, placeholder: impl Into<String>) {
+        self.placeholder = placeholder.into();
+    }
+
+    /// Set the style of the placeholder text. The default style is a dark gray text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_style(), None); // When the placeholder is disabled
+    ///
+    /// textarea.set_placeholder_text("Enter your message"); // Enable placeholder by setting non-empty text
+    ///
+    /// let style = Style::default().bg(Color::Blue);
+    /// textarea.set_placeholder_style(style);
+    /// assert_eq!(textarea.placeholder_style(), Some(style));
+    /// ```
+    pub fn set_placeholder_style(&mut self, style: Style) {
+        self.placeholder_style = style;
+    }
+
+    /// Get the placeholder text. An empty string means the placeholder is disabled. The default value is an empty string.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_text(), "");
+    /// ```
+    pub fn placeholder_text(&self) -> &'_ str {
+        self.placeholder.as_str()
+    }
+
+    /// Get the placeholder style. When the placeholder text is empty, it returns `None` since the placeholder is disabled.
+    /// The default style is a dark gray text.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_style(), None);
+    ///
+    /// textarea.set_placeholder_text("hello");
+    /// assert!(textarea.placeholder_style().is_some());
+    /// ```
+    pub fn placeholder_style(&self) -> Option<Style> {
+        if self.placeholder.is_empty() {
+            None
+        } else {
+            Some(self.placeholder_style)
+        }
     }
 
-    /// Delete a newline from **head** of current cursor line. This method returns if a newline was deleted or not in
-    /// the textarea. When some text is selected, it is deleted instead.
+    /// Set the style of rows past the end of the text. When the text has fewer lines than the height of the textarea,
+    /// the empty rows below the last line are filled with this style. It is useful for distinguishing blank lines in
+    /// the text from rows past the end.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// let mut textarea = TextArea::default();
     ///
-    /// textarea.move_cursor(CursorMove::Down);
-    /// textarea.delete_newline();
-    /// assert_eq!(textarea.lines(), ["helloworld"]);
+    /// let style = Style::default().fg(Color::Blue);
+    /// textarea.set_empty_line_style(style);
+    /// assert_eq!(textarea.empty_line_style(), style);
     /// ```
-    pub fn delete_newline(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
-
-        let (row, _) = self.cursor;
-        if row == 0 {
-            return false;
-        }
-
-        let line = self.lines.remove(row);
-        let prev_line = &mut self.lines[row - 1];
-        let prev_line_end = prev_line.len();
+    pub fn set_empty_line_style(&mut self, style: Style) {
+        self.empty_line_style = style;
+    }
 
-        self.cursor = (row - 1, prev_line.chars().count());
-        prev_line.push_str(&line);
-        self.push_history(EditKind::DeleteNewline, Pos::new(row, 0, 0), prev_line_end);
-        true
+    /// Get the style of rows past the end of the text. See [`TextArea::set_empty_line_style`].
+    pub fn empty_line_style(&self) -> Style {
+        self.empty_line_style
     }
 
-    /// Delete one character before cursor. When the cursor is at head of line, the newline before the cursor will be
-    /// removed. This method returns if some text was deleted or not in the textarea. When some text is selected, it is
-    /// deleted instead.
+    /// Set the marker character rendered at the first column of rows past the end of the text, like `~` in Vim. The
+    /// marker is styled with [`TextArea::empty_line_style`]. Passing `None` disables the marker. No marker is rendered
+    /// by default.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
+    /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::from(["abc"]);
     ///
-    /// textarea.move_cursor(CursorMove::Forward);
-    /// textarea.delete_char();
-    /// assert_eq!(textarea.lines(), ["bc"]);
+    /// textarea.set_end_of_buffer_marker(Some('~'));
+    /// assert_eq!(textarea.end_of_buffer_marker(), Some('~'));
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert_eq!(b[(0, 1)].symbol(), "~");
     /// ```
-    pub fn delete_char(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
-
-        let (row, col) = self.cursor;
-        if col == 0 {
-            return self.delete_newline();
-        }
+    pub fn set_end_of_buffer_marker(&mut self, marker: Option<char>) {
+        self.end_of_buffer_marker = marker;
+    }
 
-        let line = &mut self.lines[row];
-        if let Some((offset, c)) = line.char_indices().nth(col - 1) {
-            line.remove(offset);
-            self.cursor.1 -= 1;
-            self.push_history(
-                EditKind::DeleteChar(c),
-                Pos::new(row, col, offset + c.len_utf8()),
-                offset,
-            );
-            true
-        } else {
-            false
-        }
+    /// Get the marker character rendered at rows past the end of the text. See
+    /// [`TextArea::set_end_of_buffer_marker`].
+    pub fn end_of_buffer_marker(&self) -> Option<char> {
+        self.end_of_buffer_marker
     }
 
-    /// Delete one character next to cursor. When the cursor is at end of line, the newline next to the cursor will be
-    /// removed. This method returns if a character was deleted or not in the textarea.
+    /// Set markers visualizing invisible parts of lines such as the end of line and text hidden by horizontal
+    /// scroll. See [`ListChars`] for each marker. Markers are styled with [`TextArea::list_style`]. No marker is
+    /// rendered by default.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
+    /// use tui_textarea::{ListChars, TextArea};
     ///
     /// let mut textarea = TextArea::from(["abc"]);
     ///
-    /// textarea.move_cursor(CursorMove::Forward);
-    /// textarea.delete_next_char();
-    /// assert_eq!(textarea.lines(), ["ac"]);
+    /// let list = ListChars {
+    ///     eol: Some('$'),
+    ///     ..Default::default()
+    /// };
+    /// textarea.set_list_chars(list);
+    /// assert_eq!(textarea.list_chars(), list);
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert_eq!(b[(3, 0)].symbol(), "$");
     /// ```
-    pub fn delete_next_char(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
-
-        let before = self.cursor;
-        self.move_cursor_with_shift(CursorMove::Forward, false);
-        if before == self.cursor {
-            return false; // Cursor didn't move, meant no character at next of cursor.
-        }
+    pub fn set_list_chars(&mut self, list: ListChars) {
+        self.render_cache.clear();
+        self.list_chars = list;
+    }
 
-        self.delete_char()
+    /// Get markers visualizing invisible parts of lines. See [`TextArea::set_list_chars`].
+    pub fn list_chars(&self) -> ListChars {
+        self.list_chars
     }
 
-    /// Delete string from cursor to end of the line. When the cursor is at end of line, the newline next to the cursor
-    /// is removed. This method returns if some text was deleted or not in the textarea.
+    /// Set the style of markers set by [`TextArea::set_list_chars`]. The default style is dark gray text.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
-    ///
-    /// let mut textarea = TextArea::from(["abcde"]);
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
     ///
-    /// // Move to 'c'
-    /// textarea.move_cursor(CursorMove::Forward);
-    /// textarea.move_cursor(CursorMove::Forward);
+    /// let mut textarea = TextArea::default();
     ///
-    /// textarea.delete_line_by_end();
-    /// assert_eq!(textarea.lines(), ["ab"]);
+    /// let style = Style::default().fg(Color::Blue);
+    /// textarea.set_list_style(style);
+    /// assert_eq!(textarea.list_style(), style);
     /// ```
-    pub fn delete_line_by_end(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
-        if self.delete_piece(self.cursor.1, usize::MAX) {
-            return true;
-        }
-        self.delete_next_char() // At the end of the line. Try to delete next line
+    pub fn set_list_style(&mut self, style: Style) {
+        self.render_cache.clear();
+        self.list_style = style;
     }
 
-    /// Delete string from cursor to head of the line. When the cursor is at head of line, the newline before the cursor
-    /// will be removed. This method returns if some text was deleted or not in the textarea.
-    /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
-    ///
-    /// let mut textarea = TextArea::from(["abcde"]);
-    ///
-    /// // Move to 'c'
-    /// textarea.move_cursor(CursorMove::Forward);
-    /// textarea.move_cursor(CursorMove::Forward);
-    ///
-    /// textarea.delete_line_by_head();
-    /// assert_eq!(textarea.lines(), ["cde"]);
-    /// ```
-    pub fn delete_line_by_head(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
-        if self.delete_piece(0, self.cursor.1) {
-            return true;
-        }
-        self.delete_newline()
+    /// Get the style of markers set by [`TextArea::set_list_chars`].
+    pub fn list_style(&self) -> Style {
+        self.list_style
     }
 
-    /// Delete a word before cursor. Word boundary appears at spaces, punctuations, and others. For example `fn foo(a)`
-    /// consists of words `fn`, `foo`, `(`, `a`, `)`. When the cursor is at head of line, the newline before the cursor
-    /// will be removed.
-    ///
-    /// This method returns if some text was deleted or not in the textarea.
-    ///
+    /// Set the display columns where vertical rulers are rendered to mark line length limits, like `colorcolumn` of
+    /// Vim. Columns are 0-based so the ruler at column 80 is rendered at the cell right after 80 columns of text. Rulers
+    /// are rendered on all lines including empty ones and scroll horizontally with the text. They are styled with
+    /// [`TextArea::ruler_style`] and the styles of the text such as the cursor and the selection are rendered on top
+    /// of them. No ruler is rendered by default.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
-    ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
+    /// use ratatui::style::Color;
+    /// use tui_textarea::TextArea;
     ///
-    /// textarea.move_cursor(CursorMove::End);
+    /// let mut textarea = TextArea::from(["abc", ""]);
     ///
-    /// textarea.delete_word();
-    /// assert_eq!(textarea.lines(), ["aaa bbb "]);
-    /// textarea.delete_word();
-    /// assert_eq!(textarea.lines(), ["aaa "]);
+    /// textarea.set_ruler_columns(vec![4, 8]);
+    /// assert_eq!(textarea.ruler_columns(), &[4, 8]);
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert_eq!(b[(4, 1)].bg, Color::DarkGray);
+    /// # assert_eq!(b[(8, 0)].bg, Color::DarkGray);
+    /// # assert_eq!(b[(5, 0)].bg, Color::Reset);
     /// ```
-    pub fn delete_word(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
-        let (r, c) = self.cursor;
-        if let Some(col) = find_word_start_backward(&self.lines[r], c) {
-            self.delete_piece(col, c - col)
-        } else if c > 0 {
-            self.delete_piece(0, c)
-        } else {
-            self.delete_newline()
-        }
+    pub fn set_ruler_columns(&mut self, columns: Vec<u16>) {
+        self.ruler_columns = columns;
     }
 
-    /// Delete a word next to cursor. Word boundary appears at spaces, punctuations, and others. For example `fn foo(a)`
-    /// consists of words `fn`, `foo`, `(`, `a`, `)`. When the cursor is at end of line, the newline next to the cursor
-    /// will be removed.
-    ///
-    /// This method returns if some text was deleted or not in the textarea.
-    ///
+    /// Get the display columns of the rulers set by [`TextArea::set_ruler_columns`].
+    pub fn ruler_columns(&self) -> &[u16] {
+        &self.ruler_columns
+    }
+
+    /// Set the style of the rulers set by [`TextArea::set_ruler_columns`]. The default style is dark gray background.
     /// ```
+    /// use ratatui::style::{Color, Style};
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    /// let mut textarea = TextArea::default();
     ///
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.lines(), [" bbb ccc"]);
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.lines(), [" ccc"]);
+    /// let style = Style::default().bg(Color::Blue);
+    /// textarea.set_ruler_style(style);
+    /// assert_eq!(textarea.ruler_style(), style);
     /// ```
-    pub fn delete_next_word(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
-        let (r, c) = self.cursor;
-        let line = &self.lines[r];
-        if let Some(col) = find_word_end_forward(line, c) {
-            self.delete_piece(c, col - c)
-        } else {
-            let end_col = line.chars().count();
-            if c < end_col {
-                self.delete_piece(c, end_col - c)
-            } else if r + 1 < self.lines.len() {
-                self.cursor = (r + 1, 0);
-                self.delete_newline()
-            } else {
-                false
-            }
-        }
+    pub fn set_ruler_style(&mut self, style: Style) {
+        self.ruler_style = style;
     }
 
-    /// Paste a string previously deleted by [`TextArea::delete_line_by_head`], [`TextArea::delete_line_by_end`],
-    /// [`TextArea::delete_word`], [`TextArea::delete_next_word`]. This method returns if some text was inserted or not
-    /// in the textarea.
+    /// Get the style of the rulers set by [`TextArea::set_ruler_columns`].
+    pub fn ruler_style(&self) -> Style {
+        self.ruler_style
+    }
+
+    /// Set the style to highlight whitespaces at the end of lines. By setting the style with this method, trailing
+    /// whitespaces are highlighted, meant that they are not highlighted by default. While the cursor is in the trailing
+    /// whitespaces or at the end of line, the whitespaces in the cursor line are not highlighted so that spaces being
+    /// typed are not flashed.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::{CursorMove, TextArea};
     ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    /// let mut textarea = TextArea::from(["abc  "]);
     ///
-    /// textarea.delete_next_word();
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_trailing_whitespace_style(style);
+    /// assert_eq!(textarea.trailing_whitespace_style(), Some(style));
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert_eq!(b[(3, 0)].bg, Color::Red);
+    ///
+    /// // Trailing whitespaces are not highlighted while typing at the end of line
     /// textarea.move_cursor(CursorMove::End);
-    /// textarea.paste();
-    /// assert_eq!(textarea.lines(), [" bbb cccaaa"]);
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// # assert_eq!(b[(3, 0)].bg, Color::Reset);
     /// ```
-    pub fn paste(&mut self) -> bool {
-        self.delete_selection(false);
-        match self.yank.clone() {
-            YankText::Piece(s) => self.insert_piece(s),
-            YankText::Chunk(c) => self.insert_chunk(c),
-        }
+    pub fn set_trailing_whitespace_style(&mut self, style: Style) {
+        self.render_cache.clear();
+        self.trailing_whitespace_style = Some(style);
     }
 
-    /// Start text selection at the cursor position. If text selection is already ongoing, the start position is reset.
+    /// Remove the style set by [`TextArea::set_trailing_whitespace_style`]. After calling this method, trailing
+    /// whitespaces are no longer highlighted.
+    pub fn remove_trailing_whitespace_style(&mut self) {
+        self.render_cache.clear();
+        self.trailing_whitespace_style = None;
+    }
+
+    /// Get the style to highlight whitespaces at the end of lines if set.
+    pub fn trailing_whitespace_style(&self) -> Option<Style> {
+        self.trailing_whitespace_style
+    }
+
+    /// Set the style to highlight all occurrences of the word under the cursor. `None` disables the highlight, which
+    /// is the default. Only whole words are highlighted. For example, `foo` in `foobar` is not highlighted for the word
+    /// `foo`. Nothing is highlighted when the cursor is on a whitespace or a punctuation. Words are split in the same
+    /// way as word motions like [`CursorMove::WordForward`].
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use ratatui::style::{Modifier, Style};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    /// let mut textarea = TextArea::default();
     ///
-    /// textarea.start_selection();
-    /// textarea.move_cursor(CursorMove::WordForward);
-    /// textarea.copy();
-    /// assert_eq!(textarea.yank_text(), "aaa ");
+    /// let style = Style::default().add_modifier(Modifier::UNDERLINED);
+    /// textarea.set_highlight_current_word(Some(style));
+    /// assert_eq!(textarea.highlight_current_word(), Some(style));
     /// ```
-    pub fn start_selection(&mut self) {
-        self.selection_start = Some(self.cursor);
+    pub fn set_highlight_current_word(&mut self, style: Option<Style>) {
+        self.render_cache.clear();
+        self.current_word_style = style;
     }
 
-    /// Stop the current text selection. This method does nothing if text selection is not ongoing.
+    /// Get the style to highlight occurrences of the word under the cursor if set. See
+    /// [`TextArea::set_highlight_current_word`] for more details.
+    pub fn highlight_current_word(&self) -> Option<Style> {
+        self.current_word_style
+    }
+
+    /// Set how control characters in the text are handled. See [`ControlCharHandling`] for each policy. By default,
+    /// control characters are kept and rendered as they are.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use tui_textarea::{ControlCharHandling, TextArea};
     ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
-    ///
-    /// textarea.start_selection();
-    /// textarea.move_cursor(CursorMove::WordForward);
+    /// let mut textarea = TextArea::default();
     ///
-    /// // Cancel the ongoing text selection
-    /// textarea.cancel_selection();
+    /// textarea.set_control_char_handling(ControlCharHandling::Visualize);
+    /// assert_eq!(textarea.control_char_handling(), ControlCharHandling::Visualize);
     ///
-    /// // As the result, this `copy` call does nothing
-    /// textarea.copy();
-    /// assert_eq!(textarea.yank_text(), "");
+    /// textarea.insert_str("\0");
+    /// assert_eq!(textarea.cursor_display_col(), 2); // Rendered as `^@`
     /// ```
-    pub fn cancel_selection(&mut self) {
-        self.selection_start = None;
+    pub fn set_control_char_handling(&mut self, handling: ControlCharHandling) {
+        self.render_cache.clear();
+        self.control_chars = handling;
     }
 
-    /// Select the entire text. Cursor moves to the end of the text buffer. When text selection is already ongoing,
-    /// it is canceled.
+    /// Get how control characters in the text are handled. See [`TextArea::set_control_char_handling`].
+    pub fn control_char_handling(&self) -> ControlCharHandling {
+        self.control_chars
+    }
+
+    /// Set the style of control characters rendered with [`ControlCharHandling::Visualize`]. The default style is blue
+    /// text.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["aaa", "bbb", "ccc"]);
+    /// let mut textarea = TextArea::default();
     ///
-    /// textarea.select_all();
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_control_char_style(style);
+    /// assert_eq!(textarea.control_char_style(), style);
+    /// ```
+    pub fn set_control_char_style(&mut self, style: Style) {
+        self.render_cache.clear();
+        self.control_char_style = style;
+    }
+
+    /// Get the style of control characters rendered with [`ControlCharHandling::Visualize`].
+    pub fn control_char_style(&self) -> Style {
+        self.control_char_style
+    }
+
+    /// Specify a character masking the text. All characters in the textarea will be replaced by this character.
+    /// This API is useful for making a kind of credentials form such as a password input.
     ///
-    /// // Cut the entire text;
-    /// textarea.cut();
+    /// Masking only affects rendering. Each character including wide characters and tabs is rendered as one mask
+    /// character so that the cursor moves by one mask character per one actual character. [`TextArea::lines`],
+    /// search, selection, and yanking still work on the actual text.
+    /// ```
+    /// use tui_textarea::TextArea;
     ///
-    /// assert_eq!(textarea.lines(), [""]); // Buffer is now empty
-    /// assert_eq!(textarea.yank_text(), "aaa\nbbb\nccc");
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// textarea.set_mask_char('●');
+    /// assert_eq!(textarea.mask_char(), Some('●'));
     /// ```
-    pub fn select_all(&mut self) {
-        self.move_cursor(CursorMove::Jump(u16::MAX, u16::MAX));
-        self.selection_start = Some((0, 0));
+    pub fn set_mask_char(&mut self, mask: char) {
+        self.render_cache.clear();
+        self.mask = Some(mask);
     }
 
-    /// Return if text selection is ongoing or not.
+    /// Clear the masking character previously set by [`TextArea::set_mask_char`].
     /// ```
-    /// use tui_textarea::{TextArea};
+    /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// assert!(!textarea.is_selecting());
-    /// textarea.start_selection();
-    /// assert!(textarea.is_selecting());
-    /// textarea.cancel_selection();
-    /// assert!(!textarea.is_selecting());
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// textarea.clear_mask_char();
+    /// assert_eq!(textarea.mask_char(), None);
     /// ```
-    pub fn is_selecting(&self) -> bool {
-        self.selection_start.is_some()
-    }
-
-    fn line_offset(&self, row: usize, col: usize) -> usize {
-        let line = self
-            .lines
-            .get(row)
-            .unwrap_or(&self.lines[self.lines.len() - 1]);
-        line.char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len())
+    pub fn clear_mask_char(&mut self) {
+        self.render_cache.clear();
+        self.mask = None;
     }
 
-    /// Set the style used for text selection. The default style is light blue.
+    /// Get the character to mask text. When no character is set, `None` is returned.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use ratatui::style::{Style, Color};
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// // Change the selection color from the default to Red
-    /// textarea.set_selection_style(Style::default().bg(Color::Red));
-    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::Red));
+    /// assert_eq!(textarea.mask_char(), None);
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
     /// ```
-    pub fn set_selection_style(&mut self, style: Style) {
-        self.select_style = style;
+    pub fn mask_char(&self) -> Option<char> {
+        self.mask
     }
 
-    /// Get the style used for text selection.
+    /// Set if the textarea is in single-line mode or not. Single-line mode is useful for a one-line input form like
+    /// `<input>` in HTML. By default, single-line mode is disabled.
+    ///
+    /// In single-line mode,
+    ///
+    /// - keys to insert a newline such as Enter don't modify the text and [`TextArea::input`] returns
+    ///   [`InputResult::Submit`] instead
+    /// - [`TextArea::insert_newline`] does nothing
+    /// - newlines in text inserted by [`TextArea::insert_str`] and [`TextArea::paste`] are replaced with spaces
+    /// - the viewport never scrolls vertically
+    ///
+    /// When the textarea already contains multiple lines, they are joined with spaces into one line. Since the
+    /// positions in the edit history are no longer valid in the case, the edit history is cleared.
     /// ```
-    /// use tui_textarea::TextArea;
-    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::{TextArea, Input, InputResult, Key};
     ///
     /// let mut textarea = TextArea::default();
+    /// textarea.set_single_line(true);
+    /// assert!(textarea.single_line());
     ///
-    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::LightBlue));
+    /// let enter = Input { key: Key::Enter, ctrl: false, alt: false, shift: false };
+    /// assert_eq!(textarea.input(enter), InputResult::Submit);
+    /// assert_eq!(textarea.lines(), [""]);
+    ///
+    /// textarea.insert_str("hello\nworld");
+    /// assert_eq!(textarea.lines(), ["hello world"]);
     /// ```
-    pub fn selection_style(&mut self) -> Style {
-        self.select_style
-    }
-
-    fn selection_range(&self) -> Option<(Pos, Pos)> {
-        let (sr, sc) = self.selection_start?;
-        let (er, ec) = self.cursor;
-        let (so, eo) = (self.line_offset(sr, sc), self.line_offset(er, ec));
-        let s = Pos::new(sr, sc, so);
-        let e = Pos::new(er, ec, eo);
-        match (sr, so).cmp(&(er, eo)) {
-            Ordering::Less => Some((s, e)),
-            Ordering::Equal => None,
-            Ordering::Greater => Some((e, s)),
+    pub fn set_single_line(&mut self, enabled: bool) {
+        self.single_line = enabled;
+        if !enabled || self.lines.len() == 1 {
+            return;
         }
-    }
 
-    fn take_selection_range(&mut self) -> Option<(Pos, Pos)> {
-        let range = self.selection_range();
+        let (row, col) = self.cursor;
+        let col = self.lines[..row]
+            .iter()
+            .map(|l| l.chars().count() + 1)
+            .sum::<usize>()
+            + col;
+        let lines = vec![self.lines.join(" ")];
+        if let Some(shift) = RowShift::between(&self.lines, &lines) {
+            self.shift_rows(shift);
+        }
+        self.lines = lines;
+        self.revision = self.revision.wrapping_add(1);
+        self.set_cursor((0, col));
         self.cancel_selection();
-        range
+        self.history.clear(true);
     }
 
-    /// Copy the selection text to the yank buffer. When nothing is selected, this method does nothing.
-    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// Get if the textarea is in single-line mode or not. See [`TextArea::set_single_line`] for more details.
     /// ```
-    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
-    ///
-    /// let mut textarea = TextArea::from(["Hello World"]);
-    ///
-    /// // Start text selection at 'W'
-    /// textarea.move_cursor(CursorMove::WordForward);
-    /// textarea.start_selection();
+    /// use tui_textarea::TextArea;
     ///
-    /// // Select the word "World" and copy the selected text
-    /// textarea.move_cursor(CursorMove::End);
-    /// textarea.copy();
+    /// let mut textarea = TextArea::default();
     ///
-    /// assert_eq!(textarea.yank_text(), "World");
-    /// assert_eq!(textarea.lines(), ["Hello World"]); // Text does not change
+    /// assert!(!textarea.single_line());
+    /// textarea.set_single_line(true);
+    /// assert!(textarea.single_line());
     /// ```
-    pub fn copy(&mut self) {
-        if let Some((start, end)) = self.take_selection_range() {
-            if start.row == end.row {
-                self.yank = self.lines[start.row][start.offset..end.offset]
-                    .to_string()
-                    .into();
-                return;
-            }
-            let mut chunk = vec![self.lines[start.row][start.offset..].to_string()];
-            chunk.extend(self.lines[start.row + 1..end.row].iter().cloned());
-            chunk.push(self.lines[end.row][..end.offset].to_string());
-            self.yank = YankText::Chunk(chunk);
-        }
+    pub fn single_line(&self) -> bool {
+        self.single_line
     }
 
-    /// Cut the selected text and place it in the yank buffer. This method returns whether the text was modified.
-    /// The cursor will move to the start position of the text selection.
-    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// Set the style of cursor. By default, a cursor is rendered in the reversed color. Setting the same style as
+    /// cursor line hides a cursor. When the cursor is at the end of a line, it is rendered as a space with this style.
+    /// When the cursor is on a wide character, the style covers all cells of the character.
     /// ```
-    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
-    ///
-    /// let mut textarea = TextArea::from(["Hello World"]);
-    ///
-    /// // Start text selection at 'W'
-    /// textarea.move_cursor(CursorMove::WordForward);
-    /// textarea.start_selection();
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
     ///
-    /// // Select the word "World" and copy the selected text
-    /// textarea.move_cursor(CursorMove::End);
-    /// textarea.cut();
+    /// let mut textarea = TextArea::default();
     ///
-    /// assert_eq!(textarea.yank_text(), "World");
-    /// assert_eq!(textarea.lines(), ["Hello "]);
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_cursor_style(style);
+    /// assert_eq!(textarea.cursor_style(), style);
     /// ```
-    pub fn cut(&mut self) -> bool {
-        self.delete_selection(true)
+    pub fn set_cursor_style(&mut self, style: Style) {
+        self.cursor_style = style;
     }
 
-    fn delete_selection(&mut self, should_yank: bool) -> bool {
-        if let Some((s, e)) = self.take_selection_range() {
-            self.delete_range(s, e, should_yank);
-            return true;
-        }
-        false
+    /// Get the style of cursor.
+    pub fn cursor_style(&self) -> Style {
+        self.cursor_style
     }
 
-    /// Move the cursor to the position specified by the [`CursorMove`] parameter. For each kind of cursor moves, see
-    /// the document of [`CursorMove`].
+    /// Set if the cell at the cursor is rendered without the cursor style. This is useful when the application shows
+    /// the terminal cursor at the position returned by [`TextArea::cursor_screen_position`] instead. The terminal
+    /// cursor works with screen readers and with the cursor shape configured in the terminal.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
-    ///
-    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// use ratatui::{backend::TestBackend, Terminal};
+    /// use tui_textarea::TextArea;
     ///
-    /// textarea.move_cursor(CursorMove::Forward);
-    /// assert_eq!(textarea.cursor(), (0, 1));
-    /// textarea.move_cursor(CursorMove::Down);
-    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// let mut term = Terminal::new(TestBackend::new(10, 3)).unwrap();
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_hide_cursor_style(true);
+    /// assert!(textarea.hide_cursor_style());
+    ///
+    /// term.draw(|f| {
+    ///     f.render_widget(&mut textarea, f.size());
+    ///     // Show the terminal cursor at the position of the cursor in the textarea
+    ///     if let Some(pos) = textarea.cursor_screen_position() {
+    ///         f.set_cursor_position(pos);
+    ///     }
+    /// })
+    /// .unwrap();
     /// ```
-    pub fn move_cursor(&mut self, m: CursorMove) {
-        self.move_cursor_with_shift(m, self.selection_start.is_some());
+    pub fn set_hide_cursor_style(&mut self, hide: bool) {
+        self.hide_cursor_style = hide;
     }
 
-    fn move_cursor_with_shift(&mut self, m: CursorMove, shift: bool) {
-        if let Some(cursor) = m.next_cursor(self.cursor, &self.lines, &self.viewport) {
-            if shift {
-                if self.selection_start.is_none() {
-                    self.start_selection();
-                }
-            } else {
-                self.cancel_selection();
-            }
-            self.cursor = cursor;
-        }
+    /// Get if the cell at the cursor is rendered without the cursor style. See [`TextArea::set_hide_cursor_style`]
+    /// for more details.
+    pub fn hide_cursor_style(&self) -> bool {
+        self.hide_cursor_style
     }
 
-    /// Undo the last modification. This method returns if the undo modified text contents or not in the textarea.
+    /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
+    /// never be empty because an empty text means a slice containing one empty line. Lines are separated by newlines
+    /// and no newline follows the last line, so a newline at the end of the text is represented by an empty line at
+    /// the end of the slice. See [`TextArea::has_trailing_newline`].
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["abc def"]);
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.lines(), [""]);
     ///
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.lines(), [" def"]);
-    /// textarea.undo();
-    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// textarea.insert_char('a');
+    /// assert_eq!(textarea.lines(), ["a"]);
+    ///
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["a", ""]);
+    ///
+    /// textarea.insert_char('b');
+    /// assert_eq!(textarea.lines(), ["a", "b"]);
     /// ```
-    pub fn undo(&mut self) -> bool {
-        if let Some(cursor) = self.history.undo(&mut self.lines) {
-            self.cancel_selection();
-            self.cursor = cursor;
-            true
-        } else {
-            false
-        }
+    pub fn lines(&self) -> &[String] {
+        &self.lines
     }
 
-    /// Redo the last undo change. This method returns if the redo modified text contents or not in the textarea.
+    /// Get the line at `row`. This returns `None` when the line does not exist.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["abc def"]);
+    /// let textarea = TextArea::from(["abc", "def"]);
     ///
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.lines(), [" def"]);
-    /// textarea.undo();
-    /// assert_eq!(textarea.lines(), ["abc def"]);
-    /// textarea.redo();
-    /// assert_eq!(textarea.lines(), [" def"]);
+    /// assert_eq!(textarea.line(1), Some("def"));
+    /// assert_eq!(textarea.line(2), None);
     /// ```
-    pub fn redo(&mut self) -> bool {
-        if let Some(cursor) = self.history.redo(&mut self.lines) {
-            self.cancel_selection();
-            self.cursor = cursor;
-            true
-        } else {
-            false
-        }
+    pub fn line(&self, row: usize) -> Option<&str> {
+        self.lines.get(row).map(String::as_str)
     }
 
-    pub(crate) fn line_spans<'b>(&'b self, line: &'b str, row: usize, lnum_len: u8) -> Line<'b> {
-        let mut hl = LineHighlighter::new(
-            line,
-            self.cursor_style,
-            self.tab_len,
-            self.mask,
-            self.select_style,
-        );
-
-        if let Some(style) = self.line_number_style {
-            hl.line_number(row, lnum_len, style);
-        }
-
-        if row == self.cursor.0 {
-            hl.cursor_line(self.cursor.1, self.cursor_line_style);
-        }
-
-        #[cfg(feature = "search")]
-        if let Some(matches) = self.search.matches(line) {
-            hl.search(matches, self.search.style);
-        }
-
-        if let Some((start, end)) = self.selection_range() {
-            hl.selection(row, start.row, start.offset, end.row, end.offset);
-        }
-
-        hl.into_spans()
+    /// Get the lines from `start` to `end` (exclusive). The range is clamped into the text so this is useful for
+    /// reading only the lines in some window such as the viewport without copying them.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea: TextArea = (0..100).map(|i| i.to_string()).collect();
+    ///
+    /// let lines: Vec<&str> = textarea.lines_in_range(10, 13).collect();
+    /// assert_eq!(lines, ["10", "11", "12"]);
+    ///
+    /// // The range is clamped
+    /// assert_eq!(textarea.lines_in_range(98, 200).count(), 2);
+    /// assert_eq!(textarea.lines_in_range(5, 3).count(), 0);
+    /// ```
+    pub fn lines_in_range(&self, start: usize, end: usize) -> impl Iterator<Item = &str> + '_ {
+        let end = cmp::min(end, self.lines.len());
+        let start = cmp::min(start, end);
+        self.lines[start..end].iter().map(String::as_str)
     }
 
-    /* /// Build a ratatui (or tui-rs) widget to render the current state of the textarea. The widget instance returned
-    /// from this method can be rendered with [`ratatui::terminal::Frame::render_widget`].
-    /// ```no_run
-    /// use ratatui::backend::CrosstermBackend;
-    /// use ratatui::layout::{Constraint, Direction, Layout};
-    /// use ratatui::Terminal;
+    /// Convert [`TextArea`] instance into line texts. The lines are moved out without copying them. Empty lines at the
+    /// end of the text are removed unless the policy set by [`TextArea::set_trailing_newline`] is
+    /// [`TrailingNewline::Preserve`].
+    /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// let layout = Layout::default()
-    ///     .direction(Direction::Vertical)
-    ///     .constraints([Constraint::Min(1)].as_ref());
-    /// let backend = CrosstermBackend::new(std::io::stdout());
-    /// let mut term = Terminal::new(backend).unwrap();
-    ///
-    /// loop {
-    ///     term.draw(|f| {
-    ///         let chunks = layout.split(f.size());
-    ///         let widget = textarea.widget();
-    ///         f.render_widget(widget, chunks[0]);
-    ///     }).unwrap();
+    /// textarea.insert_char('a');
+    /// textarea.insert_newline();
+    /// textarea.insert_char('b');
     ///
-    ///     // ...
-    /// }
+    /// assert_eq!(textarea.into_lines(), ["a", "b"]);
     /// ```
-    pub fn widget(&self) -> impl Widget + {
-        Renderer::new(self)
-    } */
+    pub fn into_lines(mut self) -> Vec<String> {
+        let len = self.exported_lines().len();
+        self.lines.truncate(len);
+        self.lines
+    }
+
+    // Lines exported by `write_to` and `into_lines`
+    fn exported_lines(&self) -> &[String] {
+        let mut lines = &self.lines[..];
+        if self.trailing_newline != TrailingNewline::Preserve {
+            while let [rest @ .., last] = lines {
+                if rest.is_empty() || !last.is_empty() {
+                    break;
+                }
+                lines = rest;
+            }
+        }
+        lines
+    }
 
-    /// Set the style of textarea. By default, textarea is not styled.
+    /// Get the whole text as one string. Lines are joined with `\n`. As well as [`TextArea::write_to`], the newline at
+    /// the end of the text follows the policy set by [`TextArea::set_trailing_newline`].
     /// ```
-    /// use ratatui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, TrailingNewline};
     ///
-    /// let mut textarea = TextArea::default();
-    /// let style = Style::default().fg(Color::Red);
-    /// textarea.set_style(style);
-    /// assert_eq!(textarea.style(), style);
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// assert_eq!(textarea.text(), "hello\nworld");
+    ///
+    /// textarea.set_trailing_newline(TrailingNewline::Ensure);
+    /// assert_eq!(textarea.text(), "hello\nworld\n");
     /// ```
-    pub fn set_style(&mut self, style: Style) {
-        self.style = style;
+    pub fn text(&self) -> String {
+        let mut text = self.exported_lines().join("\n");
+        if self.trailing_newline == TrailingNewline::Ensure {
+            text.push('\n');
+        }
+        text
     }
 
-    /// Get the current style of textarea.
-    pub fn style(&self) -> Style {
-        self.style
+    /// Write the text to the writer. Lines are separated with `\n`. No newline is written after the last line unless
+    /// the policy set by [`TextArea::set_trailing_newline`] is [`TrailingNewline::Ensure`].
+    /// ```
+    /// use tui_textarea::{TextArea, TrailingNewline};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// let mut buf = vec![];
+    /// textarea.write_to(&mut buf).unwrap();
+    /// assert_eq!(buf, b"hello\nworld");
+    ///
+    /// textarea.set_trailing_newline(TrailingNewline::Ensure);
+    /// let mut buf = vec![];
+    /// textarea.write_to(&mut buf).unwrap();
+    /// assert_eq!(buf, b"hello\nworld\n");
+    /// ```
+    pub fn write_to<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        let lines = self.exported_lines();
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                w.write_all(b"\n")?;
+            }
+            w.write_all(line.as_bytes())?;
+        }
+        if self.trailing_newline == TrailingNewline::Ensure {
+            w.write_all(b"\n")?;
+        }
+        w.flush()
     }
 
-    /// Set the length of tab character. Setting 0 disables tab inputs.
+    /// Set how the newline at the end of the text is exported by [`TextArea::text`], [`TextArea::write_to`], and
+    /// [`TextArea::into_lines`]. With [`TrailingNewline::Ensure`], the text ends with exactly one newline. With
+    /// [`TrailingNewline::Strip`], the text ends without a newline. In both cases, empty lines at the end of the text
+    /// are removed on export. The default policy is [`TrailingNewline::Preserve`], which exports the lines as they are.
+    ///
+    /// Only the exported text is affected. The text buffer is not modified, so empty lines at the end of the text, for
+    /// example made by splitting a text ending with a newline by `\n` or by [`TextArea::set_text`], stay in the buffer
+    /// and the cursor can move to them.
     /// ```
-    /// use tui_textarea::{TextArea, Input, Key};
+    /// use tui_textarea::{TextArea, TrailingNewline};
     ///
-    /// let mut textarea = TextArea::default();
-    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
+    /// let mut textarea = TextArea::from(["hello", "", ""]);
+    /// assert!(textarea.has_trailing_empty_line());
     ///
-    /// textarea.set_tab_length(8);
-    /// textarea.input(tab_input.clone());
-    /// assert_eq!(textarea.lines(), ["        "]);
+    /// textarea.set_trailing_newline(TrailingNewline::Ensure);
+    /// assert_eq!(textarea.trailing_newline(), TrailingNewline::Ensure);
     ///
-    /// textarea.set_tab_length(2);
-    /// textarea.input(tab_input);
-    /// assert_eq!(textarea.lines(), ["          "]);
+    /// let mut buf = vec![];
+    /// textarea.write_to(&mut buf).unwrap();
+    /// assert_eq!(buf, b"hello\n");
+    ///
+    /// // The empty lines are still in the buffer
+    /// assert_eq!(textarea.lines(), ["hello", "", ""]);
+    ///
+    /// textarea.set_trailing_newline(TrailingNewline::Strip);
+    /// assert_eq!(textarea.into_lines(), ["hello"]);
     /// ```
-    pub fn set_tab_length(&mut self, len: u8) {
-        self.tab_len = len;
+    pub fn set_trailing_newline(&mut self, policy: TrailingNewline) {
+        self.trailing_newline = policy;
     }
 
-    /// Get how many spaces are used for representing tab character. The default value is 4.
-    pub fn tab_length(&self) -> u8 {
-        self.tab_len
+    /// Get how the newline at the end of the text is exported. See [`TextArea::set_trailing_newline`].
+    pub fn trailing_newline(&self) -> TrailingNewline {
+        self.trailing_newline
     }
 
-    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
-    /// spaces. By default, hard tab is disabled.
+    /// Return if the text buffer ends with an empty line. For example, this is `true` when the text was created by
+    /// splitting a text ending with a newline by `\n`. Exporting such text with [`TrailingNewline::Preserve`] results
+    /// in the text ending with a newline.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    ///
-    /// textarea.set_hard_tab_indent(true);
-    /// textarea.insert_tab();
-    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// assert!(!TextArea::from("hello\n".lines()).has_trailing_empty_line());
+    /// assert!(TextArea::from("hello\n".split('\n')).has_trailing_empty_line());
     /// ```
-    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
-        self.hard_tab_indent = enabled;
+    pub fn has_trailing_empty_line(&self) -> bool {
+        matches!(&self.lines[..], [_, .., last] if last.is_empty())
     }
 
-    /// Get if a hard tab is used for indent or not.
+    /// Return if the text exported by [`TextArea::text`] and [`TextArea::write_to`] ends with a newline. The last line
+    /// of the text buffer is not followed by a newline, so with [`TrailingNewline::Preserve`] this is `true` when the
+    /// text buffer ends with an empty line (see [`TextArea::has_trailing_empty_line`]). The policy set by
+    /// [`TextArea::set_trailing_newline`] is also considered. The text set by [`TextArea::set_text`] round-trips with
+    /// the trailing newline preserved.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, TrailingNewline};
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// assert!(!textarea.hard_tab_indent());
-    /// textarea.set_hard_tab_indent(true);
-    /// assert!(textarea.hard_tab_indent());
+    /// textarea.set_text("a\nb\n");
+    /// assert_eq!(textarea.lines(), ["a", "b", ""]);
+    /// assert!(textarea.has_trailing_newline());
+    ///
+    /// textarea.set_text("a\nb");
+    /// assert_eq!(textarea.lines(), ["a", "b"]);
+    /// assert!(!textarea.has_trailing_newline());
+    ///
+    /// textarea.set_trailing_newline(TrailingNewline::Ensure);
+    /// assert!(textarea.has_trailing_newline());
+    ///
+    /// textarea.set_text("a\nb\n");
+    /// textarea.set_trailing_newline(TrailingNewline::Strip);
+    /// assert!(!textarea.has_trailing_newline());
     /// ```
-    pub fn hard_tab_indent(&self) -> bool {
-        self.hard_tab_indent
+    pub fn has_trailing_newline(&self) -> bool {
+        match self.trailing_newline {
+            TrailingNewline::Preserve => self.has_trailing_empty_line(),
+            TrailingNewline::Ensure => true,
+            TrailingNewline::Strip => false,
+        }
     }
 
-    /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
+    /// Replace the whole text with the string. The string is split into lines by `\n` or `\r\n`. A newline at the end
+    /// of the string is kept as an empty line at the end of the text so that [`TextArea::text`] returns the same string
+    /// (see [`TextArea::has_trailing_newline`]). The cursor moves to the head of the text and the text selection is
+    /// cancelled. The edit history is cleared since the past modifications no longer match the text. In single-line
+    /// mode, newlines are replaced with spaces.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["hello"]);
     ///
-    /// assert_eq!(textarea.indent(), "    ");
-    /// textarea.set_tab_length(2);
-    /// assert_eq!(textarea.indent(), "  ");
-    /// textarea.set_hard_tab_indent(true);
-    /// assert_eq!(textarea.indent(), "\t");
+    /// textarea.set_text("foo\r\nbar\n");
+    /// assert_eq!(textarea.lines(), ["foo", "bar", ""]);
+    /// assert_eq!(textarea.text(), "foo\nbar\n");
+    /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
-    pub fn indent(&self) -> &'static str {
-        if self.hard_tab_indent {
-            "\t"
-        } else {
-            spaces(self.tab_len)
+    pub fn set_text(&mut self, text: &str) {
+        let mut lines: Vec<String> = text
+            .split('\n')
+            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
+            .collect();
+        if self.single_line && lines.len() > 1 {
+            lines = vec![lines.join(" ")];
         }
+        let modified = self.lines != lines;
+        if modified {
+            if let Some(shift) = RowShift::between(&self.lines, &lines) {
+                self.shift_rows(shift);
+            }
+            self.lines = lines;
+            self.revision = self.revision.wrapping_add(1);
+        }
+        self.history.clear(modified);
+        self.set_cursor((0, 0));
+        self.set_selection_start(None);
     }
 
-    /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo.
-    pub fn set_max_histories(&mut self, max: usize) {
-        self.history = History::new(max);
-    }
-
-    /// Get how many modifications are remembered for undo/redo. The default value is 50.
-    pub fn max_histories(&self) -> usize {
-        self.history.max_items()
-    }
-
-    /// Set the style of line at cursor. By default, the cursor line is styled with underline. To stop styling the
-    /// cursor line, set the default style.
+    /// Get the current cursor position. 0-base character-wise (row, col) cursor position.
     /// ```
-    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.cursor(), (0, 0));
     ///
-    /// let style = Style::default().fg(Color::Red);
-    /// textarea.set_cursor_line_style(style);
-    /// assert_eq!(textarea.cursor_line_style(), style);
+    /// textarea.insert_char('a');
+    /// textarea.insert_newline();
+    /// textarea.insert_char('b');
     ///
-    /// // Disable cursor line style
-    /// textarea.set_cursor_line_style(Style::default());
+    /// assert_eq!(textarea.cursor(), (1, 1));
     /// ```
-    pub fn set_cursor_line_style(&mut self, style: Style) {
-        self.cursor_line_style = style;
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
     }
 
-    /// Get the style of cursor line. By default it is styled with underline.
-    pub fn cursor_line_style(&self) -> Style {
-        self.cursor_line_style
+    /// Set text alignment. When [`Alignment::Center`] or [`Alignment::Right`] is set, line number is automatically
+    /// disabled because those alignments don't work well with line numbers.
+    /// ```
+    /// use ratatui::layout::Alignment;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_alignment(Alignment::Center);
+    /// assert_eq!(textarea.alignment(), Alignment::Center);
+    /// ```
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        if let Alignment::Center | Alignment::Right = alignment {
+            self.line_number_style = None;
+        }
+        self.alignment = alignment;
     }
 
-    /// Set the style of line number. By setting the style with this method, line numbers are drawn in textarea, meant
-    /// that line numbers are disabled by default. If you want to show line numbers but don't want to style them, set
-    /// the default style.
+    /// Get current text alignment. The default alignment is [`Alignment::Left`].
     /// ```
-    /// use ratatui::style::{Style, Color};
+    /// use ratatui::layout::Alignment;
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// // Show line numbers in dark gray background
-    /// let style = Style::default().bg(Color::DarkGray);
-    /// textarea.set_line_number_style(style);
-    /// assert_eq!(textarea.line_number_style(), Some(style));
+    /// assert_eq!(textarea.alignment(), Alignment::Left);
     /// ```
-    pub fn set_line_number_style(&mut self, style: Style) {
-        self.line_number_style = Some(style);
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
     }
 
-    /// Remove the style of line number which was set by [`TextArea::set_line_number_style`]. After calling this
-    /// method, Line numbers will no longer be shown.
+    /// Set the alignment of the line at `row` overriding the text alignment set by [`TextArea::set_alignment`]. `None`
+    /// removes the override so that the line follows the text alignment. This is useful for centering a title above
+    /// text aligned to the left. Only lines aligned to the left are scrolled horizontally. Note that alignment per line
+    /// is not supported with tui-rs and the override is ignored on rendering.
     /// ```
-    /// use ratatui::style::{Style, Color};
+    /// use ratatui::layout::Alignment;
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["Title", "body text"]);
     ///
-    /// textarea.set_line_number_style(Style::default().bg(Color::DarkGray));
-    /// textarea.remove_line_number();
-    /// assert_eq!(textarea.line_number_style(), None);
+    /// textarea.set_line_alignment(0, Some(Alignment::Center));
+    /// assert_eq!(textarea.line_alignment(0), Alignment::Center);
+    /// assert_eq!(textarea.line_alignment(1), Alignment::Left);
+    ///
+    /// textarea.set_line_alignment(0, None);
+    /// assert_eq!(textarea.line_alignment(0), Alignment::Left);
     /// ```
-    pub fn remove_line_number(&mut self) {
-        self.line_number_style = None;
+    pub fn set_line_alignment(&mut self, row: usize, alignment: Option<Alignment>) {
+        if let Some(alignment) = alignment {
+            self.line_alignments.insert(row, alignment);
+        } else {
+            self.line_alignments.remove(&row);
+        }
     }
 
-    /// Get the style of line number if set.
-    pub fn line_number_style(&self) -> Option<Style> {
-        self.line_number_style
+    /// Get the alignment of the line at `row`. It is the alignment set by [`TextArea::set_line_alignment`] or the text
+    /// alignment when the line has no override.
+    pub fn line_alignment(&self, row: usize) -> Alignment {
+        self.line_alignments
+            .get(&row)
+            .copied()
+            .unwrap_or(self.alignment)
     }
 
-    /// Set the placeholder text. The text is set in the textarea when no text is input. Setting a non-empty string `""`
-    /// enables the placeholder. The default value is an empty string so the placeholder is disabled by default.
-    /// To customize the text style, see [`TextArea::set_placeholder_style`].
+    /// Check if the textarea has a empty content.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_text(), "");
-    /// assert!(textarea.placeholder_style().is_none());
+    /// let textarea = TextArea::default();
+    /// assert!(textarea.is_empty());
     ///
-    /// textarea.set_placeholder_text("Hello");
-    /// assert_eq!(textarea.placeholder_text(), "Hello");
-    /// assert!(textarea.placeholder_style().is_some());
+    /// let textarea = TextArea::from(["hello"]);
+    /// assert!(!textarea.is_empty());
     /// ```
-    pub fn set_placeholder_text(&mut self, placeholder: impl Into<String>) {
-        self.placeholder = placeholder.into();
+    pub fn is_empty(&self) -> bool {
+        self.lines == [""]
     }
 
-    /// Set the style of the placeholder text. The default style is a dark gray text.
+    /// Get the yanked text. Text is automatically yanked when deleting strings by [`TextArea::delete_line_by_head`],
+    /// [`TextArea::delete_line_by_end`], [`TextArea::delete_word`], [`TextArea::delete_next_word`],
+    /// [`TextArea::delete_str`], [`TextArea::copy`], and [`TextArea::cut`]. When multiple lines were yanked, they are
+    /// always joined with `\n`.
     /// ```
-    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_style(), None); // When the placeholder is disabled
-    ///
-    /// textarea.set_placeholder_text("Enter your message"); // Enable placeholder by setting non-empty text
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.yank_text(), "abc");
     ///
-    /// let style = Style::default().bg(Color::Blue);
-    /// textarea.set_placeholder_style(style);
-    /// assert_eq!(textarea.placeholder_style(), Some(style));
+    /// // Multiple lines are joined with \n
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.delete_str(5);
+    /// assert_eq!(textarea.yank_text(), "abc\nd");
     /// ```
-    pub fn set_placeholder_style(&mut self, style: Style) {
-        self.placeholder_style = style;
+    pub fn yank_text(&self) -> String {
+        self.yank.to_string()
     }
 
-    /// Get the placeholder text. An empty string means the placeholder is disabled. The default value is an empty string.
+    /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. `\n` and `\r\n` are recognized as newline
+    /// but `\r` isn't.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_text(), "");
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_yank_text("hello\nworld");
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
     /// ```
-    pub fn placeholder_text(&self) -> &'_ str {
-        self.placeholder.as_str()
+    pub fn set_yank_text(&mut self, text: impl Into<String>) {
+        self.set_yank(YankText::from_text(&text.into()));
     }
 
-    /// Get the placeholder style. When the placeholder text is empty, it returns `None` since the placeholder is disabled.
-    /// The default style is a dark gray text.
+    // Yank the text and remember it in the kill ring
+    fn set_yank(&mut self, yank: YankText) {
+        self.kill_ring.push(yank.clone());
+        self.yank = yank;
+    }
+
+    /// Set a regular expression pattern for text search. Setting an empty string stops the text search.
+    /// When a valid pattern is set, all matches will be highlighted in the textarea. Note that the cursor does not
+    /// move. To move the cursor, use [`TextArea::search_forward`] and [`TextArea::search_back`].
+    ///
+    /// Grammar of regular expression follows [regex crate](https://docs.rs/regex/latest/regex). Patterns don't match
+    /// to newlines so match passes across no newline.
+    ///
+    /// When the pattern is invalid, the search pattern will not be updated and an error will be returned.
+    ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_style(), None);
+    /// let mut textarea = TextArea::from(["hello, world", "goodbye, world"]);
     ///
-    /// textarea.set_placeholder_text("hello");
-    /// assert!(textarea.placeholder_style().is_some());
+    /// // Search "world"
+    /// textarea.set_search_pattern("world").unwrap();
+    ///
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (1, 9));
+    ///
+    /// // Stop the text search
+    /// textarea.set_search_pattern("");
+    ///
+    /// // Invalid search pattern
+    /// assert!(textarea.set_search_pattern("(hello").is_err());
     /// ```
-    pub fn placeholder_style(&self) -> Option<Style> {
-        if self.placeholder.is_empty() {
-            None
-        } else {
-            Some(self.placeholder_style)
-        }
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_pattern(&mut self, query: impl AsRef<str>) -> Result<(), regex::Error> {
+        self.render_cache.clear();
+        self.search.set_pattern(query.as_ref())
     }
 
-    /// Specify a character masking the text. All characters in the textarea will be replaced by this character.
-    /// This API is useful for making a kind of credentials form such as a password input.
+    /// Get a regular expression which was set by [`TextArea::set_search_pattern`]. When no text search is ongoing, this
+    /// method returns `None`. In literal mode (see [`TextArea::set_search_literal`]), the returned regular expression
+    /// is the escaped pattern.
+    ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
-    /// textarea.set_mask_char('●');
-    /// assert_eq!(textarea.mask_char(), Some('●'));
+    /// assert!(textarea.search_pattern().is_none());
+    /// textarea.set_search_pattern("hello+").unwrap();
+    /// assert!(textarea.search_pattern().is_some());
+    /// assert_eq!(textarea.search_pattern().unwrap().as_str(), "hello+");
     /// ```
-    pub fn set_mask_char(&mut self, mask: char) {
-        self.mask = Some(mask);
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_pattern(&self) -> Option<&regex::Regex> {
+        self.search.pat.as_ref()
     }
 
-    /// Clear the masking character previously set by [`TextArea::set_mask_char`].
+    /// Set if text search ignores the case of letters. This option applies to the current search pattern and the
+    /// patterns set after it. The default value is `false`.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["Hello", "hello"]);
     ///
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
-    /// textarea.clear_mask_char();
-    /// assert_eq!(textarea.mask_char(), None);
+    /// textarea.set_search_case_insensitive(true);
+    /// assert!(textarea.search_case_insensitive());
+    ///
+    /// textarea.set_search_pattern("HELLO").unwrap();
+    /// assert!(textarea.search_forward(true));
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// assert!(textarea.search_forward(false));
+    /// assert_eq!(textarea.cursor(), (1, 0));
     /// ```
-    pub fn clear_mask_char(&mut self) {
-        self.mask = None;
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_case_insensitive(&mut self, enabled: bool) {
+        self.render_cache.clear();
+        self.search.set_case_insensitive(enabled);
     }
 
-    /// Get the character to mask text. When no character is set, `None` is returned.
+    /// Get if text search ignores the case of letters. See [`TextArea::set_search_case_insensitive`] for more details.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_case_insensitive(&self) -> bool {
+        self.search.case_insensitive
+    }
+
+    /// Set if the search pattern is matched literally instead of as a regular expression. In literal mode, characters
+    /// such as `.` and `*` in the pattern only match themselves and `$` in replacements of [`TextArea::replace_next`]
+    /// and [`TextArea::replace_all`] is inserted as is. This option applies to the current search pattern and the
+    /// patterns set after it. The default value is `false`.
+    ///
+    /// When the current pattern is not a valid regular expression on leaving literal mode, the text search stops.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["axb a.b"]);
     ///
-    /// assert_eq!(textarea.mask_char(), None);
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// textarea.set_search_pattern("a.b").unwrap();
+    /// assert!(textarea.search_forward(true));
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// textarea.set_search_literal(true);
+    /// assert!(textarea.search_literal());
+    /// assert!(textarea.search_forward(true));
+    /// assert_eq!(textarea.cursor(), (0, 4));
     /// ```
-    pub fn mask_char(&self) -> Option<char> {
-        self.mask
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_literal(&mut self, enabled: bool) {
+        self.render_cache.clear();
+        self.search.set_literal(enabled);
     }
 
-    /// Set the style of cursor. By default, a cursor is rendered in the reversed color. Setting the same style as
-    /// cursor line hides a cursor.
+    /// Get if the search pattern is matched literally. See [`TextArea::set_search_literal`] for more details.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_literal(&self) -> bool {
+        self.search.literal
+    }
+
+    /// Set if text search only matches whole words. When this option is enabled, a match adjacent to a character of
+    /// the same kind (word character, punctuation, or whitespace) at its start or end is ignored. Word boundaries are
+    /// the same as the ones of word motions like [`CursorMove::WordForward`]. The default value is `false`.
     /// ```
-    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["cat concat cat_1 cat"]);
     ///
-    /// let style = Style::default().bg(Color::Red);
-    /// textarea.set_cursor_style(style);
-    /// assert_eq!(textarea.cursor_style(), style);
+    /// textarea.set_search_whole_word(true);
+    /// assert!(textarea.search_whole_word());
+    ///
+    /// textarea.set_search_pattern("cat").unwrap();
+    /// assert!(textarea.search_forward(false));
+    /// assert_eq!(textarea.cursor(), (0, 17));
     /// ```
-    pub fn set_cursor_style(&mut self, style: Style) {
-        self.cursor_style = style;
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_whole_word(&mut self, enabled: bool) {
+        self.render_cache.clear();
+        self.search.whole_word = enabled;
     }
 
-    /// Get the style of cursor.
-    pub fn cursor_style(&self) -> Style {
-        self.cursor_style
+    /// Get if text search only matches whole words. See [`TextArea::set_search_whole_word`] for more details.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_whole_word(&self) -> bool {
+        self.search.whole_word
     }
 
-    /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
-    /// never be empty because an empty text means a slice containing one empty line. This is correct since any text
-    /// file must end with a newline.
+    /// Search the pattern set by [`TextArea::set_search_pattern`] forward and move the cursor to the next match
+    /// position based on the current cursor position. Text search wraps around a text buffer unless it is disabled by
+    /// [`TextArea::set_search_wrap`]. It returns `true` when some match was found. Otherwise it returns `false` and the
+    /// cursor does not move. [`TextArea::search_wrapped`] tells if the match was found by wrapping around.
+    ///
+    /// The `match_cursor` parameter represents if the search matches to the current cursor position or not. When `true`
+    /// is set and the cursor position matches to the pattern, the cursor will not move. When `false`, the cursor will
+    /// move to the next match ignoring the match at the current position.
+    ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.lines(), [""]);
+    /// let mut textarea = TextArea::from(["hello", "helloo", "hellooo"]);
     ///
-    /// textarea.insert_char('a');
-    /// assert_eq!(textarea.lines(), ["a"]);
+    /// textarea.set_search_pattern("hello+").unwrap();
     ///
-    /// textarea.insert_newline();
-    /// assert_eq!(textarea.lines(), ["a", ""]);
+    /// // Move to next position
+    /// let match_found = textarea.search_forward(false);
+    /// assert!(match_found);
+    /// assert_eq!(textarea.cursor(), (1, 0));
     ///
-    /// textarea.insert_char('b');
-    /// assert_eq!(textarea.lines(), ["a", "b"]);
-    /// ```
-    pub fn lines(&self) -> &[String] {
-        &self.lines
-    }
-
-    /// Convert [`TextArea`] instance into line texts.
-    /// ```
-    /// use tui_textarea::TextArea;
+    /// // Since the cursor position matches to "hello+", it does not move
+    /// textarea.search_forward(true);
+    /// assert_eq!(textarea.cursor(), (1, 0));
     ///
-    /// let mut textarea = TextArea::default();
+    /// // When `match_current` parameter is set to `false`, match at the cursor position is ignored
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (2, 0));
     ///
-    /// textarea.insert_char('a');
-    /// textarea.insert_newline();
-    /// textarea.insert_char('b');
+    /// // Text search wrap around the buffer
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (0, 0));
     ///
-    /// assert_eq!(textarea.into_lines(), ["a", "b"]);
+    /// // `false` is returned when no match was found
+    /// textarea.set_search_pattern("bye+").unwrap();
+    /// let match_found = textarea.search_forward(false);
+    /// assert!(!match_found);
     /// ```
-    pub fn into_lines(self) -> Vec<String> {
-        self.lines
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_forward(&mut self, match_cursor: bool) -> bool {
+        if let Some(cursor) = self.search.forward(&self.lines, self.cursor, match_cursor) {
+            self.set_cursor(cursor);
+            true
+        } else {
+            false
+        }
     }
 
-    /// Get the current cursor position. 0-base character-wise (row, col) cursor position.
+    /// Search the pattern set by [`TextArea::set_search_pattern`] backward and move the cursor to the next match
+    /// position based on the current cursor position. Text search wraps around a text buffer unless it is disabled by
+    /// [`TextArea::set_search_wrap`]. It returns `true` when some match was found. Otherwise it returns `false` and the
+    /// cursor does not move. [`TextArea::search_wrapped`] tells if the match was found by wrapping around.
+    ///
+    /// The `match_cursor` parameter represents if the search matches to the current cursor position or not. When `true`
+    /// is set and the cursor position matches to the pattern, the cursor will not move. When `false`, the cursor will
+    /// move to the next match ignoring the match at the current position.
+    ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// let mut textarea = TextArea::from(["hello", "helloo", "hellooo"]);
     ///
-    /// textarea.insert_char('a');
-    /// textarea.insert_newline();
-    /// textarea.insert_char('b');
+    /// textarea.set_search_pattern("hello+").unwrap();
     ///
-    /// assert_eq!(textarea.cursor(), (1, 1));
-    /// ```
-    pub fn cursor(&self) -> (usize, usize) {
-        self.cursor
-    }
-
-    /// Set text alignment. When [`Alignment::Center`] or [`Alignment::Right`] is set, line number is automatically
-    /// disabled because those alignments don't work well with line numbers.
-    /// ```
-    /// use ratatui::layout::Alignment;
-    /// use tui_textarea::TextArea;
+    /// // Move to next position with wrapping around the text buffer
+    /// let match_found = textarea.search_back(false);
+    /// assert!(match_found);
+    /// assert_eq!(textarea.cursor(), (2, 0));
     ///
-    /// let mut textarea = TextArea::default();
+    /// // Since the cursor position matches to "hello+", it does not move
+    /// textarea.search_back(true);
+    /// assert_eq!(textarea.cursor(), (2, 0));
     ///
-    /// textarea.set_alignment(Alignment::Center);
-    /// assert_eq!(textarea.alignment(), Alignment::Center);
+    /// // When `match_current` parameter is set to `false`, match at the cursor position is ignored
+    /// textarea.search_back(false);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// // `false` is returned when no match was found
+    /// textarea.set_search_pattern("bye+").unwrap();
+    /// let match_found = textarea.search_back(false);
+    /// assert!(!match_found);
     /// ```
-    pub fn set_alignment(&mut self, alignment: Alignment) {
-        if let Alignment::Center | Alignment::Right = alignment {
-            self.line_number_style = None;
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_back(&mut self, match_cursor: bool) -> bool {
+        if let Some(cursor) = self.search.back(&self.lines, self.cursor, match_cursor) {
+            self.set_cursor(cursor);
+            true
+        } else {
+            false
         }
-        self.alignment = alignment;
     }
 
-    /// Get current text alignment. The default alignment is [`Alignment::Left`].
+    /// Set if text search by [`TextArea::search_forward`] and [`TextArea::search_back`] wraps around the text buffer.
+    /// When it is disabled, the search stops at the end (or the start) of the text and the cursor does not move. The
+    /// default value is `true`.
     /// ```
-    /// use ratatui::layout::Alignment;
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["foo", "bar", "foo"]);
+    /// textarea.set_search_pattern("foo").unwrap();
     ///
-    /// assert_eq!(textarea.alignment(), Alignment::Left);
+    /// assert!(textarea.search_forward(false));
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    ///
+    /// textarea.set_search_wrap(false);
+    /// assert!(!textarea.search_wrap());
+    /// assert!(!textarea.search_forward(false));
+    /// assert_eq!(textarea.cursor(), (2, 0));
     /// ```
-    pub fn alignment(&self) -> Alignment {
-        self.alignment
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_wrap(&mut self, enabled: bool) {
+        self.search.wrap = enabled;
     }
 
-    /// Check if the textarea has a empty content.
+    /// Get if text search wraps around the text buffer. See [`TextArea::set_search_wrap`] for more details.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_wrap(&self) -> bool {
+        self.search.wrap
+    }
+
+    /// Return if the last search by [`TextArea::search_forward`] or [`TextArea::search_back`] found the match by
+    /// wrapping around the text buffer. This is useful to show a message like "search hit BOTTOM, continuing at TOP".
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let textarea = TextArea::default();
-    /// assert!(textarea.is_empty());
+    /// let mut textarea = TextArea::from(["foo", "bar", "foo"]);
+    /// textarea.set_search_pattern("foo").unwrap();
     ///
-    /// let textarea = TextArea::from(["hello"]);
-    /// assert!(!textarea.is_empty());
+    /// textarea.search_forward(false);
+    /// assert!(!textarea.search_wrapped());
+    /// textarea.search_forward(false);
+    /// assert!(textarea.search_wrapped());
+    /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.lines == [""]
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_wrapped(&self) -> bool {
+        self.search.wrapped
     }
 
-    /// Get the yanked text. Text is automatically yanked when deleting strings by [`TextArea::delete_line_by_head`],
-    /// [`TextArea::delete_line_by_end`], [`TextArea::delete_word`], [`TextArea::delete_next_word`],
-    /// [`TextArea::delete_str`], [`TextArea::copy`], and [`TextArea::cut`]. When multiple lines were yanked, they are
-    /// always joined with `\n`.
+    /// Get the number of matches of the search pattern in the whole text. Empty matches are not counted. It returns 0
+    /// when no search pattern is set. The matches are counted on the first call and cached until the text or the
+    /// search pattern changes.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["abc"]);
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.yank_text(), "abc");
+    /// let mut textarea = TextArea::from(["foo foo", "bar", "foo"]);
+    /// assert_eq!(textarea.search_match_count(), 0);
     ///
-    /// // Multiple lines are joined with \n
-    /// let mut textarea = TextArea::from(["abc", "def"]);
-    /// textarea.delete_str(5);
-    /// assert_eq!(textarea.yank_text(), "abc\nd");
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// assert_eq!(textarea.search_match_count(), 3);
+    ///
+    /// textarea.insert_str("foo");
+    /// assert_eq!(textarea.search_match_count(), 4);
     /// ```
-    pub fn yank_text(&self) -> String {
-        self.yank.to_string()
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_match_count(&self) -> usize {
+        self.search
+            .with_positions(&self.lines, self.revision, |positions| positions.len())
     }
 
-    /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. `\n` and `\r\n` are recognized as newline
-    /// but `\r` isn't.
+    /// Get the index of the match of the search pattern at the cursor position in all matches of the text. The index
+    /// starts from 0. It returns `None` when the cursor is not at the start of any match. Combined with
+    /// [`TextArea::search_match_count`], this is useful to show the search status like "3 of 17".
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["foo foo", "bar", "foo"]);
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// assert_eq!(textarea.current_match_index(), Some(0));
     ///
-    /// textarea.set_yank_text("hello\nworld");
-    /// textarea.paste();
-    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// textarea.search_forward(false);
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// assert_eq!(textarea.current_match_index(), Some(2));
+    ///
+    /// textarea.move_cursor(tui_textarea::CursorMove::Up);
+    /// assert_eq!(textarea.current_match_index(), None);
     /// ```
-    pub fn set_yank_text(&mut self, text: impl Into<String>) {
-        // `str::lines` is not available since it strips a newline at end
-        let lines: Vec<_> = text
-            .into()
-            .split('\n')
-            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
-            .collect();
-        self.yank = lines.into();
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn current_match_index(&self) -> Option<usize> {
+        self.search
+            .with_positions(&self.lines, self.revision, |positions| {
+                positions.binary_search(&self.cursor).ok()
+            })
     }
 
-    /// Set a regular expression pattern for text search. Setting an empty string stops the text search.
-    /// When a valid pattern is set, all matches will be highlighted in the textarea. Note that the cursor does not
-    /// move. To move the cursor, use [`TextArea::search_forward`] and [`TextArea::search_back`].
+    /// Start incremental search. The current cursor position and search pattern are saved so that they can be
+    /// restored by [`TextArea::search_cancel`]. Then update the query with [`TextArea::search_feed`] while the user is
+    /// typing it. When incremental search is already ongoing, this method restarts it at the current cursor.
+    /// ```
+    /// use tui_textarea::TextArea;
     ///
-    /// Grammar of regular expression follows [regex crate](https://docs.rs/regex/latest/regex). Patterns don't match
-    /// to newlines so match passes across no newline.
+    /// let mut textarea = TextArea::from(["hello", "world", "hello world"]);
     ///
-    /// When the pattern is invalid, the search pattern will not be updated and an error will be returned.
+    /// textarea.search_start();
+    /// assert!(textarea.is_searching());
     ///
+    /// // Typing the query previews the cursor at the nearest match
+    /// assert!(textarea.search_feed("w").unwrap());
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// assert!(textarea.search_feed("wo").unwrap());
+    /// assert!(textarea.search_feed("w.r").unwrap());
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// // Commit the search to keep the cursor at the match
+    /// textarea.search_commit();
+    /// assert!(!textarea.is_searching());
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// assert_eq!(textarea.search_pattern().unwrap().as_str(), "w.r");
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_start(&mut self) {
+        let saved = (
+            self.cursor,
+            self.search.pat.clone(),
+            self.search.query.clone(),
+        );
+        self.search.saved = Some(saved);
+    }
+
+    /// Update the query of the incremental search started by [`TextArea::search_start`]. The matches of the query are
+    /// highlighted and the cursor moves to the nearest match at or after the cursor position where the search started.
+    /// Text search wraps around the text buffer. When no match is found or the query is empty, the cursor goes back
+    /// to the position where the search started. This method returns if some match was found or not. When the query
+    /// is an invalid regular expression, an error is returned and the pattern and the cursor don't change. When
+    /// incremental search is not ongoing, it is started at the current cursor.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["hello, world", "goodbye, world"]);
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.search_start();
     ///
-    /// // Search "world"
-    /// textarea.set_search_pattern("world").unwrap();
+    /// assert!(textarea.search_feed("e").unwrap());
+    /// assert_eq!(textarea.cursor(), (1, 1));
     ///
+    /// // No match. The cursor goes back to the start position
+    /// assert!(!textarea.search_feed("ex").unwrap());
     /// assert_eq!(textarea.cursor(), (0, 0));
-    /// textarea.search_forward(false);
-    /// assert_eq!(textarea.cursor(), (0, 7));
-    /// textarea.search_forward(false);
-    /// assert_eq!(textarea.cursor(), (1, 9));
     ///
-    /// // Stop the text search
-    /// textarea.set_search_pattern("");
-    ///
-    /// // Invalid search pattern
-    /// assert!(textarea.set_search_pattern("(hello").is_err());
+    /// // Invalid pattern
+    /// assert!(textarea.search_feed("e(").is_err());
     /// ```
     #[cfg(feature = "search")]
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn set_search_pattern(&mut self, query: impl AsRef<str>) -> Result<(), regex::Error> {
-        self.search.set_pattern(query.as_ref())
+    pub fn search_feed(&mut self, query: &str) -> Result<bool, regex::Error> {
+        self.render_cache.clear();
+        if self.search.saved.is_none() {
+            self.search_start();
+        }
+        self.search.set_pattern(query)?;
+        let start = match &self.search.saved {
+            Some((cursor, _, _)) => self.clamp_pos(*cursor),
+            None => self.cursor,
+        };
+        match self.search.forward(&self.lines, start, true) {
+            Some(cursor) => {
+                self.set_cursor(cursor);
+                Ok(true)
+            }
+            None => {
+                self.set_cursor(start);
+                Ok(false)
+            }
+        }
     }
 
-    /// Get a regular expression which was set by [`TextArea::set_search_pattern`]. When no text search is ongoing, this
-    /// method returns `None`.
-    ///
+    /// Finish the incremental search keeping the cursor at the current match. The matches are still highlighted
+    /// until the search pattern is cleared. This method does nothing when incremental search is not ongoing.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_commit(&mut self) {
+        self.search.saved = None;
+    }
+
+    /// Cancel the incremental search. The cursor position and the search pattern are restored to the ones when
+    /// [`TextArea::search_start`] was called. This method does nothing when incremental search is not ongoing.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["abc", "def"]);
     ///
+    /// textarea.search_start();
+    /// textarea.search_feed("ef").unwrap();
+    /// assert_eq!(textarea.cursor(), (1, 1));
+    ///
+    /// textarea.search_cancel();
+    /// assert!(!textarea.is_searching());
+    /// assert_eq!(textarea.cursor(), (0, 0));
     /// assert!(textarea.search_pattern().is_none());
-    /// textarea.set_search_pattern("hello+").unwrap();
-    /// assert!(textarea.search_pattern().is_some());
-    /// assert_eq!(textarea.search_pattern().unwrap().as_str(), "hello+");
     /// ```
     #[cfg(feature = "search")]
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn search_pattern(&self) -> Option<&regex::Regex> {
-        self.search.pat.as_ref()
+    pub fn search_cancel(&mut self) {
+        self.render_cache.clear();
+        if let Some((cursor, pat, query)) = self.search.saved.take() {
+            self.set_cursor(self.clamp_pos(cursor));
+            self.search.pat = pat;
+            self.search.query = query;
+        }
     }
 
-    /// Search the pattern set by [`TextArea::set_search_pattern`] forward and move the cursor to the next match
-    /// position based on the current cursor position. Text search wraps around a text buffer. It returns `true` when
-    /// some match was found. Otherwise it returns `false`.
-    ///
-    /// The `match_cursor` parameter represents if the search matches to the current cursor position or not. When `true`
-    /// is set and the cursor position matches to the pattern, the cursor will not move. When `false`, the cursor will
-    /// move to the next match ignoring the match at the current position.
+    /// Return if incremental search started by [`TextArea::search_start`] is ongoing or not.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn is_searching(&self) -> bool {
+        self.search.saved.is_some()
+    }
+
+    /// Replace the match of the pattern set by [`TextArea::set_search_pattern`] at the cursor position or the next
+    /// match after the cursor. Text search wraps around a text buffer. `$1` or `$name` in the replacement are expanded
+    /// to the text of the capture group. See [`regex::Captures::expand`] for the syntax. The cursor moves to the end of
+    /// the inserted text. The replacement is recorded as one modification in the edit history. This method returns
+    /// `true` when some match was replaced. Otherwise it returns `false`.
     ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["hello", "helloo", "hellooo"]);
-    ///
-    /// textarea.set_search_pattern("hello+").unwrap();
-    ///
-    /// // Move to next position
-    /// let match_found = textarea.search_forward(false);
-    /// assert!(match_found);
-    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// let mut textarea = TextArea::from(["a = 1; b = 2;"]);
     ///
-    /// // Since the cursor position matches to "hello+", it does not move
-    /// textarea.search_forward(true);
-    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// textarea.set_search_pattern(r"(\w) = (\d)").unwrap();
     ///
-    /// // When `match_current` parameter is set to `false`, match at the cursor position is ignored
-    /// textarea.search_forward(false);
-    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// assert!(textarea.replace_next("$2 = $1"));
+    /// assert_eq!(textarea.lines(), ["1 = a; b = 2;"]);
+    /// assert_eq!(textarea.cursor(), (0, 5));
     ///
-    /// // Text search wrap around the buffer
-    /// textarea.search_forward(false);
-    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// assert!(textarea.replace_next("$2 = $1"));
+    /// assert_eq!(textarea.lines(), ["1 = a; 2 = b;"]);
     ///
-    /// // `false` is returned when no match was found
-    /// textarea.set_search_pattern("bye+").unwrap();
-    /// let match_found = textarea.search_forward(false);
-    /// assert!(!match_found);
+    /// textarea.set_search_pattern("c").unwrap();
+    /// assert!(!textarea.replace_next("d"));
     /// ```
     #[cfg(feature = "search")]
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn search_forward(&mut self, match_cursor: bool) -> bool {
-        if let Some(cursor) = self.search.forward(&self.lines, self.cursor, match_cursor) {
-            self.cursor = cursor;
-            true
-        } else {
-            false
-        }
+    pub fn replace_next(&mut self, replacement: &str) -> bool {
+        let (row, col) = match self.search.forward(&self.lines, self.cursor, true) {
+            Some(pos) => pos,
+            None => return false,
+        };
+        let (end, text) = match self.search.replacement(&self.lines[row], col, replacement) {
+            Some(r) => r,
+            None => return false,
+        };
+        // Replace the match as selected text so that the replacement is one modification in the edit history
+        self.set_selection_start(Some((row, col)));
+        self.set_cursor((row, end));
+        self.insert_str(text);
+        true
     }
 
-    /// Search the pattern set by [`TextArea::set_search_pattern`] backward and move the cursor to the next match
-    /// position based on the current cursor position. Text search wraps around a text buffer. It returns `true` when
-    /// some match was found. Otherwise it returns `false`.
-    ///
-    /// The `match_cursor` parameter represents if the search matches to the current cursor position or not. When `true`
-    /// is set and the cursor position matches to the pattern, the cursor will not move. When `false`, the cursor will
-    /// move to the next match ignoring the match at the current position.
+    /// Replace all matches of the pattern set by [`TextArea::set_search_pattern`] in the text and return the number of
+    /// replaced matches. `$1` or `$name` in the replacement are expanded to the text of the capture group as
+    /// [`TextArea::replace_next`] does. Replacing all matches is recorded as one modification in the edit history so
+    /// [`TextArea::undo`] reverts them at once. The cursor stays at the same position as far as possible. Newlines
+    /// (`\n` or `\r\n`) in the replacement split the lines. Other parts of the lines, including `\r` at the end of
+    /// lines, are kept as they are.
     ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["hello", "helloo", "hellooo"]);
-    ///
-    /// textarea.set_search_pattern("hello+").unwrap();
-    ///
-    /// // Move to next position with wrapping around the text buffer
-    /// let match_found = textarea.search_back(false);
-    /// assert!(match_found);
-    /// assert_eq!(textarea.cursor(), (2, 0));
-    ///
-    /// // Since the cursor position matches to "hello+", it does not move
-    /// textarea.search_back(true);
-    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// let mut textarea = TextArea::from(["foo bar", "foofoo"]);
     ///
-    /// // When `match_current` parameter is set to `false`, match at the cursor position is ignored
-    /// textarea.search_back(false);
-    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// assert_eq!(textarea.replace_all("baz"), 3);
+    /// assert_eq!(textarea.lines(), ["baz bar", "bazbaz"]);
     ///
-    /// // `false` is returned when no match was found
-    /// textarea.set_search_pattern("bye+").unwrap();
-    /// let match_found = textarea.search_back(false);
-    /// assert!(!match_found);
+    /// // Undo all replacements at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["foo bar", "foofoo"]);
     /// ```
     #[cfg(feature = "search")]
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn search_back(&mut self, match_cursor: bool) -> bool {
-        if let Some(cursor) = self.search.back(&self.lines, self.cursor, match_cursor) {
-            self.cursor = cursor;
-            true
-        } else {
-            false
+    pub fn replace_all(&mut self, replacement: &str) -> usize {
+        let (mut lines, count) = match self.search.replace_all(&self.lines, replacement) {
+            Some((_, 0)) | None => return 0,
+            Some(r) => r,
+        };
+        if self.single_line {
+            lines = vec![lines.join(" ")];
         }
+        let (row, col) = self.cursor;
+        let row = cmp::min(row, lines.len() - 1);
+        let col = cmp::min(col, lines[row].chars().count());
+        self.cancel_selection();
+        self.replace_lines(lines, (row, col));
+        count
     }
 
     /// Get the text style at matches of text search. The default style is colored with blue in background.
@@ -2229,17 +6869,110 @@ impl TextArea {
     #[cfg(feature = "search")]
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
     pub fn set_search_style(&mut self, style: Style) {
+        self.render_cache.clear();
         self.search.style = style;
     }
 
+    /// Get the text style at the current match of text search. The default style is colored with yellow in background.
+    ///
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.search_current_style(), Style::default().bg(Color::Yellow));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_current_style(&self) -> Style {
+        self.search.current_style
+    }
+
+    /// Set the text style at the current match of text search. The current match is the match starting at the cursor
+    /// position, which is the match the cursor moved to by [`TextArea::search_forward`] or [`TextArea::search_back`].
+    /// It is rendered with this style to be distinguished from other matches rendered with
+    /// [`TextArea::search_style`]. The default style is colored with yellow in background.
+    ///
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let red_bg = Style::default().bg(Color::Red);
+    /// textarea.set_search_current_style(red_bg);
+    ///
+    /// assert_eq!(textarea.search_current_style(), red_bg);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_current_style(&mut self, style: Style) {
+        self.search.current_style = style;
+    }
+
+    /// Set the callback called when the viewport changed on rendering the textarea. The callback receives
+    /// [`ScrollInfo`] containing the scroll position, the size of the viewport, and the number of lines. It is called
+    /// only when any of them changed from the previous rendering, so it is not called on every frame. This is useful for
+    /// keeping auxiliary widgets such as scrollbars or minimaps in sync with the textarea. The callback is shared with
+    /// clones of the textarea. Note that the callback is called while rendering so it must not lock the
+    /// [`SharedTextArea`](crate::SharedTextArea) being rendered.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use std::sync::mpsc;
+    /// use tui_textarea::{ScrollInfo, Scrolling, TextArea, TextAreaWidget};
+    ///
+    /// let (tx, rx) = mpsc::channel();
+    /// let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+    /// textarea.set_on_scroll(move |info| tx.send(info).unwrap());
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 5 };
+    /// let mut b = Buffer::empty(r);
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// assert_eq!(rx.try_recv().unwrap().scroll_top, (0, 0));
+    ///
+    /// // Nothing changed
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// assert!(rx.try_recv().is_err());
+    ///
+    /// textarea.scroll(Scrolling::PageDown);
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// let info = rx.try_recv().unwrap();
+    /// assert_eq!(info, ScrollInfo { scroll_top: (5, 0), width: 10, height: 5, lines: 20 });
+    /// ```
+    pub fn set_on_scroll(&mut self, f: impl FnMut(ScrollInfo) + Send + 'static) {
+        self.on_scroll = ScrollCallback::new(Box::new(f), self.scroll_info());
+    }
+
+    // Current state of the viewport. `None` when the textarea has not been rendered yet
+    fn scroll_info(&self) -> Option<ScrollInfo> {
+        let (row, col, width, height) = self.viewport.rect();
+        (width > 0 || height > 0).then(|| ScrollInfo {
+            scroll_top: (row, col),
+            width,
+            height,
+            lines: self.lines.len(),
+        })
+    }
+
+    pub(crate) fn notify_scroll(&mut self) {
+        if let Some(info) = self.scroll_info() {
+            self.on_scroll.notify(info);
+        }
+    }
+
     /// Scroll the textarea. See [`Scrolling`] for the argument.
     /// The cursor will not move until it goes out the viewport. When the cursor position is outside the viewport after scroll,
     /// the cursor position will be adjusted to stay in the viewport using the same logic as [`CursorMove::InViewport`].
+    /// When scrolling by pages, the cursor moves by the same number of rows keeping its column.
     ///
     /// ```
     /// # use ratatui::buffer::Buffer;
     /// # use ratatui::layout::Rect;
-    /// # use ratatui::widgets::Widget;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// # use tui_textarea::TextAreaWidget;
     /// use tui_textarea::TextArea;
     ///
     /// // Let's say terminal height is 8.
@@ -2249,7 +6982,7 @@ impl TextArea {
     /// # // Call `render` at least once to populate terminal size
     /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
     /// # let mut b = Buffer::empty(r.clone());
-    /// # textarea.widget().render(r, &mut b);
+    /// # TextAreaWidget::new().render(r, &mut b, &mut textarea);
     ///
     /// // Scroll down by 15 lines. Since terminal height is 8, cursor will go out
     /// // the viewport.
@@ -2276,10 +7009,252 @@ impl TextArea {
 
     fn scroll_with_shift(&mut self, scrolling: Scrolling, shift: bool) {
         if shift && self.selection_start.is_none() {
-            self.selection_start = Some(self.cursor);
+            self.set_selection_start(Some(self.cursor));
+        }
+        // Scrolling by pages moves the cursor along with the viewport
+        let rows = match scrolling {
+            Scrolling::Delta { .. } => 0,
+            _ => scrolling.delta(&self.viewport).0,
+        };
+        if scrolling.scroll(&mut self.viewport, self.lines.len() - 1) {
+            if rows != 0 {
+                let (row, col) = self.cursor;
+                let row = if rows > 0 {
+                    row.saturating_add(rows as usize)
+                } else {
+                    row.saturating_sub(rows.unsigned_abs() as usize)
+                };
+                let row = cmp::min(row, self.lines.len() - 1);
+                self.move_cursor_with_shift(CursorMove::Jump(row as u16, col as u16), shift);
+            }
+            self.move_cursor_with_shift(CursorMove::InViewport, shift);
+            self.keep_cursor_in_scroll_margin(shift);
+        }
+    }
+
+    // Move the cursor away from the edges of the viewport by the scroll margin. Otherwise the next render would scroll
+    // the viewport back to keep the margin around the cursor.
+    fn keep_cursor_in_scroll_margin(&mut self, shift: bool) {
+        let (top, _, bottom, _) = self.viewport.position();
+        let (_, _, _, height) = self.viewport.rect();
+        let margin = cmp::min(self.scrolloff, height.saturating_sub(1) / 2) as usize;
+        let (top, bottom) = (top as usize, bottom as usize);
+        let last_row = self.lines.len() - 1;
+        // The margin is not necessary at the start and the end of the text since the viewport cannot scroll further
+        let min_row = if top == 0 { 0 } else { top + margin };
+        let max_row = if bottom >= last_row {
+            last_row
+        } else {
+            bottom.saturating_sub(margin)
+        };
+        let (row, col) = self.cursor;
+        let clamped = cmp::min(cmp::max(row, min_row), max_row);
+        if clamped != row {
+            self.move_cursor_with_shift(CursorMove::Jump(clamped as u16, col as u16), shift);
+        }
+    }
+
+    /// Scroll down the textarea by one page. The page size is the height of the viewport at the last render. The
+    /// cursor moves down by the same number of rows keeping its column. Scrolling stops at the last line of the text
+    /// and the cursor stops at the last line. Since the viewport size is not known until the textarea is rendered,
+    /// this method does nothing before the first render.
+    /// This is equivalent to `textarea.scroll(Scrolling::PageDown)`.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{TextArea, TextAreaWidget};
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    ///
+    /// // Nothing happens before the first render
+    /// textarea.scroll_page_down();
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// // Render the textarea with height 8
+    /// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// let mut b = Buffer::empty(r.clone());
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    ///
+    /// textarea.scroll_page_down();
+    /// assert_eq!(textarea.cursor(), (8, 0));
+    /// ```
+    pub fn scroll_page_down(&mut self) {
+        self.scroll(Scrolling::PageDown);
+    }
+
+    /// Scroll up the textarea by one page. The page size is the height of the viewport at the last render. The cursor
+    /// moves up by the same number of rows keeping its column and stops at the first line. This method does nothing
+    /// before the first render. This is equivalent to `textarea.scroll(Scrolling::PageUp)`.
+    pub fn scroll_page_up(&mut self) {
+        self.scroll(Scrolling::PageUp);
+    }
+
+    /// Scroll down the textarea by half of the page. The page size is the height of the viewport at the last render.
+    /// The cursor moves down by the same number of rows keeping its column. Scrolling stops at the last line of the
+    /// text. This method does nothing before the first render. This is equivalent to
+    /// `textarea.scroll(Scrolling::HalfPageDown)`.
+    pub fn scroll_half_page_down(&mut self) {
+        self.scroll(Scrolling::HalfPageDown);
+    }
+
+    /// Scroll up the textarea by half of the page. The page size is the height of the viewport at the last render.
+    /// The cursor moves up by the same number of rows keeping its column. This method does nothing before the first
+    /// render. This is equivalent to `textarea.scroll(Scrolling::HalfPageUp)`.
+    pub fn scroll_half_page_up(&mut self) {
+        self.scroll(Scrolling::HalfPageUp);
+    }
+
+    /// Set the minimal number of rows kept above and below the cursor, like the `scrolloff` option of Vim. The
+    /// textarea starts scrolling before the cursor reaches the top or bottom edge of the viewport so that the context
+    /// around the cursor stays visible. The margin is limited to the half of the viewport height. Scrolling the
+    /// textarea with [`TextArea::scroll`] moves the cursor to keep the margin. The default value is 0.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{CursorMove, TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// textarea.set_scrolloff(2);
+    /// assert_eq!(textarea.scrolloff(), 2);
+    ///
+    /// // Render the textarea with height 8
+    /// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// let mut b = Buffer::empty(r.clone());
+    ///
+    /// // Move the cursor to line 6. Two lines below the cursor are visible
+    /// textarea.move_cursor(CursorMove::Jump(6, 0));
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// assert_eq!(b[(0, 0)].symbol(), "1");
+    /// ```
+    pub fn set_scrolloff(&mut self, rows: u16) {
+        self.scrolloff = rows;
+    }
+
+    /// Get the minimal number of rows kept above and below the cursor. See [`TextArea::set_scrolloff`].
+    pub fn scrolloff(&self) -> u16 {
+        self.scrolloff
+    }
+
+    /// Set the minimal number of columns kept at the left and right of the cursor on scrolling horizontally, like
+    /// the `sidescrolloff` option of Vim. The margin is limited to the half of the viewport width. The default value
+    /// is 0.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_sidescrolloff(5);
+    /// assert_eq!(textarea.sidescrolloff(), 5);
+    /// ```
+    pub fn set_sidescrolloff(&mut self, cols: u16) {
+        self.sidescrolloff = cols;
+    }
+
+    /// Get the minimal number of columns kept at the left and right of the cursor. See
+    /// [`TextArea::set_sidescrolloff`].
+    pub fn sidescrolloff(&self) -> u16 {
+        self.sidescrolloff
+    }
+
+    /// Set the minimal number of columns to scroll horizontally when the cursor goes out of the viewport, like the
+    /// `sidescroll` option of Vim. Scrolling by several columns at once reduces how often the viewport moves while
+    /// typing a long line. When 0 is set, the viewport scrolls by the half of its width. The step is limited to the
+    /// half of the viewport width. The default value is 1.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{CursorMove, TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea = TextArea::from(["0123456789abcdefghij"]);
+    /// textarea.set_hscroll_step(4);
+    /// assert_eq!(textarea.hscroll_step(), 4);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 1 };
+    /// let mut b = Buffer::empty(r.clone());
+    ///
+    /// // The cursor goes out of the viewport by one column but the viewport scrolls by 4 columns
+    /// textarea.move_cursor(CursorMove::Jump(0, 10));
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// assert_eq!(b[(0, 0)].symbol(), "4");
+    /// ```
+    pub fn set_hscroll_step(&mut self, cols: u16) {
+        self.hscroll_step = cols;
+    }
+
+    /// Get the minimal number of columns to scroll horizontally. See [`TextArea::set_hscroll_step`].
+    pub fn hscroll_step(&self) -> u16 {
+        self.hscroll_step
+    }
+
+    fn scroll_cursor_to(&mut self, anchor: CursorAnchor) {
+        let (_, _, _, height) = self.viewport.rect();
+        if height == 0 {
+            // The viewport size is not known until the first render. Apply the scroll on rendering.
+            self.cursor_anchor = Some(anchor);
+            return;
         }
-        scrolling.scroll(&mut self.viewport);
-        self.move_cursor_with_shift(CursorMove::InViewport, shift);
+        let top = self.anchored_top_row(anchor, height);
+        self.viewport.set_top_row(top as u16);
+    }
+
+    // Row of the first line in the viewport to place the cursor line at the anchor. Rows are counted on screen since
+    // folded lines are hidden.
+    pub(crate) fn anchored_top_row(&self, anchor: CursorAnchor, height: u16) -> usize {
+        let cursor_row = self.folds.display_row(self.cursor.0);
+        let num_rows = self.folds.display_row(self.lines.len() - 1) + 1;
+        let top = anchor.top_row(cursor_row, height as usize, num_rows);
+        self.folds.text_row(top)
+    }
+
+    /// Scroll the textarea so that the cursor line is placed at the center of the viewport, like `zz` in Vim. The
+    /// cursor does not move. The viewport does not scroll beyond the start or the end of the text so the cursor line
+    /// may not be placed at the center when the cursor is near the start or the end. When the textarea has not been
+    /// rendered yet, the scroll is applied on the next render.
+    /// ```
+    /// # use ratatui::buffer::Buffer;
+    /// # use ratatui::layout::Rect;
+    /// # use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{TextArea, TextAreaWidget, CursorMove};
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    ///
+    /// // Render the textarea with height 8
+    /// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// let mut b = Buffer::empty(r.clone());
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(10, 0));
+    /// textarea.scroll_cursor_center();
+    ///
+    /// // Line 10 is rendered at the center of the viewport
+    /// TextAreaWidget::new().render(r, &mut b, &mut textarea);
+    /// assert_eq!(b[(0, 0)].symbol(), "6");
+    /// assert_eq!(b[(0, 4)].symbol(), "1");
+    /// assert_eq!(b[(1, 4)].symbol(), "0");
+    /// ```
+    pub fn scroll_cursor_center(&mut self) {
+        self.scroll_cursor_to(CursorAnchor::Center);
+    }
+
+    /// Scroll the textarea so that the cursor line is placed at the top of the viewport, like `zt` in Vim. The cursor
+    /// does not move. The viewport does not scroll beyond the end of the text so the cursor line may not be placed at
+    /// the top when the cursor is near the end. When the textarea has not been rendered yet, the scroll is applied on
+    /// the next render.
+    pub fn scroll_cursor_top(&mut self) {
+        self.scroll_cursor_to(CursorAnchor::Top);
+    }
+
+    /// Scroll the textarea so that the cursor line is placed at the bottom of the viewport, like `zb` in Vim. The
+    /// cursor does not move. The viewport does not scroll beyond the start of the text so the cursor line may not be
+    /// placed at the bottom when the cursor is near the start. When the textarea has not been rendered yet, the scroll
+    /// is applied on the next render.
+    pub fn scroll_cursor_bottom(&mut self) {
+        self.scroll_cursor_to(CursorAnchor::Bottom);
     }
 }
 
@@ -2315,4 +7290,140 @@ mod tests {
         textarea.scroll((-5, 0));
         assert_eq!(textarea.cursor(), (12, 0));
     }
+
+    #[test]
+    fn restore_invalid_snapshot() {
+        let mut textarea = TextArea::from(["abc", "def"]);
+        let snapshot = TextAreaSnapshot {
+            lines: vec![],
+            cursor: (3, 3),
+            selection_start: Some((1, 0)),
+        };
+        textarea.restore(&snapshot, false);
+        assert_eq!(textarea.lines(), [""]);
+        assert_eq!(textarea.cursor(), (0, 0));
+        assert_eq!(textarea.selection_start, Some((0, 0)));
+
+        let snapshot = TextAreaSnapshot {
+            lines: vec!["ab".to_string(), "c".to_string()],
+            cursor: (1, 5),
+            selection_start: Some((9, 9)),
+        };
+        textarea.restore(&snapshot, true);
+        assert_eq!(textarea.lines(), ["ab", "c"]);
+        assert_eq!(textarea.cursor(), (1, 1));
+        assert_eq!(textarea.selection_start, Some((1, 1)));
+        assert!(textarea.undo());
+        assert_eq!(textarea.lines(), [""]);
+    }
+
+    #[test]
+    fn clamp_positions_before_render() {
+        use crate::ratatui::buffer::Buffer;
+        use crate::ratatui::layout::Rect;
+
+        let mut textarea = TextArea::from(["abc", "def", "ghi"]);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 2,
+        };
+        let render = |textarea: &mut TextArea| {
+            let mut b = Buffer::empty(r);
+            StatefulWidget::render(TextAreaWidget::new(), r, &mut b, textarea);
+            b
+        };
+        textarea.move_cursor(CursorMove::Bottom);
+        render(&mut textarea);
+        assert_eq!(textarea.viewport.scroll_top(), (1, 0));
+
+        // Simulate positions left out of the text after the lines are deleted
+        textarea.lines.truncate(1);
+        textarea.cursor = (2, 1);
+        textarea.selection_start = Some((1, 5));
+        let b = render(&mut textarea);
+        assert_eq!(b[(0, 0)].symbol(), "a");
+        assert_eq!(textarea.cursor(), (0, 1));
+        assert_eq!(textarea.selection_start, Some((0, 3)));
+        assert_eq!(textarea.viewport.scroll_top(), (0, 0));
+
+        textarea.lines[0].clear();
+        textarea.clamp_positions();
+        assert_eq!(textarea.cursor(), (0, 0));
+        assert_eq!(textarea.selection_start, Some((0, 0)));
+    }
+
+    #[test]
+    fn shift_row_decorations() {
+        let red = Style::default().fg(Color::Red);
+        let mut t = TextArea::from(["a", "b", "c", "d", "e"]);
+        t.set_line_sign(1, Some(('*', red)));
+        t.set_highlights(vec![Highlight {
+            start: (1, 0),
+            end: (2, 1),
+            style: red,
+        }]);
+        t.set_diff(&[(2, DiffKind::Added)]);
+        t.set_virtual_text(3, vec![("v".to_string(), red)]);
+        t.set_extra_selections(vec![((3, 0), (3, 1))]);
+        t.set_line_alignment(4, Some(Alignment::Right));
+        t.fold(2, 4);
+
+        let assert_rows = |t: &TextArea, offset: usize, context: &str| {
+            let row = |r: usize| r + offset;
+            assert_eq!(t.line_sign(row(1)), Some(('*', red)), "{context}");
+            assert_eq!(t.highlights()[0].start, (row(1), 0), "{context}");
+            assert_eq!(t.highlights()[0].end, (row(2), 1), "{context}");
+            assert_eq!(t.line_diff(row(2)), Some(DiffKind::Added), "{context}");
+            assert!(t.virtual_text(row(3)).is_some(), "{context}");
+            assert_eq!(
+                t.extra_selections(),
+                [((row(3), 0), (row(3), 1))],
+                "{context}"
+            );
+            assert_eq!(t.line_alignment(row(4)), Alignment::Right, "{context}");
+            assert_eq!(t.folded_range(row(3)), Some((row(2), row(4))), "{context}");
+        };
+        assert_rows(&t, 0, "initial");
+
+        // Lines inserted at the head of a line move it
+        t.insert_newline();
+        assert_rows(&t, 1, "insert newline");
+        t.insert_str("x\ny\n");
+        assert_rows(&t, 3, "insert lines");
+        assert!(t.undo());
+        assert_rows(&t, 1, "undo inserting lines");
+        assert!(t.undo());
+        assert_rows(&t, 0, "undo inserting newline");
+        assert!(t.redo());
+        assert_rows(&t, 1, "redo inserting newline");
+
+        // Lines inserted in the middle of a line move only the lines after it
+        t.move_cursor(CursorMove::Jump(2, 1));
+        t.insert_newline();
+        assert_eq!(t.line_sign(2), Some(('*', red)));
+        assert_eq!(t.line_diff(4), Some(DiffKind::Added));
+        assert!(t.undo());
+
+        // Decorations on the removed lines are dropped
+        t.move_cursor(CursorMove::Jump(1, 0));
+        t.start_selection();
+        t.move_cursor(CursorMove::Jump(3, 0));
+        assert!(t.cut());
+        assert_eq!(t.lines(), ["", "c", "d", "e"]);
+        assert_eq!(t.line_sign(0), None);
+        assert_eq!(t.line_sign(1), None);
+        assert!(t.highlights().is_empty());
+        assert_eq!(t.line_diff(1), Some(DiffKind::Added));
+        assert!(t.virtual_text(2).is_some());
+        assert_eq!(t.extra_selections(), [((2, 0), (2, 1))]);
+        assert_eq!(t.line_alignment(3), Alignment::Right);
+        assert_eq!(t.folded_range(2), Some((1, 3)));
+
+        // Replacing the whole text moves the rows as well
+        t.set_text("z\n\nc\nd\ne");
+        assert_eq!(t.line_diff(2), Some(DiffKind::Added));
+        assert_eq!(t.folded_range(3), Some((2, 4)));
+    }
 }
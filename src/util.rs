@@ -1,3 +1,7 @@
+use std::cmp;
+use unicode_segmentation::UnicodeSegmentation as _;
+use unicode_width::UnicodeWidthChar as _;
+
 pub fn spaces(size: u8) -> &'static str {
     const SPACES: &str = "                                                                                                                                                                                                                                                                ";
     &SPACES[..size as usize]
@@ -7,6 +11,52 @@ pub fn num_digits(i: usize) -> u8 {
     f64::log10(i as f64) as u8 + 1
 }
 
+// Width of the character on screen. The width of a hard tab depends on the display column `col` where it is rendered.
+pub fn char_display_width(c: char, col: usize, tab_len: u8) -> usize {
+    if c == '\t' {
+        if tab_len > 0 {
+            tab_len as usize - col % tab_len as usize
+        } else {
+            0
+        }
+    } else {
+        c.width().unwrap_or(0)
+    }
+}
+
+// Width of the hard tab rendered at the display column `col` which advances to the next tab stop. Past the last stop,
+// the tab is expanded with the tab length.
+pub fn tab_stop_width(col: usize, stops: &[u16], tab_len: u8) -> usize {
+    match stops.iter().find(|&&stop| stop as usize > col) {
+        Some(&stop) => stop as usize - col,
+        None => char_display_width('\t', col, tab_len),
+    }
+}
+
+// Character offsets of the boundaries of the grapheme clusters in the line, including 0 and the end of the line.
+fn grapheme_boundaries(line: &str) -> impl Iterator<Item = usize> + '_ {
+    let mut col = 0;
+    let rest = line.graphemes(true).map(move |g| {
+        col += g.chars().count();
+        col
+    });
+    std::iter::once(0).chain(rest)
+}
+
+// Character offset of the head of the grapheme cluster after the one at `col`. When `col` is at the end of the line,
+// `col` is returned.
+pub fn next_grapheme_col(line: &str, col: usize) -> usize {
+    grapheme_boundaries(line).find(|&c| c > col).unwrap_or(col)
+}
+
+// Character offset of the head of the grapheme cluster before the `col`-th character. When `col` is 0, 0 is returned.
+pub fn prev_grapheme_col(line: &str, col: usize) -> usize {
+    grapheme_boundaries(line)
+        .take_while(|&c| c < col)
+        .last()
+        .unwrap_or(0)
+}
+
 #[derive(Debug, Clone)]
 pub struct Pos {
     pub row: usize,
@@ -19,3 +69,51 @@ impl Pos {
         Self { row, col, offset }
     }
 }
+
+// Lines moved by an edit of the text. Lines before `row` stay, `removed` lines from `row` are removed, and `added`
+// lines are inserted at `row`. Lines modified in place are not counted. Positions of decorations attached to rows like
+// signs and folds are moved with this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowShift {
+    pub row: usize,
+    pub removed: usize,
+    pub added: usize,
+}
+
+impl RowShift {
+    // Shift from the lines before an edit to the lines after the edit. Lines between the common head and tail are
+    // considered modified in place as far as possible. `None` is returned when no line is moved.
+    pub fn between(before: &[String], after: &[String]) -> Option<Self> {
+        let head = before.iter().zip(after).take_while(|(b, a)| b == a).count();
+        let tail = before[head..]
+            .iter()
+            .rev()
+            .zip(after[head..].iter().rev())
+            .take_while(|(b, a)| b == a)
+            .count();
+        let (removed, added) = (before.len() - head - tail, after.len() - head - tail);
+        let modified = cmp::min(removed, added);
+        Self {
+            row: head + modified,
+            removed: removed - modified,
+            added: added - modified,
+        }
+        .moved()
+    }
+
+    // `None` is returned when no line is moved
+    pub fn moved(self) -> Option<Self> {
+        (self.removed > 0 || self.added > 0).then(|| self)
+    }
+
+    // Row of the line after the edit. `None` is returned when the line was removed
+    pub fn row(&self, row: usize) -> Option<usize> {
+        if row < self.row {
+            Some(row)
+        } else if row < self.row + self.removed {
+            None
+        } else {
+            Some(row - self.removed + self.added)
+        }
+    }
+}
@@ -1,68 +1,92 @@
-use ratatui::widgets::{Block, StatefulWidget};
+use ratatui::widgets::{
+    Block, Padding, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
+};
 
+use crate::cache::RenderScratch;
 use crate::ratatui::buffer::Buffer;
-use crate::ratatui::layout::Rect;
+use crate::ratatui::layout::{Alignment, Rect};
+use crate::ratatui::style::Style;
 use crate::ratatui::text::Text;
 use crate::ratatui::widgets::{Paragraph, Widget};
 use crate::textarea::TextArea;
-use crate::util::num_digits;
+use crate::util::spaces;
+#[cfg(feature = "ratatui")]
+use ratatui::text::Line;
+use std::borrow::Cow;
 use std::cmp;
-use std::sync::atomic::{AtomicU64, Ordering};
-
-// &mut 'a (u16, u16, u16, u16) is not available since Renderer instance totally takes over the ownership of TextArea
-// instance. In the case, the TextArea instance cannot be accessed from any other objects since it is mutablly
-// borrowed.
-//
-// `tui::terminal::Frame::render_stateful_widget` would be an assumed way to render a stateful widget. But at this
-// point we stick with using `tui::terminal::Frame::render_widget` because it is simpler API. Users don't need to
-// manage states of textarea instances separately.
-// https://docs.rs/tui/latest/tui/terminal/struct.Frame.html#method.render_stateful_widget
-#[derive(Default, Debug)]
-pub struct Viewport(AtomicU64);
-
-impl Clone for Viewport {
-    fn clone(&self) -> Self {
-        let u = self.0.load(Ordering::Relaxed);
-        Viewport(AtomicU64::new(u))
-    }
+use std::mem;
+#[cfg(feature = "tuirs")]
+use tui::text::Spans as Line;
+use unicode_segmentation::UnicodeSegmentation as _;
+use unicode_width::UnicodeWidthStr as _;
+
+/// Scroll position and size of the area where a textarea was rendered. A [`TextArea`] has its own viewport, and
+/// [`TextAreaWidget::with_viewport`] renders the textarea with a separate viewport so that one textarea can be rendered
+/// in multiple panes scrolled independently. The viewport is updated through the `&mut` state passed to
+/// [`StatefulWidget::render`], so it is plain data and no interior mutability is involved.
+///
+/// Positions in the viewport are `u16`, so the viewport can scroll only up to row and column `u16::MAX`. When the
+/// cursor is beyond the limit in a text with more than 65535 lines or in a longer line, the scroll position is clamped
+/// at the limit and the cursor is not displayed in the viewport.
+/// ```
+/// use tui_textarea::Viewport;
+///
+/// let viewport = Viewport::default();
+/// // The viewport is empty until rendered
+/// assert_eq!(viewport.rect(), (0, 0, 0, 0));
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    row: u16,
+    col: u16,
+    width: u16,
+    height: u16,
+    // Position of the text area on screen. It is used to map mouse positions into the text
+    x: u16,
+    y: u16,
 }
 
 impl Viewport {
+    /// Get the row and the column at the top-left of the viewport on the last render.
     pub fn scroll_top(&self) -> (u16, u16) {
-        let u = self.0.load(Ordering::Relaxed);
-        ((u >> 16) as u16, u as u16)
+        (self.row, self.col)
     }
 
+    /// Get the row and the column at the top-left of the viewport and the width and the height of the viewport on
+    /// the last render.
     pub fn rect(&self) -> (u16, u16, u16, u16) {
-        let u = self.0.load(Ordering::Relaxed);
-        let width = (u >> 48) as u16;
-        let height = (u >> 32) as u16;
-        let row = (u >> 16) as u16;
-        let col = u as u16;
-        (row, col, width, height)
+        (self.row, self.col, self.width, self.height)
     }
 
-    pub fn position(&self) -> (u16, u16, u16, u16) {
-        let (row_top, col_top, width, height) = self.rect();
-        let row_bottom = row_top.saturating_add(height).saturating_sub(1);
-        let col_bottom = col_top.saturating_add(width).saturating_sub(1);
+    pub(crate) fn position(&self) -> (u16, u16, u16, u16) {
+        let row_bottom = self.row.saturating_add(self.height).saturating_sub(1);
+        let col_bottom = self.col.saturating_add(self.width).saturating_sub(1);
 
         (
-            row_top,
-            col_top,
-            cmp::max(row_top, row_bottom),
-            cmp::max(col_top, col_bottom),
+            self.row,
+            self.col,
+            cmp::max(self.row, row_bottom),
+            cmp::max(self.col, col_bottom),
         )
     }
 
-    fn store(&self, row: u16, col: u16, width: u16, height: u16) {
-        // Pack four u16 values into one u64 value
-        let u =
-            ((width as u64) << 48) | ((height as u64) << 32) | ((row as u64) << 16) | col as u64;
-        self.0.store(u, Ordering::Relaxed);
+    pub(crate) fn origin(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+
+    fn store_origin(&mut self, x: u16, y: u16) {
+        self.x = x;
+        self.y = y;
     }
 
-    pub fn scroll(&mut self, rows: i16, cols: i16) {
+    fn store(&mut self, row: u16, col: u16, width: u16, height: u16) {
+        self.row = row;
+        self.col = col;
+        self.width = width;
+        self.height = height;
+    }
+
+    pub(crate) fn scroll(&mut self, rows: i16, cols: i16) {
         fn apply_scroll(pos: u16, delta: i16) -> u16 {
             if delta >= 0 {
                 pos.saturating_add(delta as u16)
@@ -71,16 +95,170 @@ impl Viewport {
             }
         }
 
-        let u = self.0.get_mut();
-        let row = apply_scroll((*u >> 16) as u16, rows);
-        let col = apply_scroll(*u as u16, cols);
-        *u = (*u & 0xffff_ffff_0000_0000) | ((row as u64) << 16) | (col as u64);
+        self.row = apply_scroll(self.row, rows);
+        self.col = apply_scroll(self.col, cols);
     }
+
+    pub(crate) fn set_top_row(&mut self, row: u16) {
+        self.row = row;
+    }
+
+    // Ensure the viewport does not scroll down beyond the last line
+    pub(crate) fn clamp_top_row(&mut self, max_row: usize) {
+        if self.row as usize > max_row {
+            self.row = cmp::min(max_row, u16::MAX as usize) as u16;
+        }
+    }
+}
+
+/// Configuration of the scrollbars rendered by [`TextAreaWidget::scrollbar`]. By default, only the vertical scrollbar
+/// is rendered.
+/// ```
+/// use ratatui::style::{Color, Style};
+/// use tui_textarea::ScrollbarConfig;
+///
+/// // Render both vertical and horizontal scrollbars with the red thumb
+/// let config = ScrollbarConfig {
+///     horizontal: true,
+///     thumb_style: Style::default().fg(Color::Red),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollbarConfig {
+    /// Render the vertical scrollbar at the right edge of the textarea.
+    pub vertical: bool,
+    /// Render the horizontal scrollbar at the bottom edge of the textarea.
+    pub horizontal: bool,
+    /// Style of the track of the scrollbars.
+    pub track_style: Style,
+    /// Style of the thumb of the scrollbars.
+    pub thumb_style: Style,
+}
+
+impl Default for ScrollbarConfig {
+    fn default() -> Self {
+        Self {
+            vertical: true,
+            horizontal: false,
+            track_style: Style::default(),
+            thumb_style: Style::default(),
+        }
+    }
+}
+
+impl ScrollbarConfig {
+    fn render(
+        &self,
+        orientation: ScrollbarOrientation,
+        area: Rect,
+        buf: &mut Buffer,
+        mut state: ScrollbarState,
+    ) {
+        Scrollbar::new(orientation)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .track_style(self.track_style)
+            .thumb_style(self.thumb_style)
+            .render(area, buf, &mut state);
+    }
+}
+
+// State of a scrollbar whose thumb covers the viewport of `len` at `pos` in the content of `total`
+fn scrollbar_state(pos: usize, len: usize, total: usize) -> ScrollbarState {
+    // Ratatui's scrollbar state counts scroll positions as its content length
+    let positions = cmp::max(total.saturating_sub(len), pos) + 1;
+    ScrollbarState::new(positions)
+        .position(pos)
+        .viewport_content_length(len)
+}
+
+// Remove the first `cols` columns of the line for horizontal scroll. When a wide character is split at the boundary,
+// its visible cell is replaced with a space so that the rest of the line stays at the same columns on screen.
+fn skip_columns(mut line: Line<'_>, cols: usize) -> Line<'_> {
+    #[cfg(feature = "ratatui")]
+    let spans = &mut line.spans;
+    #[cfg(feature = "tuirs")]
+    let spans = &mut line.0;
+
+    let mut skip = cols;
+    let mut skipped = 0;
+    for span in spans.iter_mut() {
+        let width = span.content.width();
+        if width <= skip {
+            skip -= width;
+            skipped += 1;
+            continue;
+        }
+
+        let (mut offset, mut col) = (span.content.len(), 0);
+        for (i, g) in span.content.grapheme_indices(true) {
+            if col >= skip {
+                offset = i;
+                break;
+            }
+            col += g.width();
+        }
+        let pad = col.saturating_sub(skip);
+        span.content = match mem::take(&mut span.content) {
+            Cow::Borrowed(s) if pad == 0 => Cow::Borrowed(&s[offset..]),
+            content => Cow::Owned(format!("{}{}", spaces(pad as u8), &content[offset..])),
+        };
+        break;
+    }
+    spans.drain(..skipped);
+    line
+}
+
+// Keep the first `cols` columns of the line. A wide character which does not fit in the columns is removed as it is
+// not rendered on screen.
+pub(crate) fn take_columns(mut line: Line<'_>, cols: usize) -> Line<'_> {
+    #[cfg(feature = "ratatui")]
+    let spans = &mut line.spans;
+    #[cfg(feature = "tuirs")]
+    let spans = &mut line.0;
+
+    let mut rest = cols;
+    let mut kept = 0;
+    for span in spans.iter_mut() {
+        let width = span.content.width();
+        if width <= rest {
+            rest -= width;
+            kept += 1;
+            continue;
+        }
+
+        let mut offset = 0;
+        for (i, g) in span.content.grapheme_indices(true) {
+            let w = g.width();
+            if w > rest {
+                break;
+            }
+            rest -= w;
+            offset = i + g.len();
+        }
+        if offset > 0 {
+            span.content = match mem::take(&mut span.content) {
+                Cow::Borrowed(s) => Cow::Borrowed(&s[..offset]),
+                Cow::Owned(mut s) => {
+                    s.truncate(offset);
+                    Cow::Owned(s)
+                }
+            };
+            kept += 1;
+        }
+        break;
+    }
+    spans.truncate(kept);
+    line
 }
 
 #[derive(Default)]
 pub struct TextAreaWidget<'a> {
-    block: Option<Block<'a>>
+    block: Option<Block<'a>>,
+    padding: Padding,
+    scrollbar: Option<ScrollbarConfig>,
+    viewport: Option<&'a mut Viewport>,
 }
 
 impl<'a> TextAreaWidget<'a> {
@@ -101,22 +279,186 @@ impl<'a> TextAreaWidget<'a> {
         self.block = Some(block);
         self
     }
+
+    /// Set the padding between the block and the text. The text is inset by the padding inside the inner area of the
+    /// block, in addition to the padding of the block itself. Scrollbars are rendered inside the padding. Mouse
+    /// positions are mapped into the text with the inset, so [`TextArea::on_mouse`] works as without padding. By
+    /// default, no padding is set.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::{Block, Padding, StatefulWidget};
+    /// use tui_textarea::{TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 5 };
+    /// let mut b = Buffer::empty(r);
+    /// let widget = TextAreaWidget::new()
+    ///     .block(Block::bordered())
+    ///     .padding(Padding::horizontal(1));
+    /// widget.render(r, &mut b, &mut textarea);
+    ///
+    /// // The text starts after the border and the padding
+    /// assert_eq!(b[(2, 1)].symbol(), "h");
+    /// ```
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Render scrollbars inside the block of the textarea. The vertical scrollbar occupies the rightmost column and
+    /// the horizontal scrollbar occupies the bottom row so the text is rendered in the rest of the area. The thumb of
+    /// the vertical scrollbar shows the visible rows in the whole text, and the thumb of the horizontal scrollbar
+    /// shows the visible columns in the longest line on screen. By default, no scrollbar is rendered.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{ScrollbarConfig, TextArea, TextAreaWidget};
+    ///
+    /// let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 5 };
+    /// let mut b = Buffer::empty(r);
+    /// let widget = TextAreaWidget::new().scrollbar(ScrollbarConfig::default());
+    /// widget.render(r, &mut b, &mut textarea);
+    ///
+    /// // The thumb is rendered at the top of the rightmost column
+    /// assert_eq!(b[(9, 0)].symbol(), "█");
+    /// assert_eq!(b[(9, 1)].symbol(), "║");
+    /// ```
+    pub fn scrollbar(mut self, config: ScrollbarConfig) -> Self {
+        self.scrollbar = Some(config);
+        self
+    }
+
+    /// Render the textarea with the viewport instead of the textarea's own viewport. The scroll position is read from
+    /// the viewport and the viewport is updated to keep the cursor visible in it. This is useful to render one
+    /// textarea in multiple panes, each of which keeps its own scroll position. Note that methods of [`TextArea`]
+    /// depending on the viewport like [`TextArea::scroll`] and [`TextArea::on_mouse`] use the textarea's own viewport.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::StatefulWidget;
+    /// use tui_textarea::{CursorMove, TextArea, TextAreaWidget, Viewport};
+    ///
+    /// let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+    /// let (mut left, mut right) = (Viewport::default(), Viewport::default());
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 5 };
+    /// let mut b = Buffer::empty(r);
+    ///
+    /// TextAreaWidget::new().with_viewport(&mut left).render(r, &mut b, &mut textarea);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// TextAreaWidget::new().with_viewport(&mut right).render(r, &mut b, &mut textarea);
+    ///
+    /// assert_eq!(left.scroll_top(), (0, 0));
+    /// assert_eq!(right.scroll_top(), (15, 0));
+    /// ```
+    pub fn with_viewport(mut self, viewport: &'a mut Viewport) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
 }
 
 impl<'a> StatefulWidget for TextAreaWidget<'a> {
     type State = TextArea;
-    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let Rect { width, height, .. } = if let Some(b) = &self.block {
+    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        // Swap the viewport of the widget with the textarea's own one while rendering
+        match self.viewport.take() {
+            Some(viewport) => {
+                mem::swap(&mut state.viewport, viewport);
+                self.render_textarea(area, buf, state);
+                mem::swap(&mut state.viewport, viewport);
+            }
+            None => self.render_textarea(area, buf, state),
+        }
+    }
+}
+
+/// Render the textarea with the default [`TextAreaWidget`]. This allows rendering `&mut TextArea` with
+/// `Frame::render_widget` as well as with `Frame::render_stateful_widget`.
+/// ```
+/// # use ratatui::buffer::Buffer;
+/// # use ratatui::layout::Rect;
+/// use ratatui::widgets::Widget;
+/// use tui_textarea::TextArea;
+///
+/// let mut textarea = TextArea::from(["hello"]);
+/// let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+/// let mut b = Buffer::empty(r.clone());
+/// (&mut textarea).render(r, &mut b);
+/// assert_eq!(b[(0, 0)].symbol(), "h");
+/// ```
+impl Widget for &mut TextArea {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        StatefulWidget::render(TextAreaWidget::new(), area, buf, self);
+    }
+}
+
+impl<'a> TextAreaWidget<'a> {
+    fn render_textarea(self, area: Rect, buf: &mut Buffer, state: &mut TextArea) {
+        // Positions out of the text would make indexing lines below panic
+        state.clamp_positions();
+
+        let mut inner_area = if let Some(b) = &self.block {
             b.inner(area)
         } else {
             area
         };
+        let left = cmp::min(self.padding.left, inner_area.width);
+        let top = cmp::min(self.padding.top, inner_area.height);
+        inner_area = Rect {
+            x: inner_area.x + left,
+            y: inner_area.y + top,
+            width: (inner_area.width - left).saturating_sub(self.padding.right),
+            height: (inner_area.height - top).saturating_sub(self.padding.bottom),
+        };
+        // Reserve the rightmost column and the bottom row for the scrollbars
+        let vertical = self
+            .scrollbar
+            .filter(|c| c.vertical && inner_area.width > 0);
+        let horizontal = self
+            .scrollbar
+            .filter(|c| c.horizontal && inner_area.height > 0);
+        if vertical.is_some() {
+            inner_area.width -= 1;
+        }
+        if horizontal.is_some() {
+            inner_area.height -= 1;
+        }
+
+        // The placeholder is hidden while composing text with IME
+        let show_placeholder =
+            !state.placeholder.is_empty() && state.is_empty() && state.preedit().is_none();
+        let lnum_len = state.line_number_len();
+        let gutter_width = if show_placeholder {
+            0
+        } else {
+            cmp::min(state.gutter_width(lnum_len), inner_area.width)
+        };
+        // The gutter is rendered separately from the text so that it is not scrolled horizontally
+        let gutter_area = Rect {
+            width: gutter_width,
+            ..inner_area
+        };
+        let text_area = Rect {
+            x: inner_area.x + gutter_width,
+            width: inner_area.width - gutter_width,
+            ..inner_area
+        };
+        let Rect { width, height, .. } = text_area;
 
-        fn next_scroll_top(prev_top: u16, cursor: u16, length: u16) -> u16 {
-            if cursor < prev_top {
-                cursor
-            } else if prev_top + length <= cursor {
-                cursor + 1 - length
+        // `margin` is the number of rows (or columns) kept visible around the cursor
+        fn next_scroll_top(prev_top: u16, cursor: u16, length: u16, margin: u16) -> u16 {
+            if length == 0 {
+                return prev_top; // Nothing is visible in the empty area so it is not scrolled
+            }
+            let margin = cmp::min(margin, length.saturating_sub(1) / 2);
+            if cursor < prev_top.saturating_add(margin) {
+                cursor.saturating_sub(margin)
+            } else if prev_top.saturating_add(length) <= cursor.saturating_add(margin) {
+                cursor.saturating_add(margin).saturating_add(1) - length
             } else {
                 prev_top
             }
@@ -124,43 +466,1796 @@ impl<'a> StatefulWidget for TextAreaWidget<'a> {
 
         let cursor = state.cursor();
         let (top_row, top_col) = state.viewport.scroll_top();
-        let top_row = next_scroll_top(top_row, cursor.0 as u16, height);
-        let top_col = next_scroll_top(top_col, cursor.1 as u16, width);
+        let top_row = match state.cursor_anchor.take() {
+            Some(anchor) => {
+                cmp::min(state.anchored_top_row(anchor, height), u16::MAX as usize) as u16
+            }
+            None => top_row,
+        };
+        // Calculate the scroll position with rows on screen since folded lines are hidden
+        let folds = state.folds();
+        let num_rows = cmp::min(state.display_height(width), u16::MAX as usize) as u16;
+        // The text may have become shorter than the previous scroll position, for example by replacing the whole text.
+        // Show the last line at the bottom instead of blank rows
+        let top = if (top_row as usize) < state.lines().len() {
+            let top = cmp::min(folds.display_row(top_row as usize), u16::MAX as usize) as u16;
+            cmp::min(top, num_rows.saturating_sub(height))
+        } else {
+            num_rows.saturating_sub(height)
+        };
+        // Rows past the range of the viewport are clamped instead of being wrapped around
+        let cursor_row = cmp::min(folds.display_row(cursor.0), u16::MAX as usize) as u16;
+        let next_top = next_scroll_top(top, cursor_row, height, state.scrolloff());
+        // Scroll margin does not scroll the viewport beyond the end of the text
+        let top = if next_top > top {
+            cmp::min(next_top, cmp::max(top, num_rows.saturating_sub(height)))
+        } else {
+            next_top
+        };
+        let top_row = cmp::min(folds.text_row(top as usize), u16::MAX as usize) as u16;
+        // Horizontal scroll is calculated with the display column since hard tabs and wide characters occupy multiple
+        // columns on screen
+        let cursor_col = cmp::min(state.cursor_display_col(), u16::MAX as usize) as u16;
+        let top_col = if state.line_alignment(cursor.0) == Alignment::Left {
+            let next = next_scroll_top(top_col, cursor_col, width, state.sidescrolloff());
+            // Keep the whole character at the cursor visible so that a wide character is not cut at the right edge
+            let cell_width = cmp::min(state.cursor_cell_width(), width as usize) as u16;
+            let next = cmp::max(
+                next,
+                cursor_col.saturating_add(cell_width).saturating_sub(width),
+            );
+            // Scroll by at least the step. It is limited to the half of the width not to hide the cursor
+            let step = match state.hscroll_step() {
+                0 => width / 2,
+                step => cmp::min(step, width / 2),
+            };
+            let step = cmp::max(step, 1);
+            match next.cmp(&top_col) {
+                cmp::Ordering::Greater => cmp::max(next, top_col.saturating_add(step)),
+                cmp::Ordering::Less => cmp::min(next, top_col.saturating_sub(step)),
+                cmp::Ordering::Equal => next,
+            }
+        } else {
+            0 // Lines aligned to the center or the right are not scrolled horizontally
+        };
 
-        let mut lines = Vec::new();
-        let (text, style) = if !state.placeholder.is_empty() && state.is_empty() {
+        // Buffers for rows, widths, and fills are reused across renders
+        let mut scratch = mem::take(&mut state.render_scratch);
+        scratch.clear();
+        let RenderScratch {
+            rows,
+            widths: line_widths,
+            fills,
+        } = &mut scratch;
+        let mut gutter = Vec::new();
+        let mut num_rows = 0;
+        let (text, style) = if show_placeholder {
             let text = Text::from(state.placeholder.as_str());
             (text, state.placeholder_style)
         } else {
-            let top_row = top_row as usize;
-            let height = height as usize;
             let lines_len = state.lines().len();
-            let lnum_len = num_digits(lines_len);
-            let bottom_row = cmp::min(top_row + height, lines_len);
-            for (i, line) in state.lines()[top_row..bottom_row].iter().enumerate() {
-                lines.push(state.line_spans(line.as_str(), top_row + i, lnum_len));
+            let mut row = top_row as usize;
+            while rows.len() < height as usize && row < lines_len {
+                rows.push(row);
+                row = state.folds().next_row(row);
+            }
+            if gutter_width > 0 {
+                gutter.reserve_exact(rows.len());
+            }
+            let mut lines = Vec::with_capacity(rows.len());
+
+            #[cfg(feature = "syntect")]
+            if let Some(&last) = rows.last() {
+                state.update_syntax(last);
             }
-            
+
+            // Spans of lines without the cursor and the selection are reused from the previous render
+            let mut cache = mem::take(&mut state.render_cache);
+            let cached_rows = rows.iter().copied().filter(|&r| !state.has_overlay(r));
+            let local = !state.spans_depend_on_other_lines();
+            cache.update(state.revision(), state.lines(), local, cached_rows, |r| {
+                state.line_spans(&state.lines()[r], r)
+            });
+            state.render_cache = cache;
+
+            for (i, &row) in rows.iter().enumerate() {
+                if gutter_width > 0 {
+                    gutter.push(state.gutter_spans(row, lnum_len));
+                }
+                if let Some(style) = state.line_fill_style(row) {
+                    fills.push((i as u16, style));
+                }
+                // Spans cached before the cursor moved onto the line are stale
+                let cached = if state.has_overlay(row) {
+                    None
+                } else {
+                    state.render_cache.line(row)
+                };
+                let line = match cached {
+                    Some(line) => line,
+                    None => state.line_spans(&state.lines()[row], row),
+                };
+                // tui-rs does not support the alignment of each line
+                #[cfg(feature = "ratatui")]
+                let line = match state.line_alignment(row) {
+                    alignment if alignment != state.alignment() => line.alignment(alignment),
+                    _ => line,
+                };
+                line_widths.push(line.width());
+                // Lines are scrolled here instead of by `Paragraph::scroll` which shifts the rest of the line when a
+                // wide character is split at the left edge
+                let line = match state.line_alignment(row) {
+                    Alignment::Left if top_col > 0 => skip_columns(line, top_col as usize),
+                    _ => line,
+                };
+                lines.push(line);
+            }
+            num_rows = rows.len() as u16;
+
             (Text::from(lines), state.style())
         };
 
         // To get fine control over the text color and the surrrounding block they have to be rendered separately
         // see https://github.com/ratatui-org/ratatui/issues/144
-        let mut text_area = area;
-        let mut inner = Paragraph::new(text)
-            .style(style)
-            .alignment(state.alignment());
+        let inner = Paragraph::new(text).alignment(state.alignment());
         if let Some(b) = self.block {
-            text_area = b.inner(area);
             b.clone().render(area, buf)
         }
-        if top_col != 0 {
-            inner = inner.scroll((0, top_col));
+        // Fill the background of lines before rendering the text so that the styles of text are patched on it
+        buf.set_style(text_area, style);
+        for &(y, style) in fills.iter() {
+            let area = Rect {
+                y: text_area.y + y,
+                height: 1,
+                ..text_area
+            };
+            buf.set_style(area, style);
+        }
+        // Rulers are rendered before the text as well so that the cursor and the selection are not hidden by them
+        let ruler_style = state.ruler_style();
+        for &col in state.ruler_columns() {
+            if let Some(dx) = col.checked_sub(top_col).filter(|&dx| dx < width) {
+                let area = Rect {
+                    x: text_area.x + dx,
+                    width: 1,
+                    height: num_rows,
+                    ..text_area
+                };
+                buf.set_style(area, ruler_style);
+            }
+        }
+        if gutter_width > 0 {
+            Paragraph::new(Text::from(gutter))
+                .style(state.style())
+                .render(gutter_area, buf);
         }
 
+        inner.render(text_area, buf);
+
         // Store scroll top position for rendering on the next tick
         state.viewport.store(top_row, top_col, width, height);
+        state.viewport.store_origin(text_area.x, text_area.y);
 
-        inner.render(text_area, buf);
+        // A wide character occupies multiple cells but only the first cell is styled. Copy the style to the rest of
+        // the cells so that styles like the cursor cover the whole character.
+        for y in text_area.y..text_area.y + num_rows {
+            let mut x = text_area.x;
+            while x < text_area.x + width {
+                let cell = &buf.content[buf.index_of(x, y)];
+                let cell_width = cell.symbol().width();
+                if cell_width > 1 {
+                    let area = Rect {
+                        x: x + 1,
+                        y,
+                        width: cmp::min(cell_width as u16 - 1, text_area.x + width - x - 1),
+                        height: 1,
+                    };
+                    buf.set_style(area, cell.style());
+                }
+                x += cmp::max(cell_width, 1) as u16;
+            }
+        }
+
+        let max_line_width = line_widths.iter().copied().max().unwrap_or(0);
+
+        // Render markers for the lines partially hidden by horizontal scroll
+        let list = state.list_chars();
+        if width > 0 && (list.extends.is_some() || list.precedes.is_some()) {
+            let style = state.list_style();
+            for (y, &line_width) in line_widths.iter().enumerate() {
+                let y = text_area.y + y as u16;
+                if let Some(c) = list.precedes {
+                    if top_col > 0 && line_width > 0 {
+                        buf.set_string(text_area.x, y, c.to_string(), style);
+                    }
+                }
+                if let Some(c) = list.extends {
+                    if line_width > top_col as usize + width as usize {
+                        buf.set_string(text_area.x + width - 1, y, c.to_string(), style);
+                    }
+                }
+            }
+        }
+
+        // Fill rows past the end of the text
+        if !show_placeholder {
+            let marker = state.end_of_buffer_marker().map(String::from);
+            for y in num_rows..height {
+                let area = Rect {
+                    y: inner_area.y + y,
+                    height: 1,
+                    ..inner_area
+                };
+                buf.set_style(area, state.empty_line_style());
+                if let Some(marker) = &marker {
+                    buf.set_string(area.x, area.y, marker, state.empty_line_style());
+                }
+            }
+        }
+
+        if let Some(config) = vertical {
+            let area = Rect {
+                x: inner_area.x + inner_area.width,
+                width: 1,
+                ..inner_area
+            };
+            let folds = state.folds();
+            let top = folds.display_row(top_row as usize);
+            let total = state.display_height(text_area.width);
+            let state = scrollbar_state(top, height as usize, total);
+            config.render(ScrollbarOrientation::VerticalRight, area, buf, state);
+        }
+        if let Some(config) = horizontal {
+            let area = Rect {
+                y: inner_area.y + inner_area.height,
+                height: 1,
+                ..text_area
+            };
+            let state = scrollbar_state(top_col as usize, width as usize, max_line_width);
+            config.render(ScrollbarOrientation::HorizontalBottom, area, buf, state);
+        }
+
+        state.render_scratch = scratch;
+        state.notify_scroll();
+    }
+}
+
+#[cfg(all(test, feature = "ratatui"))]
+mod tests {
+    use super::*;
+    use crate::ratatui::style::{Color, Modifier, Style};
+    use crate::{CursorMove, DiffKind, Highlight, ListChars, Scrolling};
+
+    fn render(textarea: &mut TextArea, width: u16, height: u16) -> Buffer {
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        let mut b = Buffer::empty(r);
+        StatefulWidget::render(TextAreaWidget::new(), r, &mut b, textarea);
+        b
+    }
+
+    fn row_symbols(b: &Buffer, y: u16) -> String {
+        (0..b.area.width).map(|x| b[(x, y)].symbol()).collect()
+    }
+
+    // Cells with the background color are '#' and other cells are '.'
+    fn row_bg_mask(b: &Buffer, y: u16, color: Color) -> String {
+        (0..b.area.width)
+            .map(|x| if b[(x, y)].bg == color { '#' } else { '.' })
+            .collect()
+    }
+
+    // Cells with the foreground color are '#' and other cells are '.'
+    fn row_fg_mask(b: &Buffer, y: u16, color: Color) -> String {
+        (0..b.area.width)
+            .map(|x| if b[(x, y)].fg == color { '#' } else { '.' })
+            .collect()
+    }
+
+    #[test]
+    fn sign_column() {
+        let red = Style::default().fg(Color::Red);
+        let mut textarea = TextArea::from(["abc", "def", "ghi"]);
+        textarea.set_line_sign(1, Some(('●', red)));
+
+        // Signs are not rendered unless the sign column is enabled
+        let b = render(&mut textarea, 10, 3);
+        assert_eq!(b[(0, 1)].symbol(), "d");
+
+        textarea.set_sign_column(true);
+        let b = render(&mut textarea, 10, 3);
+        assert_eq!(b[(0, 0)].symbol(), " ");
+        assert_eq!(b[(0, 1)].symbol(), "●");
+        assert_eq!(b[(0, 1)].fg, Color::Red);
+        assert_eq!(b[(1, 1)].symbol(), "d");
+
+        // Signs are rendered before line numbers
+        textarea.set_line_number_style(Style::default());
+        let b = render(&mut textarea, 10, 3);
+        assert_eq!(b[(0, 1)].symbol(), "●");
+        assert_eq!(b[(2, 1)].symbol(), "2");
+        assert_eq!(b[(4, 1)].symbol(), "d");
+
+        // Signs only render for visible rows
+        textarea.move_cursor(CursorMove::Bottom);
+        let b = render(&mut textarea, 10, 1);
+        assert_eq!(b[(0, 0)].symbol(), " ");
+        assert_eq!(b[(4, 0)].symbol(), "g");
+    }
+
+    #[test]
+    fn gutter_not_scrolled_horizontally() {
+        let mut textarea = TextArea::from(["0123456789"]);
+        textarea.set_sign_column(true);
+        textarea.set_line_sign(0, Some(('>', Style::default())));
+        textarea.set_line_number_style(Style::default());
+        textarea.move_cursor(CursorMove::End);
+
+        // The gutter is 4 cells wide so the text area is 6 cells wide
+        let b = render(&mut textarea, 10, 1);
+        assert_eq!(textarea.viewport.rect(), (0, 5, 6, 1));
+        assert_eq!(b[(0, 0)].symbol(), ">");
+        assert_eq!(b[(2, 0)].symbol(), "1");
+        assert_eq!(b[(4, 0)].symbol(), "5");
+        assert_eq!(b[(8, 0)].symbol(), "9");
+        // Cursor at the end of line is visible
+        assert_eq!(b[(9, 0)].symbol(), " ");
+    }
+
+    #[test]
+    fn line_number_min_width() {
+        let mut t: TextArea = (1..=9).map(|i| format!("l{}", i)).collect();
+        t.set_line_number_style(Style::default());
+        t.set_line_number_min_width(4);
+        // The gutter is 6 cells wide: " " + 4 digits + " "
+        let b = render(&mut t, 10, 2);
+        assert_eq!(b[(4, 0)].symbol(), "1");
+        assert_eq!(b[(6, 0)].symbol(), "l");
+
+        // The gutter keeps its width when the number of lines crosses a power of ten
+        t.move_cursor(CursorMove::Bottom);
+        t.insert_newline();
+        assert_eq!(t.lines().len(), 10);
+        let b = render(&mut t, 10, 2);
+        assert_eq!(b[(3, 1)].symbol(), "1");
+        assert_eq!(b[(4, 1)].symbol(), "0");
+        assert_eq!(b[(6, 1)].symbol(), "l");
+    }
+
+    #[test]
+    fn scroll_after_text_shrinks() {
+        let mut t: TextArea = (0..2000).map(|i| i.to_string()).collect();
+        t.move_cursor(CursorMove::Jump(1000, 0));
+        render(&mut t, 10, 5);
+        assert_eq!(t.viewport.scroll_top(), (996, 0));
+
+        // Replace the text with a short one and keep the cursor at the last line
+        t.set_text(
+            &(0..20)
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        t.move_cursor(CursorMove::Bottom);
+        let b = render(&mut t, 10, 5);
+        assert_eq!(t.viewport.scroll_top(), (15, 0));
+        assert_eq!(t.cursor_screen_position(), Some((0, 4)));
+        assert_eq!(b[(0, 0)].symbol(), "1");
+        assert_eq!(b[(1, 0)].symbol(), "5");
+
+        // The text becomes shorter than the bottom of the viewport but is still longer than the scroll position
+        t.set_text(
+            &(0..2000)
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        t.move_cursor(CursorMove::Jump(1000, 0));
+        render(&mut t, 10, 5);
+        assert_eq!(t.viewport.scroll_top(), (996, 0));
+        t.set_text(
+            &(0..998)
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        t.move_cursor(CursorMove::Bottom);
+        let b = render(&mut t, 10, 5);
+        assert_eq!(t.viewport.scroll_top(), (993, 0));
+        assert_eq!(t.cursor_screen_position(), Some((0, 4)));
+        assert_eq!(row_symbols(&b, 4), "997       ");
+
+        // The viewport is at the top when the cursor moved to the head of the text
+        t.move_cursor(CursorMove::Jump(1000, 0));
+        render(&mut t, 10, 5);
+        t.set_text("a\nb");
+        render(&mut t, 10, 5);
+        assert_eq!(t.viewport.scroll_top(), (0, 0));
+    }
+
+    #[test]
+    fn current_line_number_style() {
+        let mut t = TextArea::from(["a", "b", "c"]);
+        t.set_line_number_style(Style::default().fg(Color::DarkGray));
+        t.set_current_line_number_style(Style::default().add_modifier(Modifier::BOLD));
+        t.move_cursor(CursorMove::Down);
+        let b = render(&mut t, 10, 3);
+
+        // The number at the cursor line is emphasized on top of the line number style
+        assert_eq!(b[(1, 1)].symbol(), "2");
+        assert_eq!(b[(1, 1)].fg, Color::DarkGray);
+        assert!(b[(1, 1)].modifier.contains(Modifier::BOLD));
+        for y in [0, 2] {
+            assert_eq!(b[(1, y)].fg, Color::DarkGray);
+            assert!(!b[(1, y)].modifier.contains(Modifier::BOLD));
+        }
+
+        // The emphasis follows the cursor
+        t.move_cursor(CursorMove::Up);
+        let b = render(&mut t, 10, 3);
+        assert!(b[(1, 0)].modifier.contains(Modifier::BOLD));
+        assert!(!b[(1, 1)].modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn line_alignment() {
+        use crate::{MouseEvent, MouseEventKind};
+
+        let mut t = TextArea::from(["title", "body text"]);
+        t.set_line_alignment(0, Some(Alignment::Center));
+        let b = render(&mut t, 11, 2);
+        assert_eq!(row_symbols(&b, 0), "   title   ");
+        assert_eq!(row_symbols(&b, 1), "body text  ");
+        assert_eq!(t.cursor_screen_position(), Some((3, 0)));
+
+        // Clicks are mapped with the alignment of each line
+        let click = MouseEvent {
+            kind: MouseEventKind::Down,
+            column: 5,
+            row: 0,
+            shift: false,
+        };
+        t.on_mouse(click);
+        assert_eq!(t.cursor(), (0, 2));
+        t.on_mouse(MouseEvent { row: 1, ..click });
+        assert_eq!(t.cursor(), (1, 5));
+
+        // Lines aligned to the center are not scrolled horizontally
+        let mut t = TextArea::from(["title", "0123456789abc"]);
+        t.set_line_alignment(0, Some(Alignment::Center));
+        t.move_cursor(CursorMove::Bottom);
+        t.move_cursor(CursorMove::End);
+        let b = render(&mut t, 9, 2);
+        assert_eq!(row_symbols(&b, 0), "  title  ");
+        assert_ne!(row_symbols(&b, 1), "012345678");
+    }
+
+    #[test]
+    fn diff_background() {
+        let mut textarea = TextArea::from(["a", "bb", "ccc", "d"]);
+        textarea.set_diff(&[(1, DiffKind::Added), (2, DiffKind::Removed)]);
+
+        let b = render(&mut textarea, 6, 4);
+        for x in 0..6 {
+            assert_eq!(b[(x, 0)].bg, Color::Reset, "x={x}");
+            assert_eq!(b[(x, 1)].bg, Color::Green, "x={x}");
+            assert_eq!(b[(x, 2)].bg, Color::Red, "x={x}");
+            assert_eq!(b[(x, 3)].bg, Color::Reset, "x={x}");
+        }
+        // Only the text is crossed out
+        assert!(b[(0, 2)].modifier.contains(Modifier::CROSSED_OUT));
+        assert!(!b[(5, 2)].modifier.contains(Modifier::CROSSED_OUT));
+
+        // Marks are kept on scrolling
+        textarea.move_cursor(CursorMove::Bottom);
+        let b = render(&mut textarea, 6, 2);
+        assert_eq!(b[(0, 0)].symbol(), "c");
+        assert_eq!(b[(3, 0)].bg, Color::Red);
+        assert_eq!(b[(3, 1)].bg, Color::Reset);
+
+        textarea.move_cursor(CursorMove::Top);
+        let b = render(&mut textarea, 6, 2);
+        assert_eq!(b[(0, 0)].symbol(), "a");
+        assert_eq!(b[(3, 1)].bg, Color::Green);
+    }
+
+    #[test]
+    fn line_rules() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let mut textarea: TextArea = (0..20).map(|i| format!("INFO {i}")).collect();
+        textarea.insert_str("ERROR ");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        textarea.add_line_rule(move |line, _| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            line.contains("ERROR")
+                .then(|| Style::default().bg(Color::Red))
+        });
+        textarea.add_line_rule(|line, row| {
+            (line.contains("INFO") && row % 2 == 1).then(|| Style::default().bg(Color::Blue))
+        });
+
+        let b = render(&mut textarea, 12, 3);
+        for x in 0..12 {
+            // The first matching rule wins
+            assert_eq!(b[(x, 0)].bg, Color::Red, "x={x}");
+            assert_eq!(b[(x, 1)].bg, Color::Blue, "x={x}");
+            assert_eq!(b[(x, 2)].bg, Color::Reset, "x={x}");
+        }
+        // Rules run only for visible lines
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        // Styles follow edits of the text
+        textarea.move_cursor(CursorMove::Down);
+        textarea.move_cursor(CursorMove::Down);
+        textarea.insert_str("ERROR ");
+        let b = render(&mut textarea, 12, 3);
+        assert_eq!(b[(11, 2)].bg, Color::Red);
+
+        // Diff marks take precedence over rules
+        textarea.set_diff(&[(0, DiffKind::Added)]);
+        let b = render(&mut textarea, 12, 3);
+        assert_eq!(b[(11, 0)].bg, Color::Green);
+
+        textarea.clear_line_rules();
+        let b = render(&mut textarea, 12, 3);
+        assert_eq!(b[(11, 1)].bg, Color::Reset);
+        assert_eq!(b[(11, 2)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn folded_lines() {
+        let mut textarea: TextArea = (0..10).map(|i| i.to_string()).collect();
+        textarea.fold(1, 3);
+        textarea.fold(5, 8);
+        textarea.set_sign_column(true);
+
+        let b = render(&mut textarea, 16, 4);
+        let rows = (0..4).map(|y| row_symbols(&b, y)).collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                " 0              ",
+                "▸1 … 2 lines    ",
+                " 4              ",
+                "▸5 … 3 lines    ",
+            ],
+        );
+
+        // Scrolling to the cursor counts rows on screen
+        textarea.move_cursor(CursorMove::Bottom);
+        let b = render(&mut textarea, 16, 4);
+        assert_eq!(b[(1, 0)].symbol(), "1");
+        assert_eq!(b[(1, 3)].symbol(), "9");
+        assert_eq!(textarea.viewport.scroll_top(), (1, 0));
+
+        // Placing the cursor line in the viewport counts rows on screen
+        textarea.move_cursor(CursorMove::Jump(9, 0));
+        textarea.scroll_cursor_center();
+        assert_eq!(textarea.viewport.scroll_top(), (1, 0));
+        textarea.move_cursor(CursorMove::Jump(5, 0));
+        textarea.scroll_cursor_bottom();
+        assert_eq!(textarea.viewport.scroll_top(), (0, 0));
+        let b = render(&mut textarea, 16, 4);
+        assert_eq!(b[(1, 3)].symbol(), "5");
+    }
+
+    #[cfg(feature = "search")]
+    #[test]
+    fn search_current_match() {
+        let mut textarea = TextArea::from(["ab ab ab", "ab"]);
+        textarea.set_search_pattern("ab").unwrap();
+        assert!(textarea.search_forward(false));
+        assert_eq!(textarea.cursor(), (0, 3));
+
+        let b = render(&mut textarea, 10, 2);
+        // All matches are highlighted and the match at the cursor is emphasized
+        for (x, y, bg) in [
+            (0, 0, Some(Color::Blue)),
+            (1, 0, Some(Color::Blue)),
+            (2, 0, None),
+            (4, 0, Some(Color::Yellow)),
+            (6, 0, Some(Color::Blue)),
+            (7, 0, Some(Color::Blue)),
+            (0, 1, Some(Color::Blue)),
+        ] {
+            let want = bg.unwrap_or(Color::Reset);
+            assert_eq!(b[(x, y)].bg, want, "cell at ({x}, {y})");
+        }
+        assert!(b[(3, 0)].modifier.contains(Modifier::REVERSED));
+
+        // The current match moves with the cursor
+        assert!(textarea.search_forward(false));
+        let b = render(&mut textarea, 10, 2);
+        assert_eq!(b[(4, 0)].bg, Color::Blue);
+        assert_eq!(b[(7, 0)].bg, Color::Yellow);
+    }
+
+    #[test]
+    fn on_scroll() {
+        use crate::{ScrollInfo, Scrolling};
+        use std::sync::{Arc, Mutex};
+
+        let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+        render(&mut textarea, 10, 5);
+
+        let called = Arc::new(Mutex::new(vec![]));
+        let c = called.clone();
+        textarea.set_on_scroll(move |info| c.lock().unwrap().push(info));
+
+        // Rendering without changing the viewport does not call the callback
+        render(&mut textarea, 10, 5);
+        assert_eq!(called.lock().unwrap().len(), 0);
+
+        textarea.scroll(Scrolling::Delta { rows: 3, cols: 0 });
+        render(&mut textarea, 10, 5);
+        render(&mut textarea, 10, 5);
+        let want = ScrollInfo {
+            scroll_top: (3, 0),
+            width: 10,
+            height: 5,
+            lines: 20,
+        };
+        assert_eq!(*called.lock().unwrap(), [want]);
+
+        // Resizing the viewport and adding lines also call the callback
+        render(&mut textarea, 10, 6);
+        textarea.insert_newline();
+        render(&mut textarea, 10, 6);
+        let called = called.lock().unwrap();
+        assert_eq!(called.len(), 3);
+        assert_eq!(called[1].height, 6);
+        assert_eq!(called[2].lines, 21);
+    }
+
+    #[test]
+    fn scrollbar() {
+        fn render_with_scrollbar(textarea: &mut TextArea, config: ScrollbarConfig) -> Buffer {
+            let r = Rect {
+                x: 0,
+                y: 0,
+                width: 10,
+                height: 10,
+            };
+            let mut b = Buffer::empty(r);
+            let widget = TextAreaWidget::new().scrollbar(config);
+            StatefulWidget::render(widget, r, &mut b, textarea);
+            b
+        }
+        fn column(b: &Buffer, x: u16) -> String {
+            (0..b.area.height).map(|y| b[(x, y)].symbol()).collect()
+        }
+
+        let mut textarea: TextArea = (0..20).map(|i| format!("line {i}")).collect();
+        let config = ScrollbarConfig::default();
+
+        let b = render_with_scrollbar(&mut textarea, config);
+        assert_eq!(column(&b, 9), "█████║║║║║");
+        // The rightmost column is reserved for the scrollbar
+        assert_eq!(b[(8, 0)].symbol(), " ");
+
+        textarea.move_cursor(CursorMove::Bottom);
+        let b = render_with_scrollbar(&mut textarea, config);
+        assert_eq!(column(&b, 9), "║║║║║█████");
+
+        // The thumb shrinks as the buffer grows
+        for _ in 0..30 {
+            textarea.insert_newline();
+        }
+        textarea.move_cursor(CursorMove::Top);
+        let b = render_with_scrollbar(&mut textarea, config);
+        assert_eq!(column(&b, 9), "██║║║║║║║║");
+
+        // Horizontal scrollbar shows the visible part of the longest line
+        let mut textarea = TextArea::from(["a".repeat(17)]);
+        textarea.move_cursor(CursorMove::End);
+        let config = ScrollbarConfig {
+            vertical: false,
+            horizontal: true,
+            ..Default::default()
+        };
+        let b = render_with_scrollbar(&mut textarea, config);
+        // The line and the end-of-line cursor are 18 columns and the last 10 columns are visible
+        assert_eq!(row_symbols(&b, 9), "════██████");
+        assert_eq!(b[(9, 0)].symbol(), " ");
+    }
+
+    #[test]
+    fn mouse_click_aligned() {
+        use crate::{MouseEvent, MouseEventKind};
+
+        let click = |column, row| MouseEvent {
+            kind: MouseEventKind::Down,
+            column,
+            row,
+            shift: false,
+        };
+
+        for (alignment, clicks) in [
+            (
+                Alignment::Right,
+                // "abc" starts at x=7 and "defgh" starts at x=5
+                &[
+                    ((8, 0), (0, 1), (8, 0)),
+                    ((5, 1), (1, 0), (5, 1)),
+                    // Before the head of the line
+                    ((2, 1), (1, 0), (5, 1)),
+                    ((9, 0), (0, 2), (9, 0)),
+                ][..],
+            ),
+            (
+                Alignment::Center,
+                // "abc" starts at x=4 and "defgh" starts at x=3
+                &[
+                    ((6, 0), (0, 2), (6, 0)),
+                    ((3, 1), (1, 0), (3, 1)),
+                    // The end-of-line cell of the cursor shifts the line to the left
+                    ((8, 1), (1, 5), (7, 1)),
+                ][..],
+            ),
+        ] {
+            let mut textarea = TextArea::from(["abc", "defgh"]);
+            textarea.set_alignment(alignment);
+            for &((x, y), pos, screen) in clicks {
+                // Render before each click since the position of the aligned line depends on the cursor
+                render(&mut textarea, 10, 3);
+                textarea.on_mouse(click(x, y));
+                assert_eq!(textarea.cursor(), pos, "{alignment:?} click at ({x}, {y})");
+                render(&mut textarea, 10, 3);
+                assert_eq!(
+                    textarea.cursor_screen_position(),
+                    Some(screen),
+                    "{alignment:?} click at ({x}, {y})",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mouse_click() {
+        use crate::{InputResult, MouseEvent, MouseEventKind};
+
+        fn event(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+            MouseEvent {
+                kind,
+                column,
+                row,
+                shift: false,
+            }
+        }
+        let click = |column, row| event(MouseEventKind::Down, column, row);
+
+        let mut textarea = TextArea::from(["let foo_bar = 1;", "\tx", "末尾"]);
+        // Nothing is handled before the first render
+        assert_eq!(textarea.on_mouse(click(0, 0)), InputResult::Unhandled);
+
+        // Render with a border and line numbers so that the text starts at (4, 1)
+        textarea.set_line_number_style(Style::default());
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 24,
+            height: 6,
+        };
+        let mut b = Buffer::empty(r);
+        let widget = TextAreaWidget::new().block(Block::bordered());
+        StatefulWidget::render(widget, r, &mut b, &mut textarea);
+
+        for (column, row, pos) in [
+            (4, 1, (0, 0)),
+            (10, 1, (0, 6)),
+            // Past the end of the line
+            (22, 1, (0, 16)),
+            // Inside the hard tab
+            (6, 2, (1, 0)),
+            (8, 2, (1, 1)),
+            // Second cell of the wide character
+            (7, 3, (2, 1)),
+            // Below the last line
+            (22, 4, (2, 2)),
+        ] {
+            textarea.on_mouse(click(column, row));
+            textarea.on_mouse(event(MouseEventKind::Up, column, row));
+            assert_eq!(textarea.cursor(), pos, "click at ({column}, {row})");
+        }
+
+        // Clicks on the border or the gutter are not handled
+        assert_eq!(textarea.on_mouse(click(0, 1)), InputResult::Unhandled);
+        assert_eq!(textarea.on_mouse(click(3, 1)), InputResult::Unhandled);
+
+        // Double-click selects the word including underscores
+        assert_eq!(textarea.on_mouse(click(11, 1)), InputResult::CursorMoved);
+        assert!(!textarea.is_selecting());
+        assert_eq!(textarea.on_mouse(click(11, 1)), InputResult::CursorMoved);
+        textarea.copy();
+        assert_eq!(textarea.yank_text(), "foo_bar");
+        assert_eq!(textarea.cursor(), (0, 11));
+
+        // Triple-click selects the line
+        textarea.on_mouse(click(11, 1));
+        textarea.copy();
+        assert_eq!(textarea.yank_text(), "let foo_bar = 1;");
+
+        // Click at another position resets the click count
+        textarea.on_mouse(click(5, 1));
+        textarea.on_mouse(click(4, 1));
+        assert!(!textarea.is_selecting());
+        assert_eq!(textarea.cursor(), (0, 0));
+
+        // Dragging selects text and Shift+click extends the selection
+        textarea.on_mouse(event(MouseEventKind::Drag, 7, 1));
+        assert!(textarea.is_selecting());
+        assert_eq!(textarea.cursor(), (0, 3));
+        textarea.on_mouse(MouseEvent {
+            shift: true,
+            ..click(11, 1)
+        });
+        textarea.copy();
+        assert_eq!(textarea.yank_text(), "let foo");
+
+        // Double-click selects the word of the characters set by `TextArea::set_word_chars`
+        textarea.set_word_chars(|c| c.is_alphanumeric());
+        textarea.on_mouse(click(13, 1));
+        textarea.on_mouse(click(13, 1));
+        textarea.copy();
+        assert_eq!(textarea.yank_text(), "bar");
+    }
+
+    #[test]
+    fn click_past_eol_disabled() {
+        use crate::{MouseEvent, MouseEventKind};
+
+        let mut textarea = TextArea::from(["abc", ""]);
+        textarea.set_cursor_past_eol(false);
+        render(&mut textarea, 10, 3);
+        for (column, row, pos) in [(1, 0, (0, 1)), (8, 0, (0, 2)), (8, 1, (1, 0))] {
+            textarea.on_mouse(MouseEvent {
+                kind: MouseEventKind::Down,
+                column,
+                row,
+                shift: false,
+            });
+            assert_eq!(textarea.cursor(), pos, "click at ({column}, {row})");
+        }
+    }
+
+    #[test]
+    fn display_lines() {
+        let mut textarea = TextArea::from(["hello", "world", "", "foo", "bar", "wide あい"]);
+        textarea.set_line_number_style(Style::default().fg(Color::Yellow));
+        textarea.set_style(Style::default().bg(Color::Blue));
+        textarea.set_diff(&[(1, DiffKind::Added)]);
+        textarea.set_highlights(vec![Highlight {
+            start: (4, 0),
+            end: (4, 2),
+            style: Style::default().fg(Color::Red),
+        }]);
+        textarea.fold(2, 3);
+        textarea.move_cursor(CursorMove::Jump(1, 2));
+        textarea.start_selection();
+        textarea.move_cursor(CursorMove::Jump(2, 0));
+
+        let (width, height) = (10, 6);
+        let b = render(&mut textarea, width, height);
+        let lines: Vec<_> = textarea.display_lines(width, 0..height as usize).collect();
+        // The folded range is one row
+        assert_eq!(lines.len(), 5);
+
+        // Each cell occupied by the line is the same as the cell rendered by the widget
+        for (y, line) in lines.iter().enumerate() {
+            let mut x = 0;
+            for span in &line.spans {
+                for g in span.content.graphemes(true) {
+                    let cell = &b[(x, y as u16)];
+                    assert_eq!(cell.symbol(), g, "cell at ({}, {})", x, y);
+                    let style = Style::reset().patch(span.style);
+                    let cell_style = (cell.fg, cell.bg, cell.modifier);
+                    let want = (style.fg.unwrap(), style.bg.unwrap(), style.add_modifier);
+                    assert_eq!(cell_style, want, "style at ({}, {})", x, y);
+                    x += g.width() as u16;
+                }
+            }
+        }
+
+        // The wide character which does not fit in the width is cut
+        let last: String = lines[4].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(last, " 6 wide あ");
+
+        assert_eq!(textarea.display_lines(width, 4..10).count(), 1);
+        assert_eq!(textarea.display_lines(width, 10..20).count(), 0);
+    }
+
+    #[test]
+    fn ruler_columns() {
+        let mut textarea = TextArea::from(["0123456789", "", "abc"]);
+        textarea.set_ruler_columns(vec![2, 6, 100]);
+        textarea.set_ruler_style(Style::default().bg(Color::Red));
+        textarea.set_selection_style(Style::default().bg(Color::Blue));
+        // Rulers are rendered on empty lines but not on rows past the end of the text
+        let b = render(&mut textarea, 8, 4);
+        assert_eq!(row_bg_mask(&b, 0, Color::Red), "..#...#.");
+        assert_eq!(row_bg_mask(&b, 1, Color::Red), "..#...#.");
+        assert_eq!(row_bg_mask(&b, 2, Color::Red), "..#...#.");
+        assert_eq!(row_bg_mask(&b, 3, Color::Red), "........");
+        assert_eq!(b[(2, 0)].symbol(), "2");
+
+        // The selection is rendered on top of the rulers
+        textarea.move_cursor(CursorMove::Jump(0, 1));
+        textarea.start_selection();
+        textarea.move_cursor(CursorMove::Jump(0, 4));
+        let b = render(&mut textarea, 8, 4);
+        assert_eq!(row_bg_mask(&b, 0, Color::Blue), ".###....");
+        assert_eq!(row_bg_mask(&b, 0, Color::Red), "......#.");
+        textarea.cancel_selection();
+
+        // Rulers scroll horizontally with the text
+        textarea.move_cursor(CursorMove::End);
+        let b = render(&mut textarea, 8, 4);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 3));
+        assert_eq!(row_bg_mask(&b, 1, Color::Red), "...#....");
+        assert_eq!(b[(3, 0)].symbol(), "6");
+
+        // Rulers are rendered in the text area after the line numbers
+        textarea.set_line_number_style(Style::default());
+        textarea.move_cursor(CursorMove::Head);
+        let b = render(&mut textarea, 10, 4);
+        assert_eq!(row_bg_mask(&b, 1, Color::Red), ".....#...#");
+    }
+
+    #[test]
+    fn extra_selections() {
+        let mut textarea = TextArea::from(["abcdefgh", "ijkl", "mnop", "qrst"]);
+        let style = Style::default().bg(Color::Red);
+        textarea.set_selection_style(style);
+        textarea.set_cursor_style(Style::default());
+
+        // Two disjoint ranges on one line and overlapping ranges merged into one
+        textarea.set_extra_selections(vec![((0, 1), (0, 2)), ((0, 6), (0, 4)), ((0, 5), (0, 7))]);
+        let b = render(&mut textarea, 10, 4);
+        assert_eq!(row_bg_mask(&b, 0, Color::Red), ".#..###...");
+        for x in 4..7 {
+            assert_eq!(b[(x, 0)].style(), b[(4, 0)].style());
+        }
+
+        // Ranges across lines select the newlines. The current selection is merged with them
+        textarea.set_extra_selections(vec![((1, 2), (2, 1))]);
+        textarea.move_cursor(CursorMove::Jump(2, 0));
+        textarea.start_selection();
+        textarea.move_cursor(CursorMove::Jump(2, 3));
+        let b = render(&mut textarea, 10, 4);
+        assert_eq!(row_bg_mask(&b, 0, Color::Red), "..........");
+        assert_eq!(row_bg_mask(&b, 1, Color::Red), "..###.....");
+        assert_eq!(row_bg_mask(&b, 2, Color::Red), "###.......");
+        assert_eq!(row_bg_mask(&b, 3, Color::Red), "..........");
+
+        // Only the visible part of the range is rendered
+        textarea.cancel_selection();
+        textarea.set_extra_selections(vec![((0, 2), (3, 2))]);
+        textarea.move_cursor(CursorMove::Bottom);
+        let b = render(&mut textarea, 10, 2);
+        assert_eq!(row_bg_mask(&b, 0, Color::Red), "#####.....");
+        assert_eq!(row_bg_mask(&b, 1, Color::Red), "##........");
+
+        // Changing the style is applied to the ranges
+        textarea.set_selection_style(Style::default().bg(Color::Blue));
+        let b = render(&mut textarea, 10, 2);
+        assert_eq!(row_bg_mask(&b, 0, Color::Red), "..........");
+        assert_eq!(b[(0, 0)].bg, Color::Blue);
+
+        textarea.clear_extra_selections();
+        let b = render(&mut textarea, 10, 2);
+        assert_eq!(b[(0, 0)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn hide_cursor_style() {
+        let lines: Vec<_> = (0..10).map(|i| format!("{}あい", i)).collect();
+        let mut textarea = TextArea::from(lines);
+        textarea.set_line_number_style(Style::default());
+        textarea.move_cursor(CursorMove::Jump(8, 2));
+        let r = Rect::new(1, 1, 12, 5);
+        let mut b = Buffer::empty(Rect::new(0, 0, 14, 7));
+        StatefulWidget::render(
+            TextAreaWidget::new().block(Block::bordered()),
+            r,
+            &mut b,
+            &mut textarea,
+        );
+
+        // Lines 6..=8 are displayed. The border, the line number "  9 ", "8" and the wide character "あ" come before
+        // "い" on the screen
+        let pos = textarea.cursor_screen_position();
+        assert_eq!(pos, Some((2 + 4 + 1 + 2, 2 + 2)));
+        let (x, y) = pos.unwrap();
+        assert_eq!(b[(x, y)].symbol(), "い");
+        assert!(b[(x, y)].modifier.contains(Modifier::REVERSED));
+
+        textarea.set_hide_cursor_style(true);
+        let mut b = Buffer::empty(Rect::new(0, 0, 14, 7));
+        StatefulWidget::render(
+            TextAreaWidget::new().block(Block::bordered()),
+            r,
+            &mut b,
+            &mut textarea,
+        );
+        assert_eq!(textarea.cursor_screen_position(), pos);
+        assert_eq!(b[(x, y)].symbol(), "い");
+        assert!(!b[(x, y)].modifier.contains(Modifier::REVERSED));
+        // The cursor line style is kept on the cell at the cursor
+        assert!(b[(x, y)].modifier.contains(Modifier::UNDERLINED));
+
+        // The cell at the cursor keeps the styles of the selection, the search match, and the highlight
+        let mut textarea = TextArea::from(["abc def"]);
+        textarea.set_hide_cursor_style(true);
+        textarea.set_selection_style(Style::default().bg(Color::Blue));
+        textarea.set_highlights(vec![Highlight {
+            start: (0, 4),
+            end: (0, 7),
+            style: Style::default().fg(Color::Green),
+        }]);
+
+        textarea.move_cursor(CursorMove::Jump(0, 3));
+        textarea.start_selection();
+        textarea.move_cursor(CursorMove::Jump(0, 1));
+        let b = render(&mut textarea, 8, 1);
+        assert_eq!(b[(1, 0)].bg, Color::Blue);
+        textarea.cancel_selection();
+
+        textarea.move_cursor(CursorMove::Jump(0, 5));
+        let b = render(&mut textarea, 8, 1);
+        assert_eq!(b[(5, 0)].fg, Color::Green);
+        assert!(b[(5, 0)].modifier.contains(Modifier::UNDERLINED));
+
+        #[cfg(feature = "search")]
+        {
+            textarea.set_search_style(Style::default().bg(Color::Yellow));
+            assert!(textarea.set_search_pattern("bc").is_ok());
+            textarea.move_cursor(CursorMove::Jump(0, 1));
+            let b = render(&mut textarea, 8, 1);
+            assert_eq!(b[(1, 0)].bg, Color::Yellow);
+        }
+
+        // The end of line at the cursor is not rendered with the cursor style
+        textarea.move_cursor(CursorMove::End);
+        let b = render(&mut textarea, 8, 1);
+        assert!(!b[(7, 0)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn visible_range() {
+        let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+        assert_eq!(textarea.visible_range(), None);
+        assert_eq!(textarea.viewport_size(), (0, 0));
+
+        textarea.set_line_number_style(Style::default());
+        render(&mut textarea, 10, 5);
+        // The gutter of line numbers is excluded from the viewport
+        assert_eq!(textarea.viewport_size(), (6, 5));
+        assert_eq!(textarea.visible_range(), Some((0, 4)));
+
+        // Folded lines are not counted as visible rows
+        textarea.fold(2, 5);
+        render(&mut textarea, 10, 5);
+        assert_eq!(textarea.visible_range(), Some((0, 7)));
+
+        textarea.move_cursor(CursorMove::Bottom);
+        render(&mut textarea, 10, 5);
+        assert_eq!(textarea.visible_range(), Some((15, 19)));
+
+        // The text ends before the bottom of the viewport
+        textarea.move_cursor(CursorMove::Top);
+        render(&mut textarea, 10, 30);
+        assert_eq!(textarea.visible_range(), Some((0, 19)));
+
+        render(&mut textarea, 10, 0);
+        assert_eq!(textarea.visible_range(), None);
+    }
+
+    #[test]
+    fn render_into_empty_area() {
+        let mut textarea = TextArea::from(["abc", "あいう", "", "def"]);
+        textarea.set_line_number_style(Style::default());
+        textarea.set_sign_column(true);
+        textarea.set_scrolloff(3);
+        textarea.set_sidescrolloff(3);
+        textarea.move_cursor(CursorMove::Jump(1, 2));
+
+        for (width, height) in [(0, 0), (0, 3), (10, 0), (1, 1), (10, 1), (3, 1)] {
+            let b = render(&mut textarea, width, height);
+            assert_eq!(b.area.width, width);
+            let (_, _, w, h) = textarea.viewport.rect();
+            assert!(w <= width && h <= height, "{:?}", (w, h));
+
+            // Moving the cursor and scrolling with the empty viewport does not panic
+            for m in [
+                CursorMove::Top,
+                CursorMove::Bottom,
+                CursorMove::TopOfScreen,
+                CursorMove::MiddleOfScreen,
+                CursorMove::BottomOfScreen,
+                CursorMove::InViewport,
+            ] {
+                textarea.move_cursor(m);
+            }
+            textarea.scroll(Scrolling::PageDown);
+            textarea.scroll(Scrolling::HalfPageUp);
+            textarea.scroll((1, 1));
+            render(&mut textarea, width, height);
+            let w = TextAreaWidget::new()
+                .block(Block::bordered())
+                .padding(Padding::uniform(1))
+                .scrollbar(ScrollbarConfig::default());
+            let area = Rect::new(0, 0, width, height);
+            let mut b = Buffer::empty(area);
+            StatefulWidget::render(w, area, &mut b, &mut textarea);
+        }
+
+        // Rendering with the cursor beyond the limit of the scroll position does not panic
+        let lines: Vec<_> = (0..u16::MAX as usize + 10).map(|i| i.to_string()).collect();
+        let mut textarea = TextArea::from(lines);
+        textarea.set_scrolloff(5);
+        textarea.move_cursor(CursorMove::Bottom);
+        render(&mut textarea, 10, 5);
+        textarea.move_cursor(CursorMove::Top);
+        render(&mut textarea, 10, 5);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 0));
+    }
+
+    #[test]
+    fn block_padding() {
+        use crate::{InputResult, MouseEvent, MouseEventKind};
+
+        let mut textarea = TextArea::from(["abcdefghijklmn", "xyz"]);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 12,
+            height: 6,
+        };
+        let render = |textarea: &mut TextArea| {
+            let mut b = Buffer::empty(r);
+            let widget = TextAreaWidget::new()
+                .block(Block::bordered())
+                .padding(Padding::new(2, 1, 1, 0));
+            StatefulWidget::render(widget, r, &mut b, textarea);
+            b
+        };
+
+        // The text starts at (3, 2) inside the border and the padding
+        let b = render(&mut textarea);
+        assert_eq!(b[(3, 2)].symbol(), "a");
+        assert_eq!(b[(3, 3)].symbol(), "x");
+        assert_eq!(b[(2, 2)].symbol(), " ");
+        assert_eq!(b[(3, 1)].symbol(), " ");
+        // The text is clipped before the right padding
+        assert_eq!(b[(9, 2)].symbol(), "g");
+        assert_eq!(b[(10, 2)].symbol(), " ");
+        assert_eq!(b[(11, 2)].symbol(), "│");
+        assert_eq!(textarea.cursor_screen_position(), Some((3, 2)));
+
+        // Horizontal scroll keeps the cursor inside the padded area
+        textarea.move_cursor(CursorMove::End);
+        let b = render(&mut textarea);
+        assert_eq!(textarea.cursor_screen_position(), Some((9, 2)));
+        assert_eq!(b[(8, 2)].symbol(), "n");
+
+        // Clicks are mapped with the inset and clicks on the padding are not handled
+        textarea.move_cursor(CursorMove::Head);
+        render(&mut textarea);
+        let click = |column, row| MouseEvent {
+            kind: MouseEventKind::Down,
+            column,
+            row,
+            shift: false,
+        };
+        textarea.on_mouse(click(4, 3));
+        assert_eq!(textarea.cursor(), (1, 1));
+        assert_eq!(textarea.on_mouse(click(2, 2)), InputResult::Unhandled);
+        assert_eq!(textarea.on_mouse(click(3, 1)), InputResult::Unhandled);
+    }
+
+    #[cfg(feature = "bidi")]
+    #[test]
+    fn bidi_reordering() {
+        use crate::{MouseEvent, MouseEventKind};
+
+        let mut t = TextArea::from(["ab אבג cd"]);
+        t.move_cursor(CursorMove::Jump(0, 3));
+        let b = render(&mut t, 12, 1);
+        assert_eq!(row_symbols(&b, 0), "ab גבא cd   ");
+        // The cursor is at the first character of the right-to-left run which is rendered at its right end
+        assert_eq!(b[(5, 0)].modifier, Modifier::REVERSED);
+        assert_eq!(t.cursor_display_col(), 5);
+        assert_eq!(t.cursor_screen_position(), Some((5, 0)));
+
+        // Clicks are mapped with the visual order
+        for (x, col) in [(1, 1), (3, 5), (4, 4), (5, 3), (7, 7), (10, 9)] {
+            let click = MouseEvent {
+                kind: MouseEventKind::Down,
+                column: x,
+                row: 0,
+                shift: false,
+            };
+            t.on_mouse(click);
+            assert_eq!(t.cursor(), (0, col), "x={}", x);
+        }
+    }
+
+    #[test]
+    fn preedit() {
+        use crate::{MouseEvent, MouseEventKind};
+
+        let mut t = TextArea::from(["abc"]);
+        t.set_placeholder_text("placeholder");
+        t.move_cursor(CursorMove::Forward);
+        t.set_preedit(Some("xy".to_string()));
+        let b = render(&mut t, 10, 1);
+        assert_eq!(row_symbols(&b, 0), "axybc     ");
+        assert_eq!(b[(1, 0)].modifier, Modifier::UNDERLINED);
+        // The cursor is rendered after the preedit
+        assert_eq!(b[(3, 0)].modifier, Modifier::REVERSED);
+        assert_eq!(t.cursor_screen_position(), Some((3, 0)));
+        assert_eq!(t.lines(), ["abc"]);
+
+        // Clicks on the preedit move the cursor to the position of the preedit
+        for (x, col) in [(0, 0), (2, 1), (4, 2), (5, 3)] {
+            t.move_cursor(CursorMove::Jump(0, 1));
+            let click = MouseEvent {
+                kind: MouseEventKind::Down,
+                column: x,
+                row: 0,
+                shift: false,
+            };
+            t.on_mouse(click);
+            assert_eq!(t.cursor(), (0, col), "x={}", x);
+        }
+
+        // The preedit at the end of line and in the empty textarea with the placeholder
+        t.move_cursor(CursorMove::End);
+        let b = render(&mut t, 10, 1);
+        assert_eq!(b[(3, 0)].symbol(), "x");
+        assert_eq!(b[(5, 0)].modifier, Modifier::REVERSED);
+        let mut t = TextArea::default();
+        t.set_placeholder_text("placeholder");
+        t.set_preedit(Some("xy".to_string()));
+        let b = render(&mut t, 10, 1);
+        assert_eq!(b[(0, 0)].symbol(), "x");
+        t.set_preedit(None);
+        let b = render(&mut t, 10, 1);
+        assert_eq!(b[(0, 0)].symbol(), "p");
+    }
+
+    #[test]
+    fn elastic_tabs() {
+        use crate::{MouseEvent, MouseEventKind};
+
+        let mut t = TextArea::from(["id\tname\tscore", "1\tbob\t10", "100\talexander\t7"]);
+        t.set_tab_length(2);
+        t.set_elastic_tabs(true);
+        t.move_cursor(CursorMove::Jump(1, 2));
+        let b = render(&mut t, 20, 3);
+        assert_eq!(row_symbols(&b, 0), "id  name      score ");
+        assert_eq!(row_symbols(&b, 1), "1   bob       10    ");
+        assert_eq!(row_symbols(&b, 2), "100 alexander 7     ");
+
+        // The cursor column follows the elastic widths
+        assert_eq!(t.cursor_display_col(), 4);
+        assert_eq!(b[(4, 1)].modifier, Modifier::REVERSED);
+        let click = MouseEvent {
+            kind: MouseEventKind::Down,
+            column: 15,
+            row: 1,
+            shift: false,
+        };
+        t.on_mouse(click);
+        assert_eq!(t.cursor(), (1, 7));
+
+        // Without elastic tabstops, hard tabs are expanded with the tab length
+        t.set_elastic_tabs(false);
+        let b = render(&mut t, 20, 3);
+        assert_eq!(row_symbols(&b, 1), "1 bob 10            ");
+    }
+
+    #[test]
+    fn control_chars() {
+        use crate::{ControlCharHandling, MouseEvent, MouseEventKind};
+
+        let mut t = TextArea::from(["a\x1b[0mb\u{200b}c\tx"]);
+        t.set_tab_length(4);
+        t.set_control_char_handling(ControlCharHandling::Visualize);
+        t.move_cursor(CursorMove::Jump(0, 6));
+        let b = render(&mut t, 20, 1);
+        // The hard tab after the notation is expanded with the tab stop
+        assert_eq!(row_symbols(&b, 0), "a^[[0mb<200b>c  x   ");
+        assert_eq!(b[(1, 0)].fg, Color::Blue);
+        assert_eq!(b[(2, 0)].fg, Color::Blue);
+        assert_eq!(b[(3, 0)].fg, Color::Reset);
+
+        // The cursor and clicks are mapped with the width of the notation
+        assert_eq!(t.cursor_display_col(), 7);
+        assert_eq!(b[(7, 0)].modifier, Modifier::REVERSED);
+        let click = MouseEvent {
+            kind: MouseEventKind::Down,
+            column: 12,
+            row: 0,
+            shift: false,
+        };
+        t.on_mouse(click);
+        assert_eq!(t.cursor(), (0, 6));
+        t.on_mouse(MouseEvent {
+            column: 13,
+            ..click
+        });
+        assert_eq!(t.cursor(), (0, 7));
+
+        // Control characters are rendered as they are by default
+        t.set_control_char_handling(ControlCharHandling::Keep);
+        assert_eq!(t.cursor_display_col(), 5);
+    }
+
+    #[test]
+    fn display_height() {
+        let mut t = TextArea::from(["a", "bb", "ccc", "dddd", "eeeee", "ffffff"]);
+        t.fold(1, 3);
+        let (w, h) = (12, 10);
+        let b = render(&mut t, w, h);
+        let rendered = (0..h).filter(|&y| b[(0, y)].symbol() != " ").count();
+        assert_eq!(rendered, 4);
+        assert_eq!(t.display_height(w), rendered);
+
+        t.unfold_all();
+        let b = render(&mut t, w, h);
+        let rendered = (0..h).filter(|&y| b[(0, y)].symbol() != " ").count();
+        assert_eq!(t.display_height(w), rendered);
+    }
+
+    #[test]
+    fn tab_stops() {
+        use crate::{MouseEvent, MouseEventKind};
+
+        let mut t = TextArea::from(["id\tname\tscore", "100\tbob\t7\tx"]);
+        t.set_tab_length(2);
+        t.set_tab_stops(vec![5, 12]);
+        t.move_cursor(CursorMove::Jump(1, 4));
+        let b = render(&mut t, 20, 2);
+        assert_eq!(row_symbols(&b, 0), "id   name   score   ");
+        assert_eq!(row_symbols(&b, 1), "100  bob    7 x     ");
+
+        // The cursor column and clicks follow the tab stops
+        assert_eq!(t.cursor_display_col(), 5);
+        assert_eq!(b[(5, 1)].modifier, Modifier::REVERSED);
+        let click = MouseEvent {
+            kind: MouseEventKind::Down,
+            column: 10,
+            row: 1,
+            shift: false,
+        };
+        t.on_mouse(click);
+        assert_eq!(t.cursor(), (1, 7));
+
+        // Elastic tabstops take precedence over the tab stops
+        t.set_elastic_tabs(true);
+        let b = render(&mut t, 20, 2);
+        assert_eq!(row_symbols(&b, 1), "100 bob  7 x        ");
+    }
+
+    #[test]
+    fn render_cache() {
+        use crate::Highlight;
+
+        let mut t = TextArea::from(["abc", "def", "ghi"]);
+        render(&mut t, 5, 3);
+        // Lines without the cursor are cached
+        assert!(t.render_cache.line(0).is_none());
+        assert!(t.render_cache.line(1).is_some());
+        assert!(t.render_cache.line(2).is_some());
+
+        // The cursor is not left on the cached line
+        t.move_cursor(CursorMove::Down);
+        let b = render(&mut t, 5, 3);
+        assert_eq!(b[(0, 0)].modifier, Modifier::empty());
+        assert_eq!(b[(0, 1)].modifier, Modifier::REVERSED);
+        assert!(t.render_cache.line(0).is_some());
+
+        // Selected lines are not cached
+        t.start_selection();
+        t.move_cursor(CursorMove::Down);
+        let b = render(&mut t, 5, 3);
+        assert_eq!(b[(1, 1)].bg, Color::LightBlue);
+        t.cancel_selection();
+        let b = render(&mut t, 5, 3);
+        assert_eq!(b[(1, 1)].bg, Color::Reset);
+
+        // Modifying the text or settings invalidates the cache
+        t.delete_line_by_head();
+        let b = render(&mut t, 5, 3);
+        assert_eq!(row_symbols(&b, 2), "     ");
+        t.set_highlights(vec![Highlight {
+            start: (0, 0),
+            end: (0, 1),
+            style: Style::default().fg(Color::Red),
+        }]);
+        let b = render(&mut t, 5, 3);
+        assert_eq!(b[(0, 0)].fg, Color::Red);
+        t.set_mask_char('*');
+        let b = render(&mut t, 5, 3);
+        assert_eq!(row_symbols(&b, 0), "***  ");
+    }
+
+    #[test]
+    fn highlight_current_word() {
+        let mut t = TextArea::from(["foo foobar foo", "(foo) barfoo"]);
+        t.set_highlight_current_word(Some(Style::default().fg(Color::Green)));
+        t.move_cursor(CursorMove::Jump(0, 1));
+        let b = render(&mut t, 14, 2);
+        // The cell at the cursor is rendered with the cursor style
+        assert_eq!(row_fg_mask(&b, 0, Color::Green), "#.#........###");
+        assert_eq!(row_fg_mask(&b, 1, Color::Green), ".###..........");
+
+        // Nothing is highlighted on whitespaces
+        t.move_cursor(CursorMove::Jump(0, 3));
+        let b = render(&mut t, 14, 2);
+        assert_eq!(row_fg_mask(&b, 0, Color::Green), "..............");
+        assert_eq!(row_fg_mask(&b, 1, Color::Green), "..............");
+
+        // Lines cached before the cursor moved are updated
+        t.move_cursor(CursorMove::Jump(0, 6));
+        let b = render(&mut t, 14, 2);
+        assert_eq!(b[(4, 0)].fg, Color::Green);
+        assert_eq!(b[(1, 1)].fg, Color::Reset);
+    }
+
+    #[test]
+    fn separate_viewports() {
+        let mut t: TextArea = (0..30).map(|i| i.to_string()).collect();
+        let (mut top, mut bottom) = (Viewport::default(), Viewport::default());
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 10,
+        };
+        let render_with = |t: &mut TextArea, vp: &mut Viewport| {
+            let mut b = Buffer::empty(r);
+            TextAreaWidget::new().with_viewport(vp).render(r, &mut b, t);
+            b
+        };
+
+        t.move_cursor(CursorMove::Jump(20, 0));
+        let b = render_with(&mut t, &mut bottom);
+        assert_eq!(b[(0, 0)].symbol(), "1");
+        assert_eq!(b[(1, 0)].symbol(), "1");
+        t.move_cursor(CursorMove::Jump(5, 0));
+        let b = render_with(&mut t, &mut top);
+        assert_eq!(b[(0, 0)].symbol(), "0");
+        assert_eq!(top.scroll_top(), (0, 0));
+        assert_eq!(bottom.scroll_top(), (11, 0));
+
+        // The textarea's own viewport is not touched
+        assert_eq!(t.viewport.rect(), (0, 0, 0, 0));
+
+        // Each viewport keeps the cursor visible in it
+        t.move_cursor(CursorMove::Jump(25, 0));
+        render_with(&mut t, &mut top);
+        assert_eq!(top.scroll_top(), (16, 0));
+        assert_eq!(bottom.scroll_top(), (11, 0));
+        render(&mut t, 10, 10);
+        assert_eq!(t.viewport.scroll_top(), (16, 0));
+    }
+
+    #[test]
+    fn hscroll_step() {
+        let mut t = TextArea::from(["あ".repeat(20)]);
+        t.set_hscroll_step(4);
+        let mut tops = vec![];
+        for _ in 0..20 {
+            t.move_cursor(CursorMove::Forward);
+            render(&mut t, 10, 1);
+            let (_, col) = t.viewport.scroll_top();
+            if tops.last() != Some(&col) {
+                tops.push(col);
+            }
+        }
+        // Each wide character occupies 2 columns
+        assert_eq!(tops, [0, 4, 8, 12, 16, 20, 24, 28, 32]);
+
+        // Scrolling back to the left also scrolls by the step
+        t.move_cursor(CursorMove::Jump(0, 14));
+        render(&mut t, 10, 1);
+        assert_eq!(t.viewport.scroll_top(), (0, 28));
+
+        // The half of the width is used when 0 is set
+        t.set_hscroll_step(0);
+        t.move_cursor(CursorMove::Jump(0, 12));
+        render(&mut t, 10, 1);
+        assert_eq!(t.viewport.scroll_top(), (0, 23));
+    }
+
+    #[test]
+    fn horizontal_scroll_display_width() {
+        let mut textarea = TextArea::from(["\t日本語日本語"]);
+        textarea.move_cursor(CursorMove::End);
+
+        // The cursor at the end of the line is at column 16 on screen
+        let b = render(&mut textarea, 9, 1);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 8));
+        assert_eq!(b[(0, 0)].symbol(), "語");
+        assert_eq!(b[(6, 0)].symbol(), "語");
+        assert!(b[(8, 0)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn horizontal_scroll_split_wide_char() {
+        let mut t = TextArea::from(["あ".repeat(20), format!("a{}", "い".repeat(20))]);
+        t.set_cursor_line_style(Style::default());
+        t.set_hscroll_step(0);
+        t.move_cursor(CursorMove::Jump(0, 12));
+        let b = render(&mut t, 10, 2);
+        // The wide character at the cursor is not cut at the right edge
+        assert_eq!(t.viewport.scroll_top(), (0, 16));
+        assert_eq!(t.cursor_screen_position(), Some((8, 0)));
+        assert!(b[(8, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(b[(9, 0)].modifier.contains(Modifier::REVERSED));
+
+        // The visible half of the split character is rendered as a space and the rest keeps its columns
+        assert_eq!(b[(0, 0)].symbol(), "あ");
+        assert_eq!(b[(0, 1)].symbol(), " ");
+        assert_eq!(b[(1, 1)].symbol(), "い");
+        assert_eq!(b[(7, 1)].symbol(), "い");
+
+        // Styles of the split character are kept on the space
+        t.set_highlights(vec![Highlight {
+            start: (1, 0),
+            end: (1, 21),
+            style: Style::default().bg(Color::Blue),
+        }]);
+        let b = render(&mut t, 10, 2);
+        assert_eq!(t.viewport.scroll_top(), (0, 16));
+        assert_eq!(b[(0, 1)].symbol(), " ");
+        assert_eq!(b[(0, 1)].bg, Color::Blue);
+    }
+
+    #[test]
+    fn cursor_cells() {
+        let mut textarea = TextArea::from(["日本", "ab"]);
+        textarea.set_cursor_line_style(Style::default());
+
+        // The cursor style covers both cells of the wide character
+        let b = render(&mut textarea, 6, 2);
+        assert_eq!(b[(0, 0)].symbol(), "日");
+        assert!(b[(0, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(b[(1, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(!b[(2, 0)].modifier.contains(Modifier::REVERSED));
+
+        // The cursor at the end of the line is rendered as a styled space
+        textarea.move_cursor(CursorMove::Bottom);
+        textarea.move_cursor(CursorMove::End);
+        let b = render(&mut textarea, 6, 2);
+        assert!(!b[(0, 0)].modifier.contains(Modifier::REVERSED));
+        assert_eq!(b[(2, 1)].symbol(), " ");
+        assert!(b[(2, 1)].modifier.contains(Modifier::REVERSED));
+        assert!(!b[(3, 1)].modifier.contains(Modifier::REVERSED));
+
+        // With the mask character, the cursor covers the whole mask character
+        textarea.set_mask_char('＊');
+        textarea.move_cursor(CursorMove::Head);
+        let b = render(&mut textarea, 6, 2);
+        assert_eq!(b[(0, 1)].symbol(), "＊");
+        assert!(b[(1, 1)].modifier.contains(Modifier::REVERSED));
+        assert!(!b[(2, 1)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn virtual_text() {
+        let gray = Style::default().fg(Color::DarkGray);
+        let mut textarea = TextArea::from(["ab", "cd"]);
+        textarea.set_virtual_text(1, vec![(": hint".to_string(), gray)]);
+        textarea.move_cursor(CursorMove::End);
+
+        let b = render(&mut textarea, 6, 2);
+        let rows = (0..2).map(|y| row_symbols(&b, y)).collect::<Vec<_>>();
+        // Rendered after the content and clipped
+        assert_eq!(rows, ["ab    ", "cd: hi"]);
+        assert_eq!(b[(2, 1)].fg, Color::DarkGray);
+        assert_eq!(textarea.lines(), ["ab", "cd"]);
+
+        // The cursor is rendered before the virtual text
+        textarea.move_cursor(CursorMove::Bottom);
+        textarea.move_cursor(CursorMove::End);
+        assert_eq!(textarea.cursor(), (1, 2));
+        let b = render(&mut textarea, 8, 2);
+        assert_eq!(b[(2, 1)].symbol(), " ");
+        assert_eq!(b[(3, 1)].symbol(), ":");
+    }
+
+    #[test]
+    fn empty_lines() {
+        let mut textarea = TextArea::from(["a", "b"]);
+        let b = render(&mut textarea, 4, 5);
+        assert_eq!(b[(0, 2)].symbol(), " ");
+
+        let style = Style::default().fg(Color::Blue);
+        textarea.set_empty_line_style(style);
+        textarea.set_end_of_buffer_marker(Some('~'));
+        textarea.set_line_number_style(Style::default());
+        let b = render(&mut textarea, 4, 5);
+        for y in 0..2 {
+            assert_eq!(b[(0, y)].symbol(), " ", "y={y}");
+            assert_eq!(b[(0, y)].fg, Color::Reset, "y={y}");
+        }
+        for y in 2..5 {
+            assert_eq!(b[(0, y)].symbol(), "~", "y={y}");
+            for x in 0..4 {
+                assert_eq!(b[(x, y)].fg, Color::Blue, "x={x}, y={y}");
+            }
+        }
+    }
+
+    #[test]
+    fn list_chars() {
+        let mut textarea = TextArea::from(["abc", "0123456789", "x  "]);
+        textarea.set_cursor_line_style(Style::default());
+        textarea.set_trailing_whitespace_style(Style::default().bg(Color::Red));
+        textarea.set_list_chars(ListChars {
+            eol: Some('$'),
+            extends: Some('>'),
+            precedes: Some('<'),
+        });
+
+        let rows = |b: &Buffer| (0..3).map(|y| row_symbols(b, y)).collect::<Vec<_>>();
+
+        textarea.move_cursor(CursorMove::Bottom);
+        let b = render(&mut textarea, 6, 3);
+        assert_eq!(rows(&b), ["abc$  ", "01234>", "x  $  "]);
+        assert_eq!(b[(3, 0)].fg, Color::DarkGray);
+        assert_eq!(b[(5, 1)].fg, Color::DarkGray);
+        assert_eq!(b[(0, 2)].bg, Color::Reset);
+        assert_eq!(b[(1, 2)].bg, Color::Red);
+        assert_eq!(b[(2, 2)].bg, Color::Red);
+
+        // Scroll horizontally
+        textarea.move_cursor(CursorMove::Jump(1, 8));
+        let b = render(&mut textarea, 6, 3);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 3));
+        assert_eq!(rows(&b), ["<     ", "<4567>", "<     "]);
+        assert_eq!(b[(0, 1)].fg, Color::DarkGray);
+    }
+
+    #[test]
+    fn trailing_whitespace_at_cursor() {
+        let mut textarea = TextArea::from(["a  ", "b \t", "c"]);
+        textarea.set_cursor_line_style(Style::default());
+        textarea.set_cursor_style(Style::default());
+        textarea.set_trailing_whitespace_style(Style::default().bg(Color::Red));
+
+        // The cursor before the trailing whitespaces
+        let b = render(&mut textarea, 6, 3);
+        assert_eq!(row_bg_mask(&b, 0, Color::Red), ".##...");
+        assert_eq!(row_bg_mask(&b, 1, Color::Red), ".###..");
+
+        // Typing spaces at the end of line
+        textarea.move_cursor(CursorMove::End);
+        textarea.insert_char(' ');
+        let b = render(&mut textarea, 6, 3);
+        assert_eq!(row_bg_mask(&b, 0, Color::Red), "......");
+        assert_eq!(row_bg_mask(&b, 1, Color::Red), ".###..");
+
+        // The cursor in the trailing whitespaces
+        textarea.move_cursor(CursorMove::Jump(1, 2));
+        let b = render(&mut textarea, 6, 3);
+        assert_eq!(row_bg_mask(&b, 0, Color::Red), ".###..");
+        assert_eq!(row_bg_mask(&b, 1, Color::Red), "......");
+
+        // Leaving the line highlights the whitespaces again
+        textarea.move_cursor(CursorMove::Bottom);
+        let b = render(&mut textarea, 6, 3);
+        assert_eq!(row_bg_mask(&b, 0, Color::Red), ".###..");
+        assert_eq!(row_bg_mask(&b, 1, Color::Red), ".###..");
+    }
+
+    #[test]
+    fn scroll_margin() {
+        let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+        textarea.set_scrolloff(2);
+
+        for (row, top) in [
+            (5, 0),
+            (6, 1),
+            (10, 5),
+            (7, 5),
+            (6, 4),
+            (0, 0),
+            // Not scrolled beyond the end of the text
+            (18, 12),
+            (19, 12),
+        ] {
+            textarea.move_cursor(CursorMove::Jump(row, 0));
+            render(&mut textarea, 10, 8);
+            assert_eq!(textarea.viewport.scroll_top().0, top, "row={row}");
+        }
+
+        // Moving the cursor down line by line starts scrolling 3 lines before the bottom of the viewport
+        textarea.set_scrolloff(3);
+        textarea.move_cursor(CursorMove::Top);
+        render(&mut textarea, 10, 8);
+        for (row, top) in [(3, 0), (4, 0), (5, 1), (6, 2)] {
+            textarea.move_cursor(CursorMove::Jump(row, 0));
+            render(&mut textarea, 10, 8);
+            assert_eq!(textarea.viewport.scroll_top().0, top, "row={row}");
+        }
+
+        // Scrolling the viewport moves the cursor to keep the margin so that the next render does not scroll back
+        for (top, row) in [(3, 6), (4, 7)] {
+            textarea.scroll((1, 0));
+            assert_eq!(textarea.cursor(), (row, 0), "top={top}");
+            render(&mut textarea, 10, 8);
+            assert_eq!(textarea.viewport.scroll_top().0, top, "top={top}");
+        }
+        textarea.move_cursor(CursorMove::Jump(11, 0));
+        render(&mut textarea, 10, 8);
+        assert_eq!(textarea.viewport.scroll_top().0, 7);
+        textarea.scroll((-1, 0));
+        assert_eq!(textarea.cursor(), (10, 0));
+        render(&mut textarea, 10, 8);
+        assert_eq!(textarea.viewport.scroll_top().0, 6);
+
+        // Margin is limited to half of the viewport
+        textarea.set_scrolloff(100);
+        textarea.move_cursor(CursorMove::Jump(10, 0));
+        render(&mut textarea, 10, 5);
+        assert_eq!(textarea.viewport.scroll_top().0, 8);
+
+        let mut textarea = TextArea::from(["0123456789abcdef"]);
+        textarea.set_sidescrolloff(3);
+        for (col, top) in [(6, 0), (7, 1), (12, 6), (9, 6), (8, 5), (0, 0)] {
+            textarea.move_cursor(CursorMove::Jump(0, col));
+            render(&mut textarea, 10, 1);
+            assert_eq!(textarea.viewport.scroll_top().1, top, "col={col}");
+        }
+    }
+
+    #[cfg(feature = "syntect")]
+    #[test]
+    fn syntax_highlighting() {
+        let mut t = TextArea::from(["/* a", "b */", "c", "d"]);
+        t.move_cursor(CursorMove::Bottom);
+        assert!(t.set_syntax("rs"));
+        let b = render(&mut t, 5, 4);
+        let (comment, code) = (b[(0, 0)].fg, b[(0, 2)].fg);
+        assert!(matches!(comment, Color::Rgb(..)), "{comment:?}");
+        assert_ne!(comment, code);
+        // The parser state is carried from the previous line
+        assert_eq!(b[(0, 1)].fg, comment);
+
+        // Lines after the modified line are highlighted again
+        t.move_cursor(CursorMove::Top);
+        t.delete_str(2);
+        let b = render(&mut t, 5, 4);
+        assert_eq!(b[(0, 1)].fg, code);
+
+        // Highlights are rendered on top of the syntax highlighting
+        t.set_highlights(vec![Highlight {
+            start: (2, 0),
+            end: (2, 1),
+            style: Style::default().fg(Color::Red),
+        }]);
+        let b = render(&mut t, 5, 4);
+        assert_eq!(b[(0, 2)].fg, Color::Red);
+
+        // Masked text is not highlighted
+        t.set_mask_char('*');
+        let b = render(&mut t, 5, 4);
+        assert_eq!(b[(0, 3)].fg, Color::Reset);
+
+        t.clear_mask_char();
+        t.clear_syntax();
+        let b = render(&mut t, 5, 4);
+        assert_eq!(b[(0, 3)].fg, Color::Reset);
     }
 }
@@ -1,3 +1,13 @@
+use std::cmp;
+
+// Predicate of characters which are part of words. Whitespaces are never part of words
+pub type WordChars = fn(char) -> bool;
+
+// Characters other than whitespaces and ASCII punctuations are part of words. `_` is a part of words as well
+pub fn is_word_char(c: char) -> bool {
+    !c.is_ascii_punctuation() || c == '_'
+}
+
 #[derive(PartialEq, Eq, Clone, Copy)]
 enum CharKind {
     Space,
@@ -6,22 +16,22 @@ enum CharKind {
 }
 
 impl CharKind {
-    fn new(c: char) -> Self {
+    fn new(c: char, word: WordChars) -> Self {
         if c.is_whitespace() {
             Self::Space
-        } else if c.is_ascii_punctuation() {
-            Self::Punct
-        } else {
+        } else if word(c) {
             Self::Other
+        } else {
+            Self::Punct
         }
     }
 }
 
-pub fn find_word_start_forward(line: &str, start_col: usize) -> Option<usize> {
+pub fn find_word_start_forward(line: &str, start_col: usize, word: WordChars) -> Option<usize> {
     let mut it = line.chars().enumerate().skip(start_col);
-    let mut prev = CharKind::new(it.next()?.1);
+    let mut prev = CharKind::new(it.next()?.1, word);
     for (col, c) in it {
-        let cur = CharKind::new(c);
+        let cur = CharKind::new(c, word);
         if cur != CharKind::Space && prev != cur {
             return Some(col);
         }
@@ -30,11 +40,11 @@ pub fn find_word_start_forward(line: &str, start_col: usize) -> Option<usize> {
     None
 }
 
-pub fn find_word_end_forward(line: &str, start_col: usize) -> Option<usize> {
+pub fn find_word_end_forward(line: &str, start_col: usize, word: WordChars) -> Option<usize> {
     let mut it = line.chars().enumerate().skip(start_col);
-    let mut prev = CharKind::new(it.next()?.1);
+    let mut prev = CharKind::new(it.next()?.1, word);
     for (col, c) in it {
-        let cur = CharKind::new(c);
+        let cur = CharKind::new(c, word);
         if prev != CharKind::Space && prev != cur {
             return Some(col);
         }
@@ -43,16 +53,16 @@ pub fn find_word_end_forward(line: &str, start_col: usize) -> Option<usize> {
     None
 }
 
-pub fn find_word_start_backward(line: &str, start_col: usize) -> Option<usize> {
+pub fn find_word_start_backward(line: &str, start_col: usize, word: WordChars) -> Option<usize> {
     let idx = line
         .char_indices()
         .nth(start_col)
         .map(|(i, _)| i)
         .unwrap_or(line.len());
     let mut it = line[..idx].chars().rev().enumerate();
-    let mut cur = CharKind::new(it.next()?.1);
+    let mut cur = CharKind::new(it.next()?.1, word);
     for (i, c) in it {
-        let next = CharKind::new(c);
+        let next = CharKind::new(c, word);
         if cur != CharKind::Space && next != cur {
             return Some(start_col - i);
         }
@@ -60,3 +70,60 @@ pub fn find_word_start_backward(line: &str, start_col: usize) -> Option<usize> {
     }
     (cur != CharKind::Space).then(|| 0)
 }
+
+// Check if the byte range of the line is a whole word. Characters adjacent to the range must not be of the same kind
+// as the characters at its both ends, following the same rule as word motions.
+pub fn is_whole_word(line: &str, start: usize, end: usize, word: WordChars) -> bool {
+    let kind = |c| CharKind::new(c, word);
+    let text = &line[start..end];
+    let (first, last) = match (text.chars().next(), text.chars().next_back()) {
+        (Some(first), Some(last)) => (kind(first), kind(last)),
+        _ => return false,
+    };
+    let before = line[..start].chars().next_back().map(kind);
+    let after = line[end..].chars().next().map(kind);
+    before != Some(first) && after != Some(last)
+}
+
+// Column of the head of the word ending at the column. Only word characters are part of the word. When no word ends at
+// the column, the column itself is returned. The column is clamped into the line.
+pub fn find_word_prefix_start(line: &str, col: usize, word: WordChars) -> usize {
+    let chars: Vec<char> = line.chars().take(col).collect();
+    chars
+        .iter()
+        .rposition(|&c| CharKind::new(c, word) != CharKind::Other)
+        .map_or(0, |i| i + 1)
+}
+
+// Range of the word at the column. Adjacent characters of the same kind are considered as one word. When the column is
+// at the end of the line, the word before the column is returned.
+pub fn find_word_range(line: &str, col: usize, word: WordChars) -> (usize, usize) {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.is_empty() {
+        return (0, 0);
+    }
+    let col = cmp::min(col, chars.len() - 1);
+    let kind = CharKind::new(chars[col], word);
+    let start = chars[..col]
+        .iter()
+        .rposition(|&c| CharKind::new(c, word) != kind)
+        .map_or(0, |i| i + 1);
+    let end = chars[col..]
+        .iter()
+        .position(|&c| CharKind::new(c, word) != kind)
+        .map_or(chars.len(), |i| col + i);
+    (start, end)
+}
+
+// Byte range of the word under the column. `None` is returned when the character at the column is not a word
+// character, or when the column is at the end of the line.
+pub fn find_word_at(line: &str, col: usize, word: WordChars) -> Option<(usize, usize)> {
+    let offsets: Vec<(usize, char)> = line.char_indices().collect();
+    let &(_, c) = offsets.get(col)?;
+    if CharKind::new(c, word) != CharKind::Other {
+        return None;
+    }
+    let (start, end) = find_word_range(line, col, word);
+    let end = offsets.get(end).map_or(line.len(), |&(i, _)| i);
+    Some((offsets[start].0, end))
+}
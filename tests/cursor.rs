@@ -108,6 +108,68 @@ fn back() {
 }
 
 #[test]
+fn graphemes() {
+    // Flag emoji, 'e' with combining acute accent, and family emoji with ZWJ sequences
+    let mut t = TextArea::from(["🇯🇵e\u{301}x", "👨\u{200d}👩\u{200d}👧y"]);
+
+    for pos in [(0, 2), (0, 4), (0, 5), (1, 0), (1, 5), (1, 6)] {
+        t.move_cursor(CursorMove::Forward);
+        assert_eq!(t.cursor(), pos);
+    }
+    for pos in [(1, 5), (1, 0), (0, 5), (0, 4), (0, 2), (0, 0)] {
+        t.move_cursor(CursorMove::Back);
+        assert_eq!(t.cursor(), pos);
+    }
+
+    // Moving from the middle of a grapheme cluster goes to the boundaries of the cluster
+    t.move_cursor(CursorMove::Jump(1, 3));
+    t.move_cursor(CursorMove::Forward);
+    assert_eq!(t.cursor(), (1, 5));
+    t.move_cursor(CursorMove::Jump(1, 3));
+    t.move_cursor(CursorMove::Back);
+    assert_eq!(t.cursor(), (1, 0));
+}
+
+#[test]
+fn word_forward_back() {
+    // `_` is a part of words, so snake_case identifiers are one word
+    let mut t = TextArea::from(["let foo_bar = _x.y;", "__z"]);
+    for pos in [(0, 4), (0, 12), (0, 14), (0, 16), (0, 17), (0, 18), (1, 0)] {
+        t.move_cursor(CursorMove::WordForward);
+        assert_eq!(t.cursor(), pos);
+    }
+    for pos in [
+        (0, 19),
+        (0, 18),
+        (0, 17),
+        (0, 16),
+        (0, 14),
+        (0, 12),
+        (0, 4),
+        (0, 0),
+    ] {
+        t.move_cursor(CursorMove::WordBack);
+        assert_eq!(t.cursor(), pos);
+    }
+}
+
+#[test]
+fn word_chars() {
+    let mut t = TextArea::from(["tab-width = tab-width-max"]);
+    t.set_word_chars(|c| c.is_alphanumeric() || c == '-');
+    assert!((t.word_chars())('-'));
+    assert!(!(t.word_chars())('_'));
+
+    for pos in [(0, 10), (0, 12)] {
+        t.move_cursor(CursorMove::WordForward);
+        assert_eq!(t.cursor(), pos);
+    }
+    t.move_cursor(CursorMove::WordBack);
+    assert_eq!(t.cursor(), (0, 10));
+}
+
+#[test]
+#[rustfmt::skip]
 fn up() {
     for text in [
         ["abc", "def", "ghi"],
@@ -144,6 +206,7 @@ fn up_trim() {
 }
 
 #[test]
+#[rustfmt::skip]
 fn down() {
     for text in [
         ["abc", "def", "ghi"],
@@ -179,6 +242,99 @@ fn down_trim() {
     }
 }
 
+#[test]
+fn goal_column() {
+    let mut t = TextArea::from(["0123456789ab", "abc", "", "0123456789ab"]);
+    t.move_cursor(CursorMove::Jump(0, 10));
+
+    // The column is restored on a longer line after passing through shorter lines
+    for expected in [(1, 3), (2, 0), (3, 10), (2, 0), (1, 3), (0, 10)] {
+        let m = if expected.0 > t.cursor().0 {
+            CursorMove::Down
+        } else {
+            CursorMove::Up
+        };
+        t.move_cursor(m);
+        assert_eq!(t.cursor(), expected);
+    }
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor(CursorMove::Bottom);
+    assert_eq!(t.cursor(), (3, 10));
+
+    // A horizontal move resets the column
+    t.move_cursor(CursorMove::Up);
+    t.move_cursor(CursorMove::Up);
+    t.move_cursor(CursorMove::Back);
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.cursor(), (0, 2));
+
+    // An edit resets the column
+    t.move_cursor(CursorMove::Jump(0, 10));
+    t.move_cursor(CursorMove::Down);
+    t.insert_char('x');
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.cursor(), (0, 4));
+
+    // Deleting without moving the cursor resets the column as well
+    t.move_cursor(CursorMove::Jump(0, 10));
+    t.move_cursor(CursorMove::Down);
+    t.delete_next_char();
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.cursor(), (0, 4));
+}
+
+#[test]
+fn cursor_past_eol_disabled() {
+    let mut t = TextArea::from(["abc", "", "e\u{301}", "0123456789"]);
+    t.set_cursor_past_eol(false);
+
+    // The cursor stops on the last character
+    for expected in [(0, 1), (0, 2), (0, 2)] {
+        t.move_cursor(CursorMove::Forward);
+        assert_eq!(t.cursor(), expected);
+    }
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.cursor(), (0, 2));
+    t.move_cursor(CursorMove::Jump(2, 10));
+    assert_eq!(t.cursor(), (2, 0));
+
+    // Vertical moves clamp the column and return to the goal column on longer lines
+    t.move_cursor(CursorMove::Jump(3, 8));
+    for (m, expected) in [
+        (CursorMove::Up, (2, 0)),
+        (CursorMove::Up, (1, 0)),
+        (CursorMove::Up, (0, 2)),
+        (CursorMove::Down, (1, 0)),
+        (CursorMove::Down, (2, 0)),
+        (CursorMove::Down, (3, 8)),
+    ] {
+        t.move_cursor(m);
+        assert_eq!(t.cursor(), expected, "{:?}", m);
+    }
+
+    // Moving back from the head of line stops on the last character of the previous line
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.move_cursor(CursorMove::Back);
+    assert_eq!(t.cursor(), (0, 2));
+
+    // The selection can include the last character
+    t.move_cursor(CursorMove::Head);
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.cursor(), (0, 3));
+    t.copy();
+    assert_eq!(t.yank_text(), "abc");
+
+    // Disabling the option moves the cursor onto the last character
+    let mut t = TextArea::from(["abc"]);
+    t.move_cursor(CursorMove::End);
+    t.set_cursor_past_eol(false);
+    assert_eq!(t.cursor(), (0, 2));
+    t.set_cursor_past_eol(true);
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.cursor(), (0, 3));
+}
+
 #[test]
 fn head() {
     for text in [["efg", "h", ""], ["あいう", "👪", ""]] {
@@ -215,6 +371,7 @@ fn end() {
 }
 
 #[test]
+#[rustfmt::skip]
 fn top() {
     for text in [
         ["abc", "def", "ghi"],
@@ -248,6 +405,7 @@ fn top_trim() {
 }
 
 #[test]
+#[rustfmt::skip]
 fn bottom() {
     for text in [
         ["abc", "def", "ghi"],
@@ -279,3 +437,29 @@ fn bottom_trim() {
         assert_eq!(t.cursor(), (t.lines().len() - 1, col), "{:?}", t.lines());
     }
 }
+
+#[test]
+fn move_to() {
+    let mut t = TextArea::from(["abc", "あいうえお", ""]);
+    for ((row, col), want) in [
+        ((0, 0), (0, 0)),
+        ((1, 3), (1, 3)),
+        ((0, 10), (0, 3)),
+        ((1, 10), (1, 5)),
+        ((10, 0), (2, 0)),
+        ((usize::MAX, usize::MAX), (2, 0)),
+    ] {
+        t.move_to(row, col);
+        assert_eq!(t.cursor(), want, "{:?}", (row, col));
+    }
+}
+
+#[test]
+fn move_to_line() {
+    let mut t = TextArea::from(["abcde", "fg", "hijkl"]);
+    t.move_to(0, 4);
+    for (row, want) in [(2, (2, 4)), (1, (1, 2)), (10, (2, 2)), (0, (0, 2))] {
+        t.move_to_line(row);
+        assert_eq!(t.cursor(), want, "{:?}", row);
+    }
+}
@@ -8,3 +8,49 @@ fn disable_history() {
     assert!(t.insert_str("hello"));
     assert_eq!(t.lines(), ["hello"]);
 }
+
+#[test]
+fn saved_point() {
+    let mut t = TextArea::from(["abc"]);
+    assert!(!t.is_modified());
+
+    t.insert_char('x');
+    t.insert_char('y');
+    assert!(t.is_modified());
+    t.set_saved_point();
+    assert!(!t.is_modified());
+
+    // Undo and redo back to the saved point
+    t.undo();
+    assert!(t.is_modified());
+    t.redo();
+    assert!(!t.is_modified());
+    t.insert_char('z');
+    assert!(t.is_modified());
+    t.undo();
+    assert!(!t.is_modified());
+
+    // The saved point is not reachable after modifying the text on another branch of the history
+    t.undo();
+    t.insert_char('w');
+    t.undo();
+    assert!(t.is_modified());
+    t.set_saved_point();
+
+    // The saved point is not reachable after it is removed from the history
+    t.set_max_histories(2);
+    assert!(!t.is_modified());
+    t.insert_char('1');
+    t.insert_char('2');
+    t.insert_char('3');
+    t.undo();
+    t.undo();
+    assert!(t.is_modified());
+
+    // Modifications without history
+    t.set_saved_point();
+    t.set_max_histories(0);
+    assert!(!t.is_modified());
+    t.insert_char('4');
+    assert!(t.is_modified());
+}
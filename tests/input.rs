@@ -1,4 +1,4 @@
-use tui_textarea::{Input, Key, TextArea};
+use tui_textarea::{CursorMove, Input, InputResult, Key, TextArea};
 
 // Sanity test for checking textarea does not crash against all combination of inputs
 #[test]
@@ -76,7 +76,200 @@ fn test_insert_multi_code_unit_emoji() {
             alt: false,
             shift: false,
         };
-        assert!(t.input(input), "{c:?}");
+        assert!(t.input(input).changed(), "{c:?}");
     }
     assert_eq!(t.lines(), ["👨‍👩‍👧‍👦"]);
 }
+
+#[test]
+fn test_input_result() {
+    fn input(key: Key, ctrl: bool) -> Input {
+        Input {
+            key,
+            ctrl,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    let mut t = TextArea::from(["abc"]);
+    assert_eq!(t.input(input(Key::Char('x'), false)), InputResult::Changed);
+    assert_eq!(t.lines(), ["xabc"]);
+    assert_eq!(t.input(input(Key::Right, false)), InputResult::CursorMoved);
+    assert_eq!(t.cursor(), (0, 2));
+    assert_eq!(
+        t.input(input(Key::Char('e'), true)),
+        InputResult::CursorMoved
+    );
+    assert_eq!(t.cursor(), (0, 4));
+    // Cursor is already at the end of line
+    assert_eq!(t.input(input(Key::End, false)), InputResult::Handled);
+    assert_eq!(t.input(input(Key::Char('c'), true)), InputResult::Handled);
+    assert_eq!(t.input(input(Key::F(1), false)), InputResult::Unhandled);
+    assert_eq!(t.input(input(Key::Null, false)), InputResult::Unhandled);
+    assert_eq!(t.input(input(Key::Char('u'), true)), InputResult::Changed);
+    assert_eq!(t.lines(), ["abc"]);
+
+    assert_eq!(
+        t.input_without_shortcuts(input(Key::Backspace, false)),
+        InputResult::Handled,
+    );
+    assert_eq!(
+        t.input_without_shortcuts(input(Key::Char('y'), false)),
+        InputResult::Changed,
+    );
+    assert_eq!(
+        t.input_without_shortcuts(input(Key::Left, false)),
+        InputResult::Unhandled,
+    );
+}
+
+#[test]
+fn test_input_delete_word() {
+    fn ctrl(key: Key) -> Input {
+        Input {
+            key,
+            ctrl: true,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    let mut t = TextArea::from(["foo", "bar baz qux"]);
+    t.input(ctrl(Key::Down));
+    t.input(ctrl(Key::Char('e')));
+
+    // Ctrl+Backspace deletes a word before the cursor and merges lines at the head of line
+    assert_eq!(t.input(ctrl(Key::Backspace)), InputResult::Changed);
+    assert_eq!(t.lines(), ["foo", "bar baz "]);
+    assert_eq!(t.yank_text(), "qux");
+    t.input(ctrl(Key::Char('a')));
+    assert_eq!(t.input(ctrl(Key::Backspace)), InputResult::Changed);
+    assert_eq!(t.lines(), ["foobar baz "]);
+
+    // Ctrl+Delete deletes a word next to the cursor
+    assert_eq!(t.input(ctrl(Key::Delete)), InputResult::Changed);
+    assert_eq!(t.lines(), ["foo baz "]);
+    assert_eq!(t.yank_text(), "bar");
+}
+
+#[test]
+fn test_feed_keys() {
+    let mut t = TextArea::default();
+    assert_eq!(t.feed_keys("fn main() {<CR>}<Up><C-e>"), Ok(true));
+    assert_eq!(t.lines(), ["fn main() {", "}"]);
+    assert_eq!(t.cursor(), (0, 11));
+
+    // Moving the cursor does not modify the text
+    assert_eq!(t.feed_keys("<Home><Down><End>"), Ok(false));
+    assert_eq!(t.cursor(), (1, 1));
+
+    // Nothing is applied when the notation is invalid
+    let err = t.feed_keys("abc<Foo>").unwrap_err();
+    assert_eq!(err.to_string(), r#"unknown key "<Foo>" in "abc<Foo>""#);
+    assert_eq!(t.lines(), ["fn main() {", "}"]);
+}
+
+#[test]
+fn test_input_toggle_overwrite() {
+    let mut t = TextArea::from(["abc"]);
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+
+    assert_eq!(t.input(key(Key::Insert)), InputResult::Handled);
+    assert!(t.overwrite());
+    t.input(key(Key::Char('x')));
+    assert_eq!(t.lines(), ["xbc"]);
+
+    assert_eq!(t.input(key(Key::Insert)), InputResult::Handled);
+    assert!(!t.overwrite());
+    t.input(key(Key::Char('y')));
+    assert_eq!(t.lines(), ["xybc"]);
+}
+
+#[test]
+fn test_input_tab_indent() {
+    let tab = Input::from(Key::Tab);
+    let shift_tab = Input {
+        key: Key::Tab,
+        shift: true,
+        ..Default::default()
+    };
+
+    // Tab inserts a tab without a selection or with a selection in one line
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.move_cursor(CursorMove::Forward);
+    assert_eq!(t.input(tab.clone()), InputResult::Changed);
+    assert_eq!(t.lines(), ["a   b", "cd"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.input(tab.clone()), InputResult::Changed);
+    assert_eq!(t.lines(), ["a       ", "cd"]);
+    assert!(!t.is_selecting());
+
+    // Tab and Shift+Tab indent and dedent all lines of a multi-line selection
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.input(tab.clone()), InputResult::Changed);
+    assert_eq!(t.lines(), ["    ab", "    cd"]);
+    assert!(t.is_selecting());
+    assert_eq!(t.input_without_shortcuts(tab), InputResult::Changed);
+    assert_eq!(t.lines(), ["        ab", "        cd"]);
+    assert_eq!(t.input(shift_tab.clone()), InputResult::Changed);
+    assert_eq!(t.lines(), ["    ab", "    cd"]);
+    assert!(t.is_selecting());
+
+    // Shift+Tab dedents the cursor line without a selection
+    t.cancel_selection();
+    assert_eq!(
+        t.input_without_shortcuts(shift_tab.clone()),
+        InputResult::Changed
+    );
+    assert_eq!(t.lines(), ["    ab", "cd"]);
+    assert_eq!(t.input(shift_tab), InputResult::Handled);
+}
+
+#[test]
+fn test_input_yank_pop() {
+    let mut t = TextArea::default();
+    t.set_yank_text("a");
+    t.set_yank_text("b");
+    assert!(t.feed_keys("<C-y><A-y>").unwrap());
+    assert_eq!(t.lines(), ["a"]);
+    assert_eq!(
+        t.input(Input::parse("alt+y").unwrap()),
+        InputResult::Changed
+    );
+    assert_eq!(t.lines(), ["b"]);
+}
+
+#[test]
+fn test_input_submit_in_single_line() {
+    let mut t = TextArea::from(["abc"]);
+    t.set_single_line(true);
+    for (key, ctrl) in [
+        (Key::Enter, false),
+        (Key::Char('m'), true),
+        (Key::Char('\n'), false),
+        (Key::Char('\r'), false),
+    ] {
+        let input = Input {
+            key,
+            ctrl,
+            alt: false,
+            shift: false,
+        };
+        assert_eq!(t.input(input.clone()), InputResult::Submit, "{input:?}");
+        if !ctrl {
+            assert_eq!(
+                t.input_without_shortcuts(input.clone()),
+                InputResult::Submit,
+                "{input:?}",
+            );
+        }
+        assert_eq!(t.lines(), ["abc"], "{input:?}");
+    }
+}
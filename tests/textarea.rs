@@ -1,12 +1,14 @@
 use std::cmp;
 use std::fmt::Debug;
-use tui_textarea::{CursorMove, TextArea};
+use tui_textarea::{
+    ControlCharHandling, CursorMove, SelectionMode, SortOptions, TextArea, TrailingNewline,
+};
 
 fn assert_undo_redo<T: Debug>(
     before_pos: (usize, usize),
     before_buf: &[&str],
     after_buf: &[&str],
-    t: &mut TextArea<'_>,
+    t: &mut TextArea,
     context: T,
 ) {
     let after_pos = t.cursor();
@@ -20,7 +22,7 @@ fn assert_undo_redo<T: Debug>(
     assert_eq!(t.cursor(), after_pos, "pos after redo: {context:?}");
 }
 
-fn assert_no_undo_redo<T: Debug>(t: &mut TextArea<'_>, context: T) {
+fn assert_no_undo_redo<T: Debug>(t: &mut TextArea, context: T) {
     let pos = t.cursor();
     let buf: Vec<_> = t.lines().to_vec();
     assert!(!t.undo(), "undo modification: {context:?}");
@@ -991,7 +993,7 @@ fn test_cut_single_line() {
 fn test_copy_cut_empty() {
     for row in 0..=2 {
         for col in 0..=2 {
-            let check = |f: fn(&mut TextArea<'_>)| {
+            let check = |f: fn(&mut TextArea)| {
                 let mut t = TextArea::from(["ab", "cd", "ef"]);
                 t.move_cursor(CursorMove::Jump(row, col));
                 t.start_selection();
@@ -1311,7 +1313,8 @@ fn test_copy_cut_paste_multi_lines() {
             assert_eq!(t.cursor(), (srow, scol), "{test:?}");
             assert_eq!(t.yank_text(), yanked, "{test:?}");
             assert_eq!(t.lines(), after_cut, "{test:?}");
-            assert_undo_redo((erow, ecol), init_text, after_cut, &mut t, test);
+            // Undo restores the cursor at the start of the selection
+            assert_undo_redo((srow, scol), init_text, after_cut, &mut t, test);
 
             t.paste();
             assert_eq!(t.lines(), init_text, "{test:?}");
@@ -1573,6 +1576,110 @@ fn test_delete_char() {
     t.test((1, 0), (0, 2, &["abc"], ""));
 }
 
+#[test]
+fn test_delete_char_smart_backspace() {
+    let t = DeleteTester(&["      x", "\t  y", "a   b"], |t| {
+        t.set_smart_backspace(true);
+        t.delete_char()
+    });
+    t.test((0, 6), (0, 4, &["    x", "\t  y", "a   b"], ""));
+    t.test((0, 4), (0, 0, &["  x", "\t  y", "a   b"], ""));
+    t.test((0, 3), (0, 0, &["   x", "\t  y", "a   b"], ""));
+    t.test((1, 3), (1, 1, &["      x", "\ty", "a   b"], ""));
+    // Hard tabs and spaces after other characters are deleted one by one
+    t.test((1, 1), (1, 0, &["      x", "  y", "a   b"], ""));
+    t.test((2, 4), (2, 3, &["      x", "\t  y", "a  b"], ""));
+
+    // Spaces are deleted one by one when the tab length is 0
+    let t = DeleteTester(&["    x"], |t| {
+        t.set_smart_backspace(true);
+        t.set_tab_length(0);
+        t.delete_char()
+    });
+    t.test((0, 4), (0, 3, &["   x"], ""));
+}
+
+#[test]
+fn test_delete_char_pair_backspace() {
+    let t = DeleteTester(&["f()", "[x]", "{}}", "''", "(]"], |t| {
+        t.set_pair_backspace(true);
+        t.delete_char()
+    });
+    t.test((0, 2), (0, 1, &["f", "[x]", "{}}", "''", "(]"], ""));
+    t.test((2, 1), (2, 0, &["f()", "[x]", "}", "''", "(]"], ""));
+    t.test((3, 1), (3, 0, &["f()", "[x]", "{}}", "", "(]"], ""));
+    // Brackets which are not an empty pair are deleted one by one
+    t.test((0, 3), (0, 2, &["f(", "[x]", "{}}", "''", "(]"], ""));
+    t.test((1, 1), (1, 0, &["f()", "x]", "{}}", "''", "(]"], ""));
+    t.test((4, 1), (4, 0, &["f()", "[x]", "{}}", "''", "]"], ""));
+
+    // Only the opening bracket is deleted when pair backspace is disabled
+    let mut t = TextArea::from(["()"]);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), [")"]);
+
+    // Smart backspace for indentation works together
+    let mut t = TextArea::from(["    ()"]);
+    t.set_pair_backspace(true);
+    t.set_smart_backspace(true);
+    t.move_cursor(CursorMove::Jump(0, 5));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["    "]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), [""]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["    "]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["    ()"]);
+    assert_eq!(t.cursor(), (0, 5));
+}
+
+#[test]
+fn test_overwrite() {
+    for (pos, input, after_pos, after) in [
+        ((0, 0), "x", (0, 1), &["xbc", "de"][..]),
+        ((0, 2), "xy", (0, 4), &["abxy", "de"][..]),
+        ((0, 3), "x", (0, 4), &["abcx", "de"][..]),
+        ((0, 1), "x\ny", (1, 1), &["ax", "yc", "de"][..]),
+        ((0, 0), "e\u{301}", (0, 2), &["e\u{301}bc", "de"][..]),
+    ] {
+        let mut t = TextArea::from(["abc", "de"]);
+        t.set_overwrite(true);
+        t.move_cursor(CursorMove::Jump(pos.0 as _, pos.1 as _));
+        if input.chars().count() == 1 {
+            t.insert_char(input.chars().next().unwrap());
+        } else {
+            t.insert_str(input);
+        }
+        assert_eq!(t.lines(), after, "{input:?} at {pos:?}");
+        assert_eq!(t.cursor(), after_pos, "{input:?} at {pos:?}");
+        assert_undo_redo(pos, &["abc", "de"], after, &mut t, (input, pos));
+    }
+
+    // Grapheme clusters are overwritten as one character
+    let mut t = TextArea::from(["e\u{301}a"]);
+    t.set_overwrite(true);
+    t.insert_char('x');
+    assert_eq!(t.lines(), ["xa"]);
+
+    // Selected text is replaced as usual
+    let mut t = TextArea::from(["abcd"]);
+    t.set_overwrite(true);
+    t.start_selection();
+    t.move_cursor(CursorMove::Forward);
+    t.insert_char('x');
+    assert_eq!(t.lines(), ["xbcd"]);
+
+    // Pasting overwrites the yanked text length
+    let mut t = TextArea::from(["abcd"]);
+    t.set_overwrite(true);
+    t.set_yank_text("xy");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["xycd"]);
+    assert_eq!(t.cursor(), (0, 2));
+}
+
 #[test]
 fn test_delete_next_char() {
     let t = DeleteTester(&["ab", "c"], |t| t.delete_next_char());
@@ -1582,6 +1689,25 @@ fn test_delete_next_char() {
     t.test((1, 1), (1, 1, t.0, ""));
 }
 
+#[test]
+fn test_delete_grapheme() {
+    let t = DeleteTester(&["a🇯🇵e\u{301}"], |t| t.delete_char());
+    t.test((0, 3), (0, 1, &["ae\u{301}"], ""));
+    t.test((0, 5), (0, 3, &["a🇯🇵"], ""));
+    let t = DeleteTester(&["a🇯🇵e\u{301}"], |t| t.delete_next_char());
+    t.test((0, 1), (0, 1, &["ae\u{301}"], ""));
+    t.test((0, 3), (0, 3, &["a🇯🇵"], ""));
+
+    // Deleting a grapheme cluster is undone at once
+    let mut t = TextArea::from(["🇯🇵"]);
+    t.move_cursor(CursorMove::End);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), [""]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["🇯🇵"]);
+    assert_eq!(t.cursor(), (0, 2));
+}
+
 #[test]
 fn test_delete_line_by_end() {
     let t = DeleteTester(&["aaa bbb", "d"], |t| t.delete_line_by_end());
@@ -1616,6 +1742,12 @@ fn test_delete_word() {
     t.test((1, 0), (0, 11, &["word  ことば 🐶 x"], ""));
     t.test((1, 1), (1, 0, &["word  ことば 🐶", "x"], " "));
     t.test((1, 2), (1, 1, &["word  ことば 🐶", " "], "x"));
+
+    // `_` is a part of words
+    let t = DeleteTester(&["foo_bar.baz_"], |t| t.delete_word());
+    t.test((0, 7), (0, 0, &[".baz_"], "foo_bar"));
+    t.test((0, 8), (0, 7, &["foo_barbaz_"], "."));
+    t.test((0, 12), (0, 8, &["foo_bar."], "baz_"));
 }
 
 #[test]
@@ -1631,4 +1763,1515 @@ fn test_delete_next_word() {
     t.test((0, 11), (0, 11, &["word  ことば 🐶 x"], ""));
     t.test((1, 0), (1, 0, &["word  ことば 🐶", ""], " x"));
     t.test((1, 2), (1, 2, t.0, ""));
+
+    // `_` is a part of words
+    let t = DeleteTester(&["_foo_bar.baz"], |t| t.delete_next_word());
+    t.test((0, 0), (0, 0, &[".baz"], "_foo_bar"));
+    t.test((0, 4), (0, 4, &["_foo.baz"], "_bar"));
+}
+
+#[test]
+fn test_word_chars() {
+    let mut t = TextArea::from(["tab-width = tab-width-max"]);
+    t.set_word_chars(|c| c.is_alphanumeric() || c == '-');
+
+    assert!(t.delete_next_word());
+    assert_eq!(t.lines(), [" = tab-width-max"]);
+    assert!(t.undo());
+
+    t.move_cursor(CursorMove::End);
+    assert_eq!(
+        t.word_before_cursor(),
+        ("tab-width-max".to_string(), (0, 12)),
+    );
+    assert!(t.delete_word());
+    assert_eq!(t.lines(), ["tab-width = "]);
+    assert!(t.undo());
+
+    // Whole word search does not match a part of the kebab-case word
+    #[cfg(feature = "search")]
+    {
+        t.set_search_whole_word(true);
+        t.set_search_pattern("tab-width").unwrap();
+        t.move_cursor(CursorMove::Head);
+        assert!(t.search_forward(true));
+        assert_eq!(t.cursor(), (0, 0));
+        assert!(t.search_forward(false));
+        assert_eq!(t.cursor(), (0, 0));
+
+        // The cached matches are updated with the predicate
+        t.set_word_chars(|c| c.is_alphanumeric());
+        assert!(t.search_forward(false));
+        assert_eq!(t.cursor(), (0, 12));
+    }
+}
+
+#[test]
+fn test_single_line() {
+    let mut t = TextArea::from(["ab", "cd", "ef"]);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.insert_char('x');
+    t.set_single_line(true);
+    assert!(t.single_line());
+    assert_eq!(t.lines(), ["ab cxd ef"]);
+    assert_eq!(t.cursor(), (0, 5));
+    assert_no_undo_redo(&mut t, "history is cleared");
+
+    t.insert_newline();
+    t.insert_char('\n');
+    assert_eq!(t.lines(), ["ab cxd ef"]);
+    assert_eq!(t.cursor(), (0, 5));
+
+    t.insert_str("1\n2\r\n3");
+    assert_eq!(t.lines(), ["ab cx1 2 3d ef"]);
+    assert_eq!(t.cursor(), (0, 10));
+
+    t.set_yank_text("y\nz");
+    t.paste();
+    assert_eq!(t.lines(), ["ab cx1 2 3y zd ef"]);
+    assert_eq!(t.cursor(), (0, 13));
+    t.undo();
+    assert_eq!(t.lines(), ["ab cx1 2 3d ef"]);
+
+    t.set_single_line(false);
+    t.insert_newline();
+    assert_eq!(t.lines(), ["ab cx1 2 3", "d ef"]);
+}
+
+#[test]
+fn test_mask_char() {
+    let mut t = TextArea::from(["pass\twordあ"]);
+    t.set_mask_char('*');
+
+    // Cursor moves by actual characters
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.cursor(), (0, 10));
+    t.move_cursor(CursorMove::WordBack);
+    assert_eq!(t.cursor(), (0, 5));
+
+    // Selection and yank work on the actual text
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    t.copy();
+    assert_eq!(t.yank_text(), "wordあ");
+
+    t.insert_str("!");
+    assert_eq!(t.lines(), ["pass\twordあ!"]);
+    t.clear_mask_char();
+    assert_eq!(t.lines(), ["pass\twordあ!"]);
+}
+
+#[test]
+fn test_fold_cursor_move() {
+    let mut t: TextArea = ["abc", "d", "efgh", "ij", "k"].into_iter().collect();
+    t.move_cursor(CursorMove::Jump(2, 3));
+    t.fold(1, 3);
+    // The cursor moves out of the hidden lines
+    assert_eq!(t.cursor(), (1, 1));
+
+    for (m, want) in [
+        (CursorMove::Down, (4, 1)),
+        (CursorMove::Up, (1, 1)),
+        (CursorMove::Forward, (4, 0)),
+        (CursorMove::Back, (1, 1)),
+        (CursorMove::Jump(3, 1), (1, 1)),
+        (CursorMove::Jump(4, 1), (4, 1)),
+        (CursorMove::Jump(0, 2), (0, 2)),
+        (CursorMove::Jump(2, 2), (1, 1)),
+    ] {
+        t.move_cursor(m);
+        assert_eq!(t.cursor(), want, "{:?}", m);
+    }
+
+    // When no line is visible after the folded range, the cursor stays at the first line of the range
+    t.fold(1, 4);
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (1, 1));
+    t.move_cursor(CursorMove::Bottom);
+    assert_eq!(t.cursor(), (1, 1));
+
+    t.unfold_all();
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (2, 1));
+}
+
+#[test]
+fn test_snapshot_restore() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Down);
+    let snapshot = t.snapshot();
+    assert_eq!(snapshot.selection_start(), Some((0, 1)));
+
+    t.cut();
+    t.insert_newline();
+    t.insert_str("xyz");
+    assert_eq!(t.lines(), ["a", "xyzef"]);
+
+    // Restoring without recording history clears the history
+    t.restore(&snapshot, false);
+    assert_eq!(t.snapshot(), snapshot);
+    assert_eq!(t.lines(), ["abc", "def"]);
+    assert_eq!(t.cursor(), (1, 1));
+    assert!(t.is_selecting());
+    assert!(!t.undo());
+
+    // Restoring with recording history can be undone and redone as one edit
+    t.cancel_selection();
+    t.move_cursor(CursorMove::Bottom);
+    t.insert_str("!");
+    let edited = t.snapshot();
+    t.restore(&snapshot, true);
+    assert_eq!(t.snapshot(), snapshot);
+    assert!(t.undo());
+    assert_eq!(t.lines(), edited.lines());
+    assert_eq!(t.cursor(), edited.cursor());
+    assert!(t.redo());
+    assert_eq!(t.lines(), snapshot.lines());
+    assert_eq!(t.cursor(), snapshot.cursor());
+    assert!(t.undo());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def"]);
+}
+
+#[test]
+fn test_revision() {
+    let mut t = TextArea::from(["abc", "def"]);
+    let mut prev = t.revision();
+    let mut assert_changed = |t: &TextArea, changed: bool, context: &str| {
+        assert_eq!(t.revision() != prev, changed, "{context}");
+        prev = t.revision();
+    };
+
+    t.insert_char('x');
+    assert_changed(&t, true, "insert char");
+    t.move_cursor(CursorMove::Down);
+    assert_changed(&t, false, "move cursor");
+    t.delete_newline();
+    assert_changed(&t, true, "delete newline");
+    t.select_all();
+    t.copy();
+    assert_changed(&t, false, "copy");
+    t.paste();
+    assert_changed(&t, true, "paste");
+    t.undo();
+    assert_changed(&t, true, "undo");
+    t.redo();
+    assert_changed(&t, true, "redo");
+    t.redo();
+    assert_changed(&t, false, "redo without history");
+    t.delete_char();
+    assert_changed(&t, true, "delete char");
+
+    // The revision increases even when history is disabled
+    t.set_max_histories(0);
+    t.insert_str("y");
+    assert_changed(&t, true, "insert without history");
+}
+
+#[test]
+fn test_send_sync() {
+    // TextArea can be shared with other threads, for example behind `Arc<RwLock<_>>`
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<TextArea>();
+}
+
+#[test]
+fn test_cursor_and_selection_revisions() {
+    let mut t = TextArea::from(["abc", "def"]);
+    let (mut cursor, mut selection) = (t.cursor_revision(), t.selection_revision());
+    let mut assert_changed = |t: &TextArea, changed: (bool, bool), context: &str| {
+        let (c, s) = (t.cursor_revision(), t.selection_revision());
+        assert_eq!((c != cursor, s != selection), changed, "{context}");
+        cursor = c;
+        selection = s;
+    };
+
+    assert_changed(&t, (false, false), "no change");
+    t.move_cursor(CursorMove::Forward);
+    assert_changed(&t, (true, false), "move cursor");
+    t.move_cursor(CursorMove::Forward);
+    t.move_cursor(CursorMove::Back);
+    // Moves between two reads are counted even when the cursor is back at the same position
+    assert_changed(&t, (true, false), "move cursor back to the same position");
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert_changed(&t, (false, false), "jump to the current position");
+    t.delete_next_char();
+    assert_changed(&t, (false, false), "delete after cursor");
+    t.insert_char('x');
+    assert_changed(&t, (true, false), "insert char");
+    t.start_selection();
+    assert_changed(&t, (false, true), "start selection");
+    t.move_cursor(CursorMove::Down);
+    assert_changed(&t, (true, true), "extend selection");
+    t.cancel_selection();
+    assert_changed(&t, (false, true), "cancel selection");
+    t.select_all();
+    assert_changed(&t, (true, true), "select all");
+    t.cut();
+    assert_changed(&t, (true, true), "cut");
+    t.undo();
+    assert_changed(&t, (true, true), "undo restores selection");
+    t.redo();
+    assert_changed(&t, (true, true), "redo");
+}
+
+#[test]
+fn test_stale_positions_are_clamped() {
+    let mut t = TextArea::from(["abc", "defgh", "ij"]);
+    let stale = (2, 2);
+    t.move_cursor(CursorMove::Jump(stale.0 as u16, stale.1 as u16));
+
+    // Replacing the buffer makes the positions saved before stale
+    t.set_text("x");
+    assert_eq!(t.cursor(), (0, 0));
+
+    t.move_to(stale.0, stale.1);
+    assert_eq!(t.cursor(), (0, 1));
+    t.move_to_line(stale.0);
+    assert_eq!(t.cursor(), (0, 1));
+    t.move_cursor(CursorMove::Jump(stale.0 as u16, stale.1 as u16));
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.pos_to_lsp(stale), (0, 1));
+
+    assert!(t.insert_str_at(stale, "y"));
+    assert_eq!(t.lines(), ["xy"]);
+    assert_eq!(t.delete_range((0, 1), stale), "y");
+    assert_eq!(t.lines(), ["x"]);
+
+    // Editing at the clamped cursor works
+    t.insert_char('z');
+    t.insert_newline();
+    assert_eq!(t.lines(), ["xz", ""]);
+    assert!(t.delete_char());
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["x"]);
+}
+
+#[test]
+fn test_insert_str_at() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(0, 2));
+
+    // Inserting before the cursor shifts it right
+    assert!(t.insert_str_at((0, 1), "xy"));
+    assert_eq!(t.lines(), ["axybc", "def"]);
+    assert_eq!(t.cursor(), (0, 4));
+
+    // Inserting after the cursor does not move it
+    assert!(t.insert_str_at((1, 3), "!"));
+    assert_eq!(t.lines(), ["axybc", "def!"]);
+    assert_eq!(t.cursor(), (0, 4));
+
+    // Multi-line insertion before the cursor shifts it down
+    t.move_cursor(CursorMove::Jump(1, 2));
+    assert!(t.insert_str_at((0, 99), "\nfoo\nba"));
+    assert_eq!(t.lines(), ["axybc", "foo", "ba", "def!"]);
+    assert_eq!(t.cursor(), (3, 2));
+    assert!(t.insert_str_at((3, 1), "1\n2"));
+    assert_eq!(t.lines(), ["axybc", "foo", "ba", "d1", "2ef!"]);
+    assert_eq!(t.cursor(), (4, 2));
+
+    // The selection is shifted as well
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    t.insert_str_at((4, 0), ">");
+    t.copy();
+    assert_eq!(t.yank_text(), "f!");
+
+    // Each insertion is one undo step
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["axybc", "foo", "ba", "d1", "2ef!"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["axybc", "foo", "ba", "def!"]);
+    assert!(!t.insert_str_at((0, 0), ""));
+}
+
+#[test]
+fn test_delete_range() {
+    let mut t = TextArea::from(["abc", "def", "ghi", "jkl"]);
+    t.move_cursor(CursorMove::Jump(2, 2));
+
+    // Deleting a multi-line range before the cursor shifts it up
+    assert_eq!(t.delete_range((1, 1), (0, 1)), "bc\nd");
+    assert_eq!(t.lines(), ["aef", "ghi", "jkl"]);
+    assert_eq!(t.cursor(), (1, 2));
+
+    // Deleting a range ending at the cursor line shifts it left
+    assert_eq!(t.delete_range((0, 2), (1, 1)), "f\ng");
+    assert_eq!(t.lines(), ["aehi", "jkl"]);
+    assert_eq!(t.cursor(), (0, 3));
+
+    // The cursor in the deleted range moves to the start of the range
+    assert_eq!(t.delete_range((0, 1), (0, 99)), "ehi");
+    assert_eq!(t.lines(), ["a", "jkl"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    // Deleting after the cursor does not move it
+    assert_eq!(t.delete_range((1, 0), (99, 99)), "jkl");
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.delete_range((1, 0), (1, 0)), "");
+
+    // The deleted text is not yanked and each deletion is one undo step
+    assert_eq!(t.yank_text(), "");
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "jkl"]);
+    assert!(t.undo());
+    assert!(t.undo());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def", "ghi", "jkl"]);
+}
+
+#[test]
+fn test_delete_returning_text() {
+    type DeleteFn = fn(&mut TextArea) -> String;
+    for (name, delete, cursor, expected, lines) in [
+        (
+            "delete_word_backward",
+            TextArea::delete_word_backward as DeleteFn,
+            (1, 4),
+            "ghi ",
+            &["abc def", "jkl"][..],
+        ),
+        (
+            "delete_word_backward at head",
+            TextArea::delete_word_backward,
+            (1, 0),
+            "\n",
+            &["abc defghi jkl"][..],
+        ),
+        (
+            "delete_word_forward",
+            TextArea::delete_word_forward,
+            (0, 3),
+            " def",
+            &["abc", "ghi jkl"][..],
+        ),
+        (
+            "delete_line_to_end",
+            TextArea::delete_line_to_end,
+            (0, 1),
+            "bc def",
+            &["a", "ghi jkl"][..],
+        ),
+        (
+            "delete_line_to_head",
+            TextArea::delete_line_to_head,
+            (1, 5),
+            "ghi j",
+            &["abc def", "kl"][..],
+        ),
+    ] {
+        let mut t = TextArea::from(["abc def", "ghi jkl"]);
+        t.move_cursor(CursorMove::Jump(cursor.0, cursor.1));
+        assert_eq!(delete(&mut t), expected, "{name}");
+        assert_eq!(t.lines(), lines, "{name}");
+    }
+
+    // The selected text is deleted and returned over multiple lines
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.move_cursor(CursorMove::Forward);
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+    assert_eq!(t.delete_word_forward(), "bc\ndef\ng");
+    assert_eq!(t.lines(), ["ahi"]);
+
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.delete_selection().as_deref(), Some("hi"));
+    assert_eq!(t.yank_text(), "");
+    assert_eq!(t.delete_selection(), None);
+
+    // Nothing is returned when nothing was deleted
+    let mut t = TextArea::default();
+    assert_eq!(t.delete_word_backward(), "");
+    assert_eq!(t.delete_line_to_end(), "");
+    assert_eq!(t.lines(), [""]);
+}
+
+#[test]
+fn test_kill_line() {
+    let mut t = TextArea::from(["abc def", "ghi", "jkl"]);
+    t.move_cursor(CursorMove::Jump(0, 3));
+
+    // Killing at end of line joins the next line and yanks the newline
+    assert_eq!(t.delete_to_line_end(), " def");
+    assert_eq!(t.yank_text(), " def");
+    assert_eq!(t.delete_to_line_end(), "\n");
+    assert_eq!(t.yank_text(), "\n");
+    assert_eq!(t.lines(), ["abcghi", "jkl"]);
+    assert_eq!(t.cursor(), (0, 3));
+
+    // Each kill is undone separately
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "ghi", "jkl"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc def", "ghi", "jkl"]);
+
+    t.move_cursor(CursorMove::Jump(2, 2));
+    assert_eq!(t.delete_to_line_start(), "jk");
+    assert_eq!(t.delete_to_line_start(), "\n");
+    assert_eq!(t.lines(), ["abc def", "ghil"]);
+    assert_eq!(t.cursor(), (1, 3));
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["abc def", "ghi", "l"]);
+
+    // Nothing is yanked when nothing was killed
+    t.move_cursor(CursorMove::Jump(0, 0));
+    assert_eq!(t.delete_to_line_start(), "");
+    assert_eq!(t.yank_text(), "\n");
+}
+
+#[test]
+fn test_lsp_position() {
+    let t = TextArea::from(["abc", "x🐶y😺z", ""]);
+
+    for (lsp, pos) in [
+        ((0, 0), (0, 0)),
+        ((0, 3), (0, 3)),
+        ((1, 1), (1, 1)),
+        ((1, 3), (1, 2)),
+        ((1, 4), (1, 3)),
+        ((1, 6), (1, 4)),
+        ((1, 7), (1, 5)),
+        ((2, 0), (2, 0)),
+    ] {
+        assert_eq!(t.lsp_to_pos(lsp.0, lsp.1), pos, "{lsp:?}");
+        assert_eq!(t.pos_to_lsp(pos), lsp, "{pos:?}");
+    }
+
+    // Column in the middle of a surrogate pair
+    assert_eq!(t.lsp_to_pos(1, 2), (1, 1));
+    // Out of range positions are clamped
+    assert_eq!(t.lsp_to_pos(1, 100), (1, 5));
+    assert_eq!(t.lsp_to_pos(100, 1), (2, 0));
+    assert_eq!(t.pos_to_lsp((1, 100)), (1, 7));
+    assert_eq!(t.pos_to_lsp((100, 100)), (2, 0));
+}
+
+#[test]
+fn test_replace_selection_undo() {
+    for (name, f) in [
+        (
+            "insert_str",
+            (|t| assert!(t.insert_str("x\nyz"))) as fn(&mut TextArea),
+        ),
+        ("insert_char", |t| t.insert_char('x')),
+        ("insert_newline", |t| t.insert_newline()),
+        ("insert_tab", |t| assert!(t.insert_tab())),
+        ("paste", |t| {
+            t.set_yank_text("p");
+            assert!(t.paste());
+        }),
+    ] {
+        let mut t = TextArea::from(["abc", "def"]);
+        t.move_cursor(CursorMove::Forward);
+        t.start_selection();
+        t.move_cursor(CursorMove::Down);
+        f(&mut t);
+        let replaced = t.lines().to_vec();
+        let cursor = t.cursor();
+
+        // Deleting the selection and inserting the text are undone at once
+        assert!(t.undo(), "{name}");
+        assert_eq!(t.lines(), ["abc", "def"], "{name}");
+        assert!(!t.undo(), "{name}");
+        assert!(t.redo(), "{name}");
+        assert_eq!(t.lines(), replaced, "{name}");
+        assert_eq!(t.cursor(), cursor, "{name}");
+        assert!(!t.redo(), "{name}");
+    }
+
+    // Inserting an empty string still deletes the selection
+    let mut t = TextArea::from(["abc"]);
+    t.select_all();
+    assert!(t.insert_str(""));
+    assert_eq!(t.lines(), [""]);
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn test_replace_all() {
+    let mut t = TextArea::from(["foo bar foo", "baz", "foofoo"]);
+    t.move_cursor(CursorMove::Jump(2, 4));
+    t.set_search_pattern("foo").unwrap();
+    assert_eq!(t.replace_all("bar"), 4);
+    assert_eq!(t.lines(), ["bar bar bar", "baz", "barbar"]);
+    assert_eq!(t.cursor(), (2, 4));
+
+    // All replacements are undone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["foo bar foo", "baz", "foofoo"]);
+    assert!(!t.undo());
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["bar bar bar", "baz", "barbar"]);
+
+    // No match
+    assert_eq!(t.replace_all("x"), 0);
+    assert_eq!(t.lines(), ["bar bar bar", "baz", "barbar"]);
+
+    // Capture groups and newlines in the replacement
+    let mut t = TextArea::from(["a=1, b=2"]);
+    t.set_search_pattern(r"(\w)=(\d), ").unwrap();
+    assert_eq!(t.replace_all("$2=$1\n"), 1);
+    assert_eq!(t.lines(), ["1=a", "b=2"]);
+
+    // `\r\n` in the replacement is one newline and `\r` at the end of lines is kept
+    let mut t = TextArea::from(["a b\r", "c\r", "d"]);
+    t.set_search_pattern(" ").unwrap();
+    assert_eq!(t.replace_all("\r\n"), 1);
+    assert_eq!(t.lines(), ["a", "b\r", "c\r", "d"]);
+
+    // Replace the next match one by one
+    let mut t = TextArea::from(["foo", "foo"]);
+    t.set_search_pattern("o+").unwrap();
+    assert!(t.replace_next("0"));
+    assert_eq!(t.lines(), ["f0", "foo"]);
+    assert_eq!(t.cursor(), (0, 2));
+    assert!(t.replace_next("0"));
+    assert_eq!(t.lines(), ["f0", "f0"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["f0", "foo"]);
+
+    // The match overlapping the one before the cursor is replaced
+    let mut t = TextArea::from(["aaa"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.set_search_pattern("aa").unwrap();
+    assert!(t.replace_next("X"));
+    assert_eq!(t.lines(), ["aX"]);
+    assert_eq!(t.cursor(), (0, 2));
+}
+
+#[test]
+fn test_block_selection() {
+    let mut t = TextArea::from(["0123456", "abcdefg", "ABCDEFG", "xyz"]);
+    t.set_selection_mode(SelectionMode::Block);
+
+    // Select columns 2..5 of the first 3 lines
+    t.move_cursor(CursorMove::Jump(0, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 5));
+    t.copy();
+    assert_eq!(t.yank_text(), "234\ncde\nCDE");
+    assert!(!t.is_selecting());
+
+    // The block is pasted column-wise across the consecutive lines
+    t.move_cursor(CursorMove::Jump(1, 0));
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["0123456", "234abcdefg", "cdeABCDEFG", "CDExyz"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["0123456", "abcdefg", "ABCDEFG", "xyz"]);
+
+    // Short lines are padded and missing lines are appended
+    t.move_cursor(CursorMove::Jump(2, 5));
+    assert!(t.paste());
+    assert_eq!(
+        t.lines(),
+        ["0123456", "abcdefg", "ABCDE234FG", "xyz  cde", "     CDE"]
+    );
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["0123456", "abcdefg", "ABCDEFG", "xyz"]);
+
+    // Cutting the block deletes the rectangle from each line. Lines shorter than the block are partially deleted
+    t.move_cursor(CursorMove::Jump(1, 4));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 2));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["0123456", "abefg", "ABEFG", "xy"]);
+    assert_eq!(t.yank_text(), "cd\nCD\nz");
+    assert_eq!(t.cursor(), (1, 2));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["0123456", "abcdefg", "ABCDEFG", "xyz"]);
+    assert!(!t.undo());
+
+    // Inserting text replaces the block at once
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 3));
+    assert!(t.insert_str("!"));
+    assert_eq!(t.lines(), ["0!3456", "adefg", "ABCDEFG", "xyz"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["0123456", "abcdefg", "ABCDEFG", "xyz"]);
+
+    // Block with no width selects nothing
+    t.move_cursor(CursorMove::Jump(0, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+    t.copy();
+    assert_eq!(t.yank_text(), "cd\nCD\nz");
+}
+
+#[test]
+fn test_linewise_selection() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.set_selection_mode(SelectionMode::Linewise);
+
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_selection();
+    t.copy();
+    assert_eq!(t.yank_text(), "def\n");
+
+    // The last line is selected without a newline
+    t.start_selection();
+    t.move_cursor(CursorMove::Bottom);
+    assert!(t.cut());
+    assert_eq!(t.yank_text(), "def\nghi");
+    assert_eq!(t.lines(), ["abc", ""]);
+}
+
+#[test]
+fn test_trailing_newline_policy() {
+    fn written(t: &TextArea) -> String {
+        let mut buf = vec![];
+        t.write_to(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    for (text, preserved, ensured, stripped) in [
+        ("", "", "\n", ""),
+        ("\n", "\n", "\n", ""),
+        ("a", "a", "a\n", "a"),
+        ("a\n", "a\n", "a\n", "a"),
+        ("a\n\n\n", "a\n\n\n", "a\n", "a"),
+        ("\na", "\na", "\na\n", "\na"),
+    ] {
+        let mut t = TextArea::from(text.split('\n'));
+        assert_eq!(t.trailing_newline(), TrailingNewline::Preserve);
+        assert_eq!(
+            t.has_trailing_empty_line(),
+            text.ends_with('\n'),
+            "{text:?}"
+        );
+
+        for (policy, want) in [
+            (TrailingNewline::Preserve, preserved),
+            (TrailingNewline::Ensure, ensured),
+            (TrailingNewline::Strip, stripped),
+        ] {
+            t.set_trailing_newline(policy);
+            assert_eq!(written(&t), want, "{text:?} {policy:?}");
+            assert_eq!(t.text(), want, "{text:?} {policy:?}");
+            assert_eq!(
+                t.has_trailing_newline(),
+                want.ends_with('\n'),
+                "{text:?} {policy:?}"
+            );
+            // The text buffer is not modified
+            assert_eq!(t.lines().join("\n"), text, "{text:?} {policy:?}");
+
+            let lines = t.clone().into_lines();
+            assert!(!lines.is_empty(), "{text:?} {policy:?}");
+            let mut joined = lines.join("\n");
+            if policy == TrailingNewline::Ensure {
+                joined.push('\n');
+            }
+            assert_eq!(joined, want, "{text:?} {policy:?}");
+        }
+    }
+}
+
+#[test]
+fn test_sort_and_dedup_lines() {
+    let mut t = TextArea::from(["x", "banana", "Apple", "cherry", "apple", "y"]);
+    t.move_cursor(CursorMove::Bottom);
+
+    // Sort the lines in the range case-insensitively in reverse order
+    let opts = SortOptions {
+        case_insensitive: true,
+        reverse: true,
+        ..Default::default()
+    };
+    assert!(t.sort_lines(Some(1..5), opts));
+    assert_eq!(t.lines(), ["x", "cherry", "banana", "Apple", "apple", "y"]);
+    assert_eq!(t.cursor(), (1, 0));
+
+    // Sort the selected lines. Lines already sorted are not modified
+    t.move_cursor(CursorMove::Jump(3, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Down);
+    assert!(!t.sort_lines(None, SortOptions::default()));
+    assert_eq!(t.lines(), ["x", "cherry", "banana", "Apple", "apple", "y"]);
+    t.move_cursor(CursorMove::Up);
+    t.move_cursor(CursorMove::Up);
+    assert!(t.sort_lines(None, SortOptions::default()));
+    assert_eq!(t.lines(), ["x", "cherry", "Apple", "banana", "apple", "y"]);
+    assert!(!t.is_selecting());
+    assert!(t.undo());
+
+    // Ranges out of the text are clamped
+    assert!(t.sort_lines(Some(2..99), SortOptions::default()));
+    assert_eq!(t.lines(), ["x", "cherry", "Apple", "apple", "banana", "y"]);
+    assert!(!t.sort_lines(Some(99..100), SortOptions::default()));
+
+    // Each sort is undone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x", "cherry", "banana", "Apple", "apple", "y"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x", "banana", "Apple", "cherry", "apple", "y"]);
+    assert!(!t.undo());
+
+    // Numeric sort
+    let mut t = TextArea::from(["10 b", "-2", "x", "3.5", "10 a"]);
+    let opts = SortOptions {
+        numeric: true,
+        ..Default::default()
+    };
+    assert!(t.sort_lines(None, opts));
+    assert_eq!(t.lines(), ["-2", "x", "3.5", "10 b", "10 a"]);
+
+    // Unique sort removes lines which compare as equal
+    let mut t = TextArea::from(["b", "A", "a", "B", "c", "a"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(4, 0));
+    let opts = SortOptions {
+        case_insensitive: true,
+        unique: true,
+        ..Default::default()
+    };
+    assert!(t.sort_selected_lines(opts));
+    assert_eq!(t.lines(), ["A", "b", "c", "a"]);
+    assert!(t.is_selecting());
+    assert_eq!(t.cursor(), (2, 1));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["b", "A", "a", "B", "c", "a"]);
+    t.cancel_selection();
+    let opts = SortOptions {
+        unique: true,
+        ..Default::default()
+    };
+    assert!(t.sort_selected_lines(opts));
+    assert_eq!(t.lines(), ["A", "B", "a", "b", "c"]);
+    assert!(!t.is_selecting());
+    assert!(!t.sort_selected_lines(opts));
+
+    // Dedup removes only consecutive duplicates
+    let mut t = TextArea::from(["a", "a", "b", "b", "b", "a", "c", "c"]);
+    assert_eq!(t.dedup_lines(Some(0..5)), 3);
+    assert_eq!(t.lines(), ["a", "b", "a", "c", "c"]);
+    assert_eq!(t.dedup_lines(Some(0..3)), 0);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "a", "b", "b", "b", "a", "c", "c"]);
+    assert!(!t.undo());
+}
+
+#[test]
+fn test_toggle_line_comment() {
+    let orig = ["fn f() {", "    let x = 1;", "", "        g(x);", "}"];
+    let mut t = TextArea::from(orig);
+
+    // Select the indented lines including the empty line
+    t.move_cursor(CursorMove::Jump(1, 8));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 9));
+    assert!(t.toggle_line_comment("//"));
+    assert_eq!(
+        t.lines(),
+        ["fn f() {", "    // let x = 1;", "", "    //     g(x);", "}"],
+    );
+    // The selection is kept on the same text
+    assert!(t.is_selecting());
+    assert_eq!(t.cursor(), (3, 12));
+    t.copy();
+    assert_eq!(t.yank_text(), "x = 1;\n\n    //     g");
+
+    // Toggling again restores the original text
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 0));
+    assert!(t.toggle_line_comment("//"));
+    assert_eq!(t.lines(), orig);
+
+    // Each toggle is one undo step
+    assert!(t.undo());
+    assert_eq!(
+        t.lines(),
+        ["fn f() {", "    // let x = 1;", "", "    //     g(x);", "}"],
+    );
+    assert!(t.undo());
+    assert_eq!(t.lines(), orig);
+    assert!(!t.undo());
+
+    // When some lines are not commented, all lines are commented
+    let mut t = TextArea::from(["//a", "b"]);
+    t.select_all();
+    assert!(t.toggle_line_comment("//"));
+    assert_eq!(t.lines(), ["// //a", "// b"]);
+
+    // Without selection, only the cursor line is toggled
+    let mut t = TextArea::from(["  # a", "b", "  "]);
+    assert!(t.toggle_line_comment("#"));
+    assert_eq!(t.lines(), ["  a", "b", "  "]);
+    t.move_cursor(CursorMove::Bottom);
+    assert!(!t.toggle_line_comment("#"));
+}
+
+#[test]
+fn test_undo_redo_restores_selection() {
+    let orig = ["a", "bc", "d"];
+    let mut t = TextArea::from(orig);
+    let selected = |t: &mut TextArea| {
+        t.copy();
+        t.yank_text()
+    };
+
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert!(t.indent_lines());
+    assert_eq!(t.lines(), ["    a", "    bc", "d"]);
+    assert_eq!(t.cursor(), (1, 5));
+
+    // Undo restores the selection before indenting
+    assert!(t.undo());
+    assert_eq!(t.lines(), orig);
+    assert!(t.is_selecting());
+    assert_eq!(t.cursor(), (1, 1));
+    assert_eq!(selected(&mut t), "a\nb");
+
+    // Redo restores the selection after indenting
+    assert!(t.redo());
+    assert!(t.is_selecting());
+    assert_eq!(t.cursor(), (1, 5));
+    assert_eq!(selected(&mut t), "a\n    b");
+
+    // The selection can be indented again after undo
+    assert!(t.undo());
+    assert!(t.is_selecting());
+    assert_eq!(t.cursor(), (1, 1));
+    t.set_hard_tab_indent(true);
+    assert!(t.indent_lines());
+    assert_eq!(t.lines(), ["\ta", "\tbc", "d"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), orig);
+    assert_eq!(t.cursor(), (1, 1));
+    assert_eq!(selected(&mut t), "a\nb");
+
+    // Undo restores the selection replaced by typing
+    t.cancel_selection();
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    t.insert_char('x');
+    assert_eq!(t.lines(), ["a", "x", "d"]);
+    // Deleting the selection and inserting the character are undone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), orig);
+    assert!(t.is_selecting());
+    assert_eq!(t.cursor(), (1, 2));
+    assert_eq!(selected(&mut t), "bc");
+
+    // Undoing an edit without selection does not start selection
+    t.cancel_selection();
+    t.insert_char('y');
+    assert!(t.undo());
+    assert!(!t.is_selecting());
+    assert_eq!(t.cursor(), (1, 2));
+}
+
+#[test]
+fn test_indent_dedent_lines() {
+    let orig = ["fn f() {", "let x = 1;", "", "\tg(x);", "  }"];
+    let mut t = TextArea::from(orig);
+
+    // The selection is kept on the same text
+    t.move_cursor(CursorMove::Jump(1, 4));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 1));
+    assert!(t.indent_lines());
+    assert_eq!(
+        t.lines(),
+        ["fn f() {", "    let x = 1;", "", "    \tg(x);", "  }"],
+    );
+    assert_eq!(t.cursor(), (3, 5));
+    t.copy();
+    assert_eq!(t.yank_text(), "x = 1;\n\n    \t");
+
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 0));
+    // One tab or spaces up to the tab length are removed
+    t.set_tab_length(2);
+    assert!(t.dedent_lines());
+    assert_eq!(
+        t.lines(),
+        ["fn f() {", "  let x = 1;", "", "  \tg(x);", "  }"],
+    );
+    assert!(t.dedent_lines());
+    assert_eq!(t.lines(), orig);
+    assert!(t.dedent_lines());
+    assert_eq!(t.lines(), ["fn f() {", "let x = 1;", "", "g(x);", "  }"]);
+    assert!(!t.dedent_lines());
+
+    // Each indent or dedent is one undo step
+    assert!(t.undo());
+    assert_eq!(t.lines(), orig);
+    assert!(t.undo());
+    assert!(t.undo());
+    assert!(t.undo());
+    assert_eq!(t.lines(), orig);
+    assert!(!t.undo());
+
+    // Without selection, only the cursor line is indented with the indent string
+    let mut t = TextArea::from(["a", "b"]);
+    t.set_hard_tab_indent(true);
+    assert!(t.indent_lines());
+    assert_eq!(t.lines(), ["\ta", "b"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    // Nothing is indented when the tab length is 0 or the lines are empty
+    let mut t = TextArea::from(["a", ""]);
+    t.set_tab_length(0);
+    assert!(!t.indent_lines());
+    assert!(!t.dedent_lines());
+    t.set_tab_length(4);
+    t.move_cursor(CursorMove::Bottom);
+    assert!(!t.indent_lines());
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn test_incremental_search() {
+    let mut t = TextArea::from(["foo bar", "baz", "bar foo"]);
+    t.set_search_pattern("o+").unwrap();
+    t.move_cursor(CursorMove::Jump(0, 4));
+
+    t.search_start();
+    // The match at the start position is found
+    assert!(t.search_feed("b").unwrap());
+    assert_eq!(t.cursor(), (0, 4));
+    assert!(t.search_feed("ba").unwrap());
+    assert!(t.search_feed("baz").unwrap());
+    assert_eq!(t.cursor(), (1, 0));
+    // Search wraps around the buffer
+    assert!(t.search_feed("fo").unwrap());
+    assert_eq!(t.cursor(), (2, 4));
+    assert!(t.search_feed("^fo").unwrap());
+    assert_eq!(t.cursor(), (0, 0));
+    // The invalid pattern does not change the state
+    assert!(t.search_feed("fo(").is_err());
+    assert_eq!(t.search_pattern().unwrap().as_str(), "^fo");
+    assert_eq!(t.cursor(), (0, 0));
+    // Empty query stops highlighting and restores the cursor
+    assert!(!t.search_feed("").unwrap());
+    assert!(t.search_pattern().is_none());
+    assert_eq!(t.cursor(), (0, 4));
+
+    // Cancel restores the pattern before the search
+    assert!(t.search_feed("z").unwrap());
+    t.search_cancel();
+    assert!(!t.is_searching());
+    assert_eq!(t.cursor(), (0, 4));
+    assert_eq!(t.search_pattern().unwrap().as_str(), "o+");
+
+    // Feeding without starting the search starts it at the cursor
+    assert!(t.search_feed("az").unwrap());
+    assert!(t.is_searching());
+    t.search_commit();
+    assert!(!t.is_searching());
+    assert_eq!(t.cursor(), (1, 1));
+    assert_eq!(t.search_pattern().unwrap().as_str(), "az");
+
+    // Committing or canceling without the ongoing search does nothing
+    t.search_cancel();
+    t.search_commit();
+    assert_eq!(t.cursor(), (1, 1));
+    assert_eq!(t.search_pattern().unwrap().as_str(), "az");
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn test_search_options() {
+    let mut t = TextArea::from(["Foo foo_bar", "(foo) FOO"]);
+
+    // Flags apply to the pattern which was already set
+    t.set_search_pattern("foo").unwrap();
+    t.set_search_case_insensitive(true);
+    assert!(t.search_forward(true));
+    assert_eq!(t.cursor(), (0, 0));
+
+    t.set_search_whole_word(true);
+    for want in [(1, 1), (1, 6), (0, 0)] {
+        assert!(t.search_forward(false));
+        assert_eq!(t.cursor(), want);
+    }
+    assert!(t.search_back(false));
+    assert_eq!(t.cursor(), (1, 6));
+
+    // Whole word composes with regex
+    t.set_search_pattern(r"f\w+").unwrap();
+    t.move_cursor(CursorMove::Jump(0, 0));
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (0, 4));
+
+    // Only whole word matches are replaced
+    t.set_search_case_insensitive(false);
+    t.set_search_pattern("foo").unwrap();
+    assert_eq!(t.replace_all("x"), 1);
+    assert_eq!(t.lines(), ["Foo foo_bar", "(x) FOO"]);
+
+    t.set_search_whole_word(false);
+    t.move_cursor(CursorMove::Jump(0, 0));
+    assert!(t.replace_next("y"));
+    assert_eq!(t.lines(), ["Foo y_bar", "(x) FOO"]);
+    assert!(!t.search_case_insensitive());
+    assert!(!t.search_whole_word());
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn test_search_literal() {
+    let mut t = TextArea::from(["axb", "a.b (x) FOO $1"]);
+
+    t.set_search_literal(true);
+    t.set_search_pattern("a.b").unwrap();
+    for _ in 0..2 {
+        assert!(t.search_forward(false));
+        assert_eq!(t.cursor(), (1, 0));
+    }
+
+    // Switching the mode re-evaluates the current pattern
+    t.set_search_literal(false);
+    t.move_cursor(CursorMove::Jump(0, 0));
+    assert!(t.search_forward(true));
+    assert_eq!(t.cursor(), (0, 0));
+
+    // Invalid regex is accepted in literal mode
+    t.set_search_literal(true);
+    t.set_search_pattern("(x").unwrap();
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (1, 4));
+    t.set_search_literal(false);
+    assert!(t.search_pattern().is_none());
+
+    t.set_search_literal(true);
+    t.set_search_case_insensitive(true);
+    t.set_search_pattern("foo").unwrap();
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (1, 8));
+
+    // Replacement is not expanded
+    t.set_search_pattern("(x)").unwrap();
+    assert!(t.replace_next("$1"));
+    assert_eq!(t.lines(), ["axb", "a.b $1 FOO $1"]);
+    t.set_search_pattern("$1").unwrap();
+    assert_eq!(t.replace_all("y"), 2);
+    assert_eq!(t.lines(), ["axb", "a.b y FOO y"]);
+
+    // Incremental search restores the literal query on cancel
+    t.set_search_pattern("a.b").unwrap();
+    t.search_start();
+    t.search_feed("FOO").unwrap();
+    t.search_cancel();
+    t.move_cursor(CursorMove::Jump(0, 0));
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (1, 0));
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn test_search_wrap_and_count() {
+    let mut t = TextArea::from(["ab", "xab ab", "", "ab"]);
+    t.set_search_pattern("ab").unwrap();
+    assert_eq!(t.search_match_count(), 4);
+
+    let mut indices = vec![];
+    for _ in 0..5 {
+        assert!(t.search_forward(false));
+        indices.push((t.current_match_index(), t.search_wrapped()));
+    }
+    assert_eq!(
+        indices,
+        [
+            (Some(1), false),
+            (Some(2), false),
+            (Some(3), false),
+            (Some(0), true),
+            (Some(1), false),
+        ],
+    );
+    assert!(t.search_back(false));
+    assert_eq!(t.current_match_index(), Some(0));
+    assert!(t.search_back(false));
+    assert!(t.search_wrapped());
+    assert_eq!(t.cursor(), (3, 0));
+
+    // Search without wrap stops at the end of the text
+    t.set_search_wrap(false);
+    assert!(!t.search_forward(false));
+    assert!(!t.search_wrapped());
+    assert_eq!(t.cursor(), (3, 0));
+    t.move_cursor(CursorMove::Top);
+    assert!(!t.search_back(false));
+    assert_eq!(t.cursor(), (0, 0));
+
+    // A match only before the cursor is found by wrapping around
+    t.set_search_wrap(true);
+    t.set_search_pattern("xa").unwrap();
+    t.move_cursor(CursorMove::Bottom);
+    assert!(t.search_forward(false));
+    assert!(t.search_wrapped());
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.search_back(false));
+    assert!(t.search_wrapped());
+    assert_eq!(t.cursor(), (1, 0));
+
+    // The cursor does not move when nothing matches
+    t.set_search_pattern("zz").unwrap();
+    assert_eq!(t.search_match_count(), 0);
+    assert!(!t.search_forward(false));
+    assert!(!t.search_wrapped());
+    assert_eq!(t.cursor(), (1, 0));
+    t.set_search_pattern("ab").unwrap();
+    t.move_cursor(CursorMove::Top);
+
+    // The count follows modifications of the text and the pattern
+    t.insert_str("ab");
+    assert_eq!(t.search_match_count(), 5);
+    assert_eq!(t.current_match_index(), Some(1));
+    t.undo();
+    assert_eq!(t.search_match_count(), 4);
+    t.set_search_whole_word(true);
+    assert_eq!(t.search_match_count(), 3);
+    t.set_search_pattern("").unwrap();
+    assert_eq!(t.search_match_count(), 0);
+    assert_eq!(t.current_match_index(), None);
+}
+
+#[test]
+fn test_paste_text() {
+    let text: Vec<_> = (0..500).map(|i| format!("  line {}", i)).collect();
+    let mut t = TextArea::from(["{}"]);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.paste_text(&format!("\n{}\n", text.join("\r\n"))));
+    assert_eq!(t.lines().len(), 502);
+    assert_eq!(t.lines()[1], "  line 0");
+    assert_eq!(t.lines()[500], "  line 499");
+    assert_eq!(t.lines()[501], "}");
+    assert_eq!(t.cursor(), (501, 0));
+
+    // Pasting 500 lines is undone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["{}"]);
+    assert!(!t.undo());
+
+    // The selected text is replaced
+    let mut t = TextArea::from(["abc"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    assert!(t.paste_text("x\ry"));
+    assert_eq!(t.lines(), ["x", "y"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc"]);
+
+    // Newlines are replaced with spaces in single-line mode
+    let mut t = TextArea::default();
+    t.set_single_line(true);
+    assert!(t.paste_text("a\r\nb\rc"));
+    assert_eq!(t.lines(), ["a b c"]);
+    assert!(!t.paste_text(""));
+}
+
+#[test]
+fn test_strip_control_chars() {
+    let mut t = TextArea::from(["\x07"]);
+    t.set_control_char_handling(ControlCharHandling::Strip);
+    t.move_cursor(CursorMove::End);
+
+    // Control characters are removed from inserted text, but hard tabs and newlines are kept
+    assert!(t.insert_str("a\x1b[1m\tb\u{200b}\r\nc\0"));
+    assert_eq!(t.lines(), ["\x07a[1m\tb", "c"]);
+    t.insert_char('\x7f');
+    t.insert_char('\u{feff}');
+    t.insert_char('d');
+    assert_eq!(t.lines(), ["\x07a[1m\tb", "cd"]);
+    assert!(!t.paste_text("\x1b"));
+
+    // Joiners in emoji sequences are not control characters
+    assert!(t.insert_str("👨\u{200d}👩"));
+    assert_eq!(t.lines()[1], "cd👨\u{200d}👩");
+}
+
+#[test]
+fn test_completion() {
+    let mut t = TextArea::from(["let fo"]);
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.word_before_cursor(), ("fo".to_string(), (0, 4)));
+    assert!(t.complete_with("foo"));
+    assert_eq!(t.lines(), ["let foo"]);
+    assert_eq!(t.cursor(), (0, 7));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["let fo"]);
+    assert!(!t.undo());
+
+    // Only the part of the word before the cursor is replaced
+    let mut t = TextArea::from(["x.ab_cd(1)"]);
+    t.move_cursor(CursorMove::Jump(0, 5));
+    assert_eq!(t.word_before_cursor(), ("ab_".to_string(), (0, 2)));
+    assert!(t.complete_with("abc_"));
+    assert_eq!(t.lines(), ["x.abc_cd(1)"]);
+    assert_eq!(t.cursor(), (0, 6));
+
+    // No word before the cursor inserts the text
+    let mut t = TextArea::from(["f( "]);
+    t.move_cursor(CursorMove::End);
+    assert_eq!(t.word_before_cursor(), ("".to_string(), (0, 3)));
+    assert!(t.complete_with("x"));
+    assert_eq!(t.lines(), ["f( x"]);
+    assert!(t.complete_with(""));
+    assert_eq!(t.lines(), ["f( "]);
+    assert!(!t.complete_with(""));
+
+    // Works with the linewise selection mode
+    let mut t = TextArea::from(["aa", "あい"]);
+    t.set_selection_mode(SelectionMode::Linewise);
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert_eq!(t.word_before_cursor(), ("あ".to_string(), (1, 0)));
+    assert!(t.complete_with("いい"));
+    assert_eq!(t.lines(), ["aa", "いいい"]);
+    assert!(!t.is_selecting());
+}
+
+#[test]
+fn test_insert_and_select() {
+    let mut t = TextArea::from(["ab"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.insert_and_select("fn name() {\n    body\n}", 3, 7));
+    assert_eq!(t.lines(), ["afn name() {", "    body", "}b"]);
+    assert!(t.is_selecting());
+    assert_eq!(t.cursor(), (0, 8));
+    t.copy();
+    assert_eq!(t.yank_text(), "name");
+
+    // Selection across lines
+    let mut t = TextArea::default();
+    assert!(t.insert_and_select("ab\ncd", 1, 4));
+    t.copy();
+    assert_eq!(t.yank_text(), "b\nc");
+    assert_eq!(t.cursor(), (1, 1));
+
+    // Inserted at once and undone in one step
+    assert!(t.undo());
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.cursor(), (0, 0));
+    assert!(!t.undo());
+
+    // Empty range only places the cursor, reversed range is ordered, and out-of-range offsets are clamped
+    let mut t = TextArea::default();
+    assert!(t.insert_and_select("(あい)", 2, 2));
+    assert!(!t.is_selecting());
+    assert_eq!(t.cursor(), (0, 2));
+    let mut t = TextArea::default();
+    assert!(t.insert_and_select("abc", 9, 1));
+    t.copy();
+    assert_eq!(t.yank_text(), "bc");
+    assert_eq!(t.cursor(), (0, 3));
+
+    // Existing selection is replaced
+    let mut t = TextArea::from(["hello world"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 5));
+    assert!(t.insert_and_select("<x>", 1, 2));
+    assert_eq!(t.lines(), ["<x> world"]);
+    t.copy();
+    assert_eq!(t.yank_text(), "x");
+
+    assert!(!TextArea::default().insert_and_select("", 0, 0));
+}
+
+#[test]
+fn test_kill_ring() {
+    let mut t = TextArea::from(["one", "two", "three", ""]);
+    for _ in 0..3 {
+        t.start_selection();
+        t.move_cursor(CursorMove::Down);
+        t.move_cursor(CursorMove::Head);
+        assert!(t.cut());
+    }
+    assert_eq!(t.lines(), [""]);
+
+    // Paste the last cut text and replace it with the second-to-last one
+    t.insert_str("[]");
+    t.move_cursor(CursorMove::Back);
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["[three", "]"]);
+    assert!(t.yank_pop());
+    assert_eq!(t.lines(), ["[two", "]"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert_eq!(t.yank_text(), "two\n");
+
+    // The replacement is undone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["[three", "]"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["[two", "]"]);
+
+    // Not replaced after the text is modified
+    t.insert_char('x');
+    assert!(!t.yank_pop());
+
+    // The next paste inserts the text selected by the last replacement and cycles from there
+    let mut t = TextArea::from(["a b c"]);
+    for _ in 0..3 {
+        t.delete_next_word();
+    }
+    t.paste();
+    t.yank_pop();
+    t.paste();
+    assert_eq!(t.lines(), [" b b"]);
+    assert!(t.yank_pop());
+    assert_eq!(t.lines(), [" ba"]);
+
+    // Multi-line text replaced with a single line text
+    let mut t = TextArea::default();
+    t.set_yank_text("x\ny");
+    t.set_yank_text("z");
+    t.set_yank_text("p\nq\nr");
+    t.paste();
+    assert!(t.yank_pop());
+    assert_eq!(t.lines(), ["z"]);
+    assert!(t.yank_pop());
+    assert_eq!(t.lines(), ["x", "y"]);
+
+    // Works when the history is full
+    t.set_max_histories(1);
+    t.paste();
+    assert!(t.yank_pop());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x", "y"]);
+
+    // In overwrite mode, the replacement does not overwrite the characters after the pasted text
+    let mut t = TextArea::from(["abcdef"]);
+    t.set_yank_text("long");
+    t.set_yank_text("xy");
+    t.set_overwrite(true);
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["xycdef"]);
+    assert!(t.yank_pop());
+    assert_eq!(t.lines(), ["longcdef"]);
+    assert_eq!(t.cursor(), (0, 4));
+    assert!(t.overwrite());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["xycdef"]);
+
+    // Kill ring disabled
+    t.set_kill_ring_size(0);
+    t.set_yank_text("w");
+    t.paste();
+    assert!(!t.yank_pop());
+}
+
+#[test]
+fn test_registers() {
+    let mut t = TextArea::from(["abc", "def"]);
+    assert!(!t.copy_to_register('a'));
+    assert_eq!(t.register('a'), None);
+
+    t.start_selection();
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.copy_to_register('a'));
+    assert_eq!(t.register('a'), Some("a"));
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert!(t.cut_to_register('b'));
+    assert_eq!(t.lines(), ["aef"]);
+    assert_eq!(t.register('b'), Some("bc\nd"));
+    assert_eq!(t.yank_text(), "bc\nd");
+
+    // Pasting a register does not change the yanked text
+    t.set_register('c', "x");
+    t.move_cursor(CursorMove::End);
+    assert!(t.paste_register('a'));
+    assert!(t.paste_register('b'));
+    assert!(t.paste_register('c'));
+    assert_eq!(t.lines(), ["aefabc", "dx"]);
+    assert_eq!(t.yank_text(), "bc\nd");
+    assert!(!t.yank_pop());
+    assert!(!t.paste_register('z'));
+}
+
+#[test]
+fn test_trailing_newline_model() {
+    let mut t = TextArea::default();
+    assert!(!t.has_trailing_newline());
+
+    for (text, lines, trailing) in [
+        ("a\nb\n", &["a", "b", ""][..], true),
+        ("a\nb", &["a", "b"][..], false),
+        ("", &[""][..], false),
+        ("\n", &["", ""][..], true),
+        ("a\r\n\r\n", &["a", "", ""][..], true),
+    ] {
+        t.set_text(text);
+        assert_eq!(t.lines(), lines, "{:?}", text);
+        assert_eq!(t.has_trailing_newline(), trailing, "{:?}", text);
+        assert_eq!(t.text(), text.replace("\r\n", "\n"), "{:?}", text);
+    }
+
+    // Setting the text cannot be undone
+    t.set_text("abc");
+    t.insert_char('x');
+    assert!(t.undo());
+    assert!(!t.undo());
+    assert_eq!(t.lines(), ["abc"]);
+
+    // Inserting a newline at the end adds a trailing newline
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    assert!(t.has_trailing_newline());
+    assert_eq!(t.text(), "abc\n");
+
+    let mut t = TextArea::default();
+    t.set_single_line(true);
+    t.set_text("a\nb\n");
+    assert_eq!(t.lines(), ["a b "]);
+}
+
+#[test]
+fn test_insert_large_str() {
+    let text: Vec<_> = (0..10000).map(|i| format!("line {}", i)).collect();
+    let mut t = TextArea::from(["ab"]);
+    t.move_cursor(CursorMove::Forward);
+    let before = t.snapshot();
+
+    assert!(t.insert_str(text.join("\r\n")));
+    assert_eq!(t.lines().len(), 10000);
+    assert_eq!(t.lines()[0], "aline 0");
+    assert_eq!(t.lines()[5000], "line 5000");
+    assert_eq!(t.lines()[9999], "line 9999b");
+    assert_eq!(t.cursor(), (9999, 9));
+
+    // Inserting 10k lines is one step in the edit history
+    assert!(t.undo());
+    assert_eq!(t.snapshot(), before);
+    assert!(!t.undo());
+    assert!(t.redo());
+    assert_eq!(t.lines().len(), 10000);
+    assert_eq!(t.cursor(), (9999, 9));
+}
+
+#[test]
+fn test_lines_in_range() {
+    let t = TextArea::from(["a", "b", "c", "d"]);
+    for (start, end, want) in [
+        (0, 4, &["a", "b", "c", "d"][..]),
+        (1, 3, &["b", "c"][..]),
+        (2, 2, &[][..]),
+        (3, 100, &["d"][..]),
+        (4, 5, &[][..]),
+        (100, 200, &[][..]),
+        (3, 1, &[][..]),
+    ] {
+        let lines: Vec<_> = t.lines_in_range(start, end).collect();
+        assert_eq!(lines, want, "{}..{}", start, end);
+    }
+
+    for row in 0..4 {
+        assert_eq!(t.line(row), Some(t.lines()[row].as_str()));
+    }
+    assert_eq!(t.line(4), None);
+    assert_eq!(TextArea::default().line(0), Some(""));
 }